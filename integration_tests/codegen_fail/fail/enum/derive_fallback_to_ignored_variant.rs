@@ -0,0 +1,10 @@
+#[derive(juniper::GraphQLEnum)]
+pub enum Test {
+    Known,
+    #[graphql(ignore, fallback = Removed)]
+    Unknown(String),
+    #[graphql(ignore, fallback = Known)]
+    Removed,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(juniper::GraphQLEnum)]
+pub enum Test {
+    Known,
+    #[graphql(fallback = Known)]
+    Other,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(juniper::GraphQLEnum)]
+pub enum Test {
+    Known,
+    #[graphql(ignore)]
+    Unknown(String),
+}
+
+fn main() {}
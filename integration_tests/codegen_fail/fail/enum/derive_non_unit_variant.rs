@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLEnum)]
+pub enum Test {
+    Known,
+    Unknown(String),
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLEnum)]
+enum Character {
+    #[graphql(validate(length(max = 1)))]
+    Test,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLEnum)]
+#[graphql(rename_all = "kebab-case")]
+pub enum Test {
+    Known,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use juniper::GraphQLError;
+
+#[derive(GraphQLError)]
+#[graphql(codes = UserErrorCode)]
+enum UserError {
+    /// User is not authenticated.
+    #[graphql(code = "DENIED")]
+    Unauthenticated,
+
+    /// User is not allowed to do this.
+    #[graphql(code = "DENIED")]
+    Forbidden,
+}
+
+fn main() {}
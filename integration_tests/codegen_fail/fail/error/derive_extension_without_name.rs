@@ -0,0 +1,9 @@
+use juniper::GraphQLError;
+
+#[derive(GraphQLError)]
+enum UserError {
+    #[graphql(message = "User `{0}` is not found")]
+    NotFound(#[graphql(extension)] String),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use juniper::GraphQLError;
+
+#[derive(GraphQLError)]
+enum UserError {
+    /// User is not authenticated.
+    #[graphql(status = 401)]
+    Unauthenticated,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use juniper::GraphQLError;
+
+#[derive(GraphQLError)]
+enum UserError {
+    #[graphql(message = "User `{name}` is not found")]
+    NotFound { id: String },
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use juniper::GraphQLError;
+
+#[derive(GraphQLError)]
+struct UserError {
+    message: String,
+}
+
+fn main() {}
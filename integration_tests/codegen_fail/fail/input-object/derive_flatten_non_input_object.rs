@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLInputObject)]
+struct Object {
+    #[graphql(flatten)]
+    page: i32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[derive(juniper::GraphQLInputObject)]
+struct Pagination {
+    first: i32,
+}
+
+#[derive(juniper::GraphQLInputObject)]
+struct Object {
+    #[graphql(flatten, name = "paging")]
+    page: Pagination,
+}
+
+fn main() {}
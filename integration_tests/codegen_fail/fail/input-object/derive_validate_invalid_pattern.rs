@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLInputObject)]
+struct Object {
+    #[graphql(validate(pattern = "[a-z"))]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLInputObject)]
+struct Object {
+    #[graphql(validate(length()))]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+struct Context;
+
+impl juniper::Context for Context {}
+
+struct Obj;
+
+#[juniper::graphql_object(context = Context)]
+impl Obj {
+    fn id(&self, #[graphql(context, validate(range(min = 1)))] _ctx: &Context) -> i32 {
+        1
+    }
+}
+
+fn main() {}
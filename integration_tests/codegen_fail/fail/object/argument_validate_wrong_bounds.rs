@@ -0,0 +1,10 @@
+struct Obj;
+
+#[juniper::graphql_object]
+impl Obj {
+    fn id(&self, #[graphql(validate(range(min = 10, max = 1)))] n: i32) -> i32 {
+        n
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+struct Obj;
+
+#[juniper::graphql_object]
+impl Obj {
+    #[graphql(cache_control(max_age = 60, scope = "SHARED"))]
+    fn id(&self) -> &str {
+        "funA"
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use juniper::graphql_object;
+
+struct Page;
+
+#[graphql_object(concrete(name = "IntPage", params(i32)))]
+impl Page {
+    fn items() -> Vec<i32> {
+        vec![]
+    }
+}
+
+fn main() {}
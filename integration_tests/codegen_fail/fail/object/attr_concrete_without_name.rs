@@ -0,0 +1,12 @@
+use juniper::graphql_object;
+
+struct Page<T>(Vec<T>);
+
+#[graphql_object(concrete(params(i32)))]
+impl<T> Page<T> {
+    fn count(&self) -> i32 {
+        self.0.len() as i32
+    }
+}
+
+fn main() {}
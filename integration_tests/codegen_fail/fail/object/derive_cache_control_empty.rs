@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLObject)]
+#[graphql(cache_control())]
+struct Obj {
+    id: String,
+}
+
+fn main() {}
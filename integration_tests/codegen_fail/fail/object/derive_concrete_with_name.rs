@@ -0,0 +1,9 @@
+use juniper::GraphQLObject;
+
+#[derive(GraphQLObject)]
+#[graphql(name = "Page", concrete(name = "IntPage", params(i32)))]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+fn main() {}
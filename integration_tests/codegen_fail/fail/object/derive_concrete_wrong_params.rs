@@ -0,0 +1,10 @@
+use juniper::GraphQLObject;
+
+#[derive(GraphQLObject)]
+#[graphql(concrete(name = "IntPair", params(i32)))]
+struct Pair<A, B> {
+    left: A,
+    right: B,
+}
+
+fn main() {}
@@ -1,7 +1,8 @@
 use fnv::FnvHashMap;
 use juniper::{
-    DefaultScalarValue, FromInputValue, GraphQLEnum, GraphQLType, InputValue, Registry,
-    ToInputValue,
+    execute, graphql_object, graphql_value, DefaultScalarValue, EmptyMutation, EmptySubscription,
+    FromInputValue, GraphQLEnum, GraphQLType, InputValue, Registry, RootNode, ToInputValue,
+    Variables,
 };
 
 pub struct CustomContext {}
@@ -23,6 +24,64 @@ enum NoRenameEnum {
     AnotherVariant,
 }
 
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "camelCase")]
+enum CamelCaseEnum {
+    OneVariant,
+    #[graphql(name = "ANOTHER")]
+    AnotherVariant,
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "none")]
+enum NoRenameAllEnum {
+    OneVariant,
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+enum IgnoredVariantEnum {
+    Known,
+    #[graphql(deprecated = "Use `KNOWN` instead")]
+    Legacy,
+    #[graphql(ignore, fallback = Known)]
+    Unknown(String),
+    #[graphql(ignore, fallback = Legacy)]
+    Removed {
+        since: u32,
+    },
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(
+    to_input_value_with = legacy::to_input_value,
+    from_input_value_with = legacy::from_input_value,
+)]
+enum LegacyCodeEnum {
+    Active,
+    Inactive,
+}
+
+mod legacy {
+    use juniper::{InputValue, ScalarValue};
+
+    use super::LegacyCodeEnum;
+
+    pub(super) fn to_input_value<S: ScalarValue>(v: &LegacyCodeEnum) -> InputValue<S> {
+        InputValue::enum_value(match v {
+            LegacyCodeEnum::Active => "A01",
+            LegacyCodeEnum::Inactive => "I01",
+        })
+    }
+
+    pub(super) fn from_input_value<S: ScalarValue>(v: &InputValue<S>) -> Option<LegacyCodeEnum> {
+        match v.as_enum_value().or_else(|| v.as_string_value())? {
+            "A01" | "ACTIVE" => Some(LegacyCodeEnum::Active),
+            "I01" | "INACTIVE" => Some(LegacyCodeEnum::Inactive),
+            _ => None,
+        }
+    }
+}
+
 /// Enum doc.
 #[derive(GraphQLEnum)]
 enum DocEnum {
@@ -131,3 +190,220 @@ fn test_doc_custom_context() {
     test_context(ContextEnum::A);
     // test_context(OverrideDocEnum::Foo); does not work
 }
+
+#[test]
+fn test_rename_all() {
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&CamelCaseEnum::OneVariant),
+        InputValue::scalar("oneVariant"),
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&CamelCaseEnum::AnotherVariant),
+        InputValue::scalar("ANOTHER"),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("oneVariant")),
+        Some(CamelCaseEnum::OneVariant),
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&NoRenameAllEnum::OneVariant),
+        InputValue::scalar("OneVariant"),
+    );
+}
+
+#[test]
+fn test_ignored_variants() {
+    let mut registry: Registry = Registry::new(FnvHashMap::default());
+    let meta = IgnoredVariantEnum::meta(&(), &mut registry);
+    let values = match meta {
+        juniper::meta::MetaType::Enum(meta) => {
+            meta.values.into_iter().map(|v| v.name).collect::<Vec<_>>()
+        }
+        _ => panic!("expected enum meta"),
+    };
+    assert_eq!(values, ["KNOWN", "LEGACY"]);
+
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&IgnoredVariantEnum::Unknown("foo".into())),
+        InputValue::scalar("KNOWN"),
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&IgnoredVariantEnum::Removed { since: 2 }),
+        InputValue::scalar("LEGACY"),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("UNKNOWN")),
+        None::<IgnoredVariantEnum>,
+    );
+}
+
+#[test]
+fn test_custom_input_value_mapping() {
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&LegacyCodeEnum::Inactive),
+        InputValue::enum_value("I01"),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("A01")),
+        Some(LegacyCodeEnum::Active),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::enum_value("ACTIVE")),
+        Some(LegacyCodeEnum::Active),
+    );
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn unknown() -> IgnoredVariantEnum {
+        IgnoredVariantEnum::Unknown("foo".into())
+    }
+
+    fn legacy(code: LegacyCodeEnum) -> bool {
+        code == LegacyCodeEnum::Active
+    }
+
+    fn legacy_or_default(
+        #[graphql(default = LegacyCodeEnum::Inactive)] code: LegacyCodeEnum,
+    ) -> bool {
+        code == LegacyCodeEnum::Active
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[tokio::test]
+async fn resolves_ignored_variant_as_fallback() {
+    let schema = schema();
+
+    assert_eq!(
+        execute(
+            "{ unknown legacy(code: ACTIVE) }",
+            None,
+            &schema,
+            &Variables::new(),
+            &()
+        )
+        .await,
+        Ok((graphql_value!({"unknown": "KNOWN", "legacy": true}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn accepts_custom_input_values_in_queries() {
+    let schema = schema();
+
+    assert_eq!(
+        execute(
+            "{ code: legacy(code: A01) name: legacy(code: INACTIVE) legacyOrDefault }",
+            None,
+            &schema,
+            &Variables::new(),
+            &()
+        )
+        .await,
+        Ok((
+            graphql_value!({"code": true, "name": false, "legacyOrDefault": false}),
+            vec![],
+        )),
+    );
+
+    let vars = vec![("code".to_owned(), InputValue::scalar("A01"))]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        execute(
+            "query($code: LegacyCodeEnum!) { legacy(code: $code) }",
+            None,
+            &schema,
+            &vars,
+            &()
+        )
+        .await,
+        Ok((graphql_value!({"legacy": true}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn rejects_unknown_custom_input_values() {
+    let schema = schema();
+
+    assert!(execute(
+        "{ legacy(code: X99) }",
+        None,
+        &schema,
+        &Variables::new(),
+        &()
+    )
+    .await
+    .is_err());
+
+    let vars = vec![("code".to_owned(), InputValue::scalar("X99"))]
+        .into_iter()
+        .collect();
+    assert!(execute(
+        "query($code: LegacyCodeEnum!) { legacy(code: $code) }",
+        None,
+        &schema,
+        &vars,
+        &()
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn uses_custom_input_value_as_default_value() {
+    let schema = schema();
+
+    let (res, errors) = execute(
+        r#"{ __type(name: "Query") { fields { name args { defaultValue } } } }"#,
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(errors, vec![]);
+    let fields = res
+        .as_object_value()
+        .and_then(|o| o.get_field_value("__type"))
+        .and_then(|t| t.as_object_value())
+        .and_then(|t| t.get_field_value("fields"))
+        .and_then(|f| f.as_list_value())
+        .unwrap();
+    assert!(
+        fields.contains(&graphql_value!({
+            "name": "legacyOrDefault",
+            "args": [{"defaultValue": "I01"}],
+        })),
+        "{:?}",
+        fields,
+    );
+
+    let sdl = schema.as_schema_language();
+    assert!(
+        sdl.contains("legacyOrDefault(code: LegacyCodeEnum = I01): Boolean!"),
+        "{}",
+        sdl,
+    );
+}
+
+#[test]
+fn prints_variant_deprecation_in_sdl() {
+    let sdl = schema().as_schema_language();
+
+    assert!(
+        sdl.contains(
+            "enum IgnoredVariantEnum {\n  KNOWN\n  LEGACY @deprecated(reason: \"Use `KNOWN` instead\")\n}",
+        ),
+        "{}",
+        sdl,
+    );
+}
@@ -83,6 +83,7 @@ impl<'a> GraphQLType<DefaultScalarValue> for &'a Fake {
                 name: "fake".to_string(),
                 description: None,
                 deprecation_status: juniper::meta::DeprecationStatus::Current,
+                directives: vec![],
             }],
         );
         meta.into_meta()
@@ -189,3 +190,201 @@ fn test_doc_comment_override() {
     let meta = OverrideDocComment::meta(&(), &mut registry);
     assert_eq!(meta.description(), Some("obj override"));
 }
+
+mod flatten {
+    use juniper::{
+        execute, graphql_object, graphql_value, EmptyMutation, EmptySubscription, RootNode,
+        Variables,
+    };
+
+    use super::*;
+
+    #[derive(GraphQLInputObject, Debug, PartialEq)]
+    struct Pagination {
+        #[graphql(default = "10")]
+        first: i32,
+        after: Option<String>,
+    }
+
+    #[derive(GraphQLInputObject, Debug, PartialEq)]
+    struct UserFilter {
+        name: String,
+        #[graphql(flatten)]
+        page: Pagination,
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn users(filter: UserFilter) -> String {
+            format!(
+                "{} {} {:?}",
+                filter.name, filter.page.first, filter.page.after,
+            )
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn inlines_fields() {
+        let input: InputValue = ::serde_json::from_value(serde_json::json!({
+            "name": "a",
+            "after": "b",
+        }))
+        .unwrap();
+
+        let output: UserFilter = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(
+            output,
+            UserFilter {
+                name: "a".into(),
+                page: Pagination {
+                    first: 10,
+                    after: Some("b".into()),
+                },
+            },
+        );
+
+        let expected: InputValue = ::serde_json::from_value(serde_json::json!({
+            "name": "a",
+            "first": 10,
+            "after": "b",
+        }))
+        .unwrap();
+        assert_eq!(output.to_input_value(), expected);
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            users(filter: {name: "a", first: 5})
+        }"#;
+
+        let schema = schema();
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((graphql_value!({"users": "a 5 None"}), vec![])),
+        );
+    }
+
+    #[tokio::test]
+    async fn is_not_nested_in_introspection() {
+        const DOC: &str = r#"{
+            __type(name: "UserFilter") {
+                inputFields {
+                    name
+                    defaultValue
+                }
+            }
+        }"#;
+
+        let schema = schema();
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({"__type": {"inputFields": [
+                    {"name": "name", "defaultValue": None},
+                    {"name": "first", "defaultValue": "10"},
+                    {"name": "after", "defaultValue": None},
+                ]}}),
+                vec![],
+            )),
+        );
+        assert!(schema.schema.concrete_type_by_name("Pagination").is_none());
+    }
+
+    #[derive(GraphQLInputObject)]
+    struct ClashingFilter {
+        first: Option<i32>,
+        #[graphql(flatten)]
+        page: Pagination,
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Input object `ClashingFilter` has more than one field named `first`"
+    )]
+    fn panics_on_clashing_fields() {
+        let mut registry: Registry = Registry::new(FnvHashMap::default());
+        ClashingFilter::meta(&(), &mut registry);
+    }
+}
+
+mod default_with {
+    use juniper::{
+        execute, graphql_object, graphql_value, EmptyMutation, EmptySubscription, RootNode,
+        Variables,
+    };
+
+    use super::*;
+
+    fn default_tags() -> Vec<String> {
+        vec!["new".into(), "draft".into()]
+    }
+
+    #[derive(GraphQLInputObject, Debug, PartialEq)]
+    struct PostInput {
+        title: String,
+        #[graphql(default_with = default_tags)]
+        tags: Vec<String>,
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn tags(post: PostInput) -> Vec<String> {
+            post.tags
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            a: tags(post: {title: "a"})
+            b: tags(post: {title: "b", tags: ["x"]})
+        }"#;
+
+        let schema = schema();
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((graphql_value!({"a": ["new", "draft"], "b": ["x"]}), vec![],)),
+        );
+    }
+
+    #[tokio::test]
+    async fn has_default_value_in_introspection() {
+        const DOC: &str = r#"{
+            __type(name: "PostInput") {
+                inputFields {
+                    name
+                    defaultValue
+                }
+            }
+        }"#;
+
+        let schema = schema();
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({"__type": {"inputFields": [
+                    {"name": "title", "defaultValue": None},
+                    {"name": "tags", "defaultValue": r#"["new", "draft"]"#},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Tests for `#[derive(GraphQLError)]` macro.
+
+use juniper::{
+    execute, graphql_object, graphql_value, DefaultScalarValue, EmptyMutation, EmptySubscription,
+    ExecutionError, FieldError, GraphQLError, IntoFieldError, RootNode, Variables,
+};
+
+fn into_field_error<E: IntoFieldError>(err: E) -> FieldError {
+    err.into_field_error()
+}
+
+mod plain {
+    use super::*;
+
+    #[derive(GraphQLError)]
+    enum Error {
+        /// User is not authenticated.
+        Unauthenticated,
+
+        #[graphql(message = "Not allowed", code = "DENIED")]
+        Forbidden,
+    }
+
+    #[test]
+    fn uses_doc_comment_as_message() {
+        assert_eq!(
+            into_field_error(Error::Unauthenticated),
+            FieldError::new(
+                "User is not authenticated.",
+                graphql_value!({"code": "UNAUTHENTICATED"}),
+            ),
+        );
+    }
+
+    #[test]
+    fn uses_custom_message_and_code() {
+        assert_eq!(
+            into_field_error(Error::Forbidden),
+            FieldError::new("Not allowed", graphql_value!({"code": "DENIED"})),
+        );
+    }
+}
+
+mod fields {
+    use super::*;
+
+    #[derive(GraphQLError)]
+    enum Error {
+        #[graphql(message = "User `{user_id}` is not found in {{users}}")]
+        NotFound {
+            #[graphql(extension)]
+            user_id: String,
+            #[graphql(extension, name = "searchedIn")]
+            table: String,
+            #[allow(dead_code)]
+            hidden: bool,
+        },
+
+        #[graphql(message = "Retry in {0:.1} seconds ({1} attempts left)")]
+        RateLimited(#[graphql(extension, name = "retryAfter")] f64, i32),
+
+        /// Invalid input.
+        Invalid {
+            #[graphql(extension)]
+            field: Option<String>,
+        },
+    }
+
+    #[test]
+    fn formats_message_and_puts_extensions() {
+        assert_eq!(
+            into_field_error(Error::NotFound {
+                user_id: "42".into(),
+                table: "admins".into(),
+                hidden: true,
+            }),
+            FieldError::new(
+                "User `42` is not found in {users}",
+                graphql_value!({
+                    "code": "NOT_FOUND",
+                    "userId": "42",
+                    "searchedIn": "admins",
+                }),
+            ),
+        );
+    }
+
+    #[test]
+    fn handles_unnamed_fields() {
+        assert_eq!(
+            into_field_error(Error::RateLimited(1.25, 3)),
+            FieldError::new(
+                "Retry in 1.2 seconds (3 attempts left)",
+                graphql_value!({"code": "RATE_LIMITED", "retryAfter": 1.25}),
+            ),
+        );
+    }
+
+    #[test]
+    fn handles_optional_extensions() {
+        assert_eq!(
+            into_field_error(Error::Invalid { field: None }),
+            FieldError::new(
+                "Invalid input.",
+                graphql_value!({"code": "INVALID", "field": None}),
+            ),
+        );
+    }
+}
+
+mod generic {
+    use std::fmt;
+
+    use super::*;
+
+    #[derive(GraphQLError)]
+    enum Error<T: fmt::Display> {
+        #[graphql(message = "Value {0} is out of range")]
+        OutOfRange(#[graphql(extension, name = "value")] T),
+    }
+
+    #[test]
+    fn derives_for_generic_enum() {
+        assert_eq!(
+            into_field_error(Error::OutOfRange(7)),
+            FieldError::new(
+                "Value 7 is out of range",
+                graphql_value!({"code": "OUT_OF_RANGE", "value": 7}),
+            ),
+        );
+    }
+}
+
+mod explicit_scalar {
+    use super::*;
+
+    #[derive(GraphQLError)]
+    #[graphql(scalar = DefaultScalarValue)]
+    enum Error {
+        /// Something went wrong.
+        Failed,
+    }
+
+    #[test]
+    fn derives_for_scalar() {
+        let err: FieldError<DefaultScalarValue> = Error::Failed.into_field_error();
+
+        assert_eq!(err.message(), "Something went wrong.");
+        assert_eq!(err.extensions(), &graphql_value!({"code": "FAILED"}));
+    }
+}
+
+mod codes {
+    use super::*;
+
+    #[derive(GraphQLError)]
+    #[graphql(codes = ErrorCode)]
+    enum Error {
+        /// User is not authenticated.
+        Unauthenticated,
+
+        #[graphql(message = "User `{0}` is not found", code = "NO_USER")]
+        NotFound(String),
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn user(id: String) -> Result<Option<String>, Error> {
+            Err(Error::NotFound(id))
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+            .with_type::<ErrorCode>(&())
+    }
+
+    #[test]
+    fn returns_code() {
+        assert_eq!(Error::Unauthenticated.code(), ErrorCode::Unauthenticated);
+        assert_eq!(Error::NotFound("1".into()).code(), ErrorCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn resolves_into_error() {
+        const DOC: &str = r#"{ user(id: "1") }"#;
+
+        let (res, errs) = execute(DOC, None, &schema(), &Variables::new(), &())
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!({"user": None}));
+        assert_eq!(
+            errs,
+            vec![ExecutionError::new(
+                juniper::parser::SourcePosition::new(2, 0, 2),
+                &["user"],
+                FieldError::new("User `1` is not found", graphql_value!({"code": "NO_USER"}),),
+            )],
+        );
+    }
+
+    #[tokio::test]
+    async fn registers_codes_in_schema() {
+        const DOC: &str = r#"{
+            __type(name: "ErrorCode") {
+                kind
+                enumValues {
+                    name
+                    description
+                }
+            }
+        }"#;
+
+        let schema = schema();
+        let res = execute(DOC, None, &schema, &Variables::new(), &())
+            .await
+            .map(|(res, errs)| (res, errs.is_empty()));
+
+        assert_eq!(
+            res,
+            Ok((
+                graphql_value!({"__type": {
+                    "kind": "ENUM",
+                    "enumValues": [
+                        {"name": "UNAUTHENTICATED", "description": "User is not authenticated."},
+                        {"name": "NO_USER", "description": None},
+                    ],
+                }}),
+                true,
+            )),
+        );
+    }
+
+    #[test]
+    fn is_not_registered_by_default() {
+        let schema: RootNode<'static, Query, EmptyMutation, EmptySubscription> =
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+
+        assert!(schema.schema.concrete_type_by_name("ErrorCode").is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use juniper::{
     execute, graphql_object, graphql_scalar, graphql_value, DefaultScalarValue, EmptyMutation,
-    EmptySubscription, Object, ParseScalarResult, ParseScalarValue, RootNode, Value, Variables,
+    EmptySubscription, FromInputValue, InputValue, InputValueError, Object, ParseScalarResult,
+    ParseScalarValue, RootNode, Value, Variables,
 };
 
 use crate::custom_scalar::MyScalarValue;
@@ -345,3 +346,44 @@ async fn resolves_with_custom_scalar_value() {
         Ok((graphql_value!({"withCustomScalarValue": 0}), vec![])),
     );
 }
+
+#[test]
+fn fallible_from_input_value() {
+    #[derive(Debug, PartialEq)]
+    struct Even(i32);
+
+    #[graphql_scalar]
+    impl GraphQLScalar for Even {
+        fn resolve(&self) -> Value {
+            Value::scalar(self.0)
+        }
+
+        fn from_input_value(v: &InputValue) -> Result<Self, InputValueError> {
+            let i = v
+                .as_int_value()
+                .ok_or_else(|| InputValueError::new("Expected `Int`"))?;
+            if i % 2 != 0 {
+                return Err(InputValueError::new(format!("{} is odd", i)));
+            }
+            Ok(Self(i))
+        }
+
+        fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, DefaultScalarValue> {
+            <i32 as ParseScalarValue>::from_str(value)
+        }
+    }
+
+    let even = InputValue::<DefaultScalarValue>::scalar(2);
+    let odd = InputValue::<DefaultScalarValue>::scalar(3);
+
+    assert_eq!(Even::from_input_value(&even), Some(Even(2)));
+    assert_eq!(Even::from_input_value(&odd), None);
+    assert_eq!(
+        Even::try_from_input_value(&odd),
+        Err(InputValueError::new("3 is odd")),
+    );
+    assert_eq!(
+        Even::try_from_input_value(&InputValue::scalar("2")),
+        Err(InputValueError::new("Expected `Int`")),
+    );
+}
@@ -2,6 +2,7 @@ mod derive_enum;
 mod derive_input_object;
 mod derive_object_with_raw_idents;
 mod derive_scalar;
+mod error_derive;
 mod impl_scalar;
 mod interface_attr;
 mod object_attr;
@@ -623,6 +623,91 @@ mod generic_async {
     }
 }
 
+mod generic_concrete {
+    use super::*;
+
+    struct Tagged<T> {
+        value: T,
+        tag: &'static str,
+    }
+
+    #[graphql_object(
+        concrete(name = "TaggedInt", params(i32)),
+        concrete(name = "TaggedBool", params(bool))
+    )]
+    impl<T: ToString> Tagged<T> {
+        fn value(&self) -> String {
+            self.value.to_string()
+        }
+
+        fn tag(&self) -> &str {
+            self.tag
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn int() -> Tagged<i32> {
+            Tagged {
+                value: 42,
+                tag: "answer",
+            }
+        }
+
+        fn bool() -> Tagged<bool> {
+            Tagged {
+                value: true,
+                tag: "truth",
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            int { __typename value tag }
+            bool { __typename value tag }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({
+                    "int": {"__typename": "TaggedInt", "value": "42", "tag": "answer"},
+                    "bool": {"__typename": "TaggedBool", "value": "true", "tag": "truth"},
+                }),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn registers_each_concrete_type() {
+        const DOC: &str = r#"{
+            int: __type(name: "TaggedInt") { name kind }
+            bool: __type(name: "TaggedBool") { name kind }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({
+                    "int": {"name": "TaggedInt", "kind": "OBJECT"},
+                    "bool": {"name": "TaggedBool", "kind": "OBJECT"},
+                }),
+                vec![],
+            )),
+        );
+        assert!(schema.schema.concrete_type_by_name("Tagged").is_none());
+    }
+}
+
 mod generic_lifetime_async {
     use super::*;
 
@@ -346,6 +346,103 @@ mod generic_lifetime {
     }
 }
 
+mod generic_concrete {
+    use super::*;
+
+    #[derive(GraphQLObject)]
+    struct User {
+        id: i32,
+    }
+
+    #[derive(GraphQLObject)]
+    struct Post {
+        title: &'static str,
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(
+        concrete(name = "UserPage", params(User)),
+        concrete(name = "PostPage", params(Post))
+    )]
+    struct Page<T> {
+        items: Vec<T>,
+        has_next: bool,
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn users() -> Page<User> {
+            Page {
+                items: vec![User { id: 1 }],
+                has_next: false,
+            }
+        }
+
+        fn posts() -> Page<Post> {
+            Page {
+                items: vec![Post { title: "Hello" }],
+                has_next: true,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            users { items { id } hasNext }
+            posts { items { title } hasNext }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({
+                    "users": {"items": [{"id": 1}], "hasNext": false},
+                    "posts": {"items": [{"title": "Hello"}], "hasNext": true},
+                }),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_concrete_type_names() {
+        const DOC: &str = r#"{
+            users { __typename }
+            userPage: __type(name: "UserPage") {
+                fields { name type { ofType { ofType { ofType { name } } } } }
+            }
+            postPage: __type(name: "PostPage") {
+                fields { name type { ofType { ofType { ofType { name } } } } }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({
+                    "users": {"__typename": "UserPage"},
+                    "userPage": {"fields": [
+                        {"name": "items", "type": {"ofType": {"ofType": {"ofType": {"name": "User"}}}}},
+                        {"name": "hasNext", "type": {"ofType": {"ofType": None}}},
+                    ]},
+                    "postPage": {"fields": [
+                        {"name": "items", "type": {"ofType": {"ofType": {"ofType": {"name": "Post"}}}}},
+                        {"name": "hasNext", "type": {"ofType": {"ofType": None}}},
+                    ]},
+                }),
+                vec![],
+            )),
+        );
+    }
+}
+
 mod nested_generic_lifetime_async {
     use super::*;
 
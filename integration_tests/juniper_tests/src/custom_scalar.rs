@@ -2,7 +2,7 @@ use std::{fmt, pin::Pin};
 
 use futures::{stream, Stream};
 use juniper::{
-    execute, graphql_object, graphql_scalar, graphql_subscription,
+    execute, graphql_object, graphql_scalar, graphql_subscription, meta,
     parser::{ParseError, ScalarToken, Spanning, Token},
     serde::de,
     EmptyMutation, FieldResult, GraphQLScalarValue, InputValue, Object, ParseScalarResult,
@@ -273,3 +273,32 @@ fn deserialize_variable() {
         )])
     );
 }
+
+#[test]
+fn applies_directives_with_custom_scalars() {
+    struct Query;
+
+    #[graphql_object(scalar = MyScalarValue)]
+    impl Query {
+        #[graphql(directive = limit(max = i64::from(i32::MAX) + 1))]
+        fn long_field() -> i64 {
+            0
+        }
+    }
+
+    let schema =
+        RootNode::new_with_scalar_value(Query, EmptyMutation::<()>::new(), TestSubscriptionType);
+    let field = schema
+        .schema
+        .concrete_type_by_name("Query")
+        .and_then(|t| t.field_by_name("longField"))
+        .expect("Field not found");
+
+    assert_eq!(
+        field.directives,
+        vec![meta::AppliedDirective::new("limit").argument(
+            "max",
+            InputValue::Scalar(MyScalarValue::Long(i64::from(i32::MAX) + 1)),
+        )],
+    );
+}
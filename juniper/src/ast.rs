@@ -150,6 +150,35 @@ pub type Document<'a, S> = [Definition<'a, S>];
 #[doc(hidden)]
 pub type OwnedDocument<'a, S> = Vec<Definition<'a, S>>;
 
+/// Error returned by a failed [`FromInputValue::try_from_input_value`]
+/// conversion, optionally describing why the value was rejected.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InputValueError {
+    message: Option<String>,
+}
+
+impl InputValueError {
+    /// Constructs a new [`InputValueError`] with the given reason.
+    pub fn new<M: Into<String>>(message: M) -> Self {
+        Self {
+            message: Some(message.into()),
+        }
+    }
+
+    /// Returns the reason of this [`InputValueError`], if any.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl fmt::Display for InputValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message().unwrap_or("Invalid input value"))
+    }
+}
+
+impl std::error::Error for InputValueError {}
+
 /// Parse an unstructured input value into a Rust data type.
 ///
 /// The conversion _can_ fail, and must in that case return None. Implemented
@@ -160,6 +189,16 @@ pub trait FromInputValue<S = DefaultScalarValue>: Sized {
     /// Performs the conversion.
     fn from_input_value(v: &InputValue<S>) -> Option<Self>;
 
+    /// Performs the conversion, describing the reason of a failure.
+    ///
+    /// The reason is reported to clients in validation errors. The default
+    /// implementation uses `from_input_value` and reports no reason, so types
+    /// able to explain their failures should override this method and
+    /// implement `from_input_value` as `Self::try_from_input_value(v).ok()`.
+    fn try_from_input_value(v: &InputValue<S>) -> Result<Self, InputValueError> {
+        Self::from_input_value(v).ok_or_else(InputValueError::default)
+    }
+
     /// Performs the conversion from an absent value (e.g. to distinguish between
     /// implicit and explicit null). The default implementation just uses
     /// `from_input_value` as if an explicit null were provided.
@@ -526,9 +565,20 @@ impl<'a, S> VariableDefinitions<'a, S> {
 
 #[cfg(test)]
 mod tests {
-    use super::InputValue;
+    use super::{InputValue, InputValueError};
     use crate::parser::Spanning;
 
+    #[test]
+    fn test_input_value_error_fmt() {
+        let err = InputValueError::new("Expected a port number");
+        assert_eq!(err.to_string(), "Expected a port number");
+
+        assert_eq!(
+            InputValueError::default().to_string(),
+            "Invalid input value"
+        );
+    }
+
     #[test]
     fn test_input_value_fmt() {
         let value: InputValue = InputValue::null();
@@ -0,0 +1,228 @@
+//! Cache control hints of fields and types, and the cache policies computed
+//! from them while executing operations.
+
+use std::{cmp, fmt};
+
+/// Scope a response may be cached in.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CacheScope {
+    /// Response is the same for all clients, so may be cached by shared
+    /// caches, like CDNs.
+    #[default]
+    Public,
+
+    /// Response is specific to a client, so may only be cached by it, or on
+    /// its behalf.
+    Private,
+}
+
+impl fmt::Display for CacheScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Public => "PUBLIC",
+            Self::Private => "PRIVATE",
+        })
+    }
+}
+
+/// Hint on how long and where the value of a field, or of every field
+/// returning a type, may be cached.
+///
+/// Set with `#[graphql(cache_control(max_age = 60, scope = "PRIVATE"))]` on
+/// fields and types, or with [`Executor::set_cache_hint`] while resolving a field.
+///
+/// [`Executor::set_cache_hint`]: crate::Executor::set_cache_hint
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CacheHint {
+    /// Number of seconds the value may be cached for, if restricted.
+    pub max_age: Option<u32>,
+
+    /// Scope the value may be cached in, if restricted.
+    pub scope: Option<CacheScope>,
+}
+
+impl CacheHint {
+    /// Creates a new [`CacheHint`] not restricting anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the number of seconds the value may be cached for.
+    pub fn max_age(mut self, max_age: u32) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Restricts the scope the value may be cached in.
+    pub fn scope(mut self, scope: CacheScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Indicates whether this [`CacheHint`] doesn't restrict anything.
+    pub fn is_empty(&self) -> bool {
+        self.max_age.is_none() && self.scope.is_none()
+    }
+
+    /// Combines this [`CacheHint`] of a field with the one of the type it
+    /// returns, the former taking precedence.
+    pub(crate) fn or(self, other: Self) -> Self {
+        Self {
+            max_age: self.max_age.or(other.max_age),
+            scope: self.scope.or(other.scope),
+        }
+    }
+
+    /// Combines this [`CacheHint`] with another one into the most restrictive
+    /// of both.
+    pub(crate) fn restrict(self, other: Self) -> Self {
+        Self {
+            max_age: match (self.max_age, other.max_age) {
+                (Some(a), Some(b)) => Some(cmp::min(a, b)),
+                (a, b) => a.or(b),
+            },
+            scope: match (self.scope, other.scope) {
+                (Some(CacheScope::Private), _) | (_, Some(CacheScope::Private)) => {
+                    Some(CacheScope::Private)
+                }
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+/// Policy a response may be cached with, being the most restrictive one of
+/// the [`CacheHint`]s of all its resolved fields.
+///
+/// As in [Apollo Server][1], fields without a `max_age` hint returning objects,
+/// interfaces or unions, as well as root fields, restrict the policy to a zero
+/// `max_age`, while the other fields inherit it from their parents.
+///
+/// [1]: https://www.apollographql.com/docs/apollo-server/performance/caching
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CachePolicy {
+    max_age: Option<u32>,
+    scope: CacheScope,
+}
+
+impl CachePolicy {
+    /// Creates a new [`CachePolicy`] not restricting anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`CachePolicy`] forbidding any caching.
+    pub fn uncacheable() -> Self {
+        Self {
+            max_age: Some(0),
+            scope: CacheScope::default(),
+        }
+    }
+
+    /// Returns the number of seconds a response may be cached for, if
+    /// restricted.
+    pub fn max_age(&self) -> Option<u32> {
+        self.max_age
+    }
+
+    /// Returns the scope a response may be cached in.
+    pub fn scope(&self) -> CacheScope {
+        self.scope
+    }
+
+    /// Indicates whether a response may be cached with this [`CachePolicy`].
+    pub fn is_cacheable(&self) -> bool {
+        matches!(self.max_age, Some(age) if age > 0)
+    }
+
+    /// Restricts this [`CachePolicy`] with the given [`CacheHint`].
+    pub fn restrict(&mut self, hint: &CacheHint) {
+        if let Some(age) = hint.max_age {
+            self.max_age = Some(self.max_age.map_or(age, |a| cmp::min(a, age)));
+        }
+        if hint.scope == Some(CacheScope::Private) {
+            self.scope = CacheScope::Private;
+        }
+    }
+
+    /// Indicates whether restricting this [`CachePolicy`] with the given
+    /// [`CacheHint`] would change it.
+    pub(crate) fn is_restricted_by(&self, hint: &CacheHint) -> bool {
+        hint.max_age
+            .is_some_and(|age| self.max_age.is_none_or(|a| age < a))
+            || (hint.scope == Some(CacheScope::Private) && self.scope != CacheScope::Private)
+    }
+
+    /// Restricts this [`CachePolicy`] with the given one, so it suits the
+    /// responses of both.
+    pub fn merge(&mut self, other: &Self) {
+        self.restrict(&CacheHint {
+            max_age: other.max_age,
+            scope: Some(other.scope),
+        })
+    }
+
+    /// Returns the value of the `Cache-Control` HTTP header for the responses
+    /// cached with this [`CachePolicy`], like `max-age=60, public`.
+    ///
+    /// Uncacheable responses get a `no-store` header value.
+    pub fn header_value(&self) -> String {
+        match self.max_age {
+            Some(age) if age > 0 => format!(
+                "max-age={}, {}",
+                age,
+                match self.scope {
+                    CacheScope::Public => "public",
+                    CacheScope::Private => "private",
+                },
+            ),
+            _ => "no-store".into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheHint, CachePolicy, CacheScope};
+
+    #[test]
+    fn restricts_to_the_minimum() {
+        let mut policy = CachePolicy::new();
+        assert_eq!(policy.max_age(), None);
+        assert!(!policy.is_cacheable());
+
+        policy.restrict(&CacheHint::new().max_age(60));
+        policy.restrict(&CacheHint::new().scope(CacheScope::Public));
+        policy.restrict(&CacheHint::new().max_age(30));
+        policy.restrict(&CacheHint::new().max_age(90));
+        assert_eq!(policy.max_age(), Some(30));
+        assert_eq!(policy.scope(), CacheScope::Public);
+        assert_eq!(policy.header_value(), "max-age=30, public");
+
+        policy.restrict(&CacheHint::new().scope(CacheScope::Private));
+        assert_eq!(policy.scope(), CacheScope::Private);
+        assert_eq!(policy.header_value(), "max-age=30, private");
+
+        policy.merge(&CachePolicy::uncacheable());
+        assert!(!policy.is_cacheable());
+        assert_eq!(policy.header_value(), "no-store");
+    }
+
+    #[test]
+    fn detects_restricting_hints() {
+        let mut policy = CachePolicy::new();
+        assert!(!policy.is_restricted_by(&CacheHint::new()));
+        assert!(!policy.is_restricted_by(&CacheHint::new().scope(CacheScope::Public)));
+        assert!(policy.is_restricted_by(&CacheHint::new().max_age(60)));
+
+        policy.restrict(&CacheHint::new().max_age(30));
+        assert!(!policy.is_restricted_by(&CacheHint::new().max_age(30)));
+        assert!(!policy.is_restricted_by(&CacheHint::new().max_age(90)));
+        assert!(policy.is_restricted_by(&CacheHint::new().max_age(10)));
+        assert!(policy.is_restricted_by(&CacheHint::new().scope(CacheScope::Private)));
+
+        policy.merge(&CachePolicy::uncacheable());
+        policy.restrict(&CacheHint::new().scope(CacheScope::Private));
+        assert!(!policy.is_restricted_by(&CacheHint::new().max_age(0).scope(CacheScope::Private),));
+    }
+}
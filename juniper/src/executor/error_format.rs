@@ -0,0 +1,193 @@
+//! Formatting of the errors of executed operations before they reach clients.
+
+use std::{
+    borrow::Cow,
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher as _, Hasher as _},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+    executor::{ExecutionError, FieldError},
+    value::{DefaultScalarValue, Object, ScalarValue, Value},
+};
+
+/// Hook formatting the [`ExecutionError`]s of executed operations before
+/// they're returned to clients.
+///
+/// Registered on a schema with [`RootNode::with_error_formatter`], it's
+/// applied to the errors of queries, mutations and subscription events alike.
+/// It's implemented for closures taking and returning an [`ExecutionError`],
+/// while [`ErrorMask`] covers the usual case of hiding unexpected errors.
+///
+/// [`RootNode::with_error_formatter`]: crate::RootNode::with_error_formatter
+pub trait ErrorFormatter<S = DefaultScalarValue>: Send + Sync {
+    /// Formats the given [`ExecutionError`].
+    ///
+    /// Its [`ExecutionError::path`] and [`ExecutionError::location`] are best
+    /// kept as they are, so clients can still tell which field failed.
+    fn format_error(&self, error: ExecutionError<S>) -> ExecutionError<S>;
+}
+
+impl<S, F> ErrorFormatter<S> for F
+where
+    F: Fn(ExecutionError<S>) -> ExecutionError<S> + Send + Sync,
+{
+    fn format_error(&self, error: ExecutionError<S>) -> ExecutionError<S> {
+        self(error)
+    }
+}
+
+impl<'a, S> fmt::Debug for dyn ErrorFormatter<S> + 'a {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorFormatter")
+    }
+}
+
+/// [`ErrorFormatter`] replacing the messages of unexpected errors with a
+/// generic one, so internal details (like database errors) don't leak to
+/// clients.
+///
+/// Every masked error gets a fresh correlation ID, put into its
+/// `extensions.correlationId`, which is also passed along with the original
+/// error to the [`ErrorMask::on_masked`] callback, so the details can be
+/// logged server-side and found by the ID reported by a client:
+///
+/// ```
+/// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, ErrorMask, RootNode};
+/// #
+/// # struct Query;
+/// #
+/// # #[graphql_object]
+/// # impl Query {
+/// #     fn ok() -> bool { true }
+/// # }
+/// #
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_error_formatter(ErrorMask::new().on_masked(|error, correlation_id| {
+///         eprintln!(
+///             "[{}] {} at {:?} ({})",
+///             correlation_id,
+///             error.path().join("."),
+///             error.location(),
+///             error.error().message(),
+///         );
+///     }));
+/// ```
+///
+/// Errors having an `extensions.code` are expected to be meant for clients,
+/// so are kept as they are. That can be changed with [`ErrorMask::safe_if`].
+pub struct ErrorMask<S = DefaultScalarValue> {
+    message: Cow<'static, str>,
+    is_safe: Box<IsSafe<S>>,
+    correlation_id: Box<dyn Fn() -> String + Send + Sync>,
+    on_masked: Option<Box<OnMasked<S>>>,
+}
+
+/// Predicate of the errors an [`ErrorMask`] keeps as they are.
+type IsSafe<S> = dyn Fn(&ExecutionError<S>) -> bool + Send + Sync;
+
+/// Callback receiving the errors masked by an [`ErrorMask`].
+type OnMasked<S> = dyn Fn(&ExecutionError<S>, &str) + Send + Sync;
+
+impl<S: ScalarValue> ErrorMask<S> {
+    /// Creates a new [`ErrorMask`] replacing messages of the errors without an
+    /// `extensions.code` with `Internal server error`.
+    pub fn new() -> Self {
+        Self {
+            message: "Internal server error".into(),
+            is_safe: Box::new(has_code),
+            correlation_id: Box::new(random_correlation_id),
+            on_masked: None,
+        }
+    }
+
+    /// Specifies the message to replace the messages of masked errors with.
+    pub fn message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Specifies which errors are safe to be returned to clients as they are.
+    ///
+    /// By default, these are the ones having an `extensions.code`.
+    pub fn safe_if<F>(mut self, is_safe: F) -> Self
+    where
+        F: Fn(&ExecutionError<S>) -> bool + Send + Sync + 'static,
+    {
+        self.is_safe = Box::new(is_safe);
+        self
+    }
+
+    /// Specifies the generator of correlation IDs of masked errors.
+    ///
+    /// By default, they're random 16 hexadecimal digits.
+    pub fn correlation_id<F>(mut self, generate: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.correlation_id = Box::new(generate);
+        self
+    }
+
+    /// Specifies the callback receiving every masked error before it's masked,
+    /// along with its correlation ID.
+    pub fn on_masked<F>(mut self, on_masked: F) -> Self
+    where
+        F: Fn(&ExecutionError<S>, &str) + Send + Sync + 'static,
+    {
+        self.on_masked = Some(Box::new(on_masked));
+        self
+    }
+}
+
+impl<S: ScalarValue> Default for ErrorMask<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> fmt::Debug for ErrorMask<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorMask")
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl<S: ScalarValue> ErrorFormatter<S> for ErrorMask<S> {
+    fn format_error(&self, error: ExecutionError<S>) -> ExecutionError<S> {
+        if (self.is_safe)(&error) {
+            return error;
+        }
+
+        let correlation_id = (self.correlation_id)();
+        if let Some(on_masked) = &self.on_masked {
+            on_masked(&error, &correlation_id);
+        }
+
+        let mut extensions = Object::with_capacity(1);
+        extensions.add_field("correlationId", Value::scalar(correlation_id));
+        error.map_error(|_| FieldError::new(&self.message, Value::Object(extensions)))
+    }
+}
+
+/// Checks whether the given [`ExecutionError`] has an `extensions.code`.
+fn has_code<S: ScalarValue>(error: &ExecutionError<S>) -> bool {
+    error
+        .error()
+        .extensions()
+        .as_object_value()
+        .and_then(|ext| ext.get_field_value("code"))
+        .is_some_and(|code| !code.is_null())
+}
+
+/// Generates a random correlation ID of 16 hexadecimal digits.
+fn random_correlation_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    format!("{:016x}", hasher.finish())
+}
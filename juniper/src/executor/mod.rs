@@ -1,7 +1,7 @@
 //! Resolve the document to values
 
 use std::{
-    borrow::Cow,
+    borrow::{Borrow, Cow},
     cmp::Ordering,
     collections::HashMap,
     fmt::{Debug, Display},
@@ -28,6 +28,8 @@ use crate::{
     types::{
         async_await::{GraphQLTypeAsync, GraphQLValueAsync},
         base::{GraphQLType, GraphQLValue},
+        marker::GraphQLInputObject,
+        merged::{is_same_type, SchemaConflict},
         name::Name,
         subscriptions::{GraphQLSubscriptionType, GraphQLSubscriptionValue},
     },
@@ -36,6 +38,8 @@ use crate::{
 };
 
 pub use self::{
+    cache_control::{CacheHint, CachePolicy, CacheScope},
+    error_format::{ErrorFormatter, ErrorMask},
     look_ahead::{
         Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadMethods,
         LookAheadSelection, LookAheadValue,
@@ -43,6 +47,8 @@ pub use self::{
     owned_executor::OwnedExecutor,
 };
 
+mod cache_control;
+mod error_format;
 mod look_ahead;
 mod owned_executor;
 
@@ -54,6 +60,7 @@ mod owned_executor;
 pub struct Registry<'r, S = DefaultScalarValue> {
     /// Currently registered types
     pub types: FnvHashMap<Name, MetaType<'r, S>>,
+    conflicts: Vec<SchemaConflict>,
 }
 
 #[allow(missing_docs)]
@@ -80,6 +87,7 @@ where
     schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
     errors: &'r RwLock<Vec<ExecutionError<S>>>,
+    cache_policy: &'r RwLock<CachePolicy>,
     field_path: Arc<FieldPath<'a>>,
 }
 
@@ -537,6 +545,7 @@ where
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            cache_policy: self.cache_policy,
             field_path: self.field_path.clone(),
         }
     }
@@ -549,22 +558,25 @@ where
         location: SourcePosition,
         selection_set: Option<&'s [Selection<'a, S>]>,
     ) -> Executor<'s, 'a, CtxT, S> {
+        let field = self
+            .current_type
+            .innermost_concrete()
+            .field_by_name(field_name)
+            .expect("Field not found on inner type");
+        if !field.is_builtin() {
+            self.restrict_cache_policy(field);
+        }
+
         Executor {
             fragments: self.fragments,
             variables: self.variables,
             current_selection_set: selection_set,
             parent_selection_set: self.current_selection_set,
-            current_type: self.schema.make_type(
-                &self
-                    .current_type
-                    .innermost_concrete()
-                    .field_by_name(field_name)
-                    .expect("Field not found on inner type")
-                    .field_type,
-            ),
+            current_type: self.schema.make_type(&field.field_type),
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_policy: self.cache_policy,
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -591,10 +603,48 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_policy: self.cache_policy,
             field_path: self.field_path.clone(),
         }
     }
 
+    /// Restricts the [`CachePolicy`] of the executed operation with the given [`CacheHint`].
+    ///
+    /// This is useful when the cacheability of a field depends on its resolved value, like
+    /// when it's specific to the user being authenticated.
+    pub fn set_cache_hint(&self, hint: CacheHint) {
+        // Most fields don't change the policy any more once it's uncacheable,
+        // so don't contend for the write lock for them.
+        if self.cache_policy.read().unwrap().is_restricted_by(&hint) {
+            self.cache_policy.write().unwrap().restrict(&hint);
+        }
+    }
+
+    /// Restricts the [`CachePolicy`] of the executed operation with the [`CacheHint`]s of the
+    /// given field being resolved and of the type it returns.
+    ///
+    /// Root fields and the ones returning composite types default to a zero `max_age`, while
+    /// the other fields inherit it from their parents.
+    fn restrict_cache_policy(&self, field: &Field<'a, S>) {
+        let field_type = self
+            .schema
+            .concrete_type_by_name(field.field_type.innermost_name());
+
+        let mut hint = field
+            .cache_control
+            .or(field_type.map(MetaType::cache_control).unwrap_or_default());
+        if hint.max_age.is_none()
+            && (matches!(*self.field_path, FieldPath::Root(_))
+                || field_type.is_some_and(MetaType::is_composite))
+        {
+            hint.max_age = Some(0);
+        }
+
+        if !hint.is_empty() {
+            self.set_cache_hint(hint);
+        }
+    }
+
     /// `Executor`'s current selection set
     pub(crate) fn current_selection_set(&self) -> Option<&[Selection<'a, S>]> {
         self.current_selection_set
@@ -737,6 +787,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: RwLock::new(CachePolicy::new()),
             field_path: Arc::clone(&self.field_path),
         }
     }
@@ -784,8 +835,23 @@ impl<S> ExecutionError<S> {
     pub fn path(&self) -> &[String] {
         &self.path
     }
+
+    /// Maps the [`FieldError`] of this error, keeping its location and path.
+    pub fn map_error<F>(self, f: F) -> ExecutionError<S>
+    where
+        F: FnOnce(FieldError<S>) -> FieldError<S>,
+    {
+        ExecutionError {
+            location: self.location,
+            path: self.path,
+            error: f(self.error),
+        }
+    }
 }
 
+/// Result of executing an operation, along with the [`CachePolicy`] of its response.
+pub(crate) type CacheableOutput<S> = (Value<S>, Vec<ExecutionError<S>>, CachePolicy);
+
 /// Create new `Executor` and start query/mutation execution.
 /// Returns `IsSubscription` error if subscription is passed.
 pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
@@ -795,6 +861,32 @@ pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    execute_validated_query_with_cache_policy(document, operation, root_node, variables, context)
+        .map(|(value, errors, _)| (value, errors))
+}
+
+/// Same as [`execute_validated_query`], but also returns the [`CachePolicy`] computed from
+/// the [`CacheHint`]s of the resolved fields.
+pub(crate) fn execute_validated_query_with_cache_policy<
+    'a,
+    'b,
+    QueryT,
+    MutationT,
+    SubscriptionT,
+    S,
+>(
+    document: &'b Document<S>,
+    operation: &'b Spanning<Operation<S>>,
+    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<CacheableOutput<S>, GraphQLError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -825,6 +917,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_policy = RwLock::new(CachePolicy::new());
     let value;
 
     {
@@ -862,6 +955,7 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_policy: &cache_policy,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -877,7 +971,12 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    let cache_policy = match operation.item.operation_type {
+        OperationType::Query => cache_policy.into_inner().unwrap(),
+        OperationType::Mutation | OperationType::Subscription => CachePolicy::uncacheable(),
+    };
+
+    Ok((value, root_node.format_errors(errors), cache_policy))
 }
 
 /// Create new `Executor` and start asynchronous query execution.
@@ -889,6 +988,39 @@ pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, Subscripti
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_validated_query_async_with_cache_policy(
+        document, operation, root_node, variables, context,
+    )
+    .await
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Same as [`execute_validated_query_async`], but also returns the [`CachePolicy`] computed
+/// from the [`CacheHint`]s of the resolved fields.
+pub(crate) async fn execute_validated_query_async_with_cache_policy<
+    'a,
+    'b,
+    QueryT,
+    MutationT,
+    SubscriptionT,
+    S,
+>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<CacheableOutput<S>, GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -923,6 +1055,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_policy = RwLock::new(CachePolicy::new());
     let value;
 
     {
@@ -960,6 +1093,7 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_policy: &cache_policy,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -981,7 +1115,12 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    let cache_policy = match operation.item.operation_type {
+        OperationType::Query => cache_policy.into_inner().unwrap(),
+        OperationType::Mutation | OperationType::Subscription => CachePolicy::uncacheable(),
+    };
+
+    Ok((value, root_node.format_errors(errors), cache_policy))
 }
 
 #[doc(hidden)]
@@ -1070,6 +1209,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_policy = RwLock::new(CachePolicy::new());
     let value;
 
     {
@@ -1106,6 +1246,7 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_policy: &cache_policy,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -1122,7 +1263,10 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    Ok((
+        root_node.format_stream_errors(value),
+        root_node.format_errors(errors),
+    ))
 }
 
 impl<'r, S> Registry<'r, S>
@@ -1131,7 +1275,44 @@ where
 {
     /// Construct a new registry
     pub fn new(types: FnvHashMap<Name, MetaType<'r, S>>) -> Registry<'r, S> {
-        Registry { types }
+        Registry {
+            types,
+            conflicts: vec![],
+        }
+    }
+
+    /// Conflicts found while registering types, which make the schema invalid
+    pub fn conflicts(&self) -> &[SchemaConflict] {
+        &self.conflicts
+    }
+
+    pub(crate) fn add_conflict(&mut self, conflict: SchemaConflict) {
+        if !self.conflicts.contains(&conflict) {
+            self.conflicts.push(conflict);
+        }
+    }
+
+    /// Moves the types and conflicts of the `other` registry into this one, recording a conflict
+    /// for each type whose name is already registered with a different shape.
+    pub(crate) fn merge(&mut self, other: Registry<'r, S>) {
+        for conflict in other.conflicts {
+            self.add_conflict(conflict);
+        }
+        for (name, meta) in other.types {
+            match self.types.get(&name) {
+                Some(existing) => {
+                    if !is_same_type(existing, &meta) {
+                        let type_name: &str = name.borrow();
+                        self.add_conflict(SchemaConflict::DuplicateType {
+                            type_name: type_name.to_owned(),
+                        });
+                    }
+                }
+                None => {
+                    self.types.insert(name, meta);
+                }
+            }
+        }
     }
 
     /// Get the `Type` instance for a given GraphQL type
@@ -1169,6 +1350,8 @@ where
             arguments: None,
             field_type: self.get_type::<T>(info),
             deprecation_status: DeprecationStatus::Current,
+            directives: vec![],
+            cache_control: CacheHint::default(),
         }
     }
 
@@ -1187,6 +1370,8 @@ where
             arguments: None,
             field_type: self.get_type::<I>(info),
             deprecation_status: DeprecationStatus::Current,
+            directives: vec![],
+            cache_control: CacheHint::default(),
         }
     }
 
@@ -1273,7 +1458,7 @@ where
     pub fn build_enum_type<T>(
         &mut self,
         info: &T::TypeInfo,
-        values: &[EnumValue],
+        values: &[EnumValue<S>],
     ) -> EnumMeta<'r, S>
     where
         T: FromInputValue<S> + GraphQLType<S> + ?Sized,
@@ -1301,7 +1486,11 @@ where
     }
 
     /// Create a union meta type
-    pub fn build_union_type<T>(&mut self, info: &T::TypeInfo, types: &[Type<'r>]) -> UnionMeta<'r>
+    pub fn build_union_type<T>(
+        &mut self,
+        info: &T::TypeInfo,
+        types: &[Type<'r>],
+    ) -> UnionMeta<'r, S>
     where
         T: GraphQLType<S> + ?Sized,
     {
@@ -1321,6 +1510,37 @@ where
     {
         let name = T::name(info).expect("Input object types must be named. Implement name()");
 
+        // Flattened fields may clash with each other or with the fields declared alongside them.
+        for (i, arg) in args.iter().enumerate() {
+            if args[..i].iter().any(|a| a.name == arg.name) {
+                panic!(
+                    "Input object `{}` has more than one field named `{}`",
+                    name, arg.name,
+                );
+            }
+        }
+
         InputObjectMeta::new::<T>(Cow::Owned(name.to_string()), args)
     }
+
+    /// Create the fields of an input object, to be inlined into another input object
+    ///
+    /// The input object itself isn't registered, so it doesn't appear in the schema unless used
+    /// elsewhere.
+    ///
+    /// # Panics
+    ///
+    /// If the [`GraphQLType::meta()`] of `T` doesn't build an input object.
+    pub fn flattened_input_fields<T>(&mut self, info: &T::TypeInfo) -> Vec<Argument<'r, S>>
+    where
+        T: FromInputValue<S> + GraphQLInputObject<S> + ?Sized,
+    {
+        match T::meta(info, self) {
+            MetaType::InputObject(InputObjectMeta { input_fields, .. }) => input_fields,
+            _ => panic!(
+                "Only input objects can be flattened, `{}` is not",
+                T::name(info).unwrap_or("<unnamed>"),
+            ),
+        }
+    }
 }
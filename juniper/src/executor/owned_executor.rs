@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     ast::Fragment,
-    executor::FieldPath,
+    executor::{CachePolicy, FieldPath},
     parser::SourcePosition,
     schema::model::{SchemaType, TypeType},
     ExecutionError, Executor, Selection, Variables,
@@ -22,6 +22,7 @@ pub struct OwnedExecutor<'a, CtxT, S> {
     pub(super) schema: &'a SchemaType<'a, S>,
     pub(super) context: &'a CtxT,
     pub(super) errors: RwLock<Vec<ExecutionError<S>>>,
+    pub(super) cache_policy: RwLock<CachePolicy>,
     pub(super) field_path: Arc<FieldPath<'a>>,
 }
 
@@ -39,6 +40,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: RwLock::new(CachePolicy::new()),
             field_path: self.field_path.clone(),
         }
     }
@@ -66,6 +68,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: RwLock::new(CachePolicy::new()),
             field_path: self.field_path.clone(),
         }
     }
@@ -99,6 +102,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_policy: RwLock::new(CachePolicy::new()),
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -118,6 +122,7 @@ where
             schema: self.schema,
             context: self.context,
             errors: &self.errors,
+            cache_policy: &self.cache_policy,
             field_path: Arc::clone(&self.field_path),
         }
     }
@@ -0,0 +1,197 @@
+use crate::{
+    executor::{CacheHint, CachePolicy, CacheScope, Executor},
+    graphql_interface, graphql_object,
+    http::GraphQLRequest,
+    schema::model::RootNode,
+    types::scalars::EmptySubscription,
+    GraphQLObject, GraphQLUnion,
+};
+
+#[derive(GraphQLObject)]
+#[graphql(cache_control(max_age = 60))]
+struct Post {
+    title: String,
+    #[graphql(cache_control(max_age = 30))]
+    views: i32,
+    author: Author,
+}
+
+#[derive(GraphQLObject)]
+struct Author {
+    name: String,
+}
+
+#[graphql_interface(for = Article)]
+#[graphql_interface(cache_control(max_age = 120))]
+trait Node {
+    fn id(&self) -> &str;
+}
+
+#[derive(GraphQLObject)]
+#[graphql(impl = NodeValue, cache_control(max_age = 240))]
+struct Article {
+    id: String,
+}
+
+#[graphql_interface]
+impl Node for Article {
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[derive(GraphQLUnion)]
+#[graphql(cache_control(max_age = 15))]
+enum SearchResult {
+    Article(Article),
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn version() -> &'static str {
+        "1.0"
+    }
+
+    #[graphql(cache_control(max_age = 300))]
+    fn motd() -> &'static str {
+        "Hello"
+    }
+
+    fn post() -> Post {
+        Post {
+            title: "Caching".into(),
+            views: 42,
+            author: Author {
+                name: "Jane".into(),
+            },
+        }
+    }
+
+    #[graphql(cache_control(max_age = 90, scope = "PRIVATE"))]
+    fn me() -> Author {
+        Author {
+            name: "John".into(),
+        }
+    }
+
+    #[graphql(cache_control(max_age = 60))]
+    fn stock<S: crate::ScalarValue>(executor: &Executor<'_, '_, (), S>) -> i32 {
+        executor.set_cache_hint(CacheHint::new().max_age(5));
+        3
+    }
+
+    fn node() -> NodeValue {
+        Article { id: "1".into() }.into()
+    }
+
+    fn search() -> Vec<SearchResult> {
+        vec![SearchResult::Article(Article { id: "2".into() })]
+    }
+}
+
+struct Mutation;
+
+#[graphql_object]
+impl Mutation {
+    #[graphql(cache_control(max_age = 60))]
+    fn like() -> i32 {
+        1
+    }
+}
+
+type Schema = RootNode<'static, Query, Mutation, EmptySubscription>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, Mutation, EmptySubscription::new())
+}
+
+async fn run(query: &str) -> Option<CachePolicy> {
+    let req = GraphQLRequest::new(query.into(), None, None);
+    let schema = schema();
+    let async_policy = req.execute(&schema, &()).await.cache_policy();
+    let sync_policy = req.execute_sync(&schema, &()).cache_policy();
+    assert_eq!(async_policy, sync_policy, "sync and async policies differ");
+    async_policy
+}
+
+fn policy(max_age: u32, scope: CacheScope) -> Option<CachePolicy> {
+    let mut policy = CachePolicy::new();
+    policy.restrict(&CacheHint::new().max_age(max_age).scope(scope));
+    Some(policy)
+}
+
+#[tokio::test]
+async fn root_scalar_field_without_hint_is_uncacheable() {
+    assert_eq!(run("{ version }").await, None);
+}
+
+#[tokio::test]
+async fn uses_field_hint() {
+    assert_eq!(run("{ motd }").await, policy(300, CacheScope::Public));
+}
+
+#[tokio::test]
+async fn uses_type_hint() {
+    assert_eq!(
+        run("{ post { title } }").await,
+        policy(60, CacheScope::Public),
+    );
+}
+
+#[tokio::test]
+async fn takes_minimum_over_selection_set() {
+    assert_eq!(
+        run("{ motd post { title views } }").await,
+        policy(30, CacheScope::Public),
+    );
+}
+
+#[tokio::test]
+async fn composite_field_without_hint_is_uncacheable() {
+    assert_eq!(run("{ post { author { name } } }").await, None);
+}
+
+#[tokio::test]
+async fn private_scope_is_sticky() {
+    assert_eq!(
+        run("{ motd me { name } }").await,
+        policy(90, CacheScope::Private),
+    );
+}
+
+#[tokio::test]
+async fn restricts_dynamically() {
+    assert_eq!(run("{ stock }").await, policy(5, CacheScope::Public));
+}
+
+#[tokio::test]
+async fn uses_interface_and_union_hints() {
+    assert_eq!(
+        run("{ node { id } }").await,
+        policy(120, CacheScope::Public),
+    );
+    assert_eq!(
+        run("{ search { ... on Article { id } } }").await,
+        policy(15, CacheScope::Public),
+    );
+}
+
+#[tokio::test]
+async fn ignores_typename() {
+    assert_eq!(
+        run("{ __typename motd }").await,
+        policy(300, CacheScope::Public),
+    );
+}
+
+#[tokio::test]
+async fn mutation_is_uncacheable() {
+    assert_eq!(run("mutation { like }").await, None);
+}
+
+#[tokio::test]
+async fn failed_request_is_uncacheable() {
+    assert_eq!(run("{ unknown }").await, None);
+}
@@ -4,7 +4,7 @@ use crate::{
     parser::SourcePosition,
     schema::model::RootNode,
     types::scalars::{EmptyMutation, EmptySubscription},
-    validation::RuleError,
+    validation::{RuleError, RuleErrorCode},
     value::{DefaultScalarValue, Object},
     GraphQLEnum,
     GraphQLError::ValidationError,
@@ -101,7 +101,8 @@ async fn does_not_accept_string_literals() {
         ValidationError(vec![RuleError::new(
             r#"Invalid value for argument "color", expected type "Color!""#,
             &[SourcePosition::new(18, 0, 18)],
-        )])
+        )
+        .with_code(RuleErrorCode::ArgumentsOfCorrectType)])
     );
 }
 
@@ -144,7 +145,8 @@ async fn does_not_accept_incorrect_enum_name_in_variables() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$color" got invalid value. Invalid value for enum "Color"."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -170,6 +172,7 @@ async fn does_not_accept_incorrect_type_in_variables() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$color" got invalid value. Expected "Color", found not a string or enum."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
@@ -0,0 +1,212 @@
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use futures::{stream, Stream, StreamExt as _};
+
+use crate::{
+    executor::{ErrorMask, ExecutionError, FieldError, FieldResult, Variables},
+    graphql_object, graphql_subscription, graphql_value,
+    parser::SourcePosition,
+    schema::model::RootNode,
+    types::scalars::EmptyMutation,
+    value::{DefaultScalarValue, Value},
+};
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn unexpected() -> FieldResult<Option<i32>> {
+        Err("connection to 10.0.0.1 refused".into())
+    }
+
+    fn expected() -> FieldResult<Option<i32>> {
+        Err(FieldError::new(
+            "Not found",
+            graphql_value!({"code": "NOT_FOUND"}),
+        ))
+    }
+
+    fn ok() -> i32 {
+        1
+    }
+}
+
+struct Subscription;
+
+type IntStream = Pin<Box<dyn Stream<Item = FieldResult<i32>> + Send>>;
+
+#[graphql_subscription]
+impl Subscription {
+    async fn numbers() -> IntStream {
+        Box::pin(stream::iter(vec![Ok(1), Err("disk is full".into())]))
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation, Subscription>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, EmptyMutation::new(), Subscription)
+}
+
+async fn execute(schema: &Schema, query: &str) -> (Value, Vec<ExecutionError<DefaultScalarValue>>) {
+    crate::execute(query, None, schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed")
+}
+
+#[tokio::test]
+async fn errors_are_not_formatted_by_default() {
+    let (_, errs) = execute(&schema(), "{ unexpected }").await;
+
+    assert_eq!(
+        errs,
+        vec![ExecutionError::new(
+            SourcePosition::new(2, 0, 2),
+            &["unexpected"],
+            FieldError::new("connection to 10.0.0.1 refused", Value::null()),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn masks_unexpected_errors() {
+    let masked = Arc::new(Mutex::new(vec![]));
+    let schema = schema().with_error_formatter({
+        let masked = masked.clone();
+        ErrorMask::new()
+            .correlation_id(|| "42".into())
+            .on_masked(move |error, id| {
+                masked.lock().unwrap().push((
+                    error.path().to_vec(),
+                    *error.location(),
+                    error.error().message().to_owned(),
+                    id.to_owned(),
+                ));
+            })
+    });
+
+    let (res, errs) = execute(&schema, "{ ok unexpected expected }").await;
+
+    assert_eq!(
+        res,
+        graphql_value!({"ok": 1, "unexpected": None, "expected": None}),
+    );
+    assert_eq!(
+        errs,
+        vec![
+            ExecutionError::new(
+                SourcePosition::new(5, 0, 5),
+                &["unexpected"],
+                FieldError::new(
+                    "Internal server error",
+                    graphql_value!({"correlationId": "42"}),
+                ),
+            ),
+            ExecutionError::new(
+                SourcePosition::new(16, 0, 16),
+                &["expected"],
+                FieldError::new("Not found", graphql_value!({"code": "NOT_FOUND"})),
+            ),
+        ],
+    );
+    assert_eq!(
+        *masked.lock().unwrap(),
+        vec![(
+            vec!["unexpected".to_owned()],
+            SourcePosition::new(5, 0, 5),
+            "connection to 10.0.0.1 refused".to_owned(),
+            "42".to_owned(),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn masks_with_custom_message_and_safety() {
+    let schema = schema().with_error_formatter(
+        ErrorMask::new()
+            .message("Oops")
+            .safe_if(|e| e.error().message().starts_with("connection")),
+    );
+
+    let (_, errs) = execute(&schema, "{ unexpected expected }").await;
+
+    assert_eq!(errs[0].error().message(), "connection to 10.0.0.1 refused");
+    assert_eq!(errs[1].error().message(), "Oops");
+    let id = errs[1]
+        .error()
+        .extensions()
+        .as_object_value()
+        .and_then(|ext| ext.get_field_value("correlationId"))
+        .and_then(Value::as_string_value)
+        .expect("correlation ID");
+    assert_eq!(id.len(), 16);
+}
+
+#[tokio::test]
+async fn generates_unique_correlation_ids() {
+    let schema = schema().with_error_formatter(ErrorMask::new());
+
+    let (_, first) = execute(&schema, "{ unexpected }").await;
+    let (_, second) = execute(&schema, "{ unexpected }").await;
+
+    assert_ne!(
+        first[0].error().extensions(),
+        second[0].error().extensions()
+    );
+}
+
+#[tokio::test]
+async fn formats_errors_with_closures() {
+    let schema = schema().with_error_formatter(|e: ExecutionError<DefaultScalarValue>| {
+        let path = e.path().join(".");
+        e.map_error(|err| FieldError::new(format!("{}: {}", path, err.message()), Value::null()))
+    });
+
+    let (_, errs) = execute(&schema, "{ expected }").await;
+
+    assert_eq!(errs[0].error().message(), "expected: Not found");
+    assert_eq!(errs[0].location(), &SourcePosition::new(2, 0, 2));
+}
+
+#[tokio::test]
+async fn masks_subscription_errors() {
+    let schema = schema().with_error_formatter(ErrorMask::new().correlation_id(|| "7".into()));
+
+    let (res, errs) = crate::resolve_into_stream(
+        "subscription { numbers }",
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    )
+    .await
+    .expect("Execution failed");
+    assert_eq!(errs, []);
+
+    let stream = match res {
+        Value::Object(mut fields) => match fields.get_mut_field_value("numbers") {
+            Some(Value::Scalar(stream)) => std::mem::replace(stream, Box::pin(stream::empty())),
+            _ => panic!("no stream"),
+        },
+        _ => panic!("not an object"),
+    };
+    let events = stream.collect::<Vec<_>>().await;
+
+    assert_eq!(
+        events,
+        vec![
+            Ok(graphql_value!(1)),
+            Err(ExecutionError::new(
+                SourcePosition::new(15, 0, 15),
+                &["numbers"],
+                FieldError::new(
+                    "Internal server error",
+                    graphql_value!({"correlationId": "7"})
+                ),
+            )),
+        ],
+    );
+}
@@ -0,0 +1,278 @@
+use crate::{
+    executor::{CacheHint, CacheScope},
+    graphql_object, graphql_value,
+    schema::{
+        meta::{MetaType, ObjectMeta},
+        model::RootNode,
+    },
+    types::{
+        merged::{MergedObject, SchemaConflict},
+        scalars::{EmptyMutation, EmptySubscription},
+    },
+    GraphQLObject, Value, Variables,
+};
+
+struct Context {
+    user_name: String,
+}
+
+impl crate::Context for Context {}
+
+#[derive(GraphQLObject)]
+struct User {
+    name: String,
+}
+
+struct UserQuery;
+
+#[graphql_object(name = "Query", context = Context)]
+impl UserQuery {
+    fn me(context: &Context) -> User {
+        User {
+            name: context.user_name.clone(),
+        }
+    }
+}
+
+struct PostQuery {
+    count: i32,
+}
+
+#[graphql_object(context = Context)]
+impl PostQuery {
+    fn post_count(&self) -> i32 {
+        self.count
+    }
+
+    async fn author(context: &Context) -> User {
+        User {
+            name: context.user_name.clone(),
+        }
+    }
+}
+
+struct TagQuery;
+
+#[graphql_object(context = Context)]
+impl TagQuery {
+    fn tags() -> Vec<&'static str> {
+        vec!["rust", "graphql"]
+    }
+}
+
+fn context() -> Context {
+    Context {
+        user_name: "Alice".to_owned(),
+    }
+}
+
+#[tokio::test]
+async fn resolves_fields_of_merged_objects() {
+    let schema = RootNode::try_new(
+        MergedObject::new(
+            UserQuery,
+            MergedObject::new(PostQuery { count: 3 }, TagQuery),
+        ),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .expect("no conflicts");
+
+    let doc = r#"{
+        __typename
+        me { name }
+        postCount
+        author { name }
+        tags
+    }"#;
+
+    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &context())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        graphql_value!({
+            "__typename": "Query",
+            "me": {"name": "Alice"},
+            "postCount": 3,
+            "author": {"name": "Alice"},
+            "tags": ["rust", "graphql"],
+        }),
+    );
+}
+
+#[test]
+fn resolves_fields_of_merged_objects_sync() {
+    let schema = RootNode::new(
+        MergedObject::new(UserQuery, TagQuery),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    );
+
+    let doc = r#"{ me { name } tags }"#;
+
+    let (result, errs) = crate::execute_sync(doc, None, &schema, &Variables::new(), &context())
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        graphql_value!({
+            "me": {"name": "Alice"},
+            "tags": ["rust", "graphql"],
+        }),
+    );
+}
+
+#[test]
+fn merges_fields_into_single_type() {
+    let schema = RootNode::new(
+        MergedObject::new(UserQuery, PostQuery { count: 0 }),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    );
+
+    let fields = match schema.schema.concrete_type_by_name("Query") {
+        Some(MetaType::Object(ObjectMeta { fields, .. })) => fields,
+        _ => panic!("Query is not an object"),
+    };
+    let fields = fields
+        .iter()
+        .filter(|f| !f.is_builtin())
+        .map(|f| f.name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["me", "postCount", "author"]);
+    assert!(schema.schema.concrete_type_by_name("PostQuery").is_none());
+}
+
+#[test]
+fn restricts_cache_control_of_both_types() {
+    struct CachedQuery;
+
+    #[graphql_object(name = "Query", context = Context, cache_control(max_age = 60))]
+    impl CachedQuery {
+        fn me(context: &Context) -> User {
+            UserQuery::me(context)
+        }
+    }
+
+    struct PrivateQuery;
+
+    #[graphql_object(context = Context, cache_control(max_age = 30, scope = "PRIVATE"))]
+    impl PrivateQuery {
+        fn tags() -> Vec<&'static str> {
+            TagQuery::tags()
+        }
+    }
+
+    let schema = RootNode::new(
+        MergedObject::new(CachedQuery, PrivateQuery),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    );
+
+    assert_eq!(
+        schema
+            .schema
+            .concrete_type_by_name("Query")
+            .map(MetaType::cache_control),
+        Some(CacheHint::new().max_age(30).scope(CacheScope::Private)),
+    );
+}
+
+#[test]
+fn reports_duplicate_fields() {
+    struct OtherUserQuery;
+
+    #[graphql_object(context = Context)]
+    impl OtherUserQuery {
+        fn me() -> Option<User> {
+            None
+        }
+    }
+
+    let conflicts = RootNode::try_new(
+        MergedObject::new(UserQuery, OtherUserQuery),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .err()
+    .expect("conflicts");
+
+    assert_eq!(
+        conflicts,
+        [SchemaConflict::DuplicateField {
+            type_name: "Query".to_owned(),
+            field_name: "me".to_owned(),
+        }],
+    );
+}
+
+#[test]
+fn reports_duplicate_types() {
+    #[derive(GraphQLObject)]
+    #[graphql(name = "User")]
+    struct Account {
+        id: i32,
+    }
+
+    struct AccountQuery;
+
+    #[graphql_object(context = Context)]
+    impl AccountQuery {
+        fn account() -> Account {
+            Account { id: 1 }
+        }
+    }
+
+    let conflicts = RootNode::try_new(
+        MergedObject::new(UserQuery, AccountQuery),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .err()
+    .expect("conflicts");
+
+    assert_eq!(
+        conflicts,
+        [SchemaConflict::DuplicateType {
+            type_name: "User".to_owned(),
+        }],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Field \"me\" is defined more than once on type \"Query\"")]
+fn panics_on_conflicts_without_try() {
+    let _ = RootNode::new(
+        MergedObject::new(UserQuery, UserQuery),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    );
+}
+
+#[test]
+fn shares_equal_types() {
+    struct OtherUserQuery;
+
+    #[graphql_object(context = Context)]
+    impl OtherUserQuery {
+        fn users() -> Vec<User> {
+            vec![]
+        }
+    }
+
+    let schema = RootNode::try_new(
+        MergedObject::new(UserQuery, OtherUserQuery),
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+    .expect("no conflicts");
+
+    assert!(matches!(
+        crate::execute_sync("{ users { name } }", None, &schema, &Variables::new(), &context()),
+        Ok((Value::Object(_), ref errs)) if errs.is_empty(),
+    ));
+}
@@ -1,8 +1,14 @@
+mod cache_control;
 mod directives;
 mod enums;
+mod error_format;
 mod executor;
 mod introspection;
+mod merged;
+mod validators;
 mod variables;
+#[cfg(feature = "wide-integers")]
+mod wide_integers;
 
 mod interfaces_unions;
 
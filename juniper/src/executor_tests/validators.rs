@@ -0,0 +1,316 @@
+use crate::{
+    ast::InputValue,
+    executor::Variables,
+    graphql_value,
+    parser::SourcePosition,
+    schema::{meta::InputValidator, model::RootNode},
+    types::scalars::{EmptyMutation, EmptySubscription},
+    validation::{RuleError, RuleErrorCode},
+    value::DefaultScalarValue,
+    GraphQLError::ValidationError,
+    GraphQLInputObject,
+};
+
+#[derive(GraphQLInputObject, Debug)]
+struct PostInput {
+    #[graphql(validate(length(min = 1, max = 10)))]
+    title: String,
+    #[graphql(validate(items(max = 2), length(max = 5)))]
+    tags: Option<Vec<String>>,
+    #[cfg_attr(feature = "regex", graphql(validate(pattern = "^[a-z-]+$")))]
+    slug: Option<String>,
+    #[graphql(validate(range(min = -1.5), custom = even))]
+    rank: Option<i32>,
+}
+
+fn even(n: &i32) -> Result<(), String> {
+    if n % 2 == 0 {
+        Ok(())
+    } else {
+        Err(format!("Expected an even number, found {}", n))
+    }
+}
+
+struct TestType;
+
+#[crate::graphql_object]
+impl TestType {
+    fn post_title(post: PostInput) -> String {
+        post.title
+    }
+
+    fn page(#[graphql(validate(range(min = 1, max = 100)))] size: i32) -> i32 {
+        size
+    }
+}
+
+async fn validation_errors(query: &str, vars: Variables<DefaultScalarValue>) -> Vec<RuleError> {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    match crate::execute(query, None, &schema, &vars, &()).await {
+        Err(ValidationError(errs)) => errs,
+        res => panic!("Expected validation errors, got: {:?}", res),
+    }
+}
+
+fn argument_error(message: &str, pos: SourcePosition) -> RuleError {
+    RuleError::new(message, &[pos]).with_code(RuleErrorCode::ArgumentsOfCorrectType)
+}
+
+fn variable_error(message: &str, pos: SourcePosition) -> RuleError {
+    RuleError::new(message, &[pos]).with_code(RuleErrorCode::VariableValue)
+}
+
+#[tokio::test]
+async fn accepts_valid_values() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let query = r#"query q($size: Int!) {
+        postTitle(post: {title: "Hello", tags: ["a", "b"], slug: "hello-world", rank: 2})
+        page(size: $size)
+    }"#;
+    let vars = vec![("size".to_owned(), InputValue::scalar(100))]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        crate::execute(query, None, &schema, &vars, &()).await,
+        Ok((graphql_value!({"postTitle": "Hello", "page": 100}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn rejects_literals_violating_length() {
+    let errs = validation_errors(
+        r#"{ postTitle(post: {title: "Hello, world!"}) }"#,
+        Variables::new(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![argument_error(
+            r#"Invalid value for argument "post", expected type "PostInput!": In field "title": Expected at most 10 characters, found 13"#,
+            SourcePosition::new(18, 0, 18),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn rejects_literals_violating_list_constraints() {
+    let errs = validation_errors(
+        r#"{
+            a: postTitle(post: {title: "a", tags: ["a", "b", "c"]})
+            b: postTitle(post: {title: "b", tags: ["a", "toolong"]})
+        }"#,
+        Variables::new(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![
+            argument_error(
+                r#"Invalid value for argument "post", expected type "PostInput!": In field "tags": Expected at most 2 items, found 3"#,
+                SourcePosition::new(33, 1, 31),
+            ),
+            argument_error(
+                r#"Invalid value for argument "post", expected type "PostInput!": In field "tags": In element #1: Expected at most 5 characters, found 7"#,
+                SourcePosition::new(101, 2, 31),
+            ),
+        ],
+    );
+}
+
+#[cfg(feature = "regex")]
+#[tokio::test]
+async fn rejects_literals_violating_pattern() {
+    let errs = validation_errors(
+        r#"{ postTitle(post: {title: "a", slug: "Hello World"}) }"#,
+        Variables::new(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![argument_error(
+            r#"Invalid value for argument "post", expected type "PostInput!": In field "slug": Expected a string matching "^[a-z-]+$", found "Hello World""#,
+            SourcePosition::new(18, 0, 18),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn rejects_literals_violating_range_and_custom_validators() {
+    let errs = validation_errors(
+        r#"{
+            a: postTitle(post: {title: "a", rank: -2})
+            b: postTitle(post: {title: "b", rank: 3})
+            page(size: 0)
+        }"#,
+        Variables::new(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![
+            argument_error(
+                r#"Invalid value for argument "post", expected type "PostInput!": In field "rank": Expected at least -1.5, found -2"#,
+                SourcePosition::new(33, 1, 31),
+            ),
+            argument_error(
+                r#"Invalid value for argument "post", expected type "PostInput!": In field "rank": Expected an even number, found 3"#,
+                SourcePosition::new(88, 2, 31),
+            ),
+            argument_error(
+                r#"Invalid value for argument "size", expected type "Int!": Expected at least 1, found 0"#,
+                SourcePosition::new(134, 3, 23),
+            ),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn rejects_variables_violating_input_object_fields() {
+    let errs = validation_errors(
+        r#"query q($post: PostInput!) { postTitle(post: $post) }"#,
+        vec![(
+            "post".to_owned(),
+            InputValue::object(
+                vec![
+                    ("title", InputValue::scalar("Hello, world!")),
+                    (
+                        "tags",
+                        InputValue::list(vec![InputValue::scalar("toolong")]),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        )]
+        .into_iter()
+        .collect(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![
+            variable_error(
+                r#"Variable "$post" got invalid value. In field "tags": In element #0: Expected at most 5 characters, found 7."#,
+                SourcePosition::new(8, 0, 8),
+            ),
+            variable_error(
+                r#"Variable "$post" got invalid value. In field "title": Expected at most 10 characters, found 13."#,
+                SourcePosition::new(8, 0, 8),
+            ),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn rejects_variables_violating_arguments() {
+    let errs = validation_errors(
+        r#"query q($size: Int!) { page(size: $size) }"#,
+        vec![("size".to_owned(), InputValue::scalar(101))]
+            .into_iter()
+            .collect(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![variable_error(
+            r#"Argument "size" got invalid value from variables. Expected at most 100, found 101."#,
+            SourcePosition::new(34, 0, 34),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn rejects_variables_violating_fields_of_literals() {
+    let errs = validation_errors(
+        r#"query q($title: String!) { postTitle(post: {title: $title}) }"#,
+        vec![("title".to_owned(), InputValue::scalar(""))]
+            .into_iter()
+            .collect(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![variable_error(
+            r#"Argument "post" got invalid value from variables. In field "title": Expected at least 1 characters, found 0."#,
+            SourcePosition::new(43, 0, 43),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn rejects_variables_violating_arguments_in_fragments() {
+    let errs = validation_errors(
+        r#"query q($size: Int!) { ...paged } fragment paged on TestType { page(size: $size) }"#,
+        vec![("size".to_owned(), InputValue::scalar(0))]
+            .into_iter()
+            .collect(),
+    )
+    .await;
+
+    assert_eq!(
+        errs,
+        vec![variable_error(
+            r#"Argument "size" got invalid value from variables. Expected at least 1, found 0."#,
+            SourcePosition::new(74, 0, 74),
+        )],
+    );
+}
+
+#[tokio::test]
+async fn ignores_fragments_of_other_operations() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let query = r#"
+        query a { postTitle(post: {title: "Hello"}) }
+        query b($size: Int!) { ...paged }
+        fragment paged on TestType { page(size: $size) }
+    "#;
+    let vars = vec![("size".to_owned(), InputValue::scalar(0))]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        crate::execute(query, Some("a"), &schema, &vars, &()).await,
+        Ok((graphql_value!({"postTitle": "Hello"}), vec![])),
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn refuses_invalid_patterns() {
+    assert!(InputValidator::<DefaultScalarValue>::pattern("^[a-z").is_err());
+}
+
+#[test]
+fn reports_values_not_convertible_by_custom_validators() {
+    let validator = InputValidator::<DefaultScalarValue>::custom(even);
+
+    assert_eq!(
+        validator.validate(&InputValue::scalar("two")),
+        Err(r#"Expected a value of the validated type, found "two""#.to_owned()),
+    );
+    assert_eq!(
+        validator.validate(&InputValue::list(vec![InputValue::variable("n")])),
+        Ok(()),
+    );
+}
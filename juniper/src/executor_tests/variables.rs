@@ -5,7 +5,7 @@ use crate::{
     parser::SourcePosition,
     schema::model::RootNode,
     types::scalars::{EmptyMutation, EmptySubscription},
-    validation::RuleError,
+    validation::{RuleError, RuleErrorCode},
     value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue},
     GraphQLError::ValidationError,
     GraphQLInputObject,
@@ -35,6 +35,49 @@ impl<S: ScalarValue> GraphQLScalar for TestComplexScalar {
     }
 }
 
+#[derive(Debug)]
+struct TestDescribedScalar(i32);
+
+#[graphql_scalar]
+impl<S: ScalarValue> GraphQLScalar for TestDescribedScalar {
+    fn resolve(&self) -> Value {
+        graphql_value!(self.0)
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<TestDescribedScalar, String> {
+        let n = v
+            .as_int_value()
+            .ok_or_else(|| format!("Expected `Int`, found: {}", v))?;
+        if n < 0 {
+            return Err(format!("{} is negative", n));
+        }
+        Ok(TestDescribedScalar(n))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <i32 as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+/// Scalar converting from any input value, lists and objects included.
+#[derive(Debug)]
+struct TestLenientScalar;
+
+#[graphql_scalar]
+impl<S: ScalarValue> GraphQLScalar for TestLenientScalar {
+    fn resolve(&self) -> Value {
+        graphql_value!("Lenient")
+    }
+
+    fn from_input_value(_v: &InputValue) -> Option<TestLenientScalar> {
+        Some(TestLenientScalar)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
 #[derive(GraphQLInputObject, Debug)]
 struct TestInputObject {
     a: Option<String>,
@@ -118,6 +161,14 @@ impl TestType {
     fn float_input(value: f64) -> String {
         format!("value: {}", value)
     }
+
+    fn described_scalar_input(value: TestDescribedScalar) -> String {
+        format!("value: {:?}", value)
+    }
+
+    fn lenient_scalar_input(value: TestLenientScalar) -> String {
+        format!("value: {:?}", value)
+    }
 }
 
 async fn run_variable_query<F>(query: &str, vars: Variables<DefaultScalarValue>, f: F)
@@ -314,7 +365,8 @@ async fn variable_error_on_nested_non_null() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -340,7 +392,8 @@ async fn variable_error_on_incorrect_type() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" got invalid value. Expected "TestInputObject", found not an object."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -376,7 +429,8 @@ async fn variable_error_on_omit_non_null() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -414,11 +468,13 @@ async fn variable_multiple_errors_with_nesting() {
             RuleError::new(
                 r#"Variable "$input" got invalid value. In field "na": In field "c": Expected "String!", found null."#,
                 &[SourcePosition::new(8, 0, 8)],
-            ),
+            )
+            .with_code(RuleErrorCode::VariableValue),
             RuleError::new(
                 r#"Variable "$input" got invalid value. In field "nb": Expected "String!", found null."#,
                 &[SourcePosition::new(8, 0, 8)],
-            ),
+            )
+            .with_code(RuleErrorCode::VariableValue),
         ])
     );
 }
@@ -457,7 +513,8 @@ async fn variable_error_on_additional_field() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In field "extra": Unknown field."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -571,7 +628,8 @@ async fn does_not_allow_non_nullable_input_to_be_omitted_in_variable() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$value" of required type "String!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -597,7 +655,8 @@ async fn does_not_allow_non_nullable_input_to_be_set_to_null_in_variable() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$value" of required type "String!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -715,7 +774,8 @@ async fn does_not_allow_non_null_lists_to_be_null() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" of required type "[String]!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -828,7 +888,8 @@ async fn does_not_allow_lists_of_non_null_to_contain_null() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -861,7 +922,8 @@ async fn does_not_allow_non_null_lists_of_non_null_to_contain_null() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -887,7 +949,8 @@ async fn does_not_allow_non_null_lists_of_non_null_to_be_null() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$input" of required type "[String!]!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -1035,7 +1098,8 @@ async fn does_not_allow_missing_required_field() {
         ValidationError(vec![RuleError::new(
             r#"Invalid value for argument "arg", expected type "ExampleInputObject!""#,
             &[SourcePosition::new(20, 0, 20)],
-        )])
+        )
+        .with_code(RuleErrorCode::ArgumentsOfCorrectType)])
     );
 }
 
@@ -1059,7 +1123,8 @@ async fn does_not_allow_null_in_required_field() {
         ValidationError(vec![RuleError::new(
             r#"Invalid value for argument "arg", expected type "ExampleInputObject!""#,
             &[SourcePosition::new(20, 0, 20)],
-        )])
+        )
+        .with_code(RuleErrorCode::ArgumentsOfCorrectType)])
     );
 }
 
@@ -1083,7 +1148,8 @@ async fn does_not_allow_missing_variable_for_required_field() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$var" of required type "Int!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -1109,7 +1175,8 @@ async fn does_not_allow_null_variable_for_required_field() {
         ValidationError(vec![RuleError::new(
             r#"Variable "$var" of required type "Int!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        )])
+        )
+        .with_code(RuleErrorCode::VariableValue)])
     );
 }
 
@@ -1220,7 +1287,8 @@ mod integers {
             ValidationError(vec![RuleError::new(
                 r#"Variable "$var" got invalid value. Expected "Int"."#,
                 &[SourcePosition::new(8, 0, 8)],
-            )])
+            )
+            .with_code(RuleErrorCode::VariableValue)])
         );
     }
 
@@ -1246,7 +1314,8 @@ mod integers {
             ValidationError(vec![RuleError::new(
                 r#"Variable "$var" got invalid value. Expected "Int"."#,
                 &[SourcePosition::new(8, 0, 8)],
-            )])
+            )
+            .with_code(RuleErrorCode::VariableValue)])
         );
     }
 }
@@ -1310,7 +1379,190 @@ mod floats {
             ValidationError(vec![RuleError::new(
                 r#"Variable "$var" got invalid value. Expected "Float"."#,
                 &[SourcePosition::new(8, 0, 8)],
-            )])
+            )
+            .with_code(RuleErrorCode::VariableValue)])
+        );
+    }
+}
+
+mod described_scalars {
+    use super::*;
+
+    #[tokio::test]
+    async fn valid_values_should_work() {
+        run_variable_query(
+            r#"query q($var: TestDescribedScalar!) { describedScalarInput(value: $var) }"#,
+            vec![("var".to_owned(), InputValue::scalar(10))]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(
+                    result.get_field_value("describedScalarInput"),
+                    Some(&graphql_value!(r#"value: TestDescribedScalar(10)"#)),
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn reports_reason_for_invalid_variable() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($var: TestDescribedScalar!) { describedScalarInput(value: $var) }"#;
+        let vars = vec![("var".to_owned(), InputValue::scalar(-1))]
+            .into_iter()
+            .collect();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Variable "$var" got invalid value. Expected "TestDescribedScalar": -1 is negative."#,
+                &[SourcePosition::new(8, 0, 8)],
+            )
+            .with_code(RuleErrorCode::VariableValue)])
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_reason_for_invalid_argument() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"{ describedScalarInput(value: "10") }"#;
+        let vars = Variables::new();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Invalid value for argument "value", expected type "TestDescribedScalar!": Expected `Int`, found: "10""#,
+                &[SourcePosition::new(30, 0, 30)],
+            )
+            .with_code(RuleErrorCode::ArgumentsOfCorrectType)])
+        );
+    }
+}
+
+mod composite_scalars {
+    use super::*;
+
+    #[tokio::test]
+    async fn scalar_values_should_work() {
+        run_variable_query(
+            r#"query q($var: TestLenientScalar!) { lenientScalarInput(value: $var) }"#,
+            vec![("var".to_owned(), InputValue::scalar("foo"))]
+                .into_iter()
+                .collect(),
+            |result| {
+                assert_eq!(
+                    result.get_field_value("lenientScalarInput"),
+                    Some(&graphql_value!("value: TestLenientScalar")),
+                );
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn does_not_convert_list_or_object_variables() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"query q($var: TestLenientScalar!) { lenientScalarInput(value: $var) }"#;
+
+        for (value, found) in vec![
+            (InputValue::list(vec![InputValue::scalar("foo")]), "list"),
+            (
+                InputValue::object(
+                    vec![("foo", InputValue::scalar("bar"))]
+                        .into_iter()
+                        .collect(),
+                ),
+                "object",
+            ),
+        ] {
+            let vars = vec![("var".to_owned(), value)].into_iter().collect();
+
+            let error = crate::execute(query, None, &schema, &vars, &())
+                .await
+                .unwrap_err();
+
+            assert_eq!(
+                error,
+                ValidationError(vec![RuleError::new(
+                    &format!(
+                        r#"Variable "$var" got invalid value. Expected "TestLenientScalar", found {}."#,
+                        found,
+                    ),
+                    &[SourcePosition::new(8, 0, 8)],
+                )
+                .with_code(RuleErrorCode::VariableValue)])
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn does_not_convert_list_literals() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"{ lenientScalarInput(value: ["foo"]) }"#;
+        let vars = Variables::new();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError(vec![RuleError::new(
+                r#"Invalid value for argument "value", expected type "TestLenientScalar!""#,
+                &[SourcePosition::new(28, 0, 28)],
+            )
+            .with_code(RuleErrorCode::ArgumentsOfCorrectType)])
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_parse_object_literals() {
+        let schema = RootNode::new(
+            TestType,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let query = r#"{ lenientScalarInput(value: {foo: "bar"}) }"#;
+        let vars = Variables::new();
+
+        let error = crate::execute(query, None, &schema, &vars, &())
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(error, crate::GraphQLError::ParseError(_)),
+            "{:?}",
+            error,
         );
     }
 }
@@ -0,0 +1,121 @@
+use crate::{
+    graphql_object, graphql_value,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    value::{DefaultScalarValue, LongScalarValue, ScalarValue},
+    BigInt, InputValue, Long, UnsignedLong, Variables,
+};
+
+struct Query;
+
+#[graphql_object(scalar = S: ScalarValue + Send + Sync)]
+impl Query {
+    fn file_size() -> UnsignedLong {
+        UnsignedLong(5_000_000_000)
+    }
+
+    fn offset(by: Long) -> Long {
+        Long(by.0 - 1)
+    }
+
+    fn total() -> BigInt {
+        BigInt(i128::from(i64::MAX) + 1)
+    }
+}
+
+fn vars<S: ScalarValue>(json: &str) -> Variables<S> {
+    match serde_json::from_str::<InputValue<S>>(json).unwrap() {
+        InputValue::Object(obj) => obj.into_iter().map(|(k, v)| (k.item, v.item)).collect(),
+        _ => panic!("variables are not an object"),
+    }
+}
+
+#[tokio::test]
+async fn serializes_wide_integers_as_strings_by_default() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let doc = r#"query($by: Long!) { fileSize offset(by: $by) total small: offset(by: 11) }"#;
+
+    let (result, errs) = crate::execute(
+        doc,
+        None,
+        &schema,
+        &vars::<DefaultScalarValue>(r#"{"by": "-9223372036854775807"}"#),
+        &(),
+    )
+    .await
+    .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        graphql_value!({
+            "fileSize": 5_000_000_000.0,
+            "offset": "-9223372036854775808",
+            "total": "9223372036854775808",
+            "small": 10,
+        }),
+    );
+}
+
+#[tokio::test]
+async fn carries_wide_integers_end_to_end_with_long_scalar_value() {
+    let schema = RootNode::new_with_scalar_value(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let doc = r#"query($by: Long!) {
+        fileSize
+        offset(by: $by)
+        total
+        literal: offset(by: 9007199254740994)
+    }"#;
+
+    let (result, errs) = crate::execute(
+        doc,
+        None,
+        &schema,
+        &vars::<LongScalarValue>(r#"{"by": 9007199254740993}"#),
+        &(),
+    )
+    .await
+    .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        serde_json::to_string(&result).unwrap(),
+        r#"{"fileSize":5000000000,"offset":9007199254740992,"total":"9223372036854775808","literal":9007199254740993}"#,
+    );
+}
+
+#[tokio::test]
+async fn introspects_wide_integer_scalars() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let doc = r#"{
+        long: __type(name: "Long") { kind }
+        unsignedLong: __type(name: "UnsignedLong") { kind }
+        bigInt: __type(name: "BigInt") { kind }
+    }"#;
+
+    let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        graphql_value!({
+            "long": {"kind": "SCALAR"},
+            "unsignedLong": {"kind": "SCALAR"},
+            "bigInt": {"kind": "SCALAR"},
+        }),
+    );
+}
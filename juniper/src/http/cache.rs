@@ -0,0 +1,479 @@
+//! In-memory cache of whole [`GraphQLResponse`]s, storing the responses for as
+//! long as their [`CachePolicy`] allows.
+//!
+//! Responses are keyed by the query, the operation name and the variables of
+//! a [`GraphQLRequest`]. Responses having a [`CacheScope::Private`] policy are
+//! additionally keyed by a private key identifying the client they belong to
+//! (like a user ID), and aren't cached at all without one.
+//!
+//! [`GraphQLResponse`]: crate::http::GraphQLResponse
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    executor::{CacheHint, CachePolicy, CacheScope},
+    http::{GraphQLRequest, GraphQLResponse, Method},
+    value::ScalarValue,
+    GraphQLError, GraphQLType, GraphQLTypeAsync, RootNode,
+};
+
+/// Key a response is stored under in a [`ResponseCache`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct CacheKey {
+    query: String,
+    operation_name: Option<String>,
+    variables: String,
+    private_key: Option<String>,
+}
+
+/// Response stored in a [`ResponseCache`].
+#[derive(Debug)]
+struct CacheEntry {
+    body: Arc<str>,
+    scope: CacheScope,
+    expires_at: Instant,
+}
+
+/// Response returned by a [`ResponseCache`], either taken from it or freshly
+/// executed.
+#[derive(Clone, Debug)]
+pub struct CachedResponse {
+    body: Arc<str>,
+    is_ok: bool,
+    is_not_query: bool,
+    cache_policy: Option<CachePolicy>,
+    is_hit: bool,
+}
+
+impl CachedResponse {
+    /// Returns the JSON-encoded body of this response.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Was the request successful or not?
+    ///
+    /// See [`GraphQLResponse::is_ok`] for details.
+    pub fn is_ok(&self) -> bool {
+        self.is_ok
+    }
+
+    /// Indicates whether the request was refused, because its operation isn't
+    /// a query and it was received via [`Method::Get`].
+    ///
+    /// Such responses should be sent with the `405 Method Not Allowed` status
+    /// and an `Allow: POST` header.
+    pub fn is_not_query(&self) -> bool {
+        self.is_not_query
+    }
+
+    /// Returns the [`CachePolicy`] this response may be cached with, if any.
+    ///
+    /// For the responses taken from a [`ResponseCache`], its `max_age` is the
+    /// number of seconds left until the response expires.
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        self.cache_policy
+    }
+
+    /// Indicates whether this response has been taken from a
+    /// [`ResponseCache`], rather than freshly executed.
+    pub fn is_hit(&self) -> bool {
+        self.is_hit
+    }
+}
+
+/// In-memory cache of whole responses, storing them according to their
+/// [`CachePolicy`].
+///
+/// Holds at most the configured number of responses, evicting the ones
+/// expiring the soonest when full.
+#[derive(Debug)]
+pub struct ResponseCache {
+    capacity: usize,
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+}
+
+impl ResponseCache {
+    /// Creates a new [`ResponseCache`] holding at most `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the number of responses currently stored in this
+    /// [`ResponseCache`], including the expired ones not evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Indicates whether this [`ResponseCache`] stores no responses.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all the responses stored in this [`ResponseCache`].
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear()
+    }
+
+    /// Returns the response to the given [`GraphQLRequest`] from this
+    /// [`ResponseCache`], or executes it synchronously and stores its
+    /// response, if it may be cached.
+    ///
+    /// Requests received via [`Method::Get`] are refused unless they select a
+    /// query, as with [`GraphQLRequest::execute_sync_via`].
+    ///
+    /// `private_key` identifies the client the request is made by, so the
+    /// responses with a [`CacheScope::Private`] policy are cached for it only.
+    pub fn execute_sync<S, QueryT, MutationT, SubscriptionT>(
+        &self,
+        request: &GraphQLRequest<S>,
+        method: Method,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        private_key: Option<&str>,
+    ) -> CachedResponse
+    where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        let key = CacheKey::new(request);
+        if let Some(cached) = self.get(&key, private_key) {
+            return cached;
+        }
+        let response = request.execute_sync_via(method, root_node, context);
+        self.put(key, private_key, &response)
+    }
+
+    /// Returns the response to the given [`GraphQLRequest`] from this
+    /// [`ResponseCache`], or executes it and stores its response, if it may be
+    /// cached.
+    ///
+    /// Requests received via [`Method::Get`] are refused unless they select a
+    /// query, as with [`GraphQLRequest::execute_via`].
+    ///
+    /// `private_key` identifies the client the request is made by, so the
+    /// responses with a [`CacheScope::Private`] policy are cached for it only.
+    pub async fn execute<'a, S, QueryT, MutationT, SubscriptionT>(
+        &self,
+        request: &'a GraphQLRequest<S>,
+        method: Method,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        private_key: Option<&str>,
+    ) -> CachedResponse
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        let key = CacheKey::new(request);
+        if let Some(cached) = self.get(&key, private_key) {
+            return cached;
+        }
+        let response = request.execute_via(method, root_node, context).await;
+        self.put(key, private_key, &response)
+    }
+
+    /// Looks up a non-expired response stored under the given [`CacheKey`],
+    /// either the public one or the private one of the given `private_key`.
+    fn get(&self, key: &CacheKey, private_key: Option<&str>) -> Option<CachedResponse> {
+        let now = Instant::now();
+        let entries = self.entries.lock().unwrap();
+
+        let private = private_key.map(|k| key.private(k));
+        let entry = entries
+            .get(key)
+            .into_iter()
+            .chain(private.as_ref().and_then(|k| entries.get(k)))
+            .find(|e| e.expires_at > now)?;
+
+        let remaining = entry.expires_at.duration_since(now);
+        let max_age = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let mut policy = CachePolicy::new();
+        policy.restrict(&CacheHint::new().max_age(max_age as u32).scope(entry.scope));
+
+        Some(CachedResponse {
+            body: Arc::clone(&entry.body),
+            is_ok: true,
+            is_not_query: false,
+            cache_policy: Some(policy),
+            is_hit: true,
+        })
+    }
+
+    /// Encodes the given [`GraphQLResponse`] and stores it under the given
+    /// [`CacheKey`], if its [`CachePolicy`] allows.
+    fn put<S: ScalarValue>(
+        &self,
+        key: CacheKey,
+        private_key: Option<&str>,
+        response: &GraphQLResponse<'_, S>,
+    ) -> CachedResponse {
+        let body: Arc<str> = serde_json::to_string(response)
+            .expect("GraphQLResponse should be serializable into JSON")
+            .into();
+        let cache_policy = response.cache_policy();
+
+        let key = cache_policy.and_then(|policy| match policy.scope() {
+            CacheScope::Public => Some(key),
+            CacheScope::Private => private_key.map(|k| key.private(k)),
+        });
+        if let (Some(key), Some(policy), true) = (key, cache_policy, self.capacity > 0) {
+            let now = Instant::now();
+            let mut entries = self.entries.lock().unwrap();
+            if entries.len() >= self.capacity && !entries.contains_key(&key) {
+                entries.retain(|_, e| e.expires_at > now);
+            }
+            if entries.len() >= self.capacity && !entries.contains_key(&key) {
+                let soonest = entries
+                    .iter()
+                    .min_by_key(|(_, e)| e.expires_at)
+                    .map(|(k, _)| k.clone());
+                if let Some(soonest) = soonest {
+                    entries.remove(&soonest);
+                }
+            }
+            entries.insert(
+                key,
+                CacheEntry {
+                    body: Arc::clone(&body),
+                    scope: policy.scope(),
+                    expires_at: now + Duration::from_secs(policy.max_age().map_or(0, u64::from)),
+                },
+            );
+        }
+
+        CachedResponse {
+            body,
+            is_ok: response.is_ok(),
+            is_not_query: matches!(response.0, Err(GraphQLError::NotQuery)),
+            cache_policy,
+            is_hit: false,
+        }
+    }
+}
+
+impl CacheKey {
+    /// Creates a new public [`CacheKey`] of the given [`GraphQLRequest`].
+    fn new<S: ScalarValue>(request: &GraphQLRequest<S>) -> Self {
+        Self {
+            query: request.query.clone(),
+            operation_name: request.operation_name.clone(),
+            variables: serde_json::to_string(&request.variables)
+                .expect("variables should be serializable into JSON"),
+            private_key: None,
+        }
+    }
+
+    /// Returns the private version of this [`CacheKey`] for the given
+    /// `private_key`.
+    fn private(&self, private_key: &str) -> Self {
+        Self {
+            private_key: Some(private_key.into()),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        graphql_object,
+        http::{GraphQLRequest, Method},
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        CacheScope, Context, FieldResult, InputValue,
+    };
+
+    use super::ResponseCache;
+
+    #[derive(Default)]
+    struct Database {
+        calls: AtomicUsize,
+    }
+
+    impl Context for Database {}
+
+    struct Query;
+
+    #[graphql_object(context = Database)]
+    impl Query {
+        #[graphql(cache_control(max_age = 60))]
+        fn hello(name: Option<String>, context: &Database) -> String {
+            context.calls.fetch_add(1, Ordering::SeqCst);
+            format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+        }
+
+        #[graphql(cache_control(max_age = 60, scope = "PRIVATE"))]
+        fn me(context: &Database) -> String {
+            context.calls.fetch_add(1, Ordering::SeqCst);
+            "John".into()
+        }
+
+        #[graphql(cache_control(max_age = 60))]
+        fn fail(context: &Database) -> FieldResult<String> {
+            context.calls.fetch_add(1, Ordering::SeqCst);
+            Err("failed".into())
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+    fn schema() -> Schema {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    fn request(query: &str) -> GraphQLRequest {
+        GraphQLRequest::new(query.into(), None, None)
+    }
+
+    #[tokio::test]
+    async fn caches_public_responses() {
+        let (cache, schema, db) = (ResponseCache::new(10), schema(), Database::default());
+        let req = request("{ hello }");
+
+        let first = cache.execute(&req, Method::Post, &schema, &db, None).await;
+        assert!(!first.is_hit());
+        assert!(first.is_ok());
+        assert_eq!(first.body(), r#"{"data":{"hello":"Hello, world!"}}"#);
+        assert_eq!(first.cache_policy().unwrap().max_age(), Some(60));
+
+        let second = cache
+            .execute(&req, Method::Post, &schema, &db, Some("user"))
+            .await;
+        assert!(second.is_hit());
+        assert_eq!(second.body(), first.body());
+        assert_eq!(second.cache_policy().unwrap().scope(), CacheScope::Public);
+        assert_eq!(db.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn keys_by_variables() {
+        let (cache, schema, db) = (ResponseCache::new(10), schema(), Database::default());
+        let req = |name: &str| {
+            let vars = InputValue::object(
+                vec![("name", InputValue::scalar(name))]
+                    .into_iter()
+                    .collect(),
+            );
+            GraphQLRequest::new(
+                "query($name: String) { hello(name: $name) }".into(),
+                None,
+                Some(vars),
+            )
+        };
+
+        cache.execute_sync(&req("Jane"), Method::Get, &schema, &db, None);
+        let res = cache.execute_sync(&req("John"), Method::Get, &schema, &db, None);
+        assert!(!res.is_hit());
+        assert_eq!(res.body(), r#"{"data":{"hello":"Hello, John!"}}"#);
+        assert!(cache
+            .execute_sync(&req("Jane"), Method::Get, &schema, &db, None)
+            .is_hit());
+        assert_eq!(db.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn caches_private_responses_per_key() {
+        let (cache, schema, db) = (ResponseCache::new(10), schema(), Database::default());
+        let req = request("{ me }");
+
+        assert!(!cache
+            .execute(&req, Method::Post, &schema, &db, None)
+            .await
+            .is_hit());
+        assert!(!cache
+            .execute(&req, Method::Post, &schema, &db, None)
+            .await
+            .is_hit());
+        assert!(cache.is_empty());
+
+        assert!(!cache
+            .execute(&req, Method::Post, &schema, &db, Some("a"))
+            .await
+            .is_hit());
+        let hit = cache
+            .execute(&req, Method::Post, &schema, &db, Some("a"))
+            .await;
+        assert!(hit.is_hit());
+        assert_eq!(hit.cache_policy().unwrap().scope(), CacheScope::Private);
+        assert!(!cache
+            .execute(&req, Method::Post, &schema, &db, Some("b"))
+            .await
+            .is_hit());
+        assert!(!cache
+            .execute(&req, Method::Post, &schema, &db, None)
+            .await
+            .is_hit());
+        assert_eq!(db.calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn skips_uncacheable_responses() {
+        let (cache, schema, db) = (ResponseCache::new(10), schema(), Database::default());
+        let req = request("{ fail }");
+
+        let res = cache.execute(&req, Method::Post, &schema, &db, None).await;
+        assert!(res.cache_policy().is_none());
+        assert!(!cache
+            .execute(&req, Method::Post, &schema, &db, None)
+            .await
+            .is_hit());
+        assert!(cache.is_empty());
+        assert_eq!(db.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn refuses_get_mutations() {
+        let (cache, schema, db) = (ResponseCache::new(10), schema(), Database::default());
+        let req = request("mutation { hello }");
+
+        let res = cache.execute(&req, Method::Get, &schema, &db, None).await;
+        assert!(res.is_not_query());
+        assert!(!res.is_ok());
+        assert!(res.cache_policy().is_none());
+
+        let res = cache.execute_sync(&req, Method::Get, &schema, &db, None);
+        assert!(res.is_not_query());
+        assert!(cache.is_empty());
+        assert_eq!(db.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn evicts_when_full() {
+        let (cache, schema, db) = (ResponseCache::new(1), schema(), Database::default());
+
+        cache
+            .execute(&request("{ hello }"), Method::Post, &schema, &db, None)
+            .await;
+        cache
+            .execute(
+                &request("{ helloAgain: hello }"),
+                Method::Post,
+                &schema,
+                &db,
+                None,
+            )
+            .await;
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -1,7 +1,15 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
+#[cfg(feature = "response-cache")]
+pub mod cache;
 pub mod graphiql;
+#[cfg(feature = "multipart")]
+pub mod multipart;
+#[cfg(feature = "negotiation")]
+pub mod negotiation;
 pub mod playground;
+#[cfg(feature = "negotiation")]
+pub mod streaming;
 
 use serde::{
     de,
@@ -10,13 +18,27 @@ use serde::{
 };
 
 use crate::{
-    ast::InputValue,
-    executor::{ExecutionError, ValuesStream},
+    ast::{InputValue, OperationType},
+    executor::{CachePolicy, CacheableOutput, ExecutionError, ValuesStream},
+    schema::model::SchemaType,
     value::{DefaultScalarValue, ScalarValue},
     FieldError, GraphQLError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
     Value, Variables,
 };
 
+/// HTTP method a [`GraphQLRequest`] has been received with.
+///
+/// Only query operations may be executed via `GET` requests, as those are
+/// expected to be safe and are freely cached and issued cross-origin.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// `GET` request, allowed to execute query operations only.
+    Get,
+
+    /// `POST` request, allowed to execute any operation.
+    Post,
+}
+
 /// The expected structure of the decoded JSON document for either POST or GET requests.
 ///
 /// For POST, you can use Serde to deserialize the incoming JSON data directly
@@ -79,6 +101,28 @@ where
         }
     }
 
+    /// Returns the type of the operation this request selects in the given schema.
+    ///
+    /// Returns `None` if the query cannot be parsed or doesn't select a single
+    /// operation, leaving the error to be reported by the execution itself.
+    pub fn operation_type<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    ) -> Option<OperationType>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.operation_type_in(&root_node.schema)
+    }
+
+    /// Returns the type of the operation this request selects in the given [`SchemaType`].
+    fn operation_type_in(&self, schema: &SchemaType<S>) -> Option<OperationType> {
+        let document = crate::parse_document_source(&self.query, schema).ok()?;
+        crate::operation_type(&document, self.operation_name.as_deref())
+    }
+
     /// Execute a GraphQL request synchronously using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute_sync` function exposed at the
@@ -94,12 +138,13 @@ where
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
-        GraphQLResponse(crate::execute_sync(
+        GraphQLResponse::from_cacheable_output(crate::execute_sync_with_cache_policy(
             &self.query,
             self.operation_name.as_deref(),
             root_node,
             &self.variables(),
             context,
+            false,
         ))
     }
 
@@ -124,8 +169,74 @@ where
     {
         let op = self.operation_name.as_deref();
         let vars = &self.variables();
-        let res = crate::execute(&self.query, op, root_node, vars, context).await;
-        GraphQLResponse(res)
+        let res =
+            crate::execute_with_cache_policy(&self.query, op, root_node, vars, context, false)
+                .await;
+        GraphQLResponse::from_cacheable_output(res)
+    }
+
+    /// Execute a GraphQL request received via the given HTTP [`Method`]
+    /// synchronously using the specified schema and context
+    ///
+    /// Same as [`GraphQLRequest::execute_sync`], but refuses to execute
+    /// non-query operations received via [`Method::Get`], responding with a
+    /// [`GraphQLError::NotQuery`] instead.
+    pub fn execute_sync_via<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        method: Method,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        GraphQLResponse::from_cacheable_output(crate::execute_sync_with_cache_policy(
+            &self.query,
+            self.operation_name.as_deref(),
+            root_node,
+            &self.variables(),
+            context,
+            method == Method::Get,
+        ))
+    }
+
+    /// Execute a GraphQL request received via the given HTTP [`Method`] using
+    /// the specified schema and context
+    ///
+    /// Same as [`GraphQLRequest::execute`], but refuses to execute non-query
+    /// operations received via [`Method::Get`], responding with a
+    /// [`GraphQLError::NotQuery`] instead.
+    pub async fn execute_via<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        method: Method,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+    ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        let op = self.operation_name.as_deref();
+        let vars = &self.variables();
+        let queries_only = method == Method::Get;
+        let res = crate::execute_with_cache_policy(
+            &self.query,
+            op,
+            root_node,
+            vars,
+            context,
+            queries_only,
+        )
+        .await;
+        GraphQLResponse::from_cacheable_output(res)
     }
 }
 
@@ -165,6 +276,7 @@ where
 #[derive(Debug)]
 pub struct GraphQLResponse<'a, S = DefaultScalarValue>(
     Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    CachePolicy,
 );
 
 impl<'a, S> GraphQLResponse<'a, S>
@@ -173,12 +285,36 @@ where
 {
     /// Constructs new `GraphQLResponse` using the given result
     pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>) -> Self {
-        Self(r)
+        Self(r, CachePolicy::uncacheable())
+    }
+
+    /// Constructs new `GraphQLResponse` using the given result of an execution, along with
+    /// its `CachePolicy`.
+    fn from_cacheable_output(r: Result<CacheableOutput<S>, GraphQLError<'a>>) -> Self {
+        match r {
+            Ok((value, errors, policy)) => Self(Ok((value, errors)), policy),
+            Err(e) => Self::from_result(Err(e)),
+        }
     }
 
     /// Constructs an error response outside of the normal execution flow
     pub fn error(error: FieldError<S>) -> Self {
-        GraphQLResponse(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+        Self::from_result(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+    }
+
+    /// Returns the [`CachePolicy`] this response may be cached with, computed from the
+    /// [`CacheHint`]s of its resolved fields, if it may be cached at all.
+    ///
+    /// Responses of mutations, of failed requests and the ones having any errors are never
+    /// cached. The returned policy is meant for setting the `Cache-Control` header with
+    /// [`CachePolicy::header_value`].
+    ///
+    /// [`CacheHint`]: crate::CacheHint
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match &self.0 {
+            Ok((_, errors)) if errors.is_empty() && self.1.is_cacheable() => Some(self.1),
+            _ => None,
+        }
     }
 
     /// Was the request successful or not?
@@ -321,6 +457,98 @@ where
         }
     }
 
+    /// Execute a GraphQL batch request received via the given HTTP [`Method`]
+    /// synchronously using the specified schema and context
+    ///
+    /// This is a simple wrapper around the `execute_sync_via` function exposed
+    /// in GraphQLRequest. A batch received via [`Method::Get`] is refused as a
+    /// whole with a single [`GraphQLError::NotQuery`] if any of its operations
+    /// isn't a query, before executing any of them.
+    pub fn execute_sync_via<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        method: Method,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        if self.is_refused_via(method, &root_node.schema) {
+            return GraphQLBatchResponse::Single(GraphQLResponse::from_result(Err(
+                GraphQLError::NotQuery,
+            )));
+        }
+        match *self {
+            Self::Single(ref req) => {
+                GraphQLBatchResponse::Single(req.execute_sync_via(method, root_node, context))
+            }
+            Self::Batch(ref reqs) => GraphQLBatchResponse::Batch(
+                reqs.iter()
+                    .map(|req| req.execute_sync_via(method, root_node, context))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Executes a GraphQL request received via the given HTTP [`Method`] using
+    /// the specified schema and context
+    ///
+    /// This is a simple wrapper around the `execute_via` function exposed in
+    /// GraphQLRequest. A batch is refused the same way as by
+    /// [`GraphQLBatchRequest::execute_sync_via`].
+    pub async fn execute_via<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        method: Method,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        if self.is_refused_via(method, &root_node.schema) {
+            return GraphQLBatchResponse::Single(GraphQLResponse::from_result(Err(
+                GraphQLError::NotQuery,
+            )));
+        }
+        match self {
+            Self::Single(req) => {
+                let resp = req.execute_via(method, root_node, context).await;
+                GraphQLBatchResponse::Single(resp)
+            }
+            Self::Batch(reqs) => {
+                let resps = futures::future::join_all(
+                    reqs.iter()
+                        .map(|req| req.execute_via(method, root_node, context)),
+                )
+                .await;
+                GraphQLBatchResponse::Batch(resps)
+            }
+        }
+    }
+
+    /// Indicates whether this batch is received via [`Method::Get`] and selects
+    /// a non-query operation in any of its requests.
+    ///
+    /// Single requests are refused by their execution itself, without parsing
+    /// their query twice.
+    fn is_refused_via(&self, method: Method, schema: &SchemaType<S>) -> bool {
+        match self {
+            Self::Batch(reqs) if method == Method::Get => reqs.iter().any(|req| {
+                matches!(req.operation_type_in(schema), Some(ty) if ty != OperationType::Query)
+            }),
+            _ => false,
+        }
+    }
+
     /// The operation names of the request.
     pub fn operation_names(&self) -> Vec<Option<&str>> {
         match self {
@@ -359,6 +587,23 @@ where
             Self::Batch(resps) => resps.iter().all(GraphQLResponse::is_ok),
         }
     }
+
+    /// Returns the [`CachePolicy`] all the [`GraphQLResponse`]s of this operation may be cached
+    /// with, if every one of them may be cached.
+    ///
+    /// See [`GraphQLResponse::cache_policy`] for details.
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match self {
+            Self::Single(resp) => resp.cache_policy(),
+            Self::Batch(resps) => {
+                let mut policy = CachePolicy::new();
+                for resp in resps {
+                    policy.merge(&resp.cache_policy()?);
+                }
+                Some(policy).filter(CachePolicy::is_cacheable)
+            }
+        }
+    }
 }
 
 #[cfg(feature = "expose-test-schema")]
@@ -374,6 +619,7 @@ pub mod tests {
         pub status_code: i32,
         pub body: Option<String>,
         pub content_type: String,
+        pub allow: Option<String>,
     }
 
     /// Normalized way to make requests to the HTTP framework integration we are testing.
@@ -389,6 +635,16 @@ pub mod tests {
         /// Sends POST HTTP request to this integration with the provided raw GraphQL query as
         /// `body`, and returns response returned by this integration.
         fn post_graphql(&self, url: &str, body: &str) -> TestResponse;
+
+        /// Sends HTTP request with the provided `method`, `headers` and `body` to this integration,
+        /// and returns response returned by this integration.
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> TestResponse;
     }
 
     #[allow(missing_docs)]
@@ -419,6 +675,9 @@ pub mod tests {
         println!("  - test_invalid_field");
         test_invalid_field(integration);
 
+        println!("  - test_validation_error_json");
+        test_validation_error_json(integration);
+
         println!("  - test_duplicate_keys");
         test_duplicate_keys(integration);
 
@@ -427,6 +686,53 @@ pub mod tests {
 
         println!("  - test_invalid_graphql_post");
         test_invalid_graphql_post(integration);
+
+        println!("  - test_get_with_operation_name");
+        test_get_with_operation_name(integration);
+
+        println!("  - test_graphql_response_json");
+        test_graphql_response_json(integration);
+
+        println!("  - test_not_acceptable");
+        test_not_acceptable(integration);
+
+        println!("  - test_unsupported_media_type");
+        test_unsupported_media_type(integration);
+
+        println!("  - test_get_mutation");
+        test_get_mutation(integration);
+
+        println!("  - test_get_with_operation_name_of_mutation");
+        test_get_with_operation_name_of_mutation(integration);
+    }
+
+    /// `Content-Type` of the request bodies built by [`multipart_body`].
+    pub const MULTIPART_CONTENT_TYPE: &str = "multipart/form-data; boundary=BOUNDARY";
+
+    /// Builds the body of a [GraphQL multipart request][1] from the given
+    /// `(name, filename, contents)` parts, the ones with a `filename` being
+    /// `text/plain` files.
+    ///
+    /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+    pub fn multipart_body(parts: &[(&str, Option<&str>, &str)]) -> String {
+        let mut body = String::new();
+        for (name, filename, contents) in parts {
+            body.push_str("--BOUNDARY\r\n");
+            match filename {
+                Some(filename) => body.push_str(&format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                     Content-Type: text/plain\r\n",
+                    name, filename,
+                )),
+                None => body.push_str(&format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n",
+                    name,
+                )),
+            }
+            body.push_str(&format!("\r\n{}\r\n", contents));
+        }
+        body.push_str("--BOUNDARY--\r\n");
+        body
     }
 
     fn unwrap_json_response(response: &TestResponse) -> Json {
@@ -550,6 +856,18 @@ pub mod tests {
         assert_eq!(response.status_code, 400);
     }
 
+    /// Asserts the given `application/json` response carries request errors
+    /// only, with the `200 OK` status clients explicitly accepting this media
+    /// type opt in to.
+    fn assert_json_request_errors(response: &TestResponse) {
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        let json = unwrap_json_response(response);
+        assert!(json.get("errors").is_some());
+        assert!(json.get("data").is_none());
+    }
+
     fn test_invalid_json<T: HttpIntegration>(integration: &T) {
         let response = integration.get("/?query=blah");
         assert_eq!(response.status_code, 400);
@@ -565,6 +883,22 @@ pub mod tests {
         assert_eq!(response.status_code, 400);
     }
 
+    fn test_validation_error_json<T: HttpIntegration>(integration: &T) {
+        let accept = ("accept", "application/json");
+
+        // {hero{blah}}
+        let response = integration.request("GET", "/?query=%7Bhero%7Bblah%7D%7D", &[accept], "");
+        assert_json_request_errors(&response);
+
+        let response = integration.request(
+            "POST",
+            "/",
+            &[accept, ("content-type", "application/json")],
+            r#"{"query": "{hero{blah}}"}"#,
+        );
+        assert_json_request_errors(&response);
+    }
+
     fn test_duplicate_keys<T: HttpIntegration>(integration: &T) {
         // {hero{name}}
         let response = integration.get("/?query=%7B%22query%22%3A%20%22%7Bhero%7Bname%7D%7D%22%2C%20%22query%22%3A%20%22%7Bhero%7Bname%7D%7D%22%7D");
@@ -593,6 +927,101 @@ pub mod tests {
         assert_eq!(resp.status_code, 400);
     }
 
+    fn test_get_with_operation_name<T: HttpIntegration>(integration: &T) {
+        // query A { hero { id } } query B { hero { name } }
+        // with operationName = B
+        let response = integration.get(
+            "/?query=query%20A%20%7B%20hero%20%7B%20id%20%7D%20%7D%20query%20B%20%7B%20hero%20%7B%20name%20%7D%20%7D&operationName=B");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"),
+        );
+    }
+
+    fn test_graphql_response_json<T: HttpIntegration>(integration: &T) {
+        let accept = (
+            "accept",
+            "application/graphql-response+json, application/json;q=0.9",
+        );
+
+        let response = integration.request(
+            "POST",
+            "/",
+            &[accept, ("content-type", "application/json")],
+            r#"{"query": "{hero{name}}"}"#,
+        );
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/graphql-response+json");
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"),
+        );
+
+        // {hero{blah}}
+        let response = integration.request("GET", "/?query=%7Bhero%7Bblah%7D%7D", &[accept], "");
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/graphql-response+json");
+        assert!(unwrap_json_response(&response).get("errors").is_some());
+    }
+
+    fn test_not_acceptable<T: HttpIntegration>(integration: &T) {
+        // {hero{name}}
+        let response = integration.request(
+            "GET",
+            "/?query=%7Bhero%7Bname%7D%7D",
+            &[("accept", "text/html")],
+            "",
+        );
+        assert_eq!(response.status_code, 406);
+    }
+
+    fn test_unsupported_media_type<T: HttpIntegration>(integration: &T) {
+        let response = integration.request(
+            "POST",
+            "/",
+            &[("content-type", "text/plain")],
+            "{hero{name}}",
+        );
+        assert_eq!(response.status_code, 415);
+    }
+
+    fn test_get_mutation<T: HttpIntegration>(integration: &T) {
+        // mutation{hero{name}}
+        let response = integration.get("/?query=mutation%7Bhero%7Bname%7D%7D");
+
+        assert_eq!(response.status_code, 405);
+        assert_eq!(response.allow.as_deref(), Some("POST"));
+        assert_eq!(response.content_type.as_str(), "application/json");
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(
+                r#"{"errors": [{"message": "Only query operations are allowed over HTTP GET"}]}"#
+            )
+            .expect("Invalid JSON constant in test")
+        );
+    }
+
+    fn test_get_with_operation_name_of_mutation<T: HttpIntegration>(integration: &T) {
+        // query A{hero{name}} mutation B{hero{name}}
+        let query = "/?query=query%20A%7Bhero%7Bname%7D%7D%20mutation%20B%7Bhero%7Bname%7D%7D";
+
+        let response = integration.get(&format!("{}&operationName=A", query));
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.allow, None);
+
+        let response = integration.get(&format!("{}&operationName=B", query));
+        assert_eq!(response.status_code, 405);
+        assert_eq!(response.allow.as_deref(), Some("POST"));
+    }
+
     /// Normalized way to make requests to the WebSocket framework integration we are testing.
     pub trait WsIntegration {
         /// Runs a test with the given messages
@@ -0,0 +1,918 @@
+//! Support for the [GraphQL multipart request spec][1], allowing files to be
+//! uploaded along with GraphQL operations.
+//!
+//! A multipart request consists of an `operations` part (a regular JSON
+//! encoded [`GraphQLBatchRequest`]), a `map` part (mapping file parts to the
+//! places in the `operations` they should be put into) and the file parts
+//! themselves. Files are accepted as arguments of the [`Upload`] type.
+//!
+//! Web framework integrations feed the parts of a request into a
+//! [`MultipartParser`] as they arrive, which enforces the configured
+//! [`MultipartLimits`] and produces a [`GraphQLMultipartRequest`]. Files are
+//! buffered in memory before the request is executed, so
+//! [`MultipartLimits::max_request_size`] bounds the memory used by a request.
+//!
+//! The files of a request are only available to the [`Upload`] arguments
+//! converted while executing that very request, so no other request is able to
+//! refer to them.
+//!
+//! [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+
+use std::{cell::RefCell, collections::HashMap, error::Error, fmt, future::Future as _, sync::Arc};
+
+use bytes::Bytes;
+use futures::{future, Stream};
+
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, Registry},
+    http::{GraphQLBatchRequest, GraphQLBatchResponse},
+    parser::{ParseError, ScalarToken, Token},
+    schema::meta::MetaType,
+    types::{
+        base::{GraphQLType, GraphQLValue},
+        marker::IsInputType,
+    },
+    value::{ParseScalarResult, ParseScalarValue, ScalarValue},
+    GraphQLSubscriptionType, GraphQLTypeAsync, InputValueError, RootNode,
+};
+
+/// Prefix of the placeholders put into the `operations` instead of files.
+const UPLOAD_KEY_PREFIX: &str = "juniper-upload:";
+
+/// Files of a multipart request, by the names of their parts.
+type Files = HashMap<String, Arc<UploadedFile>>;
+
+thread_local! {
+    /// Files of the [`GraphQLMultipartRequest`] being executed on this thread,
+    /// if any.
+    static CURRENT_FILES: RefCell<Option<Arc<Files>>> = const { RefCell::new(None) };
+}
+
+/// Makes the given `files` available to the [`Upload`] arguments converted on
+/// this thread while running `f`.
+fn with_files<R>(files: &Arc<Files>, f: impl FnOnce() -> R) -> R {
+    /// Restores the previously current files, even if `f` panics.
+    struct Restore(Option<Arc<Files>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT_FILES.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(CURRENT_FILES.with(|current| current.replace(Some(files.clone()))));
+    f()
+}
+
+/// Returns the file referred to by the given placeholder `key` in the
+/// [`GraphQLMultipartRequest`] being executed on this thread, if any.
+fn lookup_file(key: &str) -> Option<Arc<UploadedFile>> {
+    let name = key.strip_prefix(UPLOAD_KEY_PREFIX)?;
+    CURRENT_FILES.with(|current| current.borrow().as_ref()?.get(name).cloned())
+}
+
+/// Limits enforced while parsing a multipart request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MultipartLimits {
+    /// Maximum size in bytes of a single file.
+    pub max_file_size: usize,
+
+    /// Maximum number of files in a single request.
+    pub max_files: usize,
+
+    /// Maximum size in bytes of the `operations` and `map` parts, each.
+    pub max_operations_size: usize,
+
+    /// Maximum total size in bytes of all the parts of a single request.
+    ///
+    /// As files are buffered in memory, this bounds the memory used by a
+    /// single request.
+    pub max_request_size: usize,
+}
+
+impl MultipartLimits {
+    /// Creates the default limits: 10 files of at most 10 MiB each, 1 MiB of
+    /// `operations` and `map` each, and 20 MiB per request in total.
+    pub fn new() -> Self {
+        Self {
+            max_file_size: 10 * 1024 * 1024,
+            max_files: 10,
+            max_operations_size: 1024 * 1024,
+            max_request_size: 20 * 1024 * 1024,
+        }
+    }
+
+    /// Specifies the maximum size in bytes of a single file.
+    pub fn max_file_size(mut self, size: usize) -> Self {
+        self.max_file_size = size;
+        self
+    }
+
+    /// Specifies the maximum number of files in a single request.
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = count;
+        self
+    }
+
+    /// Specifies the maximum size in bytes of the `operations` and `map`
+    /// parts, each.
+    pub fn max_operations_size(mut self, size: usize) -> Self {
+        self.max_operations_size = size;
+        self
+    }
+
+    /// Specifies the maximum total size in bytes of all the parts of a single
+    /// request.
+    pub fn max_request_size(mut self, size: usize) -> Self {
+        self.max_request_size = size;
+        self
+    }
+}
+
+impl Default for MultipartLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error of parsing a multipart request.
+#[derive(Debug)]
+pub enum MultipartError {
+    /// The `operations` part is missing, or isn't the first part.
+    MissingOperations,
+
+    /// The `map` part is missing, or doesn't follow the `operations` part.
+    MissingMap,
+
+    /// The `operations` part isn't a valid GraphQL request.
+    InvalidOperations(serde_json::Error),
+
+    /// The `map` part isn't a valid JSON object of string lists.
+    InvalidMap(serde_json::Error),
+
+    /// The `map` references a path not present in the `operations`.
+    InvalidPath(String),
+
+    /// A file part isn't referenced by the `map`, or is sent more than once.
+    UnexpectedFile(String),
+
+    /// A file referenced by the `map` is missing in the request.
+    MissingFile(String),
+
+    /// The request contains more files than allowed.
+    TooManyFiles(usize),
+
+    /// A part of the request is larger than allowed.
+    PartTooLarge(usize),
+
+    /// All the parts of the request together are larger than allowed.
+    RequestTooLarge(usize),
+
+    /// Data is fed into a [`MultipartParser`] without starting a part first.
+    PartNotStarted,
+}
+
+impl MultipartError {
+    /// Indicates whether this error is caused by exceeding [`MultipartLimits`],
+    /// so integrations may respond with `413 Payload Too Large`.
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(
+            self,
+            Self::TooManyFiles(_) | Self::PartTooLarge(_) | Self::RequestTooLarge(_)
+        )
+    }
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingOperations => write!(f, "`operations` must be the first part"),
+            Self::MissingMap => write!(f, "`map` must follow the `operations` part"),
+            Self::InvalidOperations(e) => write!(f, "Invalid `operations`: {}", e),
+            Self::InvalidMap(e) => write!(f, "Invalid `map`: {}", e),
+            Self::InvalidPath(path) => write!(f, "Invalid `map` path: {}", path),
+            Self::UnexpectedFile(name) => write!(f, "Unexpected file: {}", name),
+            Self::MissingFile(name) => write!(f, "Missing file: {}", name),
+            Self::TooManyFiles(max) => write!(f, "Too many files, at most {} allowed", max),
+            Self::PartTooLarge(max) => {
+                write!(f, "Request part too large, at most {} bytes allowed", max)
+            }
+            Self::RequestTooLarge(max) => {
+                write!(f, "Request too large, at most {} bytes allowed", max)
+            }
+            Self::PartNotStarted => write!(f, "No request part has been started"),
+        }
+    }
+}
+
+impl Error for MultipartError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidOperations(e) | Self::InvalidMap(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Incremental parser of a [GraphQL multipart request][1].
+///
+/// Each part of the request is fed by calling [`MultipartParser::start_part`],
+/// then [`MultipartParser::push_chunk`] for each chunk of its data and
+/// [`MultipartParser::end_part`] once it's complete.
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Debug)]
+pub struct MultipartParser {
+    limits: MultipartLimits,
+    operations: Option<serde_json::Value>,
+    map: Option<HashMap<String, Vec<String>>>,
+    files: HashMap<String, UploadedFile>,
+    current: Option<Part>,
+    size: usize,
+}
+
+#[derive(Debug)]
+enum Part {
+    Operations(Vec<u8>),
+    Map(Vec<u8>),
+    File(String, UploadedFile),
+}
+
+impl MultipartParser {
+    /// Creates a new parser enforcing the given `limits`.
+    pub fn new(limits: MultipartLimits) -> Self {
+        Self {
+            limits,
+            operations: None,
+            map: None,
+            files: HashMap::new(),
+            current: None,
+            size: 0,
+        }
+    }
+
+    /// Starts a new part of the request, named `name`.
+    ///
+    /// `filename` and `content_type` are only meaningful for file parts.
+    pub fn start_part(
+        &mut self,
+        name: &str,
+        filename: Option<String>,
+        content_type: Option<String>,
+    ) -> Result<(), MultipartError> {
+        let part = match name {
+            "operations" if self.operations.is_none() => Part::Operations(vec![]),
+            _ if self.operations.is_none() => return Err(MultipartError::MissingOperations),
+            "map" if self.map.is_none() => Part::Map(vec![]),
+            _ => {
+                let map = self.map.as_ref().ok_or(MultipartError::MissingMap)?;
+                if !map.contains_key(name) || self.files.contains_key(name) {
+                    return Err(MultipartError::UnexpectedFile(name.into()));
+                }
+                if self.files.len() >= self.limits.max_files {
+                    return Err(MultipartError::TooManyFiles(self.limits.max_files));
+                }
+                Part::File(
+                    name.into(),
+                    UploadedFile {
+                        filename,
+                        content_type,
+                        chunks: vec![],
+                        size: 0,
+                    },
+                )
+            }
+        };
+        self.current = Some(part);
+        Ok(())
+    }
+
+    /// Appends a `chunk` of data to the current part of the request.
+    ///
+    /// Fails with [`MultipartError::PartNotStarted`] if no part has been
+    /// started.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), MultipartError> {
+        let limits = &self.limits;
+        let current = self
+            .current
+            .as_mut()
+            .ok_or(MultipartError::PartNotStarted)?;
+
+        let (len, max) = match current {
+            Part::Operations(data) | Part::Map(data) => (data.len(), limits.max_operations_size),
+            Part::File(_, file) => (file.size, limits.max_file_size),
+        };
+        if len.saturating_add(chunk.len()) > max {
+            return Err(MultipartError::PartTooLarge(max));
+        }
+        if self.size.saturating_add(chunk.len()) > limits.max_request_size {
+            return Err(MultipartError::RequestTooLarge(limits.max_request_size));
+        }
+        self.size += chunk.len();
+
+        match current {
+            Part::Operations(data) | Part::Map(data) => data.extend_from_slice(chunk),
+            Part::File(_, file) => {
+                file.size += chunk.len();
+                file.chunks.push(Bytes::copy_from_slice(chunk));
+            }
+        }
+        Ok(())
+    }
+
+    /// Completes the current part of the request.
+    ///
+    /// Fails with [`MultipartError::PartNotStarted`] if no part has been
+    /// started.
+    pub fn end_part(&mut self) -> Result<(), MultipartError> {
+        match self.current.take().ok_or(MultipartError::PartNotStarted)? {
+            Part::Operations(data) => {
+                let operations =
+                    serde_json::from_slice(&data).map_err(MultipartError::InvalidOperations)?;
+                self.operations = Some(operations);
+            }
+            Part::Map(data) => {
+                let map = serde_json::from_slice(&data).map_err(MultipartError::InvalidMap)?;
+                self.map = Some(map);
+            }
+            Part::File(name, file) => {
+                self.files.insert(name, file);
+            }
+        }
+        Ok(())
+    }
+
+    /// Completes parsing of the request, putting all the files into their
+    /// places in the `operations`.
+    pub fn finish<S>(mut self) -> Result<GraphQLMultipartRequest<S>, MultipartError>
+    where
+        S: ScalarValue,
+    {
+        let mut operations = self
+            .operations
+            .take()
+            .ok_or(MultipartError::MissingOperations)?;
+        let map = self.map.take().ok_or(MultipartError::MissingMap)?;
+
+        let mut files = Files::new();
+        for (name, paths) in map {
+            let file = self
+                .files
+                .remove(&name)
+                .ok_or_else(|| MultipartError::MissingFile(name.clone()))?;
+            let key = format!("{}{}", UPLOAD_KEY_PREFIX, name);
+            for path in &paths {
+                *lookup_path(&mut operations, path)
+                    .ok_or_else(|| MultipartError::InvalidPath(path.clone()))? =
+                    serde_json::Value::String(key.clone());
+            }
+            files.insert(name, Arc::new(file));
+        }
+
+        let request =
+            serde_json::from_value(operations).map_err(MultipartError::InvalidOperations)?;
+        Ok(GraphQLMultipartRequest {
+            request,
+            files: Arc::new(files),
+        })
+    }
+}
+
+/// Returns the value at the given dot-separated `path` of the `operations`.
+fn lookup_path<'v>(
+    mut value: &'v mut serde_json::Value,
+    path: &str,
+) -> Option<&'v mut serde_json::Value> {
+    for segment in path.split('.') {
+        value = match value {
+            serde_json::Value::Object(o) => o.get_mut(segment)?,
+            serde_json::Value::Array(a) => a.get_mut(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Parsed [GraphQL multipart request][1].
+///
+/// Its files are referred to by the placeholders in its own variables, and are
+/// available to [`Upload`] arguments while it's being executed only.
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Debug)]
+pub struct GraphQLMultipartRequest<S: ScalarValue> {
+    request: GraphQLBatchRequest<S>,
+    files: Arc<Files>,
+}
+
+impl<S> GraphQLMultipartRequest<S>
+where
+    S: ScalarValue,
+{
+    /// Returns the GraphQL operations of this request, with files replaced by
+    /// opaque placeholders.
+    pub fn request(&self) -> &GraphQLBatchRequest<S> {
+        &self.request
+    }
+
+    /// Executes this request synchronously using the specified schema and
+    /// context.
+    pub fn execute_sync<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        with_files(&self.files, || {
+            self.request.execute_sync(root_node, context)
+        })
+    }
+
+    /// Executes this request using the specified schema and context.
+    pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        let mut response = Box::pin(self.request.execute(root_node, context));
+        future::poll_fn(|cx| with_files(&self.files, || response.as_mut().poll(cx))).await
+    }
+}
+
+#[derive(Debug)]
+struct UploadedFile {
+    filename: Option<String>,
+    content_type: Option<String>,
+    chunks: Vec<Bytes>,
+    size: usize,
+}
+
+/// File uploaded via a [GraphQL multipart request][1], usable as an argument
+/// of type `Upload`.
+///
+/// Files are only available while their own [`GraphQLMultipartRequest`] is
+/// being executed, and can't be provided in any other way. Their contents are
+/// fully buffered in memory before the request is executed.
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Clone, Debug)]
+pub struct Upload {
+    key: String,
+    file: Arc<UploadedFile>,
+}
+
+impl Upload {
+    /// Returns the name of this file, as provided by the client.
+    pub fn filename(&self) -> Option<&str> {
+        self.file.filename.as_deref()
+    }
+
+    /// Returns the content type of this file, as provided by the client.
+    pub fn content_type(&self) -> Option<&str> {
+        self.file.content_type.as_deref()
+    }
+
+    /// Returns the size of this file in bytes.
+    pub fn size(&self) -> usize {
+        self.file.size
+    }
+
+    /// Returns the chunks of this file's contents, as they were received.
+    ///
+    /// The contents are already buffered in memory, so this doesn't wait for
+    /// any more data from the client.
+    pub fn buffered_chunks(&self) -> impl Iterator<Item = &[u8]> {
+        self.file.chunks.iter().map(Bytes::as_ref)
+    }
+
+    /// Returns a [`Stream`] of the chunks of this file's contents, as they
+    /// were received.
+    ///
+    /// The stream doesn't borrow this [`Upload`], so it may be passed on to
+    /// any asynchronous consumer, like a storage client.
+    pub fn stream(&self) -> impl Stream<Item = Bytes> + Send + 'static {
+        futures::stream::iter(self.file.chunks.clone())
+    }
+
+    /// Returns all the contents of this file.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.file.chunks.concat()
+    }
+}
+
+impl<S> GraphQLType<S> for Upload
+where
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<&'static str> {
+        Some("Upload")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry
+            .build_scalar_type::<Self>(&())
+            .description("A file uploaded via a GraphQL multipart request")
+            .into_meta()
+    }
+}
+
+impl<S> GraphQLValue<S> for Upload
+where
+    S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Err("`Upload` is an input only type".into())
+    }
+}
+
+impl<S: ScalarValue> IsInputType<S> for Upload {}
+
+impl<S> FromInputValue<S> for Upload
+where
+    S: ScalarValue,
+{
+    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+        Self::try_from_input_value(v).ok()
+    }
+
+    fn try_from_input_value(v: &InputValue<S>) -> Result<Self, InputValueError> {
+        let key = v
+            .as_string_value()
+            .filter(|key| key.starts_with(UPLOAD_KEY_PREFIX))
+            .ok_or_else(|| InputValueError::new("Expected a file of a multipart request"))?;
+        let file = lookup_file(key)
+            .ok_or_else(|| InputValueError::new("Uploaded file is not available"))?;
+        Ok(Self {
+            key: key.into(),
+            file,
+        })
+    }
+}
+
+impl<S> ToInputValue<S> for Upload
+where
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::scalar(self.key.clone())
+    }
+}
+
+impl<S> ParseScalarValue<S> for Upload
+where
+    S: ScalarValue,
+{
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+        // Files can only be provided as variables.
+        Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt as _;
+
+    use crate::{
+        graphql_object,
+        http::{GraphQLBatchRequest, GraphQLRequest},
+        DefaultScalarValue, EmptyMutation, EmptySubscription, FromInputValue as _, InputValue,
+        RootNode,
+    };
+
+    use super::{
+        GraphQLMultipartRequest, MultipartError, MultipartLimits, MultipartParser, Upload,
+    };
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        async fn file(file: Upload) -> String {
+            let contents = String::from_utf8(file.to_bytes()).unwrap();
+            format!(
+                "{}:{}:{}:{}",
+                file.filename().unwrap_or_default(),
+                file.content_type().unwrap_or_default(),
+                file.size(),
+                contents,
+            )
+        }
+
+        fn count(files: Vec<Upload>) -> i32 {
+            files.len() as i32
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    fn feed(
+        parser: &mut MultipartParser,
+        name: &str,
+        chunks: &[&str],
+    ) -> Result<(), MultipartError> {
+        let (filename, content_type) = match name {
+            "operations" | "map" => (None, None),
+            _ => (Some(format!("{}.txt", name)), Some("text/plain".into())),
+        };
+        parser.start_part(name, filename, content_type)?;
+        for chunk in chunks {
+            parser.push_chunk(chunk.as_bytes())?;
+        }
+        parser.end_part()
+    }
+
+    fn parse(
+        limits: MultipartLimits,
+        parts: &[(&str, &[&str])],
+    ) -> Result<GraphQLMultipartRequest<DefaultScalarValue>, MultipartError> {
+        let mut parser = MultipartParser::new(limits);
+        for (name, chunks) in parts {
+            feed(&mut parser, name, chunks)?;
+        }
+        parser.finish()
+    }
+
+    #[tokio::test]
+    async fn executes_with_uploaded_files() {
+        let req = parse(
+            MultipartLimits::default(),
+            &[
+                (
+                    "operations",
+                    &[
+                        r#"{"query": "query($f: Upload!, $fs: [Upload!]!) { "#,
+                        r#"file(file: $f) count(files: $fs) }", "#,
+                        r#""variables": {"f": null, "fs": [null, null]}}"#,
+                    ],
+                ),
+                (
+                    "map",
+                    &[r#"{"0": ["variables.f", "variables.fs.0"], "1": ["variables.fs.1"]}"#],
+                ),
+                ("0", &["hello ", "world"]),
+                ("1", &[""]),
+            ],
+        )
+        .unwrap();
+
+        let schema = schema();
+        let resp = req.execute(&schema, &()).await;
+
+        assert!(resp.is_ok());
+        assert_eq!(
+            serde_json::to_string(&resp).unwrap(),
+            r#"{"data":{"file":"0.txt:text/plain:11:hello world","count":2}}"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn executes_batches() {
+        let req = parse(
+            MultipartLimits::default(),
+            &[
+                (
+                    "operations",
+                    &[r#"[
+                        {"query": "query($f: Upload!) { file(file: $f) }", "variables": {"f": null}},
+                        {"query": "{ count(files: []) }"}
+                    ]"#],
+                ),
+                ("map", &[r#"{"a": ["0.variables.f"]}"#]),
+                ("a", &["data"]),
+            ],
+        )
+        .unwrap();
+
+        let schema = schema();
+        let resp = req.execute(&schema, &()).await;
+
+        assert_eq!(
+            serde_json::to_string(&resp).unwrap(),
+            r#"[{"data":{"file":"a.txt:text/plain:4:data"}},{"data":{"count":0}}]"#,
+        );
+    }
+
+    fn single_file_request() -> GraphQLMultipartRequest<DefaultScalarValue> {
+        parse(
+            MultipartLimits::default(),
+            &[
+                (
+                    "operations",
+                    &[r#"{"query": "query($f: Upload!) { count(files: [$f]) }", "variables": {"f": null}}"#],
+                ),
+                ("map", &[r#"{"0": ["variables.f"]}"#]),
+                ("0", &["data"]),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn placeholder(req: &GraphQLMultipartRequest<DefaultScalarValue>) -> String {
+        match req.request() {
+            GraphQLBatchRequest::Single(r) => {
+                r.variables()["f"].as_string_value().unwrap().to_owned()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn provides_files_to_their_request_only() {
+        let req = single_file_request();
+        let key = placeholder(&req);
+
+        let schema = schema();
+        let other: GraphQLRequest = serde_json::from_value(serde_json::json!({
+            "query": "query($f: Upload!) { count(files: [$f]) }",
+            "variables": {"f": key},
+        }))
+        .unwrap();
+        assert!(!other.execute(&schema, &()).await.is_ok());
+        assert!(!other.execute_sync(&schema, &()).is_ok());
+
+        assert!(req.execute(&schema, &()).await.is_ok());
+        assert!(req.execute_sync(&schema, &()).is_ok());
+
+        let key = InputValue::<DefaultScalarValue>::scalar(key);
+        assert!(Upload::try_from_input_value(&key).is_err());
+    }
+
+    #[tokio::test]
+    async fn streams_file_contents() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            async fn file(file: Upload) -> String {
+                let chunks = file.stream().collect::<Vec<_>>().await;
+                String::from_utf8(chunks.concat()).unwrap()
+            }
+        }
+
+        let req = parse(
+            MultipartLimits::default(),
+            &[
+                (
+                    "operations",
+                    &[r#"{"query": "query($f: Upload!) { file(file: $f) }", "variables": {"f": null}}"#],
+                ),
+                ("map", &[r#"{"0": ["variables.f"]}"#]),
+                ("0", &["hello ", "world"]),
+            ],
+        )
+        .unwrap();
+
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::new());
+        let resp = req.execute(&schema, &()).await;
+
+        assert_eq!(
+            serde_json::to_string(&resp).unwrap(),
+            r#"{"data":{"file":"hello world"}}"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_files_not_sent_via_multipart() {
+        let schema = schema();
+        let doc = r#"{ file(file: "juniper-upload:0") }"#;
+        let res = crate::execute(doc, None, &schema, &crate::Variables::new(), &()).await;
+
+        assert!(res.is_err());
+
+        let req: GraphQLRequest = serde_json::from_str(
+            r#"{"query": "query($f: Upload!) { file(file: $f) }",
+                "variables": {"f": "juniper-upload:0"}}"#,
+        )
+        .unwrap();
+        let resp = req.execute(&schema, &()).await;
+
+        assert_eq!(
+            serde_json::to_string(&resp).unwrap(),
+            r#"{"errors":[{"message":"Variable \"$f\" got invalid value. Expected \"Upload\": Uploaded file is not available.","locations":[{"line":1,"column":7}]}]}"#,
+        );
+    }
+
+    #[test]
+    fn enforces_limits() {
+        let operations: (&str, &[&str]) = (
+            "operations",
+            &[r#"{"query": "", "variables": {"a": null, "b": null}}"#],
+        );
+        let map: (&str, &[&str]) = ("map", &[r#"{"0": ["variables.a"], "1": ["variables.b"]}"#]);
+
+        let err = parse(
+            MultipartLimits::new().max_file_size(4),
+            &[operations, map, ("0", &["abc", "de"])],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MultipartError::PartTooLarge(4)), "{}", err);
+        assert!(err.is_limit_exceeded());
+
+        let err = parse(
+            MultipartLimits::new().max_files(1),
+            &[operations, map, ("0", &["a"]), ("1", &["b"])],
+        )
+        .unwrap_err();
+        assert!(matches!(err, MultipartError::TooManyFiles(1)), "{}", err);
+        assert!(err.is_limit_exceeded());
+
+        let err = parse(MultipartLimits::new().max_operations_size(8), &[operations]).unwrap_err();
+        assert!(matches!(err, MultipartError::PartTooLarge(8)), "{}", err);
+        assert!(err.is_limit_exceeded());
+
+        let err = parse(
+            MultipartLimits::new()
+                .max_file_size(4)
+                .max_request_size(100),
+            &[operations, map, ("0", &["abcd"]), ("1", &["ab", "cd"])],
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, MultipartError::RequestTooLarge(100)),
+            "{}",
+            err
+        );
+        assert!(err.is_limit_exceeded());
+    }
+
+    #[test]
+    fn rejects_data_outside_of_parts() {
+        let mut parser = MultipartParser::new(MultipartLimits::default());
+
+        let err = parser.push_chunk(b"data").unwrap_err();
+        assert!(matches!(err, MultipartError::PartNotStarted), "{}", err);
+
+        let err = parser.end_part().unwrap_err();
+        assert!(matches!(err, MultipartError::PartNotStarted), "{}", err);
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        let operations: (&str, &[&str]) = (
+            "operations",
+            &[r#"{"query": "", "variables": {"a": null}}"#],
+        );
+
+        for (parts, expected) in [
+            (vec![("map", &["{}"] as &[&str])], "`operations` must be the first part"),
+            (vec![operations, ("0", &["a"])], "`map` must follow the `operations` part"),
+            (
+                vec![operations, ("map", &["[]"])],
+                "Invalid `map`: invalid type: sequence, expected a map at line 1 column 0",
+            ),
+            (
+                vec![operations, ("map", &[r#"{"0": ["variables.b"]}"#]), ("0", &["a"])],
+                "Invalid `map` path: variables.b",
+            ),
+            (
+                vec![operations, ("map", &[r#"{"0": ["variables.a"]}"#]), ("1", &["a"])],
+                "Unexpected file: 1",
+            ),
+            (
+                vec![operations, ("map", &[r#"{"0": ["variables.a"]}"#])],
+                "Missing file: 0",
+            ),
+            (
+                vec![("operations", &["{}"]), ("map", &["{}"])],
+                "Invalid `operations`: data did not match any variant of untagged enum GraphQLBatchRequest",
+            ),
+        ] {
+            let err = parse(MultipartLimits::default(), &parts).unwrap_err();
+
+            assert_eq!(err.to_string(), expected);
+            assert!(!err.is_limit_exceeded());
+        }
+    }
+}
@@ -0,0 +1,978 @@
+//! Framework-agnostic implementation of the [GraphQL over HTTP][1] protocol.
+//!
+//! Web framework integrations extract the method, the query string, the
+//! `Content-Type` and `Accept` headers and the body of an incoming request,
+//! and leave parsing of the request, negotiation of the response media type
+//! and choosing of the response status code to this module, so all of them
+//! behave the same way.
+//!
+//! [1]: https://graphql.github.io/graphql-over-http/draft
+
+use std::{error::Error, fmt, str};
+
+use crate::{
+    ast::InputValue,
+    http::{GraphQLBatchRequest, GraphQLBatchResponse, GraphQLRequest, GraphQLResponse, Method},
+    value::ScalarValue,
+    GraphQLError,
+};
+
+/// Media type of JSON encoded GraphQL requests and legacy GraphQL responses.
+pub const APPLICATION_JSON: &str = "application/json";
+
+/// Media type of GraphQL requests sent as a raw GraphQL document.
+pub const APPLICATION_GRAPHQL: &str = "application/graphql";
+
+/// Media type of GraphQL responses defined by the [GraphQL over HTTP][1]
+/// specification.
+///
+/// [1]: https://graphql.github.io/graphql-over-http/draft
+pub const APPLICATION_GRAPHQL_RESPONSE_JSON: &str = "application/graphql-response+json";
+
+/// Media type of HTML forms, sent cross-origin without a CORS preflight.
+const APPLICATION_X_WWW_FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+/// Media type of HTML forms with files, sent cross-origin without a CORS
+/// preflight.
+const MULTIPART_FORM_DATA: &str = "multipart/form-data";
+
+/// Media type of plain text, sent cross-origin without a CORS preflight.
+const TEXT_PLAIN: &str = "text/plain";
+
+/// Header sent by [Apollo Client][1] along with the name of the operation.
+///
+/// [1]: https://www.apollographql.com/docs/react
+pub const HEADER_X_APOLLO_OPERATION_NAME: &str = "x-apollo-operation-name";
+
+/// Header any client may send to mark its request as not a simple one.
+pub const HEADER_GRAPHQL_PREFLIGHT: &str = "graphql-preflight";
+
+/// Error of handling an HTTP request before any GraphQL operation is executed.
+#[derive(Debug)]
+pub enum RequestError {
+    /// The HTTP method is neither `GET` nor `POST`.
+    MethodNotAllowed,
+
+    /// The `Content-Type` of a `POST` request is neither [`APPLICATION_JSON`]
+    /// nor [`APPLICATION_GRAPHQL`].
+    UnsupportedMediaType,
+
+    /// The `Accept` header allows none of the supported [`ResponseFormat`]s.
+    NotAcceptable,
+
+    /// The `query` parameter is missing.
+    MissingQuery,
+
+    /// The parameter is specified multiple times in the query string.
+    DuplicateParameter(&'static str),
+
+    /// The `variables` parameter isn't a valid JSON.
+    InvalidVariables(serde_json::Error),
+
+    /// The body of a `POST` request isn't a valid JSON encoded GraphQL request.
+    InvalidBody(serde_json::Error),
+
+    /// The body of a `POST` request isn't a valid UTF-8 string.
+    InvalidUtf8(str::Utf8Error),
+
+    /// The request could have been sent cross-origin without a CORS preflight,
+    /// so has been blocked by [`CsrfPrevention`].
+    CsrfPrevented,
+}
+
+impl RequestError {
+    /// Returns the HTTP status code to respond with to the request causing
+    /// this error.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::MethodNotAllowed => 405,
+            Self::NotAcceptable => 406,
+            Self::UnsupportedMediaType => 415,
+            Self::MissingQuery
+            | Self::DuplicateParameter(_)
+            | Self::InvalidVariables(_)
+            | Self::InvalidBody(_)
+            | Self::InvalidUtf8(_)
+            | Self::CsrfPrevented => 400,
+        }
+    }
+
+    /// Returns the value of the `Allow` header to respond with along with the
+    /// [`RequestError::status_code`], if any.
+    pub fn allowed_methods(&self) -> Option<&'static str> {
+        match self {
+            Self::MethodNotAllowed => Some("GET, POST"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MethodNotAllowed => write!(f, "Only GET and POST requests are supported"),
+            Self::UnsupportedMediaType => write!(
+                f,
+                "Content-Type must be either `{}` or `{}`",
+                APPLICATION_JSON, APPLICATION_GRAPHQL,
+            ),
+            Self::NotAcceptable => write!(
+                f,
+                "Accept must allow either `{}` or `{}`",
+                APPLICATION_GRAPHQL_RESPONSE_JSON, APPLICATION_JSON,
+            ),
+            Self::MissingQuery => write!(f, "`query` parameter is missing"),
+            Self::DuplicateParameter(name) => {
+                write!(f, "`{}` parameter is specified multiple times", name)
+            }
+            Self::InvalidVariables(e) => write!(f, "Invalid `variables`: {}", e),
+            Self::InvalidBody(e) => write!(f, "Invalid request body: {}", e),
+            Self::InvalidUtf8(e) => write!(f, "Request body is not a valid UTF-8 string: {}", e),
+            Self::CsrfPrevented => write!(
+                f,
+                "This request has been blocked as a potential Cross-Site Request Forgery, \
+                 specify a Content-Type other than `{}`, `{}` or `{}`, \
+                 or a non-empty `{}` or `{}` header",
+                APPLICATION_X_WWW_FORM_URLENCODED,
+                MULTIPART_FORM_DATA,
+                TEXT_PLAIN,
+                HEADER_X_APOLLO_OPERATION_NAME,
+                HEADER_GRAPHQL_PREFLIGHT,
+            ),
+        }
+    }
+}
+
+impl Error for RequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidVariables(e) | Self::InvalidBody(e) => Some(e),
+            Self::InvalidUtf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Media type of a response body, negotiated with the client via the `Accept`
+/// header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResponseFormat {
+    /// [`APPLICATION_GRAPHQL_RESPONSE_JSON`], used whenever the client
+    /// explicitly prefers it.
+    GraphQLResponseJson,
+
+    /// [`APPLICATION_JSON`], used for legacy clients, including the ones not
+    /// sending the `Accept` header at all or accepting any media type.
+    Json,
+
+    /// [`APPLICATION_JSON`], explicitly listed in the `Accept` header.
+    ///
+    /// Clients doing so opt in to `200 OK` responses to requests failed to be
+    /// parsed or validated, as the [GraphQL over HTTP][1] specification
+    /// recommends for this media type.
+    ///
+    /// [1]: https://graphql.github.io/graphql-over-http/draft
+    ExplicitJson,
+}
+
+impl ResponseFormat {
+    /// Negotiates the [`ResponseFormat`] from the value of the `Accept` header,
+    /// respecting the quality values of the listed media ranges.
+    ///
+    /// # Errors
+    ///
+    /// If none of the listed media ranges matches a supported
+    /// [`ResponseFormat`].
+    pub fn negotiate(accept: Option<&str>) -> Result<Self, RequestError> {
+        let accept = match accept {
+            Some(accept) if !accept.trim().is_empty() => accept,
+            _ => return Ok(Self::Json),
+        };
+
+        let mut best: Option<(f32, Self)> = None;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let format = match media_type_essence(params.next().unwrap_or_default()).as_str() {
+                APPLICATION_GRAPHQL_RESPONSE_JSON => Self::GraphQLResponseJson,
+                APPLICATION_JSON => Self::ExplicitJson,
+                "application/*" | "*/*" => Self::Json,
+                _ => continue,
+            };
+            let quality = quality(params);
+            if quality <= 0.0 {
+                continue;
+            }
+            let is_better = best.is_none_or(|(q, f): (f32, Self)| {
+                quality > q || (quality == q && format.preference() > f.preference())
+            });
+            if is_better {
+                best = Some((quality, format));
+            }
+        }
+        best.map(|(_, f)| f).ok_or(RequestError::NotAcceptable)
+    }
+
+    /// Returns the value of the `Content-Type` header of a response in this
+    /// [`ResponseFormat`].
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::GraphQLResponseJson => APPLICATION_GRAPHQL_RESPONSE_JSON,
+            Self::Json | Self::ExplicitJson => APPLICATION_JSON,
+        }
+    }
+
+    /// Returns the preference of this [`ResponseFormat`] among the equally
+    /// accepted ones.
+    fn preference(self) -> u8 {
+        match self {
+            Self::Json => 0,
+            Self::ExplicitJson => 1,
+            Self::GraphQLResponseJson => 2,
+        }
+    }
+}
+
+/// Content coding of a response body, negotiated with the client via the
+/// `Accept-Encoding` header.
+///
+/// Responses are only compressed when the `compression` feature is enabled,
+/// otherwise [`ContentEncoding::negotiate`] always chooses
+/// [`ContentEncoding::Identity`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ContentEncoding {
+    /// No compression.
+    #[default]
+    Identity,
+
+    /// [Gzip][1] compression.
+    ///
+    /// [1]: https://datatracker.ietf.org/doc/html/rfc1952
+    #[cfg(feature = "compression")]
+    Gzip,
+
+    /// [Brotli][1] compression, preferred over [`ContentEncoding::Gzip`] when
+    /// the client accepts both equally.
+    ///
+    /// [1]: https://datatracker.ietf.org/doc/html/rfc7932
+    #[cfg(feature = "compression")]
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// Negotiates the [`ContentEncoding`] from the value of the
+    /// `Accept-Encoding` header, respecting the quality values of the listed
+    /// codings.
+    ///
+    /// Falls back to [`ContentEncoding::Identity`] if none of the listed
+    /// codings is supported.
+    pub fn negotiate(accept_encoding: Option<&str>) -> Self {
+        let mut best = (0.0, Self::Identity);
+        for coding in accept_encoding.unwrap_or_default().split(',') {
+            let mut params = coding.split(';');
+            let encoding = match Self::from_token(params.next().unwrap_or_default().trim()) {
+                Some(encoding) => encoding,
+                None => continue,
+            };
+            let quality = quality(params);
+            if quality <= 0.0 {
+                continue;
+            }
+            if quality > best.0
+                || (quality == best.0 && encoding.preference() > best.1.preference())
+            {
+                best = (quality, encoding);
+            }
+        }
+        best.1
+    }
+
+    /// Returns the value of the `Content-Encoding` header of a response in
+    /// this [`ContentEncoding`], if any.
+    pub fn header_value(self) -> Option<&'static str> {
+        match self {
+            Self::Identity => None,
+            #[cfg(feature = "compression")]
+            Self::Gzip => Some("gzip"),
+            #[cfg(feature = "compression")]
+            Self::Brotli => Some("br"),
+        }
+    }
+
+    /// Parses a supported [`ContentEncoding`] from the given content coding
+    /// token, treating `*` as the preferred one.
+    fn from_token(token: &str) -> Option<Self> {
+        #[cfg(feature = "compression")]
+        {
+            if token.eq_ignore_ascii_case("br") || token == "*" {
+                return Some(Self::Brotli);
+            }
+            if token.eq_ignore_ascii_case("gzip") || token.eq_ignore_ascii_case("x-gzip") {
+                return Some(Self::Gzip);
+            }
+        }
+        #[cfg(not(feature = "compression"))]
+        let _ = token;
+        None
+    }
+
+    /// Returns the preference of this [`ContentEncoding`] among the equally
+    /// accepted ones.
+    fn preference(self) -> u8 {
+        match self {
+            Self::Identity => 0,
+            #[cfg(feature = "compression")]
+            Self::Gzip => 1,
+            #[cfg(feature = "compression")]
+            Self::Brotli => 2,
+        }
+    }
+}
+
+/// Returns the HTTP status code to respond with the given
+/// [`GraphQLBatchResponse`] serialized in the negotiated [`ResponseFormat`].
+///
+/// Non-query operations refused to be executed via [`Method::Get`] are
+/// responded with `405 Method Not Allowed`. Requests failed to be parsed or
+/// validated are responded with `400 Bad Request`, unless the client opted in
+/// to `200 OK` by explicitly accepting [`ResponseFormat::ExplicitJson`]. All
+/// the other ones are considered successful, even if there are field errors in
+/// the response.
+pub fn status_code<S: ScalarValue>(
+    response: &GraphQLBatchResponse<'_, S>,
+    format: ResponseFormat,
+) -> u16 {
+    if is_not_query(response) {
+        405
+    } else if response.is_ok() || format == ResponseFormat::ExplicitJson {
+        200
+    } else {
+        400
+    }
+}
+
+/// Returns the value of the `Allow` header to respond with the given
+/// [`GraphQLBatchResponse`], if any.
+///
+/// Only responses with the `405 Method Not Allowed` [`status_code()`] have
+/// one, as the operations refused via [`Method::Get`] are only allowed via
+/// `POST`.
+pub fn allowed_methods<S: ScalarValue>(
+    response: &GraphQLBatchResponse<'_, S>,
+) -> Option<&'static str> {
+    is_not_query(response).then_some("POST")
+}
+
+/// Indicates whether the given [`GraphQLBatchResponse`] refuses a non-query
+/// operation received via [`Method::Get`].
+///
+/// Batches selecting a non-query operation are refused as a whole with a
+/// single response, so any of their items being refused doesn't count.
+fn is_not_query<S: ScalarValue>(response: &GraphQLBatchResponse<'_, S>) -> bool {
+    matches!(
+        response,
+        GraphQLBatchResponse::Single(GraphQLResponse(Err(GraphQLError::NotQuery), _)),
+    )
+}
+
+/// Guard against [Cross-Site Request Forgery][1] via simple requests.
+///
+/// Browsers send `GET` requests and `POST` requests with a
+/// [`Content-Type`][2] of `application/x-www-form-urlencoded`,
+/// `multipart/form-data` or `text/plain` cross-origin without a CORS
+/// preflight, so the operation is executed with the cookies of the user before
+/// the server has a chance to refuse it. This guard requires either another
+/// `Content-Type` or one of the [`CsrfPrevention::required_headers`], which
+/// can't be set without a preflight, and so makes the CORS policy effective.
+///
+/// Web framework integrations don't enable it by default. Clients sending
+/// `GET` requests or [multipart requests][3] have to specify one of the
+/// required headers when it's enabled.
+///
+/// [1]: https://owasp.org/www-community/attacks/csrf
+/// [2]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CORS#simple_requests
+/// [3]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CsrfPrevention {
+    /// Lowercased names of the headers any of which marks a request as not
+    /// a simple one.
+    required_headers: Vec<String>,
+}
+
+impl CsrfPrevention {
+    /// Creates a guard requiring either a non-simple `Content-Type`, or the
+    /// [`HEADER_X_APOLLO_OPERATION_NAME`] or the [`HEADER_GRAPHQL_PREFLIGHT`]
+    /// header.
+    pub fn new() -> Self {
+        Self {
+            required_headers: vec![
+                HEADER_X_APOLLO_OPERATION_NAME.to_owned(),
+                HEADER_GRAPHQL_PREFLIGHT.to_owned(),
+            ],
+        }
+    }
+
+    /// Specifies the headers any of which allows a request with a simple
+    /// `Content-Type`, replacing the default ones.
+    pub fn required_headers<I, H>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = H>,
+        H: AsRef<str>,
+    {
+        self.required_headers = headers
+            .into_iter()
+            .map(|h| h.as_ref().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Checks whether a request with the given value of the `Content-Type`
+    /// header may be executed.
+    ///
+    /// The `header` function returns the value of the request header with the
+    /// given lowercased name.
+    ///
+    /// # Errors
+    ///
+    /// [`RequestError::CsrfPrevented`] if the request could have been sent
+    /// without a CORS preflight.
+    pub fn check<'h>(
+        &self,
+        content_type: Option<&str>,
+        header: impl Fn(&str) -> Option<&'h str>,
+    ) -> Result<(), RequestError> {
+        let is_preflighted = content_type.is_some_and(|ct| {
+            let essence = media_type_essence(ct);
+            !essence.is_empty()
+                && ![
+                    APPLICATION_X_WWW_FORM_URLENCODED,
+                    MULTIPART_FORM_DATA,
+                    TEXT_PLAIN,
+                ]
+                .contains(&essence.as_str())
+        }) || self
+            .required_headers
+            .iter()
+            .any(|name| header(name).is_some_and(|v| !v.is_empty()));
+
+        if is_preflighted {
+            Ok(())
+        } else {
+            Err(RequestError::CsrfPrevented)
+        }
+    }
+}
+
+impl Default for CsrfPrevention {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Incremental parser of the query string parameters of a `GET` request.
+///
+/// Integrations with their own URL decoding feed the decoded parameters via
+/// [`GetRequestParser::push`], while [`parse_get_request`] does everything at
+/// once.
+#[derive(Debug)]
+pub struct GetRequestParser<S: ScalarValue> {
+    query: Option<String>,
+    operation_name: Option<String>,
+    variables: Option<InputValue<S>>,
+}
+
+impl<S: ScalarValue> Default for GetRequestParser<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ScalarValue> GetRequestParser<S> {
+    /// Creates a new parser of a `GET` request.
+    pub fn new() -> Self {
+        Self {
+            query: None,
+            operation_name: None,
+            variables: None,
+        }
+    }
+
+    /// Accepts the URL decoded parameter with the given `name`.
+    ///
+    /// Returns `false` if the parameter has nothing to do with GraphQL, so is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the parameter is specified multiple times, or is invalid.
+    pub fn push(&mut self, name: &str, value: String) -> Result<bool, RequestError> {
+        match name {
+            "query" => set_once(&mut self.query, "query", value)?,
+            "operationName" => set_once(&mut self.operation_name, "operationName", value)?,
+            "variables" => {
+                let variables =
+                    serde_json::from_str(&value).map_err(RequestError::InvalidVariables);
+                set_once(&mut self.variables, "variables", variables?)?
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Finishes parsing, returning the parsed [`GraphQLRequest`].
+    ///
+    /// # Errors
+    ///
+    /// If the `query` parameter is missing.
+    pub fn finish(self) -> Result<GraphQLRequest<S>, RequestError> {
+        let query = self.query.ok_or(RequestError::MissingQuery)?;
+        Ok(GraphQLRequest::new(
+            query,
+            self.operation_name,
+            self.variables,
+        ))
+    }
+}
+
+fn set_once<T>(param: &mut Option<T>, name: &'static str, value: T) -> Result<(), RequestError> {
+    if param.replace(value).is_some() {
+        return Err(RequestError::DuplicateParameter(name));
+    }
+    Ok(())
+}
+
+/// Parses a [`GraphQLRequest`] from the URL encoded query string of a `GET`
+/// request.
+///
+/// # Errors
+///
+/// See [`GetRequestParser`].
+pub fn parse_get_request<S: ScalarValue>(
+    query_string: &str,
+) -> Result<GraphQLRequest<S>, RequestError> {
+    let mut parser = GetRequestParser::new();
+    for (name, value) in form_urlencoded::parse(query_string.as_bytes()) {
+        parser.push(&name, value.into_owned())?;
+    }
+    parser.finish()
+}
+
+/// Indicates whether the body of a `POST` request with the given value of the
+/// `Content-Type` header can be parsed by [`parse_post_request`].
+pub fn is_supported_content_type(content_type: &str) -> bool {
+    matches!(
+        media_type_essence(content_type).as_str(),
+        APPLICATION_JSON | APPLICATION_GRAPHQL,
+    )
+}
+
+/// Parses a [`GraphQLBatchRequest`] from the `body` of a `POST` request with
+/// the given value of the `Content-Type` header.
+///
+/// # Errors
+///
+/// If the `Content-Type` is not supported, or the `body` doesn't match it.
+pub fn parse_post_request<S: ScalarValue>(
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<GraphQLBatchRequest<S>, RequestError> {
+    match content_type.map(media_type_essence).as_deref() {
+        Some(APPLICATION_JSON) => serde_json::from_slice(body).map_err(RequestError::InvalidBody),
+        Some(APPLICATION_GRAPHQL) => {
+            let query = str::from_utf8(body).map_err(RequestError::InvalidUtf8)?;
+            Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
+                query.into(),
+                None,
+                None,
+            )))
+        }
+        _ => Err(RequestError::UnsupportedMediaType),
+    }
+}
+
+/// Parses a [`GraphQLBatchRequest`] from an HTTP request with the given
+/// `method`, `query_string`, value of the `Content-Type` header and `body`.
+///
+/// # Errors
+///
+/// See [`parse_get_request`] and [`parse_post_request`].
+pub fn parse_request<S: ScalarValue>(
+    method: &str,
+    query_string: Option<&str>,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<GraphQLBatchRequest<S>, RequestError> {
+    match parse_method(method)? {
+        Method::Get => {
+            parse_get_request(query_string.unwrap_or_default()).map(GraphQLBatchRequest::Single)
+        }
+        Method::Post => parse_post_request(content_type, body),
+    }
+}
+
+/// Parses the given HTTP method name into a [`Method`] GraphQL requests may be
+/// received with.
+pub fn parse_method(method: &str) -> Result<Method, RequestError> {
+    match method {
+        "GET" => Ok(Method::Get),
+        "POST" => Ok(Method::Post),
+        _ => Err(RequestError::MethodNotAllowed),
+    }
+}
+
+/// Returns the quality value among the given parameters of a media range or
+/// a content coding, defaulting to `1`.
+fn quality<'p>(params: impl Iterator<Item = &'p str>) -> f32 {
+    params
+        .filter_map(|p| {
+            let (name, value) = p.split_once('=')?;
+            name.trim()
+                .eq_ignore_ascii_case("q")
+                .then(|| value.trim().parse::<f32>().ok())
+                .flatten()
+        })
+        .next()
+        .unwrap_or(1.0)
+}
+
+/// Returns the lowercased media type of the given header value, without any
+/// parameters.
+fn media_type_essence(value: &str) -> String {
+    value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        graphql_object,
+        http::{
+            GraphQLBatchRequest, GraphQLBatchResponse, GraphQLRequest, GraphQLResponse, Method,
+        },
+        value::{DefaultScalarValue, Value},
+        Context, EmptySubscription, GraphQLError, InputValue, RootNode,
+    };
+
+    use super::{
+        allowed_methods, parse_get_request, parse_post_request, parse_request, status_code,
+        ContentEncoding, CsrfPrevention, RequestError, ResponseFormat,
+    };
+
+    #[test]
+    fn prevents_csrf() {
+        let csrf = CsrfPrevention::new();
+        for (content_type, headers, allowed) in [
+            (None, vec![], false),
+            (Some("text/plain"), vec![], false),
+            (Some("application/x-www-form-urlencoded"), vec![], false),
+            (Some("Multipart/Form-Data; boundary=x"), vec![], false),
+            (Some(""), vec![], false),
+            (
+                Some("text/plain"),
+                vec![("x-apollo-operation-name", "")],
+                false,
+            ),
+            (Some("text/plain"), vec![("x-custom", "1")], false),
+            (Some("application/json"), vec![], true),
+            (Some("application/graphql"), vec![], true),
+            (None, vec![("graphql-preflight", "1")], true),
+            (
+                Some("text/plain"),
+                vec![("x-apollo-operation-name", "A")],
+                true,
+            ),
+        ] {
+            let result = csrf.check(content_type, |name| {
+                headers.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+            });
+            assert_eq!(
+                result.is_ok(),
+                allowed,
+                "Content-Type: {:?}, headers: {:?}",
+                content_type,
+                headers,
+            );
+        }
+
+        let csrf = CsrfPrevention::new().required_headers(["X-Custom"]);
+        assert!(csrf
+            .check(None, |name| (name == "x-custom").then(|| "1"))
+            .is_ok());
+        assert!(csrf
+            .check(None, |name| (name == "graphql-preflight").then(|| "1"))
+            .is_err());
+    }
+
+    #[test]
+    fn chooses_status_code() {
+        let ok = || GraphQLResponse::<DefaultScalarValue>::from_result(Ok((Value::null(), vec![])));
+        let invalid = || GraphQLResponse::from_result(Err(GraphQLError::UnknownOperationName));
+        let not_query = || GraphQLResponse::from_result(Err(GraphQLError::NotQuery));
+
+        for (format, invalid_status) in vec![
+            (ResponseFormat::GraphQLResponseJson, 400),
+            (ResponseFormat::Json, 400),
+            (ResponseFormat::ExplicitJson, 200),
+        ] {
+            let status = |resp| status_code(&resp, format);
+
+            assert_eq!(status(GraphQLBatchResponse::Single(ok())), 200);
+            assert_eq!(
+                status(GraphQLBatchResponse::Single(invalid())),
+                invalid_status,
+            );
+            assert_eq!(status(GraphQLBatchResponse::Single(not_query())), 405);
+            assert_eq!(
+                status(GraphQLBatchResponse::Batch(vec![ok(), invalid()])),
+                invalid_status,
+            );
+            assert_eq!(
+                status(GraphQLBatchResponse::Batch(vec![ok(), not_query()])),
+                invalid_status,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_get_batches_as_a_whole() {
+        #[derive(Default)]
+        struct Calls(AtomicUsize);
+
+        impl Context for Calls {}
+
+        struct Query;
+
+        #[graphql_object(context = Calls)]
+        impl Query {
+            fn value(context: &Calls) -> i32 {
+                context.0.fetch_add(1, Ordering::SeqCst) as i32
+            }
+        }
+
+        struct Mutation;
+
+        #[graphql_object(context = Calls)]
+        impl Mutation {
+            fn value(context: &Calls) -> i32 {
+                context.0.fetch_add(1, Ordering::SeqCst) as i32
+            }
+        }
+
+        let schema = RootNode::new(Query, Mutation, EmptySubscription::<Calls>::new());
+        let request = |query: &str| GraphQLRequest::new(query.into(), None, None);
+        let batch =
+            GraphQLBatchRequest::Batch(vec![request("{ value }"), request("mutation { value }")]);
+        let calls = Calls::default();
+
+        let response = batch.execute_via(Method::Get, &schema, &calls).await;
+        assert!(matches!(
+            &response,
+            GraphQLBatchResponse::Single(GraphQLResponse(Err(GraphQLError::NotQuery), _)),
+        ));
+        assert_eq!(status_code(&response, ResponseFormat::Json), 405);
+        assert_eq!(allowed_methods(&response), Some("POST"));
+
+        let response = batch.execute_sync_via(Method::Get, &schema, &calls);
+        assert_eq!(status_code(&response, ResponseFormat::Json), 405);
+        assert_eq!(calls.0.load(Ordering::SeqCst), 0);
+
+        let response = batch.execute_via(Method::Post, &schema, &calls).await;
+        assert_eq!(status_code(&response, ResponseFormat::Json), 200);
+        assert_eq!(calls.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn chooses_allowed_methods() {
+        let ok = || GraphQLResponse::<DefaultScalarValue>::from_result(Ok((Value::null(), vec![])));
+        let not_query =
+            || GraphQLResponse::<DefaultScalarValue>::from_result(Err(GraphQLError::NotQuery));
+
+        assert_eq!(allowed_methods(&GraphQLBatchResponse::Single(ok())), None);
+        assert_eq!(
+            allowed_methods(&GraphQLBatchResponse::Single(not_query())),
+            Some("POST"),
+        );
+        assert_eq!(
+            RequestError::MethodNotAllowed.allowed_methods(),
+            Some("GET, POST"),
+        );
+        assert_eq!(RequestError::MissingQuery.allowed_methods(), None);
+    }
+
+    #[test]
+    fn negotiates_response_format() {
+        for (accept, expected) in [
+            (None, ResponseFormat::Json),
+            (Some(""), ResponseFormat::Json),
+            (Some("*/*"), ResponseFormat::Json),
+            (Some("application/json"), ResponseFormat::ExplicitJson),
+            (Some("*/*, application/json"), ResponseFormat::ExplicitJson),
+            (
+                Some("application/graphql-response+json"),
+                ResponseFormat::GraphQLResponseJson,
+            ),
+            (
+                Some("application/json, application/graphql-response+json"),
+                ResponseFormat::GraphQLResponseJson,
+            ),
+            (
+                Some("application/graphql-response+json;q=0.9, application/json"),
+                ResponseFormat::ExplicitJson,
+            ),
+            (
+                Some("text/html, application/graphql-response+json; charset=utf-8"),
+                ResponseFormat::GraphQLResponseJson,
+            ),
+            (
+                Some("text/html,application/xhtml+xml,*/*;q=0.8"),
+                ResponseFormat::Json,
+            ),
+        ] {
+            assert_eq!(
+                ResponseFormat::negotiate(accept).unwrap(),
+                expected,
+                "Accept: {:?}",
+                accept,
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unacceptable_response_format() {
+        for accept in ["text/html", "application/json;q=0, text/plain"] {
+            assert!(
+                matches!(
+                    ResponseFormat::negotiate(Some(accept)),
+                    Err(RequestError::NotAcceptable),
+                ),
+                "Accept: {:?}",
+                accept,
+            );
+        }
+    }
+
+    #[test]
+    fn negotiates_content_encoding() {
+        for accept_encoding in [None, Some(""), Some("identity"), Some("deflate, zstd")] {
+            assert_eq!(
+                ContentEncoding::negotiate(accept_encoding),
+                ContentEncoding::Identity,
+                "Accept-Encoding: {:?}",
+                accept_encoding,
+            );
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn negotiates_compression() {
+        for (accept_encoding, expected) in [
+            ("gzip", ContentEncoding::Gzip),
+            ("x-gzip, deflate", ContentEncoding::Gzip),
+            ("br", ContentEncoding::Brotli),
+            ("*", ContentEncoding::Brotli),
+            ("gzip, deflate, br", ContentEncoding::Brotli),
+            ("br;q=0.5, GZIP", ContentEncoding::Gzip),
+            ("gzip;q=0, br;q=0", ContentEncoding::Identity),
+        ] {
+            assert_eq!(
+                ContentEncoding::negotiate(Some(accept_encoding)),
+                expected,
+                "Accept-Encoding: {:?}",
+                accept_encoding,
+            );
+        }
+    }
+
+    #[test]
+    fn parses_get_request() {
+        let req = parse_get_request::<DefaultScalarValue>(
+            "query=query%20Q(%24id%3A%20ID)%20%7B%20a%20%7D&operationName=Q&variables=%7B%22id%22%3A%201%7D&extensions=%7B%7D",
+        )
+        .unwrap();
+
+        assert_eq!(
+            req,
+            GraphQLRequest::new(
+                "query Q($id: ID) { a }".into(),
+                Some("Q".into()),
+                Some(InputValue::object(
+                    vec![("id", InputValue::scalar(1))].into_iter().collect(),
+                )),
+            ),
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_get_request() {
+        for (query_string, expected) in [
+            ("", "`query` parameter is missing"),
+            ("operation_name=Q", "`query` parameter is missing"),
+            (
+                "query=a&query=b",
+                "`query` parameter is specified multiple times",
+            ),
+            (
+                "query=a&operationName=A&operationName=B",
+                "`operationName` parameter is specified multiple times",
+            ),
+            (
+                "query=a&variables=blah",
+                "Invalid `variables`: expected value at line 1 column 1",
+            ),
+        ] {
+            let err = parse_get_request::<DefaultScalarValue>(query_string).unwrap_err();
+
+            assert_eq!(err.to_string(), expected, "query string: {}", query_string);
+            assert_eq!(err.status_code(), 400);
+        }
+    }
+
+    #[test]
+    fn parses_post_request() {
+        let json = parse_post_request::<DefaultScalarValue>(
+            Some("application/json; charset=utf-8"),
+            br#"{"query": "{ a }", "operationName": "A"}"#,
+        )
+        .unwrap();
+        let graphql =
+            parse_post_request::<DefaultScalarValue>(Some("Application/GraphQL"), b"{ a }")
+                .unwrap();
+
+        assert_eq!(
+            json,
+            GraphQLBatchRequest::Single(GraphQLRequest::new(
+                "{ a }".into(),
+                Some("A".into()),
+                None,
+            )),
+        );
+        assert_eq!(
+            graphql,
+            GraphQLBatchRequest::Single(GraphQLRequest::new("{ a }".into(), None, None)),
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_requests() {
+        let status = |res: Result<GraphQLBatchRequest<DefaultScalarValue>, RequestError>| {
+            res.unwrap_err().status_code()
+        };
+
+        assert_eq!(status(parse_post_request(None, b"{ a }")), 415);
+        assert_eq!(
+            status(parse_post_request(Some("text/plain"), b"{ a }")),
+            415
+        );
+        assert_eq!(
+            status(parse_post_request(Some("application/json"), b"[]")),
+            400
+        );
+        assert_eq!(status(parse_request("PUT", None, None, b"")), 405);
+    }
+}
@@ -0,0 +1,473 @@
+//! Incremental serialization of GraphQL responses as JSON.
+//!
+//! Web framework integrations turn a [`GraphQLBatchResponse`] into
+//! [`JsonChunks`] and send them as a streamed body, optionally compressed with
+//! [`JsonChunks::encoded`], so the serialized response is never held in memory
+//! as a whole.
+
+use std::{io, vec};
+
+use futures::io::{AsyncWrite, AsyncWriteExt as _};
+
+#[cfg(feature = "compression")]
+use std::{io::Write as _, mem};
+
+use crate::{
+    executor::ExecutionError,
+    http::{negotiation::ContentEncoding, GraphQLBatchResponse, GraphQLResponse},
+    value::{DefaultScalarValue, Object, ScalarValue, Value},
+};
+
+/// Default size of the chunks produced by [`JsonChunks`].
+pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Part of a response left to be serialized.
+enum Frame<S> {
+    /// Literal JSON.
+    Literal(&'static str),
+
+    /// Already serialized JSON.
+    Serialized(Vec<u8>),
+
+    /// Error of serializing a part of the response ahead of time.
+    Failed(serde_json::Error),
+
+    /// Value to be serialized.
+    Value(Value<S>),
+
+    /// Errors of a response to be serialized.
+    Errors(Vec<ExecutionError<S>>),
+
+    /// Remaining items of a list, the first of them not preceded by a comma.
+    List {
+        items: vec::IntoIter<Value<S>>,
+        first: bool,
+    },
+
+    /// Remaining fields of an object, the first of them not preceded by a
+    /// comma.
+    Object {
+        fields: <Object<S> as IntoIterator>::IntoIter,
+        first: bool,
+    },
+}
+
+/// Iterator over the JSON serialization of a [`GraphQLBatchResponse`], split
+/// into chunks of about [`JsonChunks::chunk_size`] bytes.
+///
+/// The response is serialized lazily while iterating, and the already
+/// serialized parts of it are dropped along the way. The concatenated chunks
+/// are the same as the result of [`serde_json::to_vec`].
+pub struct JsonChunks<S = DefaultScalarValue> {
+    /// Parts of the response left to be serialized, the next one on top.
+    stack: Vec<Frame<S>>,
+    chunk_size: usize,
+}
+
+impl<S> JsonChunks<S>
+where
+    S: ScalarValue,
+{
+    /// Creates chunks of the given [`GraphQLBatchResponse`].
+    pub fn new(response: GraphQLBatchResponse<'_, S>) -> Self {
+        let mut frames = Vec::new();
+        match response {
+            GraphQLBatchResponse::Single(response) => push_response(&mut frames, response),
+            GraphQLBatchResponse::Batch(responses) => {
+                frames.push(Frame::Literal("["));
+                for (i, response) in responses.into_iter().enumerate() {
+                    if i > 0 {
+                        frames.push(Frame::Literal(","));
+                    }
+                    push_response(&mut frames, response);
+                }
+                frames.push(Frame::Literal("]"));
+            }
+        }
+        frames.reverse();
+
+        Self {
+            stack: frames,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Specifies the size of the produced chunks, [`DEFAULT_CHUNK_SIZE`] by
+    /// default.
+    ///
+    /// Chunks may be slightly bigger, as scalars and strings are never split.
+    pub fn chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size.max(1);
+        self
+    }
+
+    /// Compresses these chunks with the given [`ContentEncoding`].
+    pub fn encoded(self, encoding: ContentEncoding) -> EncodedChunks<Self> {
+        EncodedChunks::new(self, encoding)
+    }
+
+    /// Writes all the chunks into the given `writer`.
+    pub async fn write_to<W: AsyncWrite + Unpin>(self, writer: &mut W) -> io::Result<()> {
+        write_chunks(self, writer).await
+    }
+
+    /// Serializes the given [`Frame`] into the `buf`, pushing its parts left
+    /// to be serialized onto the stack.
+    fn serialize(&mut self, frame: Frame<S>, buf: &mut Vec<u8>) -> serde_json::Result<()> {
+        match frame {
+            Frame::Literal(json) => buf.extend_from_slice(json.as_bytes()),
+            Frame::Serialized(json) => buf.extend_from_slice(&json),
+            Frame::Failed(e) => return Err(e),
+            Frame::Value(Value::Null) => buf.extend_from_slice(b"null"),
+            Frame::Value(Value::Scalar(s)) => serde_json::to_writer(buf, &s)?,
+            Frame::Value(Value::List(items)) => {
+                buf.push(b'[');
+                self.stack.push(Frame::List {
+                    items: items.into_iter(),
+                    first: true,
+                });
+            }
+            Frame::Value(Value::Object(fields)) => {
+                buf.push(b'{');
+                self.stack.push(Frame::Object {
+                    fields: fields.into_iter(),
+                    first: true,
+                });
+            }
+            Frame::Errors(errors) => serde_json::to_writer(buf, &errors)?,
+            Frame::List { mut items, first } => match items.next() {
+                Some(item) => {
+                    if !first {
+                        buf.push(b',');
+                    }
+                    self.stack.push(Frame::List {
+                        items,
+                        first: false,
+                    });
+                    self.stack.push(Frame::Value(item));
+                }
+                None => buf.push(b']'),
+            },
+            Frame::Object { mut fields, first } => match fields.next() {
+                Some((name, value)) => {
+                    if !first {
+                        buf.push(b',');
+                    }
+                    serde_json::to_writer(&mut *buf, &name)?;
+                    buf.push(b':');
+                    self.stack.push(Frame::Object {
+                        fields,
+                        first: false,
+                    });
+                    self.stack.push(Frame::Value(value));
+                }
+                None => buf.push(b'}'),
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Pushes the [`Frame`]s of the given [`GraphQLResponse`] in the order they
+/// are serialized in.
+fn push_response<S: ScalarValue>(frames: &mut Vec<Frame<S>>, response: GraphQLResponse<'_, S>) {
+    match response.0 {
+        Ok((data, errors)) => {
+            frames.push(Frame::Literal(r#"{"data":"#));
+            frames.push(Frame::Value(data));
+            if !errors.is_empty() {
+                frames.push(Frame::Literal(r#","errors":"#));
+                frames.push(Frame::Errors(errors));
+            }
+            frames.push(Frame::Literal("}"));
+        }
+        // Errors may borrow the request, so are serialized ahead of time.
+        Err(_) => frames.push(match serde_json::to_vec(&response) {
+            Ok(json) => Frame::Serialized(json),
+            Err(e) => Frame::Failed(e),
+        }),
+    }
+}
+
+impl<S> Iterator for JsonChunks<S>
+where
+    S: ScalarValue,
+{
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = Vec::new();
+        while buf.len() < self.chunk_size {
+            let frame = match self.stack.pop() {
+                Some(frame) => frame,
+                None => break,
+            };
+            if let Err(e) = self.serialize(frame, &mut buf) {
+                self.stack.clear();
+                return Some(Err(e.into()));
+            }
+        }
+        if buf.is_empty() {
+            None
+        } else {
+            Some(Ok(buf))
+        }
+    }
+}
+
+/// Compressor of a response body.
+enum Encoder {
+    #[cfg(feature = "compression")]
+    Gzip(flate2::write::GzEncoder<Vec<u8>>),
+    #[cfg(feature = "compression")]
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+}
+
+impl Encoder {
+    /// Creates a new [`Encoder`] for the given [`ContentEncoding`], if it
+    /// compresses anything.
+    fn new(encoding: ContentEncoding) -> Option<Self> {
+        match encoding {
+            ContentEncoding::Identity => None,
+            #[cfg(feature = "compression")]
+            ContentEncoding::Gzip => Some(Self::Gzip(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::fast(),
+            ))),
+            // Quality and window size favour speed, as responses are compressed on the fly.
+            #[cfg(feature = "compression")]
+            ContentEncoding::Brotli => Some(Self::Brotli(Box::new(brotli::CompressorWriter::new(
+                Vec::new(),
+                4096,
+                4,
+                22,
+            )))),
+        }
+    }
+
+    /// Compresses the given `chunk`, returning the compressed data available
+    /// so far.
+    fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        #[cfg(not(feature = "compression"))]
+        let _ = chunk;
+        match *self {
+            #[cfg(feature = "compression")]
+            Self::Gzip(ref mut e) => {
+                e.write_all(chunk)?;
+                Ok(mem::take(e.get_mut()))
+            }
+            #[cfg(feature = "compression")]
+            Self::Brotli(ref mut e) => {
+                e.write_all(chunk)?;
+                Ok(mem::take(e.get_mut()))
+            }
+        }
+    }
+
+    /// Finishes compression, returning the remaining compressed data.
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "compression")]
+            Self::Gzip(e) => e.finish(),
+            #[cfg(feature = "compression")]
+            Self::Brotli(e) => Ok(e.into_inner()),
+        }
+    }
+}
+
+/// Iterator over chunks compressed with a [`ContentEncoding`].
+///
+/// Created by [`JsonChunks::encoded`].
+pub struct EncodedChunks<I> {
+    chunks: I,
+    encoder: Option<Encoder>,
+    finished: bool,
+}
+
+impl<I> EncodedChunks<I>
+where
+    I: Iterator<Item = io::Result<Vec<u8>>>,
+{
+    /// Creates an iterator compressing the given `chunks` with the given
+    /// [`ContentEncoding`].
+    pub fn new(chunks: I, encoding: ContentEncoding) -> Self {
+        Self {
+            chunks,
+            encoder: Encoder::new(encoding),
+            finished: false,
+        }
+    }
+
+    /// Writes all the chunks into the given `writer`.
+    pub async fn write_to<W: AsyncWrite + Unpin>(self, writer: &mut W) -> io::Result<()> {
+        write_chunks(self, writer).await
+    }
+}
+
+impl<I> Iterator for EncodedChunks<I>
+where
+    I: Iterator<Item = io::Result<Vec<u8>>>,
+{
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let encoder = match &mut self.encoder {
+            Some(encoder) => encoder,
+            None => return self.chunks.next(),
+        };
+        // Compressors buffer their input, so some chunks produce no output.
+        loop {
+            match self.chunks.next() {
+                Some(Ok(chunk)) => match encoder.encode(&chunk) {
+                    Ok(encoded) if encoded.is_empty() => continue,
+                    res => return Some(res),
+                },
+                Some(Err(e)) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.finished = true;
+                    return self.encoder.take().map(Encoder::finish);
+                }
+            }
+        }
+    }
+}
+
+/// Writes all the given `chunks` into the `writer`, flushing it at the end.
+async fn write_chunks<W, I>(chunks: I, writer: &mut W) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    I: Iterator<Item = io::Result<Vec<u8>>>,
+{
+    for chunk in chunks {
+        writer.write_all(&chunk?).await?;
+    }
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graphql_value,
+        http::{negotiation::ContentEncoding, GraphQLBatchResponse, GraphQLResponse},
+        value::{DefaultScalarValue, Object, Value},
+        ExecutionError, FieldError, GraphQLError,
+    };
+
+    use super::JsonChunks;
+
+    fn responses() -> Vec<GraphQLBatchResponse<'static, DefaultScalarValue>> {
+        let empty = Value::list(vec![
+            Value::object(Object::with_capacity(0)),
+            Value::list(vec![]),
+        ]);
+        let data: Value = graphql_value!({
+            "hero": {
+                "name": "R2-D2 \"Artoo\"",
+                "friends": [
+                    {"name": "Luke", "height": 1.72, "id": 1000},
+                    {"name": None, "appearsIn": ["EMPIRE"]},
+                ],
+                "ok": true,
+            },
+        });
+        let error = || ExecutionError::at_origin(FieldError::new("boom", Value::null()));
+        vec![
+            GraphQLBatchResponse::Single(GraphQLResponse::from_result(Ok((data.clone(), vec![])))),
+            GraphQLBatchResponse::Single(GraphQLResponse::from_result(Ok((empty, vec![])))),
+            GraphQLBatchResponse::Single(GraphQLResponse::from_result(Ok((
+                Value::null(),
+                vec![error()],
+            )))),
+            GraphQLBatchResponse::Single(GraphQLResponse::from_result(Err(
+                GraphQLError::NoOperationProvided,
+            ))),
+            GraphQLBatchResponse::Batch(vec![]),
+            GraphQLBatchResponse::Batch(vec![
+                GraphQLResponse::from_result(Ok((data, vec![error()]))),
+                GraphQLResponse::from_result(Err(GraphQLError::NotQuery)),
+            ]),
+        ]
+    }
+
+    fn collect(chunks: impl Iterator<Item = std::io::Result<Vec<u8>>>) -> Vec<u8> {
+        chunks.flat_map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn serializes_as_serde_json() {
+        for (expected, response) in responses().iter().zip(responses()) {
+            let expected = serde_json::to_vec(expected).unwrap();
+            assert_eq!(
+                String::from_utf8(collect(JsonChunks::new(response))).unwrap(),
+                String::from_utf8(expected).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn splits_into_chunks() {
+        for (expected, response) in responses().iter().zip(responses()) {
+            let expected = serde_json::to_vec(expected).unwrap();
+            let chunks = JsonChunks::new(response)
+                .chunk_size(8)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert!(chunks.iter().all(|c| !c.is_empty()));
+            assert!(expected.len() <= 64 || chunks.len() > 1);
+            assert_eq!(chunks.concat(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_to_async_writer() {
+        for (expected, response) in responses().iter().zip(responses()) {
+            let mut body = Vec::new();
+            JsonChunks::new(response)
+                .encoded(ContentEncoding::Identity)
+                .write_to(&mut body)
+                .await
+                .unwrap();
+            assert_eq!(body, serde_json::to_vec(expected).unwrap());
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compresses() {
+        use std::io::Read as _;
+
+        for (expected, response) in responses().iter().zip(responses()) {
+            let expected = serde_json::to_vec(expected).unwrap();
+            let body = collect(
+                JsonChunks::new(response)
+                    .chunk_size(8)
+                    .encoded(ContentEncoding::Gzip),
+            );
+            let mut decoded = Vec::new();
+            flate2::read::GzDecoder::new(&body[..])
+                .read_to_end(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, expected);
+        }
+
+        for (expected, response) in responses().iter().zip(responses()) {
+            let expected = serde_json::to_vec(expected).unwrap();
+            let body = collect(
+                JsonChunks::new(response)
+                    .chunk_size(8)
+                    .encoded(ContentEncoding::Brotli),
+            );
+            let mut decoded = Vec::new();
+            brotli::Decompressor::new(&body[..], 4096)
+                .read_to_end(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, expected);
+        }
+    }
+}
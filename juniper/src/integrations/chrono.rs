@@ -36,9 +36,13 @@ where
         Value::scalar(self.to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<DateTime<FixedOffset>> {
+    fn from_input_value(v: &InputValue) -> Result<DateTime<FixedOffset>, String> {
         v.as_string_value()
-            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(s)
+                    .map_err(|e| format!("Failed to parse `DateTimeFixedOffset`: {}", e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -59,9 +63,13 @@ where
         Value::scalar(self.to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<DateTime<Utc>> {
+    fn from_input_value(v: &InputValue) -> Result<DateTime<Utc>, String> {
         v.as_string_value()
-            .and_then(|s| (s.parse::<DateTime<Utc>>().ok()))
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                s.parse::<DateTime<Utc>>()
+                    .map_err(|e| format!("Failed to parse `DateTimeUtc`: {}", e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -87,9 +95,13 @@ where
         Value::scalar(self.format("%Y-%m-%d").to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveDate> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveDate, String> {
         v.as_string_value()
-            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                    .map_err(|e| format!("Failed to parse `NaiveDate`: {}", e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -111,9 +123,13 @@ where
         Value::scalar(self.format("%H:%M:%S").to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveTime> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveTime, String> {
         v.as_string_value()
-            .and_then(|s| NaiveTime::parse_from_str(s, "%H:%M:%S").ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                NaiveTime::parse_from_str(s, "%H:%M:%S")
+                    .map_err(|e| format!("Failed to parse `NaiveTime`: {}", e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -220,6 +236,19 @@ mod test {
         assert_eq!(parsed.day(), d);
     }
 
+    #[test]
+    fn naivedate_from_invalid_input_value() {
+        let input: crate::InputValue<DefaultScalarValue> =
+            InputValue::scalar("1996-13-19".to_string());
+
+        let err = <NaiveDate as crate::FromInputValue>::try_from_input_value(&input).unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            Some("Failed to parse `NaiveDate`: input is out of range"),
+        );
+    }
+
     #[test]
     #[cfg(feature = "scalar-naivetime")]
     fn naivetime_from_input_value() {
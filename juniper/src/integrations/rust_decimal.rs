@@ -0,0 +1,210 @@
+//! GraphQL support for [rust_decimal](https://docs.rs/rust_decimal) types.
+//!
+//! `Decimal` is resolved into a string. Input strings are parsed with its
+//! `FromStr` implementation, falling back to `Decimal::from_scientific` for
+//! exponent notation like `"1.5e3"`, and values exceeding its 96-bit mantissa
+//! are rejected. Integers are converted exactly, while floats are converted from
+//! their shortest representation, so `0.1` stays `0.1`.
+
+use rust_decimal::Decimal;
+
+use crate::{
+    parser::ScalarToken,
+    value::{ParseScalarResult, ParseScalarValue},
+    Value,
+};
+
+#[crate::graphql_scalar(description = "Decimal")]
+impl<S> GraphQLScalar for Decimal
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Decimal, String> {
+        let s = v
+            .as_scalar()
+            .ok_or_else(|| format!("Expected `String`, `Int` or `Float`, found: {}", v))?;
+        if let Some(i) = s.as_i64() {
+            Ok(Decimal::from(i))
+        } else if let Some(f) = s.as_float() {
+            // `f64`'s `Display` yields the shortest string parsing back into
+            // the same `f64`, so `0.1` is not turned into `0.1000000000000000055...`.
+            parse_decimal(&f.to_string())
+        } else if let Some(s) = s.as_str() {
+            parse_decimal(s)
+        } else {
+            Err(format!("Expected `String`, `Int` or `Float`, found: {}", v))
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(value),
+            // Numeric literals are kept as they are written, so they don't
+            // lose precision by being parsed into an `f64`.
+            ScalarToken::Int(v) | ScalarToken::Float(v) => Ok(S::from(v.to_owned())),
+        }
+    }
+}
+
+fn parse_decimal(s: &str) -> Result<Decimal, String> {
+    s.parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(s))
+        .map_err(|e| format!("Failed to parse `Decimal`: {}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use rust_decimal::Decimal;
+
+    use crate::{
+        parser::ScalarToken, value::ParseScalarValue, DefaultScalarValue, FromInputValue as _,
+        InputValue, ToInputValue as _,
+    };
+
+    #[test]
+    fn decimal_from_input_value() {
+        for (input, expected) in &[
+            (InputValue::scalar("4.20"), "4.20"),
+            (
+                InputValue::scalar("-0.000000000000000000000000001"),
+                "-0.000000000000000000000000001",
+            ),
+            (InputValue::scalar("1.5e3"), "1500"),
+            (InputValue::scalar(42), "42"),
+            (InputValue::scalar(0.1), "0.1"),
+        ] {
+            let input: &InputValue<DefaultScalarValue> = input;
+            let parsed = Decimal::from_input_value(input);
+
+            assert_eq!(
+                parsed,
+                Some(expected.parse::<Decimal>().unwrap()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_from_invalid_input_value() {
+        for input in &[
+            InputValue::scalar("4.2.0"),
+            InputValue::scalar(true),
+            InputValue::list(vec![]),
+        ] {
+            let input: &InputValue<DefaultScalarValue> = input;
+
+            assert!(Decimal::try_from_input_value(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn decimal_from_literal() {
+        for token in &[
+            ScalarToken::Float("79228162514264337593543950.335"),
+            ScalarToken::Int("79228162514264337593543950335"),
+        ] {
+            let parsed = <Decimal as ParseScalarValue<DefaultScalarValue>>::from_str(*token);
+            let input = InputValue::Scalar(parsed.unwrap());
+            let expected = match token {
+                ScalarToken::Float(s) | ScalarToken::Int(s) | ScalarToken::String(s) => s,
+            };
+
+            assert_eq!(
+                Decimal::from_input_value(&input),
+                Some(expected.parse::<Decimal>().unwrap()),
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_literal_as_string() {
+        for (token, expected) in &[
+            (ScalarToken::Float("1.5"), r#""1.5""#),
+            (ScalarToken::Int("42"), r#""42""#),
+        ] {
+            let parsed = <Decimal as ParseScalarValue<DefaultScalarValue>>::from_str(*token);
+            let input = InputValue::Scalar(parsed.unwrap());
+
+            assert_eq!(input.to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        let d = "12345678901234567890.123456789".parse::<Decimal>().unwrap();
+
+        let input: InputValue<DefaultScalarValue> = d.to_input_value();
+
+        assert_eq!(input, InputValue::scalar("12345678901234567890.123456789"));
+        assert_eq!(Decimal::from_input_value(&input), Some(d));
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use rust_decimal::Decimal;
+
+    use crate::{
+        graphql_object, graphql_value,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        InputValue, Variables,
+    };
+
+    struct Root;
+
+    #[graphql_object]
+    impl Root {
+        fn sum(a: Decimal, b: Decimal) -> Decimal {
+            a + b
+        }
+
+        fn price() -> Decimal {
+            "19.99".parse::<Decimal>().unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serialization() {
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let doc = r#"query($a: Decimal!) {
+            price
+            literal: sum(a: 0.1, b: "0.2")
+            variable: sum(a: $a, b: 1)
+        }"#;
+        let vars = match serde_json::from_str(r#"{"a": "10.10"}"#).unwrap() {
+            InputValue::Object(obj) => obj
+                .into_iter()
+                .map(|(k, v)| (k.item, v.item))
+                .collect::<Variables>(),
+            _ => unreachable!(),
+        };
+
+        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({
+                "price": "19.99",
+                "literal": "0.3",
+                "variable": "11.10",
+            }),
+        );
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"price":"19.99","literal":"0.3","variable":"11.10"}"#,
+        );
+    }
+}
@@ -78,6 +78,10 @@ impl<'a> ser::Serialize for GraphQLError<'a> {
                 message: "Expected subscription, got query",
             }]
             .serialize(serializer),
+            GraphQLError::NotQuery => [SerializeHelper {
+                message: "Only query operations are allowed over HTTP GET",
+            }]
+            .serialize(serializer),
         }
     }
 }
@@ -285,6 +289,34 @@ where
     }
 }
 
+impl<'de, S> de::Deserialize<'de> for Value<S>
+where
+    S: ScalarValue,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Value<S>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        fn into_value<S: ScalarValue>(input: InputValue<S>) -> Value<S> {
+            match input {
+                InputValue::Null | InputValue::Variable(_) => Value::null(),
+                InputValue::Scalar(s) => Value::Scalar(s),
+                InputValue::Enum(e) => Value::Scalar(S::from(e)),
+                InputValue::List(l) => {
+                    Value::list(l.into_iter().map(|i| into_value(i.item)).collect())
+                }
+                InputValue::Object(o) => Value::object(
+                    o.into_iter()
+                        .map(|(k, v)| (k.item, into_value(v.item)))
+                        .collect(),
+                ),
+            }
+        }
+
+        InputValue::deserialize(deserializer).map(into_value)
+    }
+}
+
 impl<T> ser::Serialize for InputValue<T>
 where
     T: ScalarValue,
@@ -0,0 +1,714 @@
+//! GraphQL support for [serde_json](https://docs.rs/serde_json) types.
+//!
+//! [`serde_json::Value`] is exposed as the `Json` scalar, resolving into
+//! GraphQL objects and lists as they are, and accepting object and list
+//! literals (as well as variables) as input. Variables nested inside such
+//! literals are not supported though.
+//!
+//! [`Json`] wraps any [`JsonScalar`] type into a scalar of its own, converting
+//! it through [`serde_json::Value`].
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, Registry},
+    parser::{ParseError, ScalarToken, Token},
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
+    },
+    value::{Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+    BoxFuture, InputValueError,
+};
+
+impl<S> GraphQLType<S> for JsonValue
+where
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<&'static str> {
+        Some("Json")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry
+            .build_scalar_type::<Self>(&())
+            .description("Arbitrary JSON value")
+            .accepts_composite()
+            .into_meta()
+    }
+}
+
+impl<S> GraphQLValue<S> for JsonValue
+where
+    S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, _: &'i Self::TypeInfo) -> Option<&'i str> {
+        Some("Json")
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Ok(json_to_value(self))
+    }
+}
+
+impl<S> GraphQLValueAsync<S> for JsonValue
+where
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        use futures::future;
+        Box::pin(future::ready(self.resolve(info, selection_set, executor)))
+    }
+}
+
+impl<S> IsInputType<S> for JsonValue where S: ScalarValue {}
+
+impl<S> IsOutputType<S> for JsonValue where S: ScalarValue {}
+
+impl<S> FromInputValue<S> for JsonValue
+where
+    S: ScalarValue,
+{
+    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+        Self::try_from_input_value(v).ok()
+    }
+
+    fn try_from_input_value(v: &InputValue<S>) -> Result<Self, InputValueError> {
+        input_value_to_json(v).map_err(InputValueError::new)
+    }
+}
+
+impl<S> ToInputValue<S> for JsonValue
+where
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        json_to_input_value(self)
+    }
+}
+
+impl<S> ParseScalarValue<S> for JsonValue
+where
+    S: ScalarValue,
+{
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+        parse_json_token(value)
+    }
+}
+
+/// Type exposed as a GraphQL scalar by the [`Json`] wrapper.
+///
+/// Every such type needs a scalar name of its own, as input values of a scalar
+/// are validated by converting them into the type registered under its name.
+///
+/// ```
+/// # use juniper::integrations::serde_json::JsonScalar;
+/// #[derive(serde::Deserialize, serde::Serialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl JsonScalar for Point {
+///     const NAME: &'static str = "Point";
+/// }
+/// ```
+pub trait JsonScalar: Serialize + DeserializeOwned {
+    /// Name of the GraphQL scalar representing this type.
+    const NAME: &'static str;
+}
+
+/// Wrapper exposing a [`JsonScalar`] type as a GraphQL scalar named after
+/// [`JsonScalar::NAME`].
+///
+/// Values are converted through [`serde_json::Value`], so `T` is resolved as
+/// its JSON representation, and input is accepted whenever it deserializes
+/// into `T`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    /// Unwraps the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(v: T) -> Self {
+        Self(v)
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Json<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<S, T> GraphQLType<S> for Json<T>
+where
+    S: ScalarValue,
+    T: JsonScalar + 'static,
+{
+    fn name(_: &()) -> Option<&'static str> {
+        Some(T::NAME)
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry
+            .build_scalar_type::<Self>(&())
+            .accepts_composite()
+            .into_meta()
+    }
+}
+
+impl<S, T> GraphQLValue<S> for Json<T>
+where
+    S: ScalarValue,
+    T: JsonScalar,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, _: &'i Self::TypeInfo) -> Option<&'i str> {
+        Some(T::NAME)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Ok(json_to_value(&serde_json::to_value(&self.0)?))
+    }
+}
+
+impl<S, T> GraphQLValueAsync<S> for Json<T>
+where
+    S: ScalarValue + Send + Sync,
+    T: JsonScalar + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        use futures::future;
+        Box::pin(future::ready(self.resolve(info, selection_set, executor)))
+    }
+}
+
+impl<S, T> IsInputType<S> for Json<T>
+where
+    S: ScalarValue,
+    T: JsonScalar + 'static,
+{
+}
+
+impl<S, T> IsOutputType<S> for Json<T>
+where
+    S: ScalarValue,
+    T: JsonScalar + 'static,
+{
+}
+
+impl<S, T> FromInputValue<S> for Json<T>
+where
+    S: ScalarValue,
+    T: DeserializeOwned,
+{
+    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+        Self::try_from_input_value(v).ok()
+    }
+
+    fn try_from_input_value(v: &InputValue<S>) -> Result<Self, InputValueError> {
+        let json = input_value_to_json(v).map_err(InputValueError::new)?;
+        serde_json::from_value(json)
+            .map(Self)
+            .map_err(|e| InputValueError::new(format!("Failed to deserialize `Json`: {}", e)))
+    }
+}
+
+impl<S, T> ToInputValue<S> for Json<T>
+where
+    S: ScalarValue,
+    T: Serialize,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        // `Serialize` implementations may fail (e.g. on maps with non-string
+        // keys), in which case there is nothing better to represent them.
+        serde_json::to_value(&self.0)
+            .map(|json| json_to_input_value(&json))
+            .unwrap_or_else(|_| InputValue::null())
+    }
+}
+
+impl<S, T> ParseScalarValue<S> for Json<T>
+where
+    S: ScalarValue,
+{
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+        parse_json_token(value)
+    }
+}
+
+fn parse_json_token<S: ScalarValue>(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+    match value {
+        ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(value),
+        // Integers not fitting into an `Int` are still valid JSON numbers.
+        ScalarToken::Int(v) => v
+            .parse::<i32>()
+            .map(S::from)
+            .or_else(|_| v.parse::<f64>().map(S::from))
+            .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value))),
+        ScalarToken::Float(v) => v
+            .parse::<f64>()
+            .map(S::from)
+            .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value))),
+    }
+}
+
+fn number_to_scalar<S: ScalarValue>(n: &Number) -> S {
+    if let Some(i) = n.as_i64() {
+        S::from_i64(i)
+    } else if n.is_u64() {
+        S::from(n.to_string())
+    } else {
+        n.as_f64()
+            .map(S::from)
+            .unwrap_or_else(|| S::from(n.to_string()))
+    }
+}
+
+fn json_to_value<S: ScalarValue>(json: &JsonValue) -> Value<S> {
+    match json {
+        JsonValue::Null => Value::null(),
+        JsonValue::Bool(b) => Value::scalar(*b),
+        JsonValue::Number(n) => Value::Scalar(number_to_scalar(n)),
+        JsonValue::String(s) => Value::scalar(s.clone()),
+        JsonValue::Array(a) => Value::list(a.iter().map(json_to_value).collect()),
+        JsonValue::Object(o) => {
+            let mut obj = Object::with_capacity(o.len());
+            for (k, v) in o {
+                obj.add_field(k.as_str(), json_to_value(v));
+            }
+            Value::object(obj)
+        }
+    }
+}
+
+fn json_to_input_value<S: ScalarValue>(json: &JsonValue) -> InputValue<S> {
+    match json {
+        JsonValue::Null => InputValue::null(),
+        JsonValue::Bool(b) => InputValue::scalar(*b),
+        JsonValue::Number(n) => InputValue::Scalar(number_to_scalar(n)),
+        JsonValue::String(s) => InputValue::scalar(s.clone()),
+        JsonValue::Array(a) => InputValue::list(a.iter().map(json_to_input_value).collect()),
+        JsonValue::Object(o) => InputValue::object(
+            o.iter()
+                .map(|(k, v)| (k.as_str(), json_to_input_value(v)))
+                .collect(),
+        ),
+    }
+}
+
+fn input_value_to_json<S: ScalarValue>(v: &InputValue<S>) -> Result<JsonValue, String> {
+    match v {
+        InputValue::Null => Ok(JsonValue::Null),
+        InputValue::Scalar(s) => {
+            if let Some(i) = s.as_i64() {
+                Ok(JsonValue::from(i))
+            } else if let Some(f) = s.as_float() {
+                Number::from_f64(f)
+                    .map(JsonValue::Number)
+                    .ok_or_else(|| format!("`{}` is not a valid JSON number", f))
+            } else if let Some(s) = s.as_str() {
+                Ok(JsonValue::from(s))
+            } else if let Some(b) = s.as_boolean() {
+                Ok(JsonValue::from(b))
+            } else {
+                Err(format!("Unsupported scalar: {}", v))
+            }
+        }
+        // Unquoted names inside `Json` literals, like `{status: ACTIVE}`.
+        InputValue::Enum(e) => Ok(JsonValue::from(e.as_str())),
+        InputValue::Variable(name) => Err(format!(
+            "Variables are not supported inside `Json` literals, found: ${}",
+            name,
+        )),
+        InputValue::List(l) => l
+            .iter()
+            .map(|i| input_value_to_json(&i.item))
+            .collect::<Result<_, _>>()
+            .map(JsonValue::Array),
+        InputValue::Object(o) => o
+            .iter()
+            .map(|(k, v)| Ok((k.item.clone(), input_value_to_json(&v.item)?)))
+            .collect::<Result<Map<_, _>, String>>()
+            .map(JsonValue::Object),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{
+        graphql_value, DefaultScalarValue, FromInputValue as _, InputValue, ScalarValue as _,
+        ToInputValue as _,
+    };
+
+    use super::Json;
+
+    fn input(json: &str) -> InputValue<DefaultScalarValue> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn json_from_input_value() {
+        let input = input(
+            r#"{
+                "int": 1,
+                "float": 1.5,
+                "string": "str",
+                "bool": false,
+                "null": null,
+                "list": [1, [2, {"nested": true}]]
+            }"#,
+        );
+
+        assert_eq!(
+            serde_json::Value::from_input_value(&input),
+            Some(json!({
+                "int": 1,
+                "float": 1.5,
+                "string": "str",
+                "bool": false,
+                "null": null,
+                "list": [1, [2, {"nested": true}]],
+            })),
+        );
+    }
+
+    #[test]
+    fn json_from_invalid_input_value() {
+        for input in &[
+            InputValue::variable("var"),
+            InputValue::list(vec![InputValue::scalar(f64::NAN)]),
+        ] {
+            let input: &InputValue<DefaultScalarValue> = input;
+
+            assert!(
+                serde_json::Value::try_from_input_value(input).is_err(),
+                "{}",
+                input,
+            );
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let json = json!({"a": [1, 2.5, "three", null], "b": {"c": true}});
+
+        let input: InputValue<DefaultScalarValue> = json.to_input_value();
+
+        assert_eq!(serde_json::Value::from_input_value(&input), Some(json));
+    }
+
+    #[test]
+    fn json_resolves_into_value() {
+        let json = json!({"a": [1, 2.5, "three", null], "b": {"c": true}});
+
+        assert_eq!(
+            super::json_to_value::<DefaultScalarValue>(&json),
+            graphql_value!({"a": [1, 2.5, "three", None], "b": {"c": true}}),
+        );
+    }
+
+    #[test]
+    fn json_keeps_wide_integers_intact() {
+        for i in [(1_i64 << 53) + 1, i64::MAX, i64::MIN] {
+            let scalar = super::number_to_scalar::<DefaultScalarValue>(&i.into());
+
+            assert_eq!(scalar, DefaultScalarValue::from_i64(i), "{}", i);
+            assert_ne!(scalar, DefaultScalarValue::from(i as f64), "{}", i);
+        }
+
+        assert_eq!(
+            super::number_to_scalar::<DefaultScalarValue>(&u64::MAX.into()),
+            DefaultScalarValue::from(u64::MAX.to_string()),
+        );
+    }
+
+    #[test]
+    fn typed_json_from_input_value() {
+        assert_eq!(
+            Json::<Vec<u8>>::from_input_value(&input("[1, 2, 3]")),
+            Some(Json(vec![1, 2, 3])),
+        );
+
+        let err = Json::<Vec<u8>>::try_from_input_value(&input(r#""oops""#)).unwrap_err();
+        assert!(
+            err.message()
+                .unwrap()
+                .starts_with("Failed to deserialize `Json`: "),
+            "{:?}",
+            err,
+        );
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        graphql_object, graphql_value,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        InputValue, Variables,
+    };
+
+    use super::{Json, JsonScalar};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl JsonScalar for Point {
+        const NAME: &'static str = "Point";
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Label {
+        text: String,
+    }
+
+    impl JsonScalar for Label {
+        const NAME: &'static str = "Label";
+    }
+
+    struct Root;
+
+    #[graphql_object]
+    impl Root {
+        fn echo(value: serde_json::Value) -> serde_json::Value {
+            value
+        }
+
+        fn config() -> serde_json::Value {
+            serde_json::json!({"name": "juniper", "tags": ["graphql", {"rust": true}]})
+        }
+    }
+
+    struct TypedRoot;
+
+    #[graphql_object]
+    impl TypedRoot {
+        fn shift(point: Json<Point>, by: i32) -> Json<Point> {
+            Json(Point {
+                x: point.x + by,
+                y: point.y + by,
+            })
+        }
+
+        fn label(label: Json<Label>) -> String {
+            label.into_inner().text
+        }
+    }
+
+    fn vars(json: &str) -> Variables {
+        match serde_json::from_str(json).unwrap() {
+            InputValue::Object(obj) => obj
+                .into_iter()
+                .map(|(k, v)| (k.item, v.item))
+                .collect::<Variables>(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn schema() -> RootNode<'static, Root, EmptyMutation<()>, EmptySubscription<()>> {
+        RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    fn typed_schema() -> RootNode<'static, TypedRoot, EmptyMutation<()>, EmptySubscription<()>> {
+        RootNode::new(
+            TypedRoot,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_nested_literals() {
+        let doc = r#"{
+            config
+            object: echo(value: {a: [1, 2.5, "three", null, {b: true}], c: {d: ENUM}})
+            scalar: echo(value: 9007199254740993)
+        }"#;
+
+        let (result, errs) = crate::execute(doc, None, &schema(), &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({
+                "config": {"name": "juniper", "tags": ["graphql", {"rust": true}]},
+                "object": {"a": [1, 2.5, "three", None, {"b": true}], "c": {"d": "ENUM"}},
+                "scalar": 9007199254740993.0,
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_literals() {
+        let doc = r#"{ echo(value: [[1], [], [{}], {}]) }"#;
+
+        let (result, errs) = crate::execute(doc, None, &schema(), &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"echo":[[1],[],[{}],{}]}"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_variables() {
+        let doc = r#"query($value: Json!) { echo(value: $value) }"#;
+        let vars = vars(r#"{"value": {"a": [1, {"b": [null, "c"]}], "d": 0.5}}"#);
+
+        let (result, errs) = crate::execute(doc, None, &schema(), &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"echo":{"a":[1,{"b":[null,"c"]}],"d":0.5}}"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_json() {
+        let doc = r#"query($point: Point!) {
+            literal: shift(point: {x: 1, y: 2}, by: 1)
+            variable: shift(point: $point, by: -1)
+        }"#;
+        let vars = vars(r#"{"point": {"x": 10, "y": 20}}"#);
+
+        let (result, errs) = crate::execute(doc, None, &typed_schema(), &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({
+                "literal": {"x": 2, "y": 3},
+                "variable": {"x": 9, "y": 19},
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_json_types_are_distinct_scalars() {
+        let doc = r#"query($label: Label!, $point: Point!) {
+            label(label: $label)
+            shift(point: $point, by: 0)
+        }"#;
+        let vars = vars(r#"{"label": {"text": "origin"}, "point": {"x": 0, "y": 0}}"#);
+
+        let schema = typed_schema();
+        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({"label": "origin", "shift": {"x": 0, "y": 0}}),
+        );
+
+        for (doc, vars) in &[
+            (r#"{ label(label: {x: 1, y: 2}) }"#, Variables::new()),
+            (
+                r#"query($label: Label!) { label(label: $label) }"#,
+                self::vars(r#"{"label": {"x": 1, "y": 2}}"#),
+            ),
+        ] {
+            let res = crate::execute(doc, None, &schema, vars, &()).await;
+
+            assert!(
+                matches!(res, Err(crate::GraphQLError::ValidationError(_))),
+                "{:?}",
+                res,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_typed_json_rejects_invalid_input() {
+        let doc = r#"{ shift(point: {x: 1}, by: 1) }"#;
+
+        let schema = typed_schema();
+        let res = crate::execute(doc, None, &schema, &Variables::new(), &()).await;
+
+        assert!(res.is_err(), "{:?}", res);
+    }
+}
@@ -0,0 +1,390 @@
+/*!
+
+# Supported types
+
+The scalars mirror the names and representations of the `chrono` ones, so a
+schema may switch between both crates without changing.
+
+| Rust Type           | GraphQL Scalar        | JSON Serialization     | Notes                                     |
+|---------------------|-----------------------|------------------------|-------------------------------------------|
+| `OffsetDateTime`    | `DateTimeFixedOffset` | RFC3339 string         |                                           |
+| `Date`              | `NaiveDate`           | YYYY-MM-DD             |                                           |
+| `PrimitiveDateTime` | `NaiveDateTime`       | float (unix timestamp) | Values will be truncated to second        |
+|                     |                       |                        | resolution.                               |
+| `Time`              | `NaiveTime`           | H:M:S                  | Optional. Use the `scalar-naivetime`      |
+|                     |                       |                        | feature.                                  |
+| `UtcOffset`         | `UtcOffset`           | ±hh:mm                 | Also parsed from `Z`.                     |
+
+*/
+#![allow(clippy::needless_lifetimes)]
+
+#[cfg(feature = "scalar-naivetime")]
+use time::Time;
+use time::{
+    format_description::{well_known::Rfc3339, FormatItem},
+    macros::format_description,
+    Date, OffsetDateTime, PrimitiveDateTime, UtcOffset,
+};
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::{ParseScalarResult, ParseScalarValue},
+    Value,
+};
+
+const DATE_TIME_FORMAT: &[FormatItem<'_>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+const DATE_FORMAT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day]");
+#[cfg(feature = "scalar-naivetime")]
+const TIME_FORMAT: &[FormatItem<'_>] = format_description!("[hour]:[minute]:[second]");
+const UTC_OFFSET_FORMAT: &[FormatItem<'_>] =
+    format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+
+#[crate::graphql_scalar(name = "DateTimeFixedOffset", description = "DateTime")]
+impl<S> GraphQLScalar for OffsetDateTime
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(format_rfc3339(self))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<OffsetDateTime, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                OffsetDateTime::parse(s, &Rfc3339)
+                    .map_err(|e| format!("Failed to parse `DateTimeFixedOffset`: {}", e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[crate::graphql_scalar(name = "NaiveDate", description = "NaiveDate")]
+impl<S> GraphQLScalar for Date
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(DATE_FORMAT)
+                .expect("`Date` is always formattable"),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Date, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                Date::parse(s, DATE_FORMAT)
+                    .map_err(|e| format!("Failed to parse `NaiveDate`: {}", e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(feature = "scalar-naivetime")]
+#[crate::graphql_scalar(name = "NaiveTime", description = "NaiveTime")]
+impl<S> GraphQLScalar for Time
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(TIME_FORMAT)
+                .expect("`Time` is always formattable"),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Time, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                Time::parse(s, TIME_FORMAT)
+                    .map_err(|e| format!("Failed to parse `NaiveTime`: {}", e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+// JSON numbers (i.e. IEEE doubles) are not precise enough for nanosecond
+// datetimes, so, just like for `chrono::NaiveDateTime`, only whole seconds are
+// represented.
+#[crate::graphql_scalar(name = "NaiveDateTime", description = "NaiveDateTime")]
+impl<S> GraphQLScalar for PrimitiveDateTime
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.assume_utc().unix_timestamp() as f64)
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<PrimitiveDateTime, String> {
+        v.as_float_value()
+            .ok_or_else(|| format!("Expected `Float`, found: {}", v))
+            .and_then(|f| {
+                OffsetDateTime::from_unix_timestamp(f as i64)
+                    .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()))
+                    .map_err(|e| format!("Failed to parse `NaiveDateTime`: {}", e))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <f64 as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+#[crate::graphql_scalar(name = "UtcOffset", description = "UtcOffset")]
+impl<S> GraphQLScalar for UtcOffset
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(UTC_OFFSET_FORMAT)
+                .expect("`UtcOffset` is always formattable"),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<UtcOffset, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| match s {
+                "Z" | "z" => Ok(UtcOffset::UTC),
+                _ => UtcOffset::parse(s, UTC_OFFSET_FORMAT)
+                    .map_err(|e| format!("Failed to parse `UtcOffset`: {}", e)),
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+/// Formats the given [`OffsetDateTime`] the same way as
+/// `chrono::DateTime::to_rfc3339()` does: fractional seconds are omitted when
+/// zero, and UTC is written as `+00:00` rather than `Z`.
+fn format_rfc3339(dt: &OffsetDateTime) -> String {
+    let mut out = dt
+        .format(DATE_TIME_FORMAT)
+        .expect("`OffsetDateTime` is always formattable");
+    match dt.nanosecond() {
+        0 => {}
+        ns if ns % 1_000_000 == 0 => out.push_str(&format!(".{:03}", ns / 1_000_000)),
+        ns if ns % 1_000 == 0 => out.push_str(&format!(".{:06}", ns / 1_000)),
+        ns => out.push_str(&format!(".{:09}", ns)),
+    }
+    out.push_str(
+        &dt.offset()
+            .format(UTC_OFFSET_FORMAT)
+            .expect("`UtcOffset` is always formattable"),
+    );
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use time::{
+        macros::{date, datetime, offset},
+        Date, OffsetDateTime, PrimitiveDateTime, UtcOffset,
+    };
+
+    use crate::{value::DefaultScalarValue, FromInputValue as _, InputValue};
+
+    fn offset_date_time_test(raw: &'static str, expected: OffsetDateTime) {
+        let input = <InputValue<DefaultScalarValue>>::scalar(raw.to_string());
+
+        let parsed = OffsetDateTime::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn offset_date_time_from_input_value() {
+        offset_date_time_test(
+            "2014-11-28T21:00:09+09:00",
+            datetime!(2014-11-28 21:00:09 +09:00),
+        );
+    }
+
+    #[test]
+    fn offset_date_time_from_input_value_with_z_timezone() {
+        offset_date_time_test("2014-11-28T21:00:09Z", datetime!(2014-11-28 21:00:09 UTC));
+    }
+
+    #[test]
+    fn offset_date_time_from_input_value_with_fractional_seconds() {
+        offset_date_time_test(
+            "2014-11-28T21:00:09.05+09:00",
+            datetime!(2014-11-28 21:00:09.05 +09:00),
+        );
+    }
+
+    #[test]
+    fn offset_date_time_is_formatted_like_chrono() {
+        for (dt, expected) in &[
+            (
+                datetime!(1996-12-19 16:39:57 -08:00),
+                "1996-12-19T16:39:57-08:00",
+            ),
+            (
+                datetime!(1970-01-01 00:01:01 UTC),
+                "1970-01-01T00:01:01+00:00",
+            ),
+            (
+                datetime!(2014-11-28 21:00:09.05 +09:00),
+                "2014-11-28T21:00:09.050+09:00",
+            ),
+            (
+                datetime!(2014-11-28 21:00:09.000_001 +09:00),
+                "2014-11-28T21:00:09.000001+09:00",
+            ),
+        ] {
+            assert_eq!(super::format_rfc3339(dt), *expected);
+        }
+    }
+
+    #[test]
+    fn date_from_input_value() {
+        let input = <InputValue<DefaultScalarValue>>::scalar("1996-12-19".to_string());
+
+        let parsed = Date::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, date!(1996 - 12 - 19));
+    }
+
+    #[test]
+    fn date_from_invalid_input_value() {
+        let input = <InputValue<DefaultScalarValue>>::scalar("1996-13-19".to_string());
+
+        let err = Date::try_from_input_value(&input).unwrap_err();
+
+        assert!(err
+            .message()
+            .unwrap()
+            .starts_with("Failed to parse `NaiveDate`: "));
+    }
+
+    #[test]
+    #[cfg(feature = "scalar-naivetime")]
+    fn time_from_input_value() {
+        use time::{macros::time, Time};
+
+        let input = <InputValue<DefaultScalarValue>>::scalar("21:12:19".to_string());
+
+        let parsed = Time::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, time!(21:12:19));
+    }
+
+    #[test]
+    fn primitive_date_time_from_input_value() {
+        let input = <InputValue<DefaultScalarValue>>::scalar(1_000_000_000.0);
+
+        let parsed = PrimitiveDateTime::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, datetime!(2001-09-09 01:46:40));
+    }
+
+    #[test]
+    fn utc_offset_from_input_value() {
+        for (raw, expected) in &[
+            ("+09:00", offset!(+9)),
+            ("-03:30", offset!(-3:30)),
+            ("Z", UtcOffset::UTC),
+        ] {
+            let input = <InputValue<DefaultScalarValue>>::scalar(raw.to_string());
+
+            assert_eq!(UtcOffset::from_input_value(&input), Some(*expected));
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use time::{
+        macros::{date, datetime, offset},
+        Date, OffsetDateTime, PrimitiveDateTime, UtcOffset,
+    };
+
+    use crate::{
+        executor::Variables,
+        graphql_object, graphql_value,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+    };
+
+    #[tokio::test]
+    async fn test_serialization() {
+        struct Root;
+
+        #[graphql_object]
+        impl Root {
+            fn example_naive_date() -> Date {
+                date!(2015 - 03 - 14)
+            }
+            fn example_naive_date_time() -> PrimitiveDateTime {
+                datetime!(2016-07-08 09:10:11)
+            }
+            fn example_date_time_fixed_offset() -> OffsetDateTime {
+                datetime!(1996-12-19 16:39:57 -08:00)
+            }
+            fn example_utc_offset() -> UtcOffset {
+                offset!(+9)
+            }
+        }
+
+        let doc = r#"{
+            exampleNaiveDate,
+            exampleNaiveDateTime,
+            exampleDateTimeFixedOffset,
+            exampleUtcOffset,
+        }"#;
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({
+                "exampleNaiveDate": "2015-03-14",
+                "exampleNaiveDateTime": 1_467_969_011.0,
+                "exampleDateTimeFixedOffset": "1996-12-19T16:39:57-08:00",
+                "exampleUtcOffset": "+09:00",
+            }),
+        );
+    }
+}
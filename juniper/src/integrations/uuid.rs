@@ -19,8 +19,10 @@ where
         Value::scalar(self.to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<Uuid> {
-        v.as_string_value().and_then(|s| Uuid::parse_str(s).ok())
+    fn from_input_value(v: &InputValue) -> Result<Uuid, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| Uuid::parse_str(s).map_err(|e| format!("Failed to parse `Uuid`: {}", e)))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -47,4 +49,20 @@ mod test {
 
         assert_eq!(parsed, id);
     }
+
+    #[test]
+    fn uuid_from_invalid_input_value() {
+        let input = <InputValue<DefaultScalarValue>>::scalar("not-a-uuid".to_string());
+
+        let err = <Uuid as crate::FromInputValue>::try_from_input_value(&input).unwrap_err();
+
+        assert!(err
+            .message()
+            .unwrap()
+            .starts_with("Failed to parse `Uuid`: "));
+        assert_eq!(
+            <Uuid as crate::FromInputValue>::from_input_value(&input),
+            None,
+        );
+    }
 }
@@ -0,0 +1,598 @@
+//! Building a [`SchemaType`] back from the result of an introspection query, so operations can
+//! be validated against a schema without the Rust types it was defined with.
+
+use std::{
+    borrow::{Borrow, Cow},
+    error::Error,
+    fmt,
+};
+
+use fnv::FnvHashMap;
+use once_cell::sync::OnceCell;
+
+use crate::{
+    ast::{FromInputValue, InputValue, InputValueError, Type},
+    executor::{CacheHint, Registry},
+    parser::{parse_value_literal, Lexer, Parser, ScalarToken, Token},
+    schema::{
+        meta::{
+            Argument, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
+            InterfaceMeta, MetaType, ObjectMeta, ScalarMeta, UnionMeta,
+        },
+        model::{DirectiveLocation, DirectiveType, SchemaType},
+    },
+    types::{name::Name, scalars::ID},
+    value::{ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+};
+
+/// Error of building a [`SchemaType`] from an introspection result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntrospectionError {
+    message: String,
+}
+
+impl IntrospectionError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// Returns the message describing this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for IntrospectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for IntrospectionError {}
+
+/// Argument or input field with a default value, which is parsed once all the types are known.
+struct PendingDefault<'a> {
+    owner: Owner,
+    name: String,
+    arg_type: Type<'a>,
+    source: String,
+}
+
+/// Definition owning an argument or an input field.
+enum Owner {
+    Field { type_name: String, field: String },
+    InputObject(String),
+    Directive(String),
+}
+
+impl<'a, S> SchemaType<'a, S>
+where
+    S: ScalarValue + 'a,
+{
+    /// Builds a schema from the result of an introspection query (see [`introspect()`]).
+    ///
+    /// The `result` may be either the `data` of an introspection response, or the whole
+    /// response. To load it from JSON, deserialize it into a [`Value`] first.
+    ///
+    /// The built schema only describes the types, so it can be used for validating operations
+    /// (see [`visit_all_rules()`]), but not for executing them. As the parsing rules of custom
+    /// scalars are not exposed by introspection, they accept any scalar value.
+    ///
+    /// ```
+    /// # use juniper::{
+    /// #     graphql_object, introspect, parser::parse_document_source,
+    /// #     validation::{visit_all_rules, ValidatorContext},
+    /// #     EmptyMutation, EmptySubscription, IntrospectionFormat, RootNode, SchemaType,
+    /// # };
+    /// struct Query;
+    ///
+    /// #[graphql_object]
+    /// impl Query {
+    ///     fn hello(name: String) -> String {
+    ///         format!("Hello, {}!", name)
+    ///     }
+    /// }
+    ///
+    /// let root = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+    /// let (result, _) = introspect(&root, &(), IntrospectionFormat::default()).unwrap();
+    ///
+    /// let schema = SchemaType::from_introspection(&result).expect("valid introspection");
+    ///
+    /// let document = parse_document_source("{ hello }", &schema).unwrap();
+    /// let mut ctx = ValidatorContext::new(&schema, &document);
+    /// visit_all_rules(&mut ctx, &document);
+    /// assert_eq!(
+    ///     ctx.into_errors()[0].message(),
+    ///     r#"Field "hello" argument "name" of type "String!" is required but not provided"#,
+    /// );
+    /// ```
+    ///
+    /// [`introspect()`]: crate::introspect
+    /// [`visit_all_rules()`]: crate::validation::visit_all_rules
+    pub fn from_introspection(result: &Value<S>) -> Result<Self, IntrospectionError> {
+        let data = field(result, "data").unwrap_or(result);
+        let schema = field(data, "__schema")
+            .ok_or_else(|| IntrospectionError::new("Missing `__schema` in introspection result"))?;
+
+        let mut registry = Registry::new(FnvHashMap::default());
+        registry.get_type::<SchemaType<S>>(&());
+
+        let mut defaults = vec![];
+        for ty in list(schema, "types") {
+            let name = required_str(ty, "name", "type")?;
+            if name.starts_with("__") {
+                continue;
+            }
+            match name {
+                "Boolean" => {
+                    registry.get_type::<bool>(&());
+                }
+                "Float" => {
+                    registry.get_type::<f64>(&());
+                }
+                "ID" => {
+                    registry.get_type::<ID>(&());
+                }
+                "Int" => {
+                    registry.get_type::<i32>(&());
+                }
+                "String" => {
+                    registry.get_type::<String>(&());
+                }
+                _ => {
+                    let key = name
+                        .parse::<Name>()
+                        .map_err(|e| IntrospectionError::new(e.to_string()))?;
+                    let meta = build_type(ty, name, &mut defaults)?;
+                    registry.types.insert(key, meta);
+                }
+            }
+        }
+
+        let query_type_name = root_type_name(schema, "queryType").ok_or_else(|| {
+            IntrospectionError::new("Missing `queryType` in introspection result")
+        })?;
+        match registry.types.get(query_type_name.as_str()) {
+            Some(MetaType::Object(_)) => {}
+            _ => {
+                return Err(IntrospectionError::new(format!(
+                    "Query type `{}` is not an object",
+                    query_type_name,
+                )))
+            }
+        }
+        let mut meta_fields = SchemaType::meta_fields(&mut registry);
+        if let Some(MetaType::Object(ObjectMeta { fields, .. })) =
+            registry.types.get_mut(query_type_name.as_str())
+        {
+            fields.append(&mut meta_fields);
+        }
+
+        let mut directives = FnvHashMap::default();
+        for directive in list(schema, "directives") {
+            let directive = build_directive(directive, &mut defaults)?;
+            directives.insert(directive.name.clone(), directive);
+        }
+
+        let mut schema = SchemaType {
+            types: registry.types,
+            query_type_name,
+            mutation_type_name: root_type_name(schema, "mutationType"),
+            subscription_type_name: root_type_name(schema, "subscriptionType"),
+            directives,
+            has_input_validators: OnceCell::new(),
+        };
+        schema.check_type_references()?;
+
+        let defaults = defaults
+            .into_iter()
+            .map(|d| {
+                let value = parse_default_value(&d.source, &d.arg_type, &schema).map_err(|e| {
+                    IntrospectionError::new(
+                        format!("Invalid default value of `{}`: {}", d.name, e,),
+                    )
+                })?;
+                Ok((d.owner, d.name, value))
+            })
+            .collect::<Result<Vec<_>, IntrospectionError>>()?;
+        for (owner, name, value) in defaults {
+            if let Some(arg) = schema
+                .arguments_mut(&owner)
+                .and_then(|args| args.iter_mut().find(|a| a.name == name))
+            {
+                arg.default_value = Some(value);
+            }
+        }
+
+        Ok(schema)
+    }
+
+    /// Checks that all the types referenced by fields, arguments, interfaces and unions are
+    /// defined in this schema.
+    fn check_type_references(&self) -> Result<(), IntrospectionError> {
+        let check = |name: &str, referrer: &str| {
+            if self.types.contains_key(name) {
+                Ok(())
+            } else {
+                Err(IntrospectionError::new(format!(
+                    "Unknown type `{}` referenced by `{}`",
+                    name, referrer,
+                )))
+            }
+        };
+        let check_args = |args: &[Argument<S>], referrer: &str| {
+            args.iter().try_for_each(|a| {
+                check(
+                    a.arg_type.innermost_name(),
+                    &format!("{}.{}", referrer, a.name),
+                )
+            })
+        };
+
+        for (type_name, meta) in &self.types {
+            let type_name: &str = type_name.borrow();
+            let (fields, names) = match meta {
+                MetaType::Object(ObjectMeta {
+                    fields,
+                    interface_names,
+                    ..
+                }) => (&fields[..], &interface_names[..]),
+                MetaType::Interface(InterfaceMeta { fields, .. }) => (&fields[..], &[][..]),
+                MetaType::Union(UnionMeta { of_type_names, .. }) => (&[][..], &of_type_names[..]),
+                MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                    check_args(input_fields, type_name)?;
+                    continue;
+                }
+                _ => continue,
+            };
+            for field in fields {
+                let path = format!("{}.{}", type_name, field.name);
+                check(field.field_type.innermost_name(), &path)?;
+                check_args(field.arguments.as_deref().unwrap_or_default(), &path)?;
+            }
+            for name in names {
+                check(name, type_name)?;
+            }
+        }
+        for directive in self.directives.values() {
+            check_args(&directive.arguments, &format!("@{}", directive.name))?;
+        }
+        Ok(())
+    }
+
+    fn arguments_mut(&mut self, owner: &Owner) -> Option<&mut Vec<Argument<'a, S>>> {
+        match owner {
+            Owner::Field { type_name, field } => match self.types.get_mut(type_name.as_str())? {
+                MetaType::Object(ObjectMeta { fields, .. })
+                | MetaType::Interface(InterfaceMeta { fields, .. }) => fields
+                    .iter_mut()
+                    .find(|f| f.name == *field)?
+                    .arguments
+                    .as_mut(),
+                _ => None,
+            },
+            Owner::InputObject(type_name) => match self.types.get_mut(type_name.as_str())? {
+                MetaType::InputObject(InputObjectMeta { input_fields, .. }) => Some(input_fields),
+                _ => None,
+            },
+            Owner::Directive(name) => Some(&mut self.directives.get_mut(name)?.arguments),
+        }
+    }
+}
+
+fn field<'v, S: ScalarValue>(value: &'v Value<S>, name: &str) -> Option<&'v Value<S>> {
+    value
+        .as_object_value()?
+        .get_field_value(name)
+        .filter(|v| !v.is_null())
+}
+
+fn string<'v, S: ScalarValue>(value: &'v Value<S>, name: &str) -> Option<&'v str> {
+    field(value, name)?.as_scalar()?.as_str()
+}
+
+fn required_str<'v, S: ScalarValue>(
+    value: &'v Value<S>,
+    name: &str,
+    context: &str,
+) -> Result<&'v str, IntrospectionError> {
+    string(value, name)
+        .ok_or_else(|| IntrospectionError::new(format!("Missing `{}` of {}", name, context)))
+}
+
+fn list<'v, S: ScalarValue>(value: &'v Value<S>, name: &str) -> &'v [Value<S>] {
+    field(value, name)
+        .and_then(Value::as_list_value)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn root_type_name<S: ScalarValue>(schema: &Value<S>, name: &str) -> Option<String> {
+    field(schema, name)
+        .and_then(|t| string(t, "name"))
+        .map(ToOwned::to_owned)
+}
+
+fn deprecation_status<S: ScalarValue>(value: &Value<S>) -> DeprecationStatus {
+    let is_deprecated = field(value, "isDeprecated")
+        .and_then(Value::as_scalar)
+        .and_then(ScalarValue::as_boolean)
+        .unwrap_or(false);
+    if is_deprecated {
+        DeprecationStatus::Deprecated(string(value, "deprecationReason").map(ToOwned::to_owned))
+    } else {
+        DeprecationStatus::Current
+    }
+}
+
+fn build_type<'a, S: ScalarValue + 'a>(
+    ty: &Value<S>,
+    name: &str,
+    defaults: &mut Vec<PendingDefault<'a>>,
+) -> Result<MetaType<'a, S>, IntrospectionError> {
+    let description = string(ty, "description").map(ToOwned::to_owned);
+    let name_cow = Cow::Owned(name.to_owned());
+    let kind = required_str(ty, "kind", &format!("type `{}`", name))?;
+
+    Ok(match kind {
+        "SCALAR" => MetaType::Scalar(ScalarMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            try_parse_fn: is_scalar::<S>,
+            parse_fn: parse_scalar_token::<S>,
+            accepts_composite: false,
+        }),
+        "OBJECT" => MetaType::Object(ObjectMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            cache_control: CacheHint::default(),
+            fields: build_fields(ty, name, defaults)?,
+            interface_names: type_names(ty, "interfaces", name)?,
+        }),
+        "INTERFACE" => MetaType::Interface(InterfaceMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            cache_control: CacheHint::default(),
+            fields: build_fields(ty, name, defaults)?,
+        }),
+        "UNION" => MetaType::Union(UnionMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            cache_control: CacheHint::default(),
+            of_type_names: type_names(ty, "possibleTypes", name)?,
+        }),
+        "ENUM" => MetaType::Enum(EnumMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            values: list(ty, "enumValues")
+                .iter()
+                .map(|v| {
+                    Ok(EnumValue {
+                        name: required_str(v, "name", &format!("value of `{}`", name))?.to_owned(),
+                        description: string(v, "description").map(ToOwned::to_owned),
+                        deprecation_status: deprecation_status(v),
+                        directives: vec![],
+                    })
+                })
+                .collect::<Result<_, IntrospectionError>>()?,
+            try_parse_fn: is_enum::<S>,
+            accepts_custom_values: false,
+        }),
+        "INPUT_OBJECT" => MetaType::InputObject(InputObjectMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            input_fields: build_arguments(
+                list(ty, "inputFields"),
+                || Owner::InputObject(name.to_owned()),
+                name,
+                defaults,
+            )?,
+            try_parse_fn: is_object::<S>,
+        }),
+        _ => {
+            return Err(IntrospectionError::new(format!(
+                "Unsupported kind `{}` of type `{}`",
+                kind, name,
+            )))
+        }
+    })
+}
+
+fn build_fields<'a, S: ScalarValue + 'a>(
+    ty: &Value<S>,
+    type_name: &str,
+    defaults: &mut Vec<PendingDefault<'a>>,
+) -> Result<Vec<Field<'a, S>>, IntrospectionError> {
+    let mut fields = list(ty, "fields")
+        .iter()
+        .map(|f| {
+            let name = required_str(f, "name", &format!("field of `{}`", type_name))?;
+            let path = format!("{}.{}", type_name, name);
+            let arguments = build_arguments(
+                list(f, "args"),
+                || Owner::Field {
+                    type_name: type_name.to_owned(),
+                    field: name.to_owned(),
+                },
+                &path,
+                defaults,
+            )?;
+            Ok(Field {
+                name: name.into(),
+                description: string(f, "description").map(ToOwned::to_owned),
+                arguments: if arguments.is_empty() {
+                    None
+                } else {
+                    Some(arguments)
+                },
+                field_type: build_type_ref(field(f, "type"), &path)?,
+                deprecation_status: deprecation_status(f),
+                directives: vec![],
+                cache_control: CacheHint::default(),
+            })
+        })
+        .collect::<Result<Vec<_>, IntrospectionError>>()?;
+
+    fields.push(Field {
+        name: "__typename".into(),
+        description: None,
+        arguments: None,
+        field_type: Type::NonNullNamed(Cow::Borrowed("String")),
+        deprecation_status: DeprecationStatus::Current,
+        directives: vec![],
+        cache_control: CacheHint::default(),
+    });
+    Ok(fields)
+}
+
+fn build_arguments<'a, S: ScalarValue + 'a>(
+    args: &[Value<S>],
+    owner: impl Fn() -> Owner,
+    context: &str,
+    defaults: &mut Vec<PendingDefault<'a>>,
+) -> Result<Vec<Argument<'a, S>>, IntrospectionError> {
+    args.iter()
+        .map(|a| {
+            let name = required_str(a, "name", &format!("argument of `{}`", context))?;
+            let arg_type = build_type_ref(field(a, "type"), &format!("{}.{}", context, name))?;
+            if let Some(source) = string(a, "defaultValue") {
+                defaults.push(PendingDefault {
+                    owner: owner(),
+                    name: name.to_owned(),
+                    arg_type: arg_type.clone(),
+                    source: source.to_owned(),
+                });
+            }
+            let mut arg = Argument::new(name, arg_type);
+            arg.description = string(a, "description").map(ToOwned::to_owned);
+            Ok(arg)
+        })
+        .collect()
+}
+
+fn build_type_ref<'a, S: ScalarValue>(
+    ty: Option<&Value<S>>,
+    context: &str,
+) -> Result<Type<'a>, IntrospectionError> {
+    let ty = ty.ok_or_else(|| IntrospectionError::new(format!("Missing type of `{}`", context)))?;
+    let kind = required_str(ty, "kind", &format!("type of `{}`", context))?;
+    Ok(match kind {
+        "NON_NULL" => match build_type_ref(field(ty, "ofType"), context)? {
+            Type::Named(name) => Type::NonNullNamed(name),
+            Type::List(inner, size) => Type::NonNullList(inner, size),
+            _ => {
+                return Err(IntrospectionError::new(format!(
+                    "Type of `{}` is non-null twice",
+                    context,
+                )))
+            }
+        },
+        "LIST" => Type::List(
+            Box::new(build_type_ref(field(ty, "ofType"), context)?),
+            None,
+        ),
+        _ => Type::Named(Cow::Owned(
+            required_str(ty, "name", &format!("type of `{}`", context))?.to_owned(),
+        )),
+    })
+}
+
+fn type_names<S: ScalarValue>(
+    ty: &Value<S>,
+    name: &str,
+    type_name: &str,
+) -> Result<Vec<String>, IntrospectionError> {
+    list(ty, name)
+        .iter()
+        .map(|t| required_str(t, "name", &format!("`{}` of `{}`", name, type_name)).map(Into::into))
+        .collect()
+}
+
+fn build_directive<'a, S: ScalarValue + 'a>(
+    directive: &Value<S>,
+    defaults: &mut Vec<PendingDefault<'a>>,
+) -> Result<DirectiveType<'a, S>, IntrospectionError> {
+    let name = required_str(directive, "name", "directive")?;
+    let context = format!("@{}", name);
+    let locations = list(directive, "locations")
+        .iter()
+        .map(|l| {
+            l.as_scalar()
+                .and_then(ScalarValue::as_str)
+                .and_then(|l| DirectiveLocation::from_input_value(&InputValue::<S>::enum_value(l)))
+                .ok_or_else(|| {
+                    IntrospectionError::new(format!("Invalid location of `{}`", context))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let arguments = build_arguments(
+        list(directive, "args"),
+        || Owner::Directive(name.to_owned()),
+        &context,
+        defaults,
+    )?;
+
+    let mut directive_type = DirectiveType::new(name, &locations, &arguments);
+    directive_type.description = string(directive, "description").map(ToOwned::to_owned);
+    directive_type.is_repeatable = field(directive, "isRepeatable")
+        .and_then(Value::as_scalar)
+        .and_then(ScalarValue::as_boolean)
+        .unwrap_or(false);
+    Ok(directive_type)
+}
+
+fn parse_default_value<S: ScalarValue>(
+    source: &str,
+    arg_type: &Type,
+    schema: &SchemaType<S>,
+) -> Result<InputValue<S>, String> {
+    let mut lexer = Lexer::new(source);
+    let mut parser = Parser::new(&mut lexer).map_err(|e| e.item.to_string())?;
+    let value = parse_value_literal(&mut parser, true, schema, schema.lookup_type(arg_type))
+        .map_err(|e| e.item.to_string())?;
+    if parser.peek().item != Token::EndOfFile {
+        return Err(format!("Unexpected `{}`", parser.peek().item));
+    }
+    Ok(value.item)
+}
+
+fn is_scalar<S>(value: &InputValue<S>) -> Result<(), InputValueError> {
+    accept_if(matches!(value, InputValue::Scalar(_)))
+}
+
+fn is_enum<S: ScalarValue>(value: &InputValue<S>) -> Result<(), InputValueError> {
+    accept_if(match value {
+        InputValue::Enum(_) => true,
+        InputValue::Scalar(s) => s.as_str().is_some(),
+        _ => false,
+    })
+}
+
+fn is_object<S>(value: &InputValue<S>) -> Result<(), InputValueError> {
+    accept_if(matches!(value, InputValue::Object(_)))
+}
+
+fn accept_if(ok: bool) -> Result<(), InputValueError> {
+    if ok {
+        Ok(())
+    } else {
+        Err(InputValueError::default())
+    }
+}
+
+fn parse_scalar_token<S: ScalarValue>(token: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+    match token {
+        ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(token),
+        ScalarToken::Int(_) => <i32 as ParseScalarValue<S>>::from_str(token)
+            .or_else(|_| <f64 as ParseScalarValue<S>>::from_str(token)),
+        ScalarToken::Float(_) => <f64 as ParseScalarValue<S>>::from_str(token),
+    }
+}
@@ -111,7 +111,7 @@ pub use futures::future::{BoxFuture, LocalBoxFuture};
 // functionality automatically.
 pub use juniper_codegen::{
     graphql_interface, graphql_object, graphql_scalar, graphql_subscription, graphql_union,
-    GraphQLEnum, GraphQLInputObject, GraphQLObject, GraphQLScalarValue, GraphQLUnion,
+    GraphQLEnum, GraphQLError, GraphQLInputObject, GraphQLObject, GraphQLScalarValue, GraphQLUnion,
 };
 
 #[macro_use]
@@ -143,36 +143,40 @@ mod executor_tests;
 pub use crate::util::to_camel_case;
 
 use crate::{
-    executor::{execute_validated_query, get_operation},
+    executor::{get_operation, CacheableOutput},
     introspection::{INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS},
     parser::parse_document_source,
-    validation::{validate_input_values, visit_all_rules, ValidatorContext},
+    validation::{
+        validate_input_values, validate_variable_usages, visit_all_rules, ValidatorContext,
+    },
 };
 
 pub use crate::{
     ast::{
-        Definition, Document, FromInputValue, InputValue, Operation, OperationType, Selection,
-        ToInputValue, Type,
+        Definition, Document, FromInputValue, InputValue, InputValueError, Operation,
+        OperationType, Selection, ToInputValue, Type,
     },
     executor::{
-        Applies, Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-        FromContext, IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
+        Applies, CacheHint, CachePolicy, CacheScope, Context, ErrorFormatter, ErrorMask,
+        ExecutionError, ExecutionResult, Executor, FieldError, FieldResult, FromContext,
+        IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods, LookAheadSelection,
+        LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
     },
-    introspection::IntrospectionFormat,
+    introspection::{IntrospectionError, IntrospectionFormat},
     macros::helper::{
         subscription::{ExtractTypeFromStream, IntoFieldResult},
         AsDynGraphQLValue,
     },
     parser::{ParseError, Spanning},
     schema::{
-        meta,
-        model::{RootNode, SchemaType},
+        diff as schema_diff, meta,
+        model::{DirectiveLocation, DirectiveType, RootNode, SchemaType},
     },
     types::{
         async_await::{DynGraphQLValueAsync, GraphQLTypeAsync, GraphQLValueAsync},
         base::{Arguments, DynGraphQLValue, GraphQLType, GraphQLValue, TypeKind},
-        marker::{self, GraphQLInterface, GraphQLObject, GraphQLUnion},
+        marker::{self, GraphQLInputObject, GraphQLInterface, GraphQLObject, GraphQLUnion},
+        merged::{MergedObject, SchemaConflict},
         nullable::Nullable,
         scalars::{EmptyMutation, EmptySubscription, ID},
         subscriptions::{
@@ -180,10 +184,16 @@ pub use crate::{
             SubscriptionConnection, SubscriptionCoordinator,
         },
     },
-    validation::RuleError,
-    value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+    validation::{RuleError, RuleErrorCode},
+    value::{
+        DefaultScalarValue, LongScalarValue, Object, ParseScalarResult, ParseScalarValue,
+        ScalarValue, Value,
+    },
 };
 
+#[cfg(feature = "wide-integers")]
+pub use crate::types::wide_integers::{BigInt, Long, UnsignedLong};
+
 /// An error that prevented query execution
 #[derive(Debug, PartialEq)]
 #[allow(missing_docs)]
@@ -195,6 +205,7 @@ pub enum GraphQLError<'a> {
     UnknownOperationName,
     IsSubscription,
     NotSubscription,
+    NotQuery,
 }
 
 impl<'a> fmt::Display for GraphQLError<'a> {
@@ -212,6 +223,7 @@ impl<'a> fmt::Display for GraphQLError<'a> {
             GraphQLError::UnknownOperationName => write!(f, "Unknown operation name"),
             GraphQLError::IsSubscription => write!(f, "Operation is a subscription"),
             GraphQLError::NotSubscription => write!(f, "Operation is not a subscription"),
+            GraphQLError::NotQuery => write!(f, "Operation is not a query"),
         }
     }
 }
@@ -226,6 +238,35 @@ pub fn execute_sync<'a, S, QueryT, MutationT, SubscriptionT>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    execute_sync_with_cache_policy(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        false,
+    )
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Same as [`execute_sync`], but also returns the [`CachePolicy`] of the response.
+///
+/// Refuses to execute operations other than queries with a [`GraphQLError::NotQuery`] if
+/// `queries_only` is set.
+pub(crate) fn execute_sync_with_cache_policy<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    queries_only: bool,
+) -> Result<CacheableOutput<S>, GraphQLError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -234,6 +275,10 @@ where
 {
     let document = parse_document_source(document_source, &root_node.schema)?;
 
+    if queries_only {
+        ensure_query(&document, operation_name)?;
+    }
+
     {
         let mut ctx = ValidatorContext::new(&root_node.schema, &document);
         visit_all_rules(&mut ctx, &document);
@@ -247,14 +292,46 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let mut errors = validate_input_values(variables, operation, &root_node.schema);
+        if errors.is_empty() {
+            errors = validate_variable_usages(variables, &document, operation, &root_node.schema);
+        }
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    execute_validated_query(&document, operation, root_node, variables, context)
+    executor::execute_validated_query_with_cache_policy(
+        &document, operation, root_node, variables, context,
+    )
+}
+
+/// Refuses a non-query operation selected in the `document`, leaving the errors of selecting one
+/// to be reported by the execution.
+fn ensure_query<'a, S>(
+    document: &Document<S>,
+    operation_name: Option<&str>,
+) -> Result<(), GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    match operation_type(document, operation_name) {
+        Some(ty) if ty != OperationType::Query => Err(GraphQLError::NotQuery),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the type of the operation selected in the `document`, if a single one is selected.
+pub(crate) fn operation_type<S>(
+    document: &Document<S>,
+    operation_name: Option<&str>,
+) -> Option<OperationType>
+where
+    S: ScalarValue,
+{
+    let operation = get_operation(document, operation_name).ok()?;
+    Some(operation.item.operation_type.clone())
 }
 
 /// Execute a query in a provided schema
@@ -265,6 +342,40 @@ pub async fn execute<'a, S, QueryT, MutationT, SubscriptionT>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_with_cache_policy(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        false,
+    )
+    .await
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Same as [`execute`], but also returns the [`CachePolicy`] of the response.
+///
+/// Refuses to execute operations other than queries with a [`GraphQLError::NotQuery`] if
+/// `queries_only` is set.
+pub(crate) async fn execute_with_cache_policy<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    queries_only: bool,
+) -> Result<CacheableOutput<S>, GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -277,6 +388,10 @@ where
 {
     let document = parse_document_source(document_source, &root_node.schema)?;
 
+    if queries_only {
+        ensure_query(&document, operation_name)?;
+    }
+
     {
         let mut ctx = ValidatorContext::new(&root_node.schema, &document);
         visit_all_rules(&mut ctx, &document);
@@ -290,15 +405,20 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let mut errors = validate_input_values(variables, operation, &root_node.schema);
+        if errors.is_empty() {
+            errors = validate_variable_usages(variables, &document, operation, &root_node.schema);
+        }
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    executor::execute_validated_query_async(&document, operation, root_node, variables, context)
-        .await
+    executor::execute_validated_query_async_with_cache_policy(
+        &document, operation, root_node, variables, context,
+    )
+    .await
 }
 
 /// Resolve subscription into `ValuesStream`
@@ -335,7 +455,10 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let mut errors = validate_input_values(variables, operation, &root_node.schema);
+        if errors.is_empty() {
+            errors = validate_variable_usages(variables, &document, operation, &root_node.schema);
+        }
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
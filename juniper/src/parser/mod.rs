@@ -11,6 +11,7 @@ mod value;
 mod tests;
 
 pub use self::document::parse_document_source;
+pub(crate) use self::value::parse_value_literal;
 
 pub use self::{
     lexer::{Lexer, LexerError, ScalarToken, Token},
@@ -40,6 +40,15 @@ where
             },
             Some(&MetaType::InputObject(ref o)),
         ) => parse_object_literal(parser, is_const, schema, Some(o)),
+        // Scalars accepting object literals (e.g. `Json`) have the whole
+        // literal parsed against the scalar itself.
+        (
+            &Spanning {
+                item: Token::CurlyOpen,
+                ..
+            },
+            Some(&MetaType::Scalar(ref s)),
+        ) if s.accepts_composite => parse_scalar_object_literal(parser, is_const, schema, tpe),
         (
             &Spanning {
                 item: Token::Dollar,
@@ -155,6 +164,33 @@ where
         .map(|items| InputValue::parsed_object(items.into_iter().map(|s| s.item).collect())))
 }
 
+fn parse_scalar_object_literal<'a, 'b, S>(
+    parser: &mut Parser<'a>,
+    is_const: bool,
+    schema: &'b SchemaType<'b, S>,
+    scalar_tpe: Option<&MetaType<'b, S>>,
+) -> ParseResult<'a, InputValue<S>>
+where
+    S: ScalarValue,
+{
+    Ok(parser
+        .delimited_list(
+            &Token::CurlyOpen,
+            |p| {
+                let key = p.expect_name()?;
+                p.expect(&Token::Colon)?;
+                let value = parse_value_literal(p, is_const, schema, scalar_tpe)?;
+                Ok(Spanning::start_end(
+                    &key.start,
+                    &value.end.clone(),
+                    (key.map(|s| s.to_owned()), value),
+                ))
+            },
+            &Token::CurlyClose,
+        )?
+        .map(|items| InputValue::parsed_object(items.into_iter().map(|s| s.item).collect())))
+}
+
 fn parse_object_field<'a, 'b, S>(
     parser: &mut Parser<'a>,
     is_const: bool,
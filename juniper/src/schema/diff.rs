@@ -0,0 +1,1193 @@
+//! Comparison of two GraphQL schemas, detecting breaking changes between them.
+
+use std::fmt;
+
+use crate::{
+    ast::Type,
+    schema::{
+        meta::{Argument, DeprecationStatus, EnumValue, Field, MetaType},
+        model::SchemaType,
+    },
+    types::base::TypeKind,
+    value::ScalarValue,
+};
+
+/// How dangerous a [`Change`] is for the existing clients of a schema.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Criticality {
+    /// The change breaks existing queries, e.g. a removed field.
+    Breaking,
+
+    /// The change keeps existing queries valid, but may alter their results or how clients deal
+    /// with them, e.g. a new enum value.
+    Dangerous,
+
+    /// The change is backwards compatible.
+    Safe,
+}
+
+/// Kind of a [`Change`] between two schemas.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeKind {
+    /// A new type was added.
+    TypeAdded,
+
+    /// A type was removed.
+    TypeRemoved,
+
+    /// A type changed its kind, e.g. from an object to an interface.
+    TypeKindChanged {
+        /// The kind in the old schema.
+        old: TypeKind,
+        /// The kind in the new schema.
+        new: TypeKind,
+    },
+
+    /// The type of an operation's root changed, or the operation was added or removed.
+    RootTypeChanged {
+        /// Root type name in the old schema, if the operation was supported.
+        old: Option<String>,
+        /// Root type name in the new schema, if the operation is supported.
+        new: Option<String>,
+    },
+
+    /// A field was added to an object or an interface.
+    FieldAdded,
+
+    /// A field was removed from an object or an interface.
+    FieldRemoved,
+
+    /// A field was deprecated.
+    FieldDeprecated,
+
+    /// The type of a field changed.
+    FieldTypeChanged {
+        /// The type in the old schema.
+        old: String,
+        /// The type in the new schema.
+        new: String,
+    },
+
+    /// An argument was added to a field.
+    ArgumentAdded {
+        /// Whether the argument must be provided.
+        required: bool,
+    },
+
+    /// An argument was removed from a field.
+    ArgumentRemoved,
+
+    /// An optional argument became required.
+    ArgumentBecameRequired,
+
+    /// The type of an argument changed.
+    ArgumentTypeChanged {
+        /// The type in the old schema.
+        old: String,
+        /// The type in the new schema.
+        new: String,
+    },
+
+    /// The default value of an argument or an input field changed.
+    DefaultValueChanged {
+        /// The default value in the old schema.
+        old: Option<String>,
+        /// The default value in the new schema.
+        new: Option<String>,
+    },
+
+    /// A field was added to an input object.
+    InputFieldAdded {
+        /// Whether the field must be provided.
+        required: bool,
+    },
+
+    /// A field was removed from an input object.
+    InputFieldRemoved,
+
+    /// An optional input field became required.
+    InputFieldBecameRequired,
+
+    /// The type of an input field changed.
+    InputFieldTypeChanged {
+        /// The type in the old schema.
+        old: String,
+        /// The type in the new schema.
+        new: String,
+    },
+
+    /// A value was added to an enum.
+    EnumValueAdded,
+
+    /// A value was removed from an enum.
+    EnumValueRemoved,
+
+    /// A value of an enum was deprecated.
+    EnumValueDeprecated,
+
+    /// A member type was added to a union.
+    UnionMemberAdded,
+
+    /// A member type was removed from a union.
+    UnionMemberRemoved,
+
+    /// An object started implementing an interface.
+    InterfaceAdded,
+
+    /// An object stopped implementing an interface.
+    InterfaceRemoved,
+}
+
+/// Single change between two schemas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    /// Schema coordinate of the changed element, like `Type`, `Type.field`,
+    /// `Type.field(argument:)` or `Enum.VALUE`.
+    ///
+    /// For [`ChangeKind::UnionMemberAdded`], [`ChangeKind::UnionMemberRemoved`],
+    /// [`ChangeKind::InterfaceAdded`] and [`ChangeKind::InterfaceRemoved`] this is the changed
+    /// type, while the member or interface name is in [`Change::subject`].
+    ///
+    /// For [`ChangeKind::RootTypeChanged`] this is the name of the operation.
+    pub path: String,
+
+    /// Name of the type added or removed from a union or an object's interfaces.
+    pub subject: Option<String>,
+
+    /// What changed.
+    pub kind: ChangeKind,
+
+    /// How dangerous the change is for existing clients.
+    pub criticality: Criticality,
+}
+
+impl Change {
+    fn new(path: impl Into<String>, kind: ChangeKind, criticality: Criticality) -> Self {
+        Self {
+            path: path.into(),
+            subject: None,
+            kind,
+            criticality,
+        }
+    }
+
+    fn with_subject(mut self, subject: &str) -> Self {
+        self.subject = Some(subject.to_owned());
+        self
+    }
+
+    /// Indicates whether this change breaks existing queries.
+    pub fn is_breaking(&self) -> bool {
+        self.criticality == Criticality::Breaking
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = &self.path;
+        let subject = self.subject.as_deref().unwrap_or_default();
+        match &self.kind {
+            ChangeKind::TypeAdded => write!(f, "Type `{}` was added", path),
+            ChangeKind::TypeRemoved => write!(f, "Type `{}` was removed", path),
+            ChangeKind::TypeKindChanged { old, new } => {
+                write!(f, "Type `{}` changed from {:?} to {:?}", path, old, new,)
+            }
+            ChangeKind::RootTypeChanged { old, new } => match (old, new) {
+                (Some(old), Some(new)) => write!(
+                    f,
+                    "Root type of {} changed from `{}` to `{}`",
+                    path, old, new,
+                ),
+                (None, _) => write!(f, "Schema now supports {}", path),
+                (_, None) => write!(f, "Schema no longer supports {}", path),
+            },
+            ChangeKind::FieldAdded => write!(f, "Field `{}` was added", path),
+            ChangeKind::FieldRemoved => write!(f, "Field `{}` was removed", path),
+            ChangeKind::FieldDeprecated => write!(f, "Field `{}` was deprecated", path),
+            ChangeKind::FieldTypeChanged { old, new } => write!(
+                f,
+                "Field `{}` changed type from `{}` to `{}`",
+                path, old, new,
+            ),
+            ChangeKind::ArgumentAdded { required } => write!(
+                f,
+                "{} argument `{}` was added",
+                if *required { "Required" } else { "Optional" },
+                path,
+            ),
+            ChangeKind::ArgumentRemoved => write!(f, "Argument `{}` was removed", path),
+            ChangeKind::ArgumentBecameRequired => {
+                write!(f, "Argument `{}` became required", path)
+            }
+            ChangeKind::ArgumentTypeChanged { old, new } => write!(
+                f,
+                "Argument `{}` changed type from `{}` to `{}`",
+                path, old, new,
+            ),
+            ChangeKind::DefaultValueChanged { old, new } => write!(
+                f,
+                "Default value of `{}` changed from `{}` to `{}`",
+                path,
+                old.as_deref().unwrap_or("none"),
+                new.as_deref().unwrap_or("none"),
+            ),
+            ChangeKind::InputFieldAdded { required } => write!(
+                f,
+                "{} input field `{}` was added",
+                if *required { "Required" } else { "Optional" },
+                path,
+            ),
+            ChangeKind::InputFieldRemoved => write!(f, "Input field `{}` was removed", path),
+            ChangeKind::InputFieldBecameRequired => {
+                write!(f, "Input field `{}` became required", path)
+            }
+            ChangeKind::InputFieldTypeChanged { old, new } => write!(
+                f,
+                "Input field `{}` changed type from `{}` to `{}`",
+                path, old, new,
+            ),
+            ChangeKind::EnumValueAdded => write!(f, "Enum value `{}` was added", path),
+            ChangeKind::EnumValueRemoved => write!(f, "Enum value `{}` was removed", path),
+            ChangeKind::EnumValueDeprecated => {
+                write!(f, "Enum value `{}` was deprecated", path)
+            }
+            ChangeKind::UnionMemberAdded => {
+                write!(f, "Type `{}` was added to union `{}`", subject, path)
+            }
+            ChangeKind::UnionMemberRemoved => {
+                write!(f, "Type `{}` was removed from union `{}`", subject, path)
+            }
+            ChangeKind::InterfaceAdded => {
+                write!(f, "Type `{}` now implements `{}`", path, subject)
+            }
+            ChangeKind::InterfaceRemoved => {
+                write!(f, "Type `{}` no longer implements `{}`", path, subject)
+            }
+        }
+    }
+}
+
+/// Compares the `old` schema with the `new` one, returning all the changes between them.
+///
+/// Built-in types and fields (like introspection ones) are not compared. Changes are ordered by
+/// the names of the changed types.
+///
+/// ```
+/// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, RootNode};
+/// # mod v1 {
+/// #     pub struct Query;
+/// #     #[juniper::graphql_object]
+/// #     impl Query {
+/// #         fn name() -> &'static str { "v1" }
+/// #         fn version() -> i32 { 1 }
+/// #     }
+/// # }
+/// # mod v2 {
+/// #     pub struct Query;
+/// #     #[juniper::graphql_object]
+/// #     impl Query {
+/// #         fn name() -> &'static str { "v2" }
+/// #     }
+/// # }
+/// let old = RootNode::new(v1::Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+/// let new = RootNode::new(v2::Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+///
+/// let changes = juniper::schema_diff::diff(&old.schema, &new.schema);
+/// assert!(changes
+///     .iter()
+///     .any(|c| c.criticality == juniper::schema_diff::Criticality::Breaking));
+/// assert_eq!(changes[0].to_string(), "Field `Query.version` was removed");
+/// ```
+pub fn diff<S: ScalarValue>(old: &SchemaType<S>, new: &SchemaType<S>) -> Vec<Change> {
+    let mut changes = vec![];
+
+    diff_root(
+        "query",
+        Some(&old.query_type_name),
+        Some(&new.query_type_name),
+        &mut changes,
+    );
+    diff_root(
+        "mutation",
+        old.mutation_type_name.as_ref(),
+        new.mutation_type_name.as_ref(),
+        &mut changes,
+    );
+    diff_root(
+        "subscription",
+        old.subscription_type_name.as_ref(),
+        new.subscription_type_name.as_ref(),
+        &mut changes,
+    );
+
+    let mut names = old
+        .types
+        .values()
+        .chain(new.types.values())
+        .filter(|t| !t.is_builtin())
+        .filter_map(MetaType::name)
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+
+    for name in names {
+        match (
+            old.concrete_type_by_name(name),
+            new.concrete_type_by_name(name),
+        ) {
+            (Some(_), None) => changes.push(Change::new(
+                name,
+                ChangeKind::TypeRemoved,
+                Criticality::Breaking,
+            )),
+            (None, Some(_)) => {
+                changes.push(Change::new(name, ChangeKind::TypeAdded, Criticality::Safe))
+            }
+            (Some(old), Some(new)) => diff_type(name, old, new, &mut changes),
+            (None, None) => {}
+        }
+    }
+
+    changes
+}
+
+fn diff_root(operation: &str, old: Option<&String>, new: Option<&String>, out: &mut Vec<Change>) {
+    if old != new {
+        out.push(Change::new(
+            operation,
+            ChangeKind::RootTypeChanged {
+                old: old.cloned(),
+                new: new.cloned(),
+            },
+            if old.is_none() {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            },
+        ));
+    }
+}
+
+fn diff_type<S: ScalarValue>(
+    name: &str,
+    old: &MetaType<S>,
+    new: &MetaType<S>,
+    out: &mut Vec<Change>,
+) {
+    match (old, new) {
+        (MetaType::Object(o), MetaType::Object(n)) => {
+            diff_fields(name, &o.fields, &n.fields, out);
+            diff_names(
+                name,
+                &o.interface_names,
+                &n.interface_names,
+                (ChangeKind::InterfaceAdded, Criticality::Safe),
+                (ChangeKind::InterfaceRemoved, Criticality::Breaking),
+                out,
+            );
+        }
+        (MetaType::Interface(o), MetaType::Interface(n)) => {
+            diff_fields(name, &o.fields, &n.fields, out)
+        }
+        (MetaType::Union(o), MetaType::Union(n)) => diff_names(
+            name,
+            &o.of_type_names,
+            &n.of_type_names,
+            (ChangeKind::UnionMemberAdded, Criticality::Dangerous),
+            (ChangeKind::UnionMemberRemoved, Criticality::Breaking),
+            out,
+        ),
+        (MetaType::Enum(o), MetaType::Enum(n)) => diff_enum_values(name, &o.values, &n.values, out),
+        (MetaType::InputObject(o), MetaType::InputObject(n)) => {
+            diff_input_fields(name, &o.input_fields, &n.input_fields, out)
+        }
+        (MetaType::Scalar(_), MetaType::Scalar(_)) => {}
+        _ => out.push(Change::new(
+            name,
+            ChangeKind::TypeKindChanged {
+                old: old.type_kind(),
+                new: new.type_kind(),
+            },
+            Criticality::Breaking,
+        )),
+    }
+}
+
+fn diff_fields<S: ScalarValue>(
+    type_name: &str,
+    old: &[Field<S>],
+    new: &[Field<S>],
+    out: &mut Vec<Change>,
+) {
+    for o in old.iter().filter(|f| !f.is_builtin()) {
+        let path = format!("{}.{}", type_name, o.name);
+        let n = match new.iter().find(|f| f.name == o.name) {
+            Some(n) => n,
+            None => {
+                out.push(Change::new(
+                    path,
+                    ChangeKind::FieldRemoved,
+                    Criticality::Breaking,
+                ));
+                continue;
+            }
+        };
+
+        if o.field_type != n.field_type {
+            out.push(Change::new(
+                path.clone(),
+                ChangeKind::FieldTypeChanged {
+                    old: o.field_type.to_string(),
+                    new: n.field_type.to_string(),
+                },
+                if is_safe_output_change(&o.field_type, &n.field_type) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                },
+            ));
+        }
+        if !o.deprecation_status.is_deprecated() && n.deprecation_status.is_deprecated() {
+            out.push(Change::new(
+                path.clone(),
+                ChangeKind::FieldDeprecated,
+                Criticality::Safe,
+            ));
+        }
+
+        diff_arguments(
+            &path,
+            o.arguments.as_deref().unwrap_or_default(),
+            n.arguments.as_deref().unwrap_or_default(),
+            out,
+        );
+    }
+
+    for n in new.iter().filter(|f| !f.is_builtin()) {
+        if !old.iter().any(|f| f.name == n.name) {
+            out.push(Change::new(
+                format!("{}.{}", type_name, n.name),
+                ChangeKind::FieldAdded,
+                Criticality::Safe,
+            ));
+        }
+    }
+}
+
+fn diff_arguments<S: ScalarValue>(
+    field_path: &str,
+    old: &[Argument<S>],
+    new: &[Argument<S>],
+    out: &mut Vec<Change>,
+) {
+    diff_input_values(
+        old,
+        new,
+        |name| format!("{}({}:)", field_path, name),
+        InputValueKinds {
+            added: |required| ChangeKind::ArgumentAdded { required },
+            removed: ChangeKind::ArgumentRemoved,
+            became_required: ChangeKind::ArgumentBecameRequired,
+            type_changed: |old, new| ChangeKind::ArgumentTypeChanged { old, new },
+        },
+        out,
+    )
+}
+
+fn diff_input_fields<S: ScalarValue>(
+    type_name: &str,
+    old: &[Argument<S>],
+    new: &[Argument<S>],
+    out: &mut Vec<Change>,
+) {
+    diff_input_values(
+        old,
+        new,
+        |name| format!("{}.{}", type_name, name),
+        InputValueKinds {
+            added: |required| ChangeKind::InputFieldAdded { required },
+            removed: ChangeKind::InputFieldRemoved,
+            became_required: ChangeKind::InputFieldBecameRequired,
+            type_changed: |old, new| ChangeKind::InputFieldTypeChanged { old, new },
+        },
+        out,
+    )
+}
+
+/// [`ChangeKind`]s to report for arguments or input fields.
+struct InputValueKinds<A, T> {
+    added: A,
+    removed: ChangeKind,
+    became_required: ChangeKind,
+    type_changed: T,
+}
+
+fn diff_input_values<S, P, A, T>(
+    old: &[Argument<S>],
+    new: &[Argument<S>],
+    path: P,
+    kinds: InputValueKinds<A, T>,
+    out: &mut Vec<Change>,
+) where
+    S: ScalarValue,
+    P: Fn(&str) -> String,
+    A: Fn(bool) -> ChangeKind,
+    T: Fn(String, String) -> ChangeKind,
+{
+    let is_required = |arg: &Argument<S>| arg.arg_type.is_non_null() && arg.default_value.is_none();
+
+    for o in old {
+        let n = match new.iter().find(|a| a.name == o.name) {
+            Some(n) => n,
+            None => {
+                out.push(Change::new(
+                    path(&o.name),
+                    kinds.removed.clone(),
+                    Criticality::Breaking,
+                ));
+                continue;
+            }
+        };
+
+        let became_required = !is_required(o) && is_required(n);
+        let type_changed = |criticality| {
+            Change::new(
+                path(&o.name),
+                (kinds.type_changed)(o.arg_type.to_string(), n.arg_type.to_string()),
+                criticality,
+            )
+        };
+        if o.arg_type != n.arg_type && !is_tightened(&o.arg_type, &n.arg_type) {
+            out.push(type_changed(
+                if is_safe_input_change(&o.arg_type, &n.arg_type) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                },
+            ));
+        } else if became_required {
+            out.push(Change::new(
+                path(&o.name),
+                kinds.became_required.clone(),
+                Criticality::Breaking,
+            ));
+        } else if o.arg_type != n.arg_type {
+            // Made non-null, but still optional thanks to its default value, so only explicit
+            // `null`s are rejected now.
+            out.push(type_changed(Criticality::Dangerous));
+        }
+
+        let old_default = o.default_value.as_ref().map(ToString::to_string);
+        let new_default = n.default_value.as_ref().map(ToString::to_string);
+        if old_default != new_default && !became_required {
+            out.push(Change::new(
+                path(&o.name),
+                ChangeKind::DefaultValueChanged {
+                    old: old_default,
+                    new: new_default,
+                },
+                Criticality::Dangerous,
+            ));
+        }
+    }
+
+    for n in new {
+        if !old.iter().any(|a| a.name == n.name) {
+            let required = is_required(n);
+            out.push(Change::new(
+                path(&n.name),
+                (kinds.added)(required),
+                if required {
+                    Criticality::Breaking
+                } else {
+                    Criticality::Safe
+                },
+            ));
+        }
+    }
+}
+
+fn diff_enum_values<S>(
+    type_name: &str,
+    old: &[EnumValue<S>],
+    new: &[EnumValue<S>],
+    out: &mut Vec<Change>,
+) {
+    for o in old {
+        let path = format!("{}.{}", type_name, o.name);
+        match new.iter().find(|v| v.name == o.name) {
+            None => out.push(Change::new(
+                path,
+                ChangeKind::EnumValueRemoved,
+                Criticality::Breaking,
+            )),
+            Some(n) => {
+                if let (DeprecationStatus::Current, DeprecationStatus::Deprecated(_)) =
+                    (&o.deprecation_status, &n.deprecation_status)
+                {
+                    out.push(Change::new(
+                        path,
+                        ChangeKind::EnumValueDeprecated,
+                        Criticality::Safe,
+                    ));
+                }
+            }
+        }
+    }
+    for n in new {
+        if !old.iter().any(|v| v.name == n.name) {
+            out.push(Change::new(
+                format!("{}.{}", type_name, n.name),
+                ChangeKind::EnumValueAdded,
+                Criticality::Dangerous,
+            ));
+        }
+    }
+}
+
+fn diff_names(
+    type_name: &str,
+    old: &[String],
+    new: &[String],
+    (added, added_criticality): (ChangeKind, Criticality),
+    (removed, removed_criticality): (ChangeKind, Criticality),
+    out: &mut Vec<Change>,
+) {
+    for o in old.iter().filter(|o| !new.contains(o)) {
+        out.push(Change::new(type_name, removed.clone(), removed_criticality).with_subject(o));
+    }
+    for n in new.iter().filter(|n| !old.contains(n)) {
+        out.push(Change::new(type_name, added.clone(), added_criticality).with_subject(n));
+    }
+}
+
+/// Indicates whether the `new` type only differs from the `old` one by being non-null.
+fn is_tightened(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(o), Type::NonNullNamed(n)) => o == n,
+        (Type::List(o, _), Type::NonNullList(n, _)) => o == n,
+        _ => false,
+    }
+}
+
+/// Indicates whether a field of the `old` type may return the `new` type without breaking
+/// clients, i.e. the types only differ by the `new` one being non-null in more positions.
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(o), Type::Named(n) | Type::NonNullNamed(n))
+        | (Type::NonNullNamed(o), Type::NonNullNamed(n)) => o == n,
+        (Type::List(o, _), Type::List(n, _) | Type::NonNullList(n, _))
+        | (Type::NonNullList(o, _), Type::NonNullList(n, _)) => is_safe_output_change(o, n),
+        _ => false,
+    }
+}
+
+/// Indicates whether an input of the `old` type may accept the `new` type without breaking
+/// clients, i.e. the types only differ by the `new` one being nullable in more positions.
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    is_safe_output_change(new, old)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::Type,
+        graphql_interface, graphql_object,
+        schema::{
+            meta::{MetaType, ObjectMeta},
+            model::SchemaType,
+        },
+        types::base::TypeKind,
+        EmptyMutation, EmptySubscription, GraphQLEnum, GraphQLInputObject, GraphQLObject,
+        GraphQLUnion, RootNode,
+    };
+
+    use super::{diff, Change, ChangeKind, Criticality};
+
+    mod v1 {
+        use super::*;
+
+        #[derive(GraphQLEnum)]
+        pub enum Role {
+            Admin,
+            Guest,
+        }
+
+        #[derive(GraphQLInputObject)]
+        pub struct UserFilter {
+            pub name: Option<String>,
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct User {
+            pub name: Option<String>,
+            pub email: String,
+            pub role: Role,
+        }
+
+        pub struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn users(_filter: Option<UserFilter>, _limit: Option<i32>) -> Vec<User> {
+                vec![]
+            }
+            fn user(_id: i32) -> Option<User> {
+                None
+            }
+        }
+    }
+
+    mod v2 {
+        use super::*;
+
+        #[derive(GraphQLEnum)]
+        pub enum Role {
+            Admin,
+            Editor,
+        }
+
+        #[derive(GraphQLInputObject)]
+        pub struct UserFilter {
+            pub name: String,
+            pub active: Option<bool>,
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct User {
+            pub name: String,
+            pub role: Role,
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct Post {
+            pub title: String,
+        }
+
+        pub struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn users(_filter: Option<UserFilter>, _limit: i32) -> Vec<User> {
+                vec![]
+            }
+            fn user(_id: Option<i32>) -> Option<User> {
+                None
+            }
+            #[deprecated]
+            fn posts() -> Vec<Post> {
+                vec![]
+            }
+        }
+    }
+
+    mod v3 {
+        use super::*;
+
+        #[graphql_interface(for = [Article, Comment])]
+        pub trait Node {
+            fn id(&self) -> &str;
+        }
+
+        #[derive(GraphQLObject)]
+        #[graphql(impl = NodeValue)]
+        pub struct Article {
+            pub id: String,
+        }
+
+        #[graphql_interface]
+        impl Node for Article {
+            fn id(&self) -> &str {
+                &self.id
+            }
+        }
+
+        #[derive(GraphQLObject)]
+        #[graphql(impl = NodeValue)]
+        pub struct Comment {
+            pub id: String,
+        }
+
+        #[graphql_interface]
+        impl Node for Comment {
+            fn id(&self) -> &str {
+                &self.id
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(GraphQLUnion)]
+        pub enum SearchResult {
+            Article(Article),
+            Comment(Comment),
+        }
+
+        #[derive(GraphQLEnum)]
+        pub enum Shape {
+            Circle,
+            Square,
+        }
+
+        pub struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn node() -> Option<NodeValue> {
+                None
+            }
+            fn comments() -> Vec<Comment> {
+                vec![]
+            }
+            fn search() -> Vec<SearchResult> {
+                vec![]
+            }
+            fn shape() -> Option<Shape> {
+                None
+            }
+            fn count(_limit: Option<i32>) -> i32 {
+                0
+            }
+        }
+
+        pub struct Mutation;
+
+        #[graphql_object]
+        impl Mutation {
+            fn reset() -> bool {
+                true
+            }
+        }
+    }
+
+    mod v4 {
+        use super::*;
+
+        #[graphql_interface(for = Article)]
+        pub trait Node {
+            fn id(&self) -> &str;
+        }
+
+        #[derive(GraphQLObject)]
+        #[graphql(impl = NodeValue)]
+        pub struct Article {
+            pub id: String,
+        }
+
+        #[graphql_interface]
+        impl Node for Article {
+            fn id(&self) -> &str {
+                &self.id
+            }
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct Comment {
+            pub id: String,
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct Video {
+            pub url: String,
+        }
+
+        #[allow(dead_code)]
+        #[derive(GraphQLUnion)]
+        pub enum SearchResult {
+            Article(Article),
+            Video(Video),
+        }
+
+        #[derive(GraphQLObject)]
+        pub struct Shape {
+            pub sides: i32,
+        }
+
+        pub struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn node() -> Option<NodeValue> {
+                None
+            }
+            fn comments() -> Vec<Comment> {
+                vec![]
+            }
+            fn search() -> Vec<SearchResult> {
+                vec![]
+            }
+            fn shape() -> Option<Shape> {
+                None
+            }
+            fn count(#[graphql(default = 10)] _limit: i32) -> i32 {
+                0
+            }
+        }
+    }
+
+    fn changes() -> Vec<Change> {
+        let old = RootNode::new(
+            v1::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let new = RootNode::new(
+            v2::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        diff(&old.schema, &new.schema)
+    }
+
+    fn change(path: &str, kind: ChangeKind, criticality: Criticality) -> Change {
+        Change {
+            path: path.to_owned(),
+            subject: None,
+            kind,
+            criticality,
+        }
+    }
+
+    #[test]
+    fn detects_changes() {
+        assert_eq!(
+            changes(),
+            vec![
+                change("Post", ChangeKind::TypeAdded, Criticality::Safe),
+                change(
+                    "Query.users(limit:)",
+                    ChangeKind::ArgumentBecameRequired,
+                    Criticality::Breaking
+                ),
+                change(
+                    "Query.user(id:)",
+                    ChangeKind::ArgumentTypeChanged {
+                        old: "Int!".to_owned(),
+                        new: "Int".to_owned(),
+                    },
+                    Criticality::Safe,
+                ),
+                change("Query.posts", ChangeKind::FieldAdded, Criticality::Safe),
+                change(
+                    "Role.GUEST",
+                    ChangeKind::EnumValueRemoved,
+                    Criticality::Breaking
+                ),
+                change(
+                    "Role.EDITOR",
+                    ChangeKind::EnumValueAdded,
+                    Criticality::Dangerous
+                ),
+                change(
+                    "User.name",
+                    ChangeKind::FieldTypeChanged {
+                        old: "String".to_owned(),
+                        new: "String!".to_owned(),
+                    },
+                    Criticality::Safe,
+                ),
+                change(
+                    "User.email",
+                    ChangeKind::FieldRemoved,
+                    Criticality::Breaking
+                ),
+                change(
+                    "UserFilter.name",
+                    ChangeKind::InputFieldBecameRequired,
+                    Criticality::Breaking
+                ),
+                change(
+                    "UserFilter.active",
+                    ChangeKind::InputFieldAdded { required: false },
+                    Criticality::Safe,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn no_changes_between_same_schemas() {
+        let schema = RootNode::new(
+            v1::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        assert_eq!(diff(&schema.schema, &schema.schema), vec![]);
+    }
+
+    #[test]
+    fn displays_changes() {
+        let messages = changes()
+            .iter()
+            .filter(|c| c.is_breaking())
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "Argument `Query.users(limit:)` became required",
+                "Enum value `Role.GUEST` was removed",
+                "Field `User.email` was removed",
+                "Input field `UserFilter.name` became required",
+            ],
+        );
+    }
+
+    #[test]
+    fn detects_abstract_type_and_kind_changes() {
+        let old = RootNode::new(
+            v3::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let new = RootNode::new(
+            v4::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            diff(&old.schema, &new.schema),
+            vec![
+                change(
+                    "Comment",
+                    ChangeKind::InterfaceRemoved,
+                    Criticality::Breaking
+                )
+                .with_subject("Node"),
+                change(
+                    "Query.count(limit:)",
+                    ChangeKind::DefaultValueChanged {
+                        old: None,
+                        new: Some("10".to_owned()),
+                    },
+                    Criticality::Dangerous,
+                ),
+                change(
+                    "SearchResult",
+                    ChangeKind::UnionMemberRemoved,
+                    Criticality::Breaking
+                )
+                .with_subject("Comment"),
+                change(
+                    "SearchResult",
+                    ChangeKind::UnionMemberAdded,
+                    Criticality::Dangerous
+                )
+                .with_subject("Video"),
+                change(
+                    "Shape",
+                    ChangeKind::TypeKindChanged {
+                        old: TypeKind::Enum,
+                        new: TypeKind::Object,
+                    },
+                    Criticality::Breaking,
+                ),
+                change("Video", ChangeKind::TypeAdded, Criticality::Safe),
+            ],
+        );
+    }
+
+    #[test]
+    fn detects_root_type_changes() {
+        let without_mutation = RootNode::new(
+            v3::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let with_mutation = RootNode::new(v3::Query, v3::Mutation, EmptySubscription::<()>::new());
+        let root_changes = |old: &SchemaType<_>, new: &SchemaType<_>| {
+            diff(old, new)
+                .into_iter()
+                .filter(|c| matches!(c.kind, ChangeKind::RootTypeChanged { .. }))
+                .collect::<Vec<_>>()
+        };
+
+        let added = root_changes(&without_mutation.schema, &with_mutation.schema);
+        assert_eq!(
+            added,
+            vec![change(
+                "mutation",
+                ChangeKind::RootTypeChanged {
+                    old: None,
+                    new: Some("Mutation".to_owned()),
+                },
+                Criticality::Safe,
+            )],
+        );
+        assert_eq!(added[0].to_string(), "Schema now supports mutation");
+
+        let removed = root_changes(&with_mutation.schema, &without_mutation.schema);
+        assert_eq!(removed[0].criticality, Criticality::Breaking);
+        assert_eq!(removed[0].to_string(), "Schema no longer supports mutation");
+
+        let replaced = RootNode::new(
+            v3::Mutation,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let changed = diff(&without_mutation.schema, &replaced.schema);
+        assert_eq!(
+            changed[0],
+            change(
+                "query",
+                ChangeKind::RootTypeChanged {
+                    old: Some("Query".to_owned()),
+                    new: Some("Mutation".to_owned()),
+                },
+                Criticality::Breaking,
+            ),
+        );
+    }
+
+    #[test]
+    fn arguments_with_default_values_do_not_become_required() {
+        let old = RootNode::new(
+            v3::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let mut new = RootNode::new(
+            v4::Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        // Schemas built from introspection may declare non-null arguments with default values.
+        for ty in new.schema.types.values_mut() {
+            if let MetaType::Object(ObjectMeta { name, fields, .. }) = ty {
+                if name == "Query" {
+                    for field in fields.iter_mut().filter(|f| f.name == "count") {
+                        for arg in field.arguments.iter_mut().flatten() {
+                            arg.arg_type = Type::NonNullNamed("Int".into());
+                        }
+                    }
+                }
+            }
+        }
+
+        let changes = diff(&old.schema, &new.schema)
+            .into_iter()
+            .filter(|c| c.path == "Query.count(limit:)")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                change(
+                    "Query.count(limit:)",
+                    ChangeKind::ArgumentTypeChanged {
+                        old: "Int".to_owned(),
+                        new: "Int!".to_owned(),
+                    },
+                    Criticality::Dangerous,
+                ),
+                change(
+                    "Query.count(limit:)",
+                    ChangeKind::DefaultValueChanged {
+                        old: None,
+                        new: Some("10".to_owned()),
+                    },
+                    Criticality::Dangerous,
+                ),
+            ],
+        );
+    }
+}
@@ -3,10 +3,15 @@
 use std::{
     borrow::{Cow, ToOwned},
     fmt,
+    sync::Arc,
 };
 
+#[cfg(feature = "regex")]
+use regex::Regex;
+
 use crate::{
-    ast::{FromInputValue, InputValue, Type},
+    ast::{FromInputValue, InputValue, InputValueError, Type},
+    executor::CacheHint,
     parser::{ParseError, ScalarToken},
     schema::model::SchemaType,
     types::base::TypeKind,
@@ -46,8 +51,11 @@ pub struct ScalarMeta<'a, S> {
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> Result<(), InputValueError>,
     pub(crate) parse_fn: for<'b> fn(ScalarToken<'b>) -> Result<S, ParseError<'b>>,
+    pub(crate) accepts_composite: bool,
 }
 
 /// List type metadata
@@ -75,6 +83,10 @@ pub struct ObjectMeta<'a, S> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
+    pub cache_control: CacheHint,
+    #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
     #[doc(hidden)]
     pub interface_names: Vec<String>,
@@ -87,8 +99,11 @@ pub struct EnumMeta<'a, S> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub values: Vec<EnumValue>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
+    pub values: Vec<EnumValue<S>>,
+    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> Result<(), InputValueError>,
+    pub(crate) accepts_custom_values: bool,
 }
 
 /// Interface type metadata
@@ -99,17 +114,25 @@ pub struct InterfaceMeta<'a, S> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
+    pub cache_control: CacheHint,
+    #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
 }
 
 /// Union type metadata
 #[derive(Debug)]
-pub struct UnionMeta<'a> {
+pub struct UnionMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
+    pub cache_control: CacheHint,
+    #[doc(hidden)]
     pub of_type_names: Vec<String>,
 }
 
@@ -120,8 +143,10 @@ pub struct InputObjectMeta<'a, S> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
     pub input_fields: Vec<Argument<'a, S>>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> Result<(), InputValueError>,
 }
 
 /// A placeholder for not-yet-registered types
@@ -150,7 +175,7 @@ pub enum MetaType<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     Interface(InterfaceMeta<'a, S>),
     #[doc(hidden)]
-    Union(UnionMeta<'a>),
+    Union(UnionMeta<'a, S>),
     #[doc(hidden)]
     InputObject(InputObjectMeta<'a, S>),
     #[doc(hidden)]
@@ -170,6 +195,10 @@ pub struct Field<'a, S> {
     pub field_type: Type<'a>,
     #[doc(hidden)]
     pub deprecation_status: DeprecationStatus,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
+    pub cache_control: CacheHint,
 }
 
 impl<'a, S> Field<'a, S> {
@@ -191,6 +220,10 @@ pub struct Argument<'a, S> {
     pub arg_type: Type<'a>,
     #[doc(hidden)]
     pub default_value: Option<InputValue<S>>,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    #[doc(hidden)]
+    pub validators: Vec<InputValidator<S>>,
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -203,7 +236,7 @@ impl<'a, S> Argument<'a, S> {
 
 /// Metadata for a single value in an enum
 #[derive(Debug, Clone)]
-pub struct EnumValue {
+pub struct EnumValue<S> {
     /// The name of the enum value
     ///
     /// This is the string literal representation of the enum in responses.
@@ -215,6 +248,185 @@ pub struct EnumValue {
     pub description: Option<String>,
     /// Whether the field is deprecated or not, with an optional reason.
     pub deprecation_status: DeprecationStatus,
+    /// Directives applied to the enum value, other than `@deprecated`.
+    pub directives: Vec<AppliedDirective<S>>,
+}
+
+/// Metadata for a directive applied to a type system definition
+///
+/// These are the directives like `@cacheControl(maxAge: 60)` attached to objects, fields,
+/// arguments and the like. They are only emitted in the schema language and don't affect
+/// execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedDirective<S> {
+    /// The name of the directive, without the leading `@`.
+    pub name: String,
+    /// The arguments the directive is applied with, in the order they were provided.
+    pub arguments: Vec<(String, InputValue<S>)>,
+}
+
+/// Constraint on the values of an argument or an input object field
+///
+/// Validators are checked while validating both literals and variables, so violating values are
+/// rejected before the execution starts. Null values are never checked.
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum InputValidator<S> {
+    /// The number of characters in a string must lie within the inclusive bounds.
+    ///
+    /// Checked against each item of a list.
+    Length {
+        /// The minimum number of characters, if any.
+        min: Option<usize>,
+        /// The maximum number of characters, if any.
+        max: Option<usize>,
+    },
+    /// A number must lie within the inclusive bounds.
+    ///
+    /// Checked against each item of a list.
+    Range {
+        /// The minimum value, if any.
+        min: Option<f64>,
+        /// The maximum value, if any.
+        max: Option<f64>,
+    },
+    /// A string must match the regular expression.
+    ///
+    /// Checked against each item of a list.
+    ///
+    /// Requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    Pattern(Regex),
+    /// The number of items in a list must lie within the inclusive bounds.
+    ///
+    /// A single value is considered a list of one item.
+    Items {
+        /// The minimum number of items, if any.
+        min: Option<usize>,
+        /// The maximum number of items, if any.
+        max: Option<usize>,
+    },
+    /// A custom check, returning the reason of the violation, if any.
+    Custom(Arc<dyn Fn(&InputValue<S>) -> Result<(), String> + Send + Sync>),
+}
+
+impl<S> InputValidator<S> {
+    /// Construct a validator requiring strings to match the given regular expression
+    ///
+    /// Fails if the `pattern` is not a valid regular expression.
+    #[cfg(feature = "regex")]
+    pub fn pattern(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self::Pattern)
+    }
+
+    /// Construct a validator calling the given function with the value converted into `T`
+    ///
+    /// Values not convertible into `T` are reported as violations, unless they contain variables,
+    /// which are checked once their values are known.
+    pub fn custom<T, F>(f: F) -> Self
+    where
+        S: ScalarValue + 'static,
+        T: FromInputValue<S> + 'static,
+        F: Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(move |v| match T::from_input_value(v) {
+            Some(v) => f(&v),
+            None if !v.referenced_variables().is_empty() => Ok(()),
+            None => Err(format!(
+                "Expected a value of the validated type, found {}",
+                v
+            )),
+        }))
+    }
+}
+
+impl<S: ScalarValue> InputValidator<S> {
+    /// Check the given value, returning the reason of the violation, if any
+    ///
+    /// Variables are considered valid, as their values are checked separately.
+    pub fn validate(&self, value: &InputValue<S>) -> Result<(), String> {
+        match (self, value) {
+            (_, InputValue::Null) | (_, InputValue::Variable(_)) => Ok(()),
+            (Self::Items { min, max }, v) => {
+                let len = v.to_list_value().map_or(1, |l| l.len());
+                check_bounds(len, *min, *max, |b| format!("{} items", b))
+            }
+            (Self::Custom(f), v) => f(v),
+            (_, InputValue::List(items)) => items.iter().enumerate().try_for_each(|(i, item)| {
+                self.validate(&item.item)
+                    .map_err(|e| format!("In element #{}: {}", i, e))
+            }),
+            (Self::Length { min, max }, v) => v.as_string_value().map_or(Ok(()), |s| {
+                check_bounds(s.chars().count(), *min, *max, |b| {
+                    format!("{} characters", b)
+                })
+            }),
+            (Self::Range { min, max }, v) => v
+                .as_scalar()
+                .and_then(ScalarValue::as_float)
+                .map_or(Ok(()), |n| check_bounds(n, *min, *max, |b| b.to_string())),
+            #[cfg(feature = "regex")]
+            (Self::Pattern(re), v) => v.as_string_value().map_or(Ok(()), |s| {
+                if re.is_match(s) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        r#"Expected a string matching "{}", found "{}""#,
+                        re, s
+                    ))
+                }
+            }),
+        }
+    }
+}
+
+fn check_bounds<T>(
+    actual: T,
+    min: Option<T>,
+    max: Option<T>,
+    describe: impl Fn(T) -> String,
+) -> Result<(), String>
+where
+    T: Copy + PartialOrd + fmt::Display,
+{
+    match (min, max) {
+        (Some(min), _) if actual < min => Err(format!(
+            "Expected at least {}, found {}",
+            describe(min),
+            actual,
+        )),
+        (_, Some(max)) if actual > max => Err(format!(
+            "Expected at most {}, found {}",
+            describe(max),
+            actual,
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl<S> fmt::Debug for InputValidator<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length { min, max } => f
+                .debug_struct("Length")
+                .field("min", min)
+                .field("max", max)
+                .finish(),
+            Self::Range { min, max } => f
+                .debug_struct("Range")
+                .field("min", min)
+                .field("max", max)
+                .finish(),
+            #[cfg(feature = "regex")]
+            Self::Pattern(re) => f.debug_tuple("Pattern").field(re).finish(),
+            Self::Items { min, max } => f
+                .debug_struct("Items")
+                .field("min", min)
+                .field("max", max)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish(),
+        }
+    }
 }
 
 impl<'a, S> MetaType<'a, S> {
@@ -248,6 +460,33 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// Access the directives applied to the type, if applicable
+    ///
+    /// Lists, nullable wrappers, and placeholders don't have directives.
+    pub fn directives(&self) -> &[AppliedDirective<S>] {
+        match self {
+            MetaType::Scalar(ScalarMeta { directives, .. })
+            | MetaType::Object(ObjectMeta { directives, .. })
+            | MetaType::Enum(EnumMeta { directives, .. })
+            | MetaType::Interface(InterfaceMeta { directives, .. })
+            | MetaType::Union(UnionMeta { directives, .. })
+            | MetaType::InputObject(InputObjectMeta { directives, .. }) => directives,
+            _ => &[],
+        }
+    }
+
+    /// Access the cache control hint of the type, if applicable
+    ///
+    /// Only objects, interfaces and unions have cache control hints.
+    pub fn cache_control(&self) -> CacheHint {
+        match self {
+            MetaType::Object(ObjectMeta { cache_control, .. })
+            | MetaType::Interface(InterfaceMeta { cache_control, .. })
+            | MetaType::Union(UnionMeta { cache_control, .. }) => *cache_control,
+            _ => CacheHint::default(),
+        }
+    }
+
     /// Construct a `TypeKind` for a given type
     ///
     /// # Panics
@@ -320,10 +559,13 @@ impl<'a, S> MetaType<'a, S> {
     /// Access the input value parse function, if applicable
     ///
     /// An input value parse function is a function that takes an `InputValue` instance and returns
-    /// `true` if it can be parsed as the provided type.
+    /// `Ok` if it can be parsed as the provided type, or the reason why it cannot otherwise.
     ///
     /// Only scalars, enums, and input objects have parse functions.
-    pub fn input_value_parse_fn(&self) -> Option<for<'b> fn(&'b InputValue<S>) -> bool> {
+    #[allow(clippy::type_complexity)]
+    pub fn input_value_parse_fn(
+        &self,
+    ) -> Option<for<'b> fn(&'b InputValue<S>) -> Result<(), InputValueError>> {
         match *self {
             MetaType::Scalar(ScalarMeta {
                 ref try_parse_fn, ..
@@ -419,8 +661,10 @@ where
         ScalarMeta {
             name,
             description: None,
+            directives: vec![],
             try_parse_fn: try_parse_fn::<S, T>,
             parse_fn: <T as ParseScalarValue<S>>::from_str,
+            accepts_composite: false,
         }
     }
 
@@ -432,6 +676,23 @@ where
         self
     }
 
+    /// Apply a directive to the scalar type
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> ScalarMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Make the scalar type accept list and object values as input
+    ///
+    /// Otherwise, such values are rejected during validation, whatever the `FromInputValue`
+    /// implementation of the scalar does.
+    pub fn accepts_composite(mut self) -> ScalarMeta<'a, S> {
+        self.accepts_composite = true;
+        self
+    }
+
     /// Wrap the scalar in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Scalar(self)
@@ -474,6 +735,8 @@ where
         ObjectMeta {
             name,
             description: None,
+            directives: vec![],
+            cache_control: CacheHint::default(),
             fields: fields.to_vec(),
             interface_names: vec![],
         }
@@ -487,6 +750,22 @@ where
         self
     }
 
+    /// Apply a directive to the object
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> ObjectMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Set the cache control hint of all the fields returning the type
+    ///
+    /// If a hint was provided prior to calling this method, it will be overwritten.
+    pub fn cache_control(mut self, hint: CacheHint) -> ObjectMeta<'a, S> {
+        self.cache_control = hint;
+        self
+    }
+
     /// Set the interfaces this type implements
     ///
     /// If a list of interfaces already was provided prior to calling this method, they will be
@@ -510,15 +789,17 @@ where
     S: ScalarValue + 'a,
 {
     /// Build a new enum type with the specified name and possible values
-    pub fn new<T>(name: Cow<'a, str>, values: &[EnumValue]) -> Self
+    pub fn new<T>(name: Cow<'a, str>, values: &[EnumValue<S>]) -> Self
     where
         T: FromInputValue<S>,
     {
         EnumMeta {
             name,
             description: None,
+            directives: vec![],
             values: values.to_vec(),
             try_parse_fn: try_parse_fn::<S, T>,
+            accepts_custom_values: false,
         }
     }
 
@@ -530,6 +811,22 @@ where
         self
     }
 
+    /// Apply a directive to the type
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> EnumMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Make the enum type accept any input its `FromInputValue` implementation accepts
+    ///
+    /// Otherwise, values not named by any of the enum values are rejected during validation.
+    pub fn accepts_custom_values(mut self) -> EnumMeta<'a, S> {
+        self.accepts_custom_values = true;
+        self
+    }
+
     /// Wrap this enum type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Enum(self)
@@ -545,6 +842,8 @@ where
         InterfaceMeta {
             name,
             description: None,
+            directives: vec![],
+            cache_control: CacheHint::default(),
             fields: fields.to_vec(),
         }
     }
@@ -557,18 +856,36 @@ where
         self
     }
 
+    /// Apply a directive to the type
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> InterfaceMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Set the cache control hint of all the fields returning the type
+    ///
+    /// If a hint was provided prior to calling this method, it will be overwritten.
+    pub fn cache_control(mut self, hint: CacheHint) -> InterfaceMeta<'a, S> {
+        self.cache_control = hint;
+        self
+    }
+
     /// Wrap this interface type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Interface(self)
     }
 }
 
-impl<'a> UnionMeta<'a> {
+impl<'a, S> UnionMeta<'a, S> {
     /// Build a new union type with the specified name and possible types
-    pub fn new(name: Cow<'a, str>, of_types: &[Type]) -> UnionMeta<'a> {
+    pub fn new(name: Cow<'a, str>, of_types: &[Type]) -> UnionMeta<'a, S> {
         UnionMeta {
             name,
             description: None,
+            directives: vec![],
+            cache_control: CacheHint::default(),
             of_type_names: of_types
                 .iter()
                 .map(|t| t.innermost_name().to_owned())
@@ -579,13 +896,29 @@ impl<'a> UnionMeta<'a> {
     /// Set the description of the type
     ///
     /// If a description was provided prior to calling this method, it will be overwritten.
-    pub fn description(mut self, description: &str) -> UnionMeta<'a> {
+    pub fn description(mut self, description: &str) -> UnionMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
 
+    /// Apply a directive to the type
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> UnionMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Set the cache control hint of all the fields returning the type
+    ///
+    /// If a hint was provided prior to calling this method, it will be overwritten.
+    pub fn cache_control(mut self, hint: CacheHint) -> UnionMeta<'a, S> {
+        self.cache_control = hint;
+        self
+    }
+
     /// Wrap this union type in a generic meta type
-    pub fn into_meta<S>(self) -> MetaType<'a, S> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Union(self)
     }
 }
@@ -602,6 +935,7 @@ where
         InputObjectMeta {
             name,
             description: None,
+            directives: vec![],
             input_fields: input_fields.to_vec(),
             try_parse_fn: try_parse_fn::<S, T>,
         }
@@ -615,6 +949,14 @@ where
         self
     }
 
+    /// Apply a directive to the type
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> InputObjectMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::InputObject(self)
@@ -653,6 +995,22 @@ impl<'a, S> Field<'a, S> {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Apply a directive to the field
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Set the cache control hint of the field, taking precedence over the one of its type
+    ///
+    /// This overwrites the hint if any was previously set.
+    pub fn cache_control(mut self, hint: CacheHint) -> Self {
+        self.cache_control = hint;
+        self
+    }
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -663,6 +1021,8 @@ impl<'a, S> Argument<'a, S> {
             description: None,
             arg_type,
             default_value: None,
+            directives: vec![],
+            validators: vec![],
         }
     }
 
@@ -681,22 +1041,39 @@ impl<'a, S> Argument<'a, S> {
         self.default_value = Some(default_value);
         self
     }
+
+    /// Apply a directive to the argument
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Add a validator constraining the values of the argument
+    ///
+    /// Validators are checked in the order they were added.
+    pub fn validator(mut self, validator: InputValidator<S>) -> Self {
+        self.validators.push(validator);
+        self
+    }
 }
 
-impl EnumValue {
+impl<S> EnumValue<S> {
     /// Construct a new enum value with the provided name
-    pub fn new(name: &str) -> EnumValue {
+    pub fn new(name: &str) -> Self {
         EnumValue {
             name: name.to_owned(),
             description: None,
             deprecation_status: DeprecationStatus::Current,
+            directives: vec![],
         }
     }
 
     /// Set the description of the enum value
     ///
     /// This overwrites the description if any was previously set.
-    pub fn description(mut self, description: &str) -> EnumValue {
+    pub fn description(mut self, description: &str) -> Self {
         self.description = Some(description.to_owned());
         self
     }
@@ -708,6 +1085,32 @@ impl EnumValue {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Apply a directive to the enum value
+    ///
+    /// Directives are emitted in the order they were applied.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl<S> AppliedDirective<S> {
+    /// Construct a new applied directive with the provided name and no arguments
+    pub fn new(name: &str) -> Self {
+        AppliedDirective {
+            name: name.to_owned(),
+            arguments: vec![],
+        }
+    }
+
+    /// Add an argument to the applied directive
+    ///
+    /// Arguments are emitted in the order they were added.
+    pub fn argument(mut self, name: &str, value: InputValue<S>) -> Self {
+        self.arguments.push((name.to_owned(), value));
+        self
+    }
 }
 
 impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
@@ -715,6 +1118,7 @@ impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
         fmt.debug_struct("ScalarMeta")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("directives", &self.directives)
             .finish()
     }
 }
@@ -724,6 +1128,7 @@ impl<'a, S: fmt::Debug> fmt::Debug for EnumMeta<'a, S> {
         fmt.debug_struct("EnumMeta")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("directives", &self.directives)
             .field("values", &self.values)
             .finish()
     }
@@ -734,14 +1139,15 @@ impl<'a, S: fmt::Debug> fmt::Debug for InputObjectMeta<'a, S> {
         fmt.debug_struct("InputObjectMeta")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("directives", &self.directives)
             .field("input_fields", &self.input_fields)
             .finish()
     }
 }
 
-fn try_parse_fn<S, T>(v: &InputValue<S>) -> bool
+fn try_parse_fn<S, T>(v: &InputValue<S>) -> Result<(), InputValueError>
 where
     T: FromInputValue<S>,
 {
-    <T as FromInputValue<S>>::from_input_value(v).is_some()
+    <T as FromInputValue<S>>::try_from_input_value(v).map(drop)
 }
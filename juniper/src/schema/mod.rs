@@ -1,5 +1,6 @@
 #![allow(clippy::module_inception)]
 
+pub mod diff;
 pub mod meta;
 pub mod model;
 pub mod schema;
@@ -1,15 +1,19 @@
-use std::fmt;
+use std::{fmt, mem};
 
 use fnv::FnvHashMap;
 #[cfg(feature = "graphql-parser-integration")]
 use graphql_parser::schema::Document;
+use once_cell::sync::OnceCell;
 
 use crate::{
     ast::Type,
-    executor::{Context, Registry},
-    schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta},
-    types::{base::GraphQLType, name::Name},
-    value::{DefaultScalarValue, ScalarValue},
+    executor::{Context, ErrorFormatter, ExecutionError, Registry, ValuesStream},
+    schema::meta::{
+        Argument, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta,
+        UnionMeta,
+    },
+    types::{base::GraphQLType, merged::SchemaConflict, name::Name},
+    value::{DefaultScalarValue, Object, ScalarValue, Value},
     GraphQLEnum,
 };
 
@@ -44,6 +48,8 @@ pub struct RootNode<
     pub subscription_info: SubscriptionT::TypeInfo,
     #[doc(hidden)]
     pub schema: SchemaType<'a, S>,
+    #[doc(hidden)]
+    pub error_formatter: Option<Box<dyn ErrorFormatter<S> + 'a>>,
 }
 
 /// Metadata for a schema
@@ -53,7 +59,9 @@ pub struct SchemaType<'a, S> {
     pub(crate) query_type_name: String,
     pub(crate) mutation_type_name: Option<String>,
     pub(crate) subscription_type_name: Option<String>,
-    directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    /// Whether any argument or input object field has validators, computed on first use.
+    pub(crate) has_input_validators: OnceCell<bool>,
 }
 
 impl<'a, S> Context for SchemaType<'a, S> {}
@@ -65,16 +73,25 @@ pub enum TypeType<'a, S: 'a> {
     List(Box<TypeType<'a, S>>, Option<usize>),
 }
 
+/// Definition of a directive in a schema
 #[derive(Debug)]
 pub struct DirectiveType<'a, S> {
+    /// The name of the directive, without the leading `@`.
     pub name: String,
+    /// The optional description of the directive.
     pub description: Option<String>,
+    /// The locations this directive may be used at.
     pub locations: Vec<DirectiveLocation>,
+    /// The arguments this directive accepts.
     pub arguments: Vec<Argument<'a, S>>,
+    /// Whether this directive may be used more than once at a single location.
+    pub is_repeatable: bool,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, GraphQLEnum)]
+// Doc comments here would end up as the `__DirectiveLocation` description.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, GraphQLEnum)]
 #[graphql(name = "__DirectiveLocation", internal)]
+#[allow(missing_docs)]
 pub enum DirectiveLocation {
     Query,
     Mutation,
@@ -86,6 +103,22 @@ pub enum DirectiveLocation {
     FragmentSpread,
     #[graphql(name = "INLINE_FRAGMENT")]
     InlineFragment,
+    Schema,
+    Scalar,
+    Object,
+    #[graphql(name = "FIELD_DEFINITION")]
+    FieldDefinition,
+    #[graphql(name = "ARGUMENT_DEFINITION")]
+    ArgumentDefinition,
+    Interface,
+    Union,
+    Enum,
+    #[graphql(name = "ENUM_VALUE")]
+    EnumValue,
+    #[graphql(name = "INPUT_OBJECT")]
+    InputObject,
+    #[graphql(name = "INPUT_FIELD_DEFINITION")]
+    InputFieldDefinition,
 }
 
 impl<'a, QueryT, MutationT, SubscriptionT>
@@ -100,6 +133,21 @@ where
     pub fn new(query: QueryT, mutation: MutationT, subscription: SubscriptionT) -> Self {
         Self::new_with_info(query, mutation, subscription, (), (), ())
     }
+
+    /// Constructs a new [`RootNode`] from `query`, `mutation` and `subscription` nodes,
+    /// parametrizing it with a [`DefaultScalarValue`].
+    ///
+    /// Unlike [`RootNode::new`], which panics on them, returns the [`SchemaConflict`]s between
+    /// the merged parts of the schema (see [`MergedObject`]).
+    ///
+    /// [`MergedObject`]: crate::MergedObject
+    pub fn try_new(
+        query: QueryT,
+        mutation: MutationT,
+        subscription: SubscriptionT,
+    ) -> Result<Self, Vec<SchemaConflict>> {
+        Self::try_new_with_info(query, mutation, subscription, (), (), ())
+    }
 }
 
 impl<'a, QueryT, MutationT, SubscriptionT, S> RootNode<'a, QueryT, MutationT, SubscriptionT, S>
@@ -150,7 +198,144 @@ where
             query_info,
             mutation_info,
             subscription_info,
+            error_formatter: None,
+        }
+    }
+
+    /// Construct a new root node from query and mutation nodes,
+    /// while also providing type info objects for the query and
+    /// mutation types.
+    ///
+    /// Unlike [`RootNode::new_with_info`], which panics on them, returns the
+    /// [`SchemaConflict`]s between the merged parts of the schema (see [`MergedObject`]).
+    ///
+    /// [`MergedObject`]: crate::MergedObject
+    pub fn try_new_with_info(
+        query_obj: QueryT,
+        mutation_obj: MutationT,
+        subscription_obj: SubscriptionT,
+        query_info: QueryT::TypeInfo,
+        mutation_info: MutationT::TypeInfo,
+        subscription_info: SubscriptionT::TypeInfo,
+    ) -> Result<Self, Vec<SchemaConflict>> {
+        Ok(RootNode {
+            query_type: query_obj,
+            mutation_type: mutation_obj,
+            subscription_type: subscription_obj,
+            schema: SchemaType::try_new::<QueryT, MutationT, SubscriptionT>(
+                &query_info,
+                &mutation_info,
+                &subscription_info,
+            )?,
+            query_info,
+            mutation_info,
+            subscription_info,
+            error_formatter: None,
+        })
+    }
+
+    /// Registers a custom directive definition in this schema.
+    ///
+    /// The provided function receives the [`Registry`] of this schema, so the types of the
+    /// directive's arguments can be registered along with it.
+    ///
+    /// # Panics
+    ///
+    /// If a directive with the same name is already registered, including the built-in `@skip`,
+    /// `@include` and `@deprecated` ones, or if the types of the directive's arguments are left
+    /// unresolved.
+    pub fn with_directive<F>(self, directive: F) -> Self
+    where
+        F: FnOnce(&mut Registry<'a, S>) -> DirectiveType<'a, S>,
+    {
+        self.try_with_directive(directive)
+            .unwrap_or_else(|conflict| panic!("{}", conflict))
+    }
+
+    /// Registers a custom directive definition in this schema.
+    ///
+    /// Unlike [`RootNode::with_directive`], which panics on it, returns a
+    /// [`SchemaConflict::DuplicateDirective`] if a directive with the same name is already
+    /// registered.
+    ///
+    /// # Panics
+    ///
+    /// If the types of the directive's arguments are left unresolved.
+    pub fn try_with_directive<F>(mut self, directive: F) -> Result<Self, SchemaConflict>
+    where
+        F: FnOnce(&mut Registry<'a, S>) -> DirectiveType<'a, S>,
+    {
+        let mut registry = Registry::new(mem::take(&mut self.schema.types));
+        let directive = directive(&mut registry);
+        if directive.name == "deprecated" || self.schema.directives.contains_key(&directive.name) {
+            return Err(SchemaConflict::DuplicateDirective {
+                directive_name: directive.name,
+            });
         }
+        SchemaType::ensure_resolved(&registry.types);
+        self.schema.types = registry.types;
+        self.schema.add_directive(directive);
+        Ok(self)
+    }
+
+    /// Registers the given type in this schema, even if it's not reachable from its root types,
+    /// so clients can still discover it through introspection.
+    ///
+    /// This is useful for the enums of error codes generated by `#[derive(GraphQLError)]`, which
+    /// are returned in the `extensions` of errors rather than as field values.
+    pub fn with_type<T>(mut self, info: &T::TypeInfo) -> Self
+    where
+        T: GraphQLType<S> + ?Sized,
+    {
+        let mut registry = Registry::new(mem::take(&mut self.schema.types));
+        registry.get_type::<T>(info);
+        self.schema.types = registry.types;
+        self
+    }
+
+    /// Registers the [`ErrorFormatter`] applied to the errors of all the operations executed
+    /// in this schema, replacing the previously registered one, if any.
+    ///
+    /// Use an [`ErrorMask`] to hide the details of unexpected errors from clients.
+    ///
+    /// [`ErrorMask`]: crate::ErrorMask
+    pub fn with_error_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: ErrorFormatter<S> + 'a,
+    {
+        self.error_formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Formats the given errors with the registered [`ErrorFormatter`], if any.
+    pub(crate) fn format_errors(&self, errors: Vec<ExecutionError<S>>) -> Vec<ExecutionError<S>> {
+        match &self.error_formatter {
+            Some(formatter) => errors
+                .into_iter()
+                .map(|e| formatter.format_error(e))
+                .collect(),
+            None => errors,
+        }
+    }
+
+    /// Formats the errors yielded by the given subscription streams with the registered
+    /// [`ErrorFormatter`], if any.
+    pub(crate) fn format_stream_errors<'r>(
+        &'r self,
+        value: Value<ValuesStream<'r, S>>,
+    ) -> Value<ValuesStream<'r, S>>
+    where
+        S: Send,
+    {
+        let formatter = match &self.error_formatter {
+            Some(formatter) => &**formatter,
+            None => return value,
+        };
+        map_streams(value, &mut |stream| {
+            Box::pin(futures::StreamExt::map(stream, move |r| {
+                r.map_err(|e| formatter.format_error(e))
+            }))
+        })
     }
 
     #[cfg(feature = "schema-language")]
@@ -170,13 +355,61 @@ where
     }
 }
 
+/// Maps all the streams held by the given [`Value`].
+fn map_streams<'r, S>(
+    value: Value<ValuesStream<'r, S>>,
+    f: &mut impl FnMut(ValuesStream<'r, S>) -> ValuesStream<'r, S>,
+) -> Value<ValuesStream<'r, S>> {
+    match value {
+        Value::Null => Value::Null,
+        Value::Scalar(stream) => Value::Scalar(f(stream)),
+        Value::List(items) => Value::List(items.into_iter().map(|v| map_streams(v, f)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(name, v)| (name, map_streams(v, f)))
+                .collect::<Object<_>>(),
+        ),
+    }
+}
+
 impl<'a, S> SchemaType<'a, S> {
     /// Create a new schema.
+    ///
+    /// # Panics
+    ///
+    /// If there are [`SchemaConflict`]s between the merged parts of the schema.
     pub fn new<QueryT, MutationT, SubscriptionT>(
         query_info: &QueryT::TypeInfo,
         mutation_info: &MutationT::TypeInfo,
         subscription_info: &SubscriptionT::TypeInfo,
     ) -> Self
+    where
+        S: ScalarValue + 'a,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S>,
+        SubscriptionT: GraphQLType<S>,
+    {
+        Self::try_new::<QueryT, MutationT, SubscriptionT>(
+            query_info,
+            mutation_info,
+            subscription_info,
+        )
+        .unwrap_or_else(|conflicts| {
+            let conflicts = conflicts
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            panic!("Schema has conflicts: {}", conflicts.join("; "))
+        })
+    }
+
+    /// Create a new schema, returning the [`SchemaConflict`]s between its merged parts, if any.
+    pub fn try_new<QueryT, MutationT, SubscriptionT>(
+        query_info: &QueryT::TypeInfo,
+        mutation_info: &MutationT::TypeInfo,
+        subscription_info: &SubscriptionT::TypeInfo,
+    ) -> Result<Self, Vec<SchemaConflict>>
     where
         S: ScalarValue + 'a,
         QueryT: GraphQLType<S>,
@@ -212,12 +445,7 @@ impl<'a, S> SchemaType<'a, S> {
             DirectiveType::new_include(&mut registry),
         );
 
-        let mut meta_fields = vec![
-            registry.field::<SchemaType<S>>("__schema", &()),
-            registry
-                .field::<TypeType<S>>("__type", &())
-                .argument(registry.arg::<String>("name", &())),
-        ];
+        let mut meta_fields = Self::meta_fields(&mut registry);
 
         if let Some(root_type) = registry.types.get_mut(&query_type_name) {
             if let MetaType::Object(ObjectMeta { ref mut fields, .. }) = *root_type {
@@ -229,12 +457,12 @@ impl<'a, S> SchemaType<'a, S> {
             panic!("Root type not found");
         }
 
-        for meta_type in registry.types.values() {
-            if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
-                panic!("Type {:?} is still a placeholder type", of_type);
-            }
+        if !registry.conflicts().is_empty() {
+            return Err(registry.conflicts().to_vec());
         }
-        SchemaType {
+
+        Self::ensure_resolved(&registry.types);
+        Ok(SchemaType {
             types: registry.types,
             query_type_name,
             mutation_type_name: if &mutation_type_name != "_EmptyMutation" {
@@ -248,12 +476,60 @@ impl<'a, S> SchemaType<'a, S> {
                 None
             },
             directives,
+            has_input_validators: OnceCell::new(),
+        })
+    }
+
+    /// Panics if any of the given types is still a placeholder type.
+    fn ensure_resolved(types: &FnvHashMap<Name, MetaType<'a, S>>) {
+        for meta_type in types.values() {
+            if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
+                panic!("Type {:?} is still a placeholder type", of_type);
+            }
         }
     }
 
+    /// Introspection fields added to the query type.
+    pub(crate) fn meta_fields(registry: &mut Registry<'a, S>) -> Vec<Field<'a, S>>
+    where
+        S: ScalarValue + 'a,
+    {
+        vec![
+            registry.field::<SchemaType<S>>("__schema", &()),
+            registry
+                .field::<TypeType<S>>("__type", &())
+                .argument(registry.arg::<String>("name", &())),
+        ]
+    }
+
     /// Add a directive like `skip` or `include`.
     pub fn add_directive(&mut self, directive: DirectiveType<'a, S>) {
         self.directives.insert(directive.name.clone(), directive);
+        self.has_input_validators = OnceCell::new();
+    }
+
+    /// Indicates whether any argument or input object field of this schema has
+    /// [`InputValidator`]s.
+    ///
+    /// [`InputValidator`]: crate::meta::InputValidator
+    pub(crate) fn has_input_validators(&self) -> bool {
+        *self.has_input_validators.get_or_init(|| {
+            let any_validated =
+                |args: &[Argument<S>]| args.iter().any(|a| !a.validators.is_empty());
+            self.directives
+                .values()
+                .any(|d| any_validated(&d.arguments))
+                || self.types.values().any(|t| match t {
+                    MetaType::Object(ObjectMeta { fields, .. })
+                    | MetaType::Interface(InterfaceMeta { fields, .. }) => fields
+                        .iter()
+                        .any(|f| f.arguments.as_deref().is_some_and(any_validated)),
+                    MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                        any_validated(input_fields)
+                    }
+                    _ => false,
+                })
+        })
     }
 
     /// Get a type by name.
@@ -485,6 +761,7 @@ impl<'a, S> DirectiveType<'a, S>
 where
     S: ScalarValue + 'a,
 {
+    /// Build a new directive definition with the provided name, locations and arguments
     pub fn new(
         name: &str,
         locations: &[DirectiveLocation],
@@ -495,6 +772,7 @@ where
             description: None,
             locations: locations.to_vec(),
             arguments: arguments.to_vec(),
+            is_repeatable: false,
         }
     }
 
@@ -528,10 +806,24 @@ where
         )
     }
 
+    /// Set the description of the directive
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
     pub fn description(mut self, description: &str) -> DirectiveType<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
+
+    /// Allow the directive to be used more than once at a single location
+    pub fn repeatable(mut self) -> DirectiveType<'a, S> {
+        self.is_repeatable = true;
+        self
+    }
+
+    /// Returns true if the directive is built-in to GraphQL.
+    pub fn is_builtin(&self) -> bool {
+        self.name == "skip" || self.name == "include"
+    }
 }
 
 impl fmt::Display for DirectiveLocation {
@@ -544,6 +836,17 @@ impl fmt::Display for DirectiveLocation {
             DirectiveLocation::FragmentDefinition => "fragment definition",
             DirectiveLocation::FragmentSpread => "fragment spread",
             DirectiveLocation::InlineFragment => "inline fragment",
+            DirectiveLocation::Schema => "schema",
+            DirectiveLocation::Scalar => "scalar",
+            DirectiveLocation::Object => "object",
+            DirectiveLocation::FieldDefinition => "field definition",
+            DirectiveLocation::ArgumentDefinition => "argument definition",
+            DirectiveLocation::Interface => "interface",
+            DirectiveLocation::Union => "union",
+            DirectiveLocation::Enum => "enum",
+            DirectiveLocation::EnumValue => "enum value",
+            DirectiveLocation::InputObject => "input object",
+            DirectiveLocation::InputFieldDefinition => "input field definition",
         })
     }
 }
@@ -601,8 +904,8 @@ mod test {
     #[cfg(feature = "schema-language")]
     mod schema_language {
         use crate::{
-            graphql_object, EmptyMutation, EmptySubscription, GraphQLEnum, GraphQLInputObject,
-            GraphQLObject, GraphQLUnion, RootNode,
+            graphql_object, DirectiveLocation, DirectiveType, EmptyMutation, EmptySubscription,
+            GraphQLEnum, GraphQLInputObject, GraphQLObject, GraphQLUnion, RootNode,
         };
 
         #[test]
@@ -703,5 +1006,150 @@ mod test {
             .unwrap();
             assert_eq!(format!("{}", ast), schema.as_schema_language());
         }
+
+        #[test]
+        fn directives() {
+            #[derive(GraphQLEnum)]
+            enum Fruit {
+                #[graphql(directive = tag(name = "red"))]
+                Apple,
+                Orange,
+            }
+            #[derive(GraphQLInputObject)]
+            #[graphql(directive = tag(name = "input"))]
+            struct Coordinate {
+                #[graphql(directive = tag(name = "lat"))]
+                latitude: f64,
+            }
+            struct Query;
+            #[graphql_object(directive = key(fields = "id"))]
+            impl Query {
+                fn id() -> i32 {
+                    1
+                }
+                #[graphql(directive = tag(name = "a"), directive = tag(name = "b"))]
+                #[deprecated]
+                fn fruit(
+                    #[graphql(directive = tag(name = "arg"))] _at: Option<Coordinate>,
+                ) -> Fruit {
+                    Fruit::Apple
+                }
+            }
+
+            let schema = RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .with_directive(|registry| {
+                DirectiveType::new(
+                    "tag",
+                    &[
+                        DirectiveLocation::FieldDefinition,
+                        DirectiveLocation::ArgumentDefinition,
+                        DirectiveLocation::EnumValue,
+                        DirectiveLocation::InputObject,
+                        DirectiveLocation::InputFieldDefinition,
+                    ],
+                    &[registry.arg::<String>("name", &())],
+                )
+                .repeatable()
+            })
+            .with_directive(|registry| {
+                DirectiveType::new(
+                    "key",
+                    &[DirectiveLocation::Object],
+                    &[registry.arg::<String>("fields", &())],
+                )
+                .description("Marks the key fields of an object.")
+            });
+            let ast = graphql_parser::parse_schema::<&str>(
+                r#"
+                enum Fruit {
+                    APPLE @tag(name: "red")
+                    ORANGE
+                }
+                type Query @key(fields: "id") {
+                  id: Int!
+                  fruit(at: Coordinate @tag(name: "arg")): Fruit! @deprecated @tag(name: "a") @tag(name: "b")
+                }
+                input Coordinate @tag(name: "input") {
+                    latitude: Float! @tag(name: "lat")
+                }
+                "Marks the key fields of an object."
+                directive @key(fields: String!) on OBJECT
+                directive @tag(name: String!) repeatable on FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+                schema {
+                  query: Query
+                }
+            "#,
+            )
+            .unwrap();
+            assert_eq!(format!("{}", ast), schema.as_schema_language());
+        }
+    }
+
+    mod with_directive {
+        use crate::{
+            graphql_object, DirectiveLocation, DirectiveType, EmptyMutation, EmptySubscription,
+            RootNode, SchemaConflict,
+        };
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn id() -> i32 {
+                1
+            }
+        }
+
+        fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+            RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+        }
+
+        #[test]
+        #[should_panic(expected = "Directive @include is already registered")]
+        fn rejects_built_in_names() {
+            schema().with_directive(|_| {
+                DirectiveType::new("include", &[DirectiveLocation::Field], &[])
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Directive @deprecated is already registered")]
+        fn rejects_deprecated() {
+            schema().with_directive(|_| {
+                DirectiveType::new("deprecated", &[DirectiveLocation::FieldDefinition], &[])
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "Directive @key is already registered")]
+        fn rejects_duplicate_names() {
+            schema()
+                .with_directive(|_| DirectiveType::new("key", &[DirectiveLocation::Object], &[]))
+                .with_directive(|_| DirectiveType::new("key", &[DirectiveLocation::Object], &[]));
+        }
+
+        #[test]
+        fn returns_conflict_on_duplicate_names() {
+            let schema = schema()
+                .try_with_directive(|_| {
+                    DirectiveType::new("key", &[DirectiveLocation::Object], &[])
+                })
+                .expect("no conflicts");
+
+            assert!(matches!(
+                schema.try_with_directive(|_| {
+                    DirectiveType::new("key", &[DirectiveLocation::Object], &[])
+                }),
+                Err(SchemaConflict::DuplicateDirective { directive_name }) if directive_name == "key",
+            ));
+        }
     }
 }
@@ -285,7 +285,10 @@ impl<'a, S: ScalarValue + 'a> TypeType<'a, S> {
         }
     }
 
-    fn enum_values(&self, #[graphql(default)] include_deprecated: bool) -> Option<Vec<&EnumValue>> {
+    fn enum_values(
+        &self,
+        #[graphql(default)] include_deprecated: bool,
+    ) -> Option<Vec<&EnumValue<S>>> {
         match self {
             TypeType::Concrete(&MetaType::Enum(EnumMeta { ref values, .. })) => Some(
                 values
@@ -361,8 +364,8 @@ impl<'a, S: ScalarValue + 'a> Argument<'a, S> {
     }
 }
 
-#[graphql_object(name = "__EnumValue", internal)]
-impl EnumValue {
+#[graphql_object(name = "__EnumValue", scalar = S, internal)]
+impl<S: ScalarValue> EnumValue<S> {
     fn name(&self) -> &str {
         &self.name
     }
@@ -405,6 +408,10 @@ impl<'a, S: ScalarValue + 'a> DirectiveType<'a, S> {
         &self.arguments
     }
 
+    fn is_repeatable(&self) -> bool {
+        self.is_repeatable
+    }
+
     // Included for compatibility with the introspection query in GraphQL.js
     #[graphql(deprecated = "Use the locations array instead")]
     fn on_operation(&self) -> bool {
@@ -3,10 +3,12 @@ use std::{boxed::Box, collections::BTreeMap};
 use graphql_parser::{
     query::{Directive as ExternalDirective, Number as ExternalNumber, Type as ExternalType},
     schema::{
-        Definition, Document, EnumType as ExternalEnum, EnumValue as ExternalEnumValue,
-        Field as ExternalField, InputObjectType as ExternalInputObjectType,
-        InputValue as ExternalInputValue, InterfaceType as ExternalInterfaceType,
-        ObjectType as ExternalObjectType, ScalarType as ExternalScalarType, SchemaDefinition, Text,
+        Definition, DirectiveDefinition as ExternalDirectiveDefinition,
+        DirectiveLocation as ExternalDirectiveLocation, Document, EnumType as ExternalEnum,
+        EnumValue as ExternalEnumValue, Field as ExternalField,
+        InputObjectType as ExternalInputObjectType, InputValue as ExternalInputValue,
+        InterfaceType as ExternalInterfaceType, ObjectType as ExternalObjectType,
+        ScalarType as ExternalScalarType, SchemaDefinition, Text,
         TypeDefinition as ExternalTypeDefinition, UnionType as ExternalUnionType,
         Value as ExternalValue,
     },
@@ -16,8 +18,8 @@ use graphql_parser::{
 use crate::{
     ast::{InputValue, Type},
     schema::{
-        meta::{Argument, DeprecationStatus, EnumValue, Field, MetaType},
-        model::SchemaType,
+        meta::{AppliedDirective, Argument, DeprecationStatus, EnumValue, Field, MetaType},
+        model::{DirectiveLocation, DirectiveType, SchemaType},
         translate::SchemaTranslator,
     },
     value::ScalarValue,
@@ -56,6 +58,20 @@ where
             .collect();
         doc.definitions.append(&mut types);
 
+        // Translate custom directive defs, sorted to keep the output stable.
+        let mut directives = input
+            .directive_list()
+            .into_iter()
+            .filter(|d| !d.is_builtin())
+            .collect::<Vec<_>>();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+        doc.definitions.extend(
+            directives
+                .into_iter()
+                .map(GraphQLParserTranslator::translate_directive_type)
+                .map(Definition::DirectiveDefinition),
+        );
+
         doc.definitions
             .push(Definition::SchemaDefinition(SchemaDefinition {
                 position: Pos::default(),
@@ -90,7 +106,36 @@ impl GraphQLParserTranslator {
                 .default_value
                 .as_ref()
                 .map(|x| GraphQLParserTranslator::translate_value(x)),
-            directives: vec![],
+            directives: input
+                .directives
+                .iter()
+                .map(translate_applied_directive)
+                .collect(),
+        }
+    }
+
+    fn translate_directive_type<'a, S, T>(
+        input: &'a DirectiveType<S>,
+    ) -> ExternalDirectiveDefinition<'a, T>
+    where
+        S: ScalarValue,
+        T: Text<'a>,
+    {
+        ExternalDirectiveDefinition {
+            position: Pos::default(),
+            description: input.description.clone(),
+            name: From::from(input.name.as_str()),
+            arguments: input
+                .arguments
+                .iter()
+                .map(GraphQLParserTranslator::translate_argument)
+                .collect(),
+            repeatable: input.is_repeatable,
+            locations: input
+                .locations
+                .iter()
+                .map(translate_directive_location)
+                .collect(),
         }
     }
 
@@ -162,13 +207,21 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(From::from),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(translate_applied_directive)
+                    .collect(),
             }),
             MetaType::Enum(x) => ExternalTypeDefinition::Enum(ExternalEnum {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(translate_applied_directive)
+                    .collect(),
                 values: x
                     .values
                     .iter()
@@ -179,7 +232,11 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(translate_applied_directive)
+                    .collect(),
                 types: x
                     .of_type_names
                     .iter()
@@ -190,20 +247,29 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(translate_applied_directive)
+                    .collect(),
                 fields: x
                     .fields
                     .iter()
                     .filter(|x| !x.is_builtin())
                     .map(GraphQLParserTranslator::translate_field)
                     .collect(),
+                implements_interfaces: vec![],
             }),
             MetaType::InputObject(x) => {
                 ExternalTypeDefinition::InputObject(ExternalInputObjectType {
                     position: Pos::default(),
                     description: x.description.as_ref().map(|s| From::from(s.as_str())),
                     name: From::from(x.name.as_ref()),
-                    directives: vec![],
+                    directives: x
+                        .directives
+                        .iter()
+                        .map(translate_applied_directive)
+                        .collect(),
                     fields: x
                         .input_fields
                         .iter()
@@ -216,7 +282,11 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(translate_applied_directive)
+                    .collect(),
                 fields: x
                     .fields
                     .iter()
@@ -233,15 +303,16 @@ impl GraphQLParserTranslator {
         }
     }
 
-    fn translate_enum_value<'a, T>(input: &'a EnumValue) -> ExternalEnumValue<'a, T>
+    fn translate_enum_value<'a, S, T>(input: &'a EnumValue<S>) -> ExternalEnumValue<'a, T>
     where
+        S: ScalarValue,
         T: Text<'a>,
     {
         ExternalEnumValue {
             position: Pos::default(),
             name: From::from(input.name.as_ref()),
             description: input.description.as_ref().map(|s| From::from(s.as_str())),
-            directives: generate_directives(&input.deprecation_status),
+            directives: generate_directives(&input.deprecation_status, &input.directives),
         }
     }
 
@@ -265,7 +336,7 @@ impl GraphQLParserTranslator {
             position: Pos::default(),
             name: From::from(input.name.as_str()),
             description: input.description.as_ref().map(|s| From::from(s.as_str())),
-            directives: generate_directives(&input.deprecation_status),
+            directives: generate_directives(&input.deprecation_status, &input.directives),
             field_type: GraphQLParserTranslator::translate_type(&input.field_type),
             arguments,
         }
@@ -293,16 +364,63 @@ where
     }
 }
 
-// Right now the only directive supported is `@deprecated`. `@skip` and `@include`
-// are dealt with elsewhere.
-// <https://facebook.github.io/graphql/draft/#sec-Type-System.Directives>
-fn generate_directives<'a, T>(status: &DeprecationStatus) -> Vec<ExternalDirective<'a, T>>
+fn translate_applied_directive<'a, S, T>(input: &'a AppliedDirective<S>) -> ExternalDirective<'a, T>
 where
+    S: ScalarValue,
     T: Text<'a>,
 {
-    if let Some(d) = deprecation_to_directive(status) {
-        vec![d]
-    } else {
-        vec![]
+    ExternalDirective {
+        position: Pos::default(),
+        name: From::from(input.name.as_str()),
+        arguments: input
+            .arguments
+            .iter()
+            .map(|(name, value)| {
+                (
+                    From::from(name.as_str()),
+                    GraphQLParserTranslator::translate_value(value),
+                )
+            })
+            .collect(),
     }
 }
+
+fn translate_directive_location(input: &DirectiveLocation) -> ExternalDirectiveLocation {
+    match input {
+        DirectiveLocation::Query => ExternalDirectiveLocation::Query,
+        DirectiveLocation::Mutation => ExternalDirectiveLocation::Mutation,
+        DirectiveLocation::Subscription => ExternalDirectiveLocation::Subscription,
+        DirectiveLocation::Field => ExternalDirectiveLocation::Field,
+        DirectiveLocation::FragmentDefinition => ExternalDirectiveLocation::FragmentDefinition,
+        DirectiveLocation::FragmentSpread => ExternalDirectiveLocation::FragmentSpread,
+        DirectiveLocation::InlineFragment => ExternalDirectiveLocation::InlineFragment,
+        DirectiveLocation::Schema => ExternalDirectiveLocation::Schema,
+        DirectiveLocation::Scalar => ExternalDirectiveLocation::Scalar,
+        DirectiveLocation::Object => ExternalDirectiveLocation::Object,
+        DirectiveLocation::FieldDefinition => ExternalDirectiveLocation::FieldDefinition,
+        DirectiveLocation::ArgumentDefinition => ExternalDirectiveLocation::ArgumentDefinition,
+        DirectiveLocation::Interface => ExternalDirectiveLocation::Interface,
+        DirectiveLocation::Union => ExternalDirectiveLocation::Union,
+        DirectiveLocation::Enum => ExternalDirectiveLocation::Enum,
+        DirectiveLocation::EnumValue => ExternalDirectiveLocation::EnumValue,
+        DirectiveLocation::InputObject => ExternalDirectiveLocation::InputObject,
+        DirectiveLocation::InputFieldDefinition => ExternalDirectiveLocation::InputFieldDefinition,
+    }
+}
+
+// `@deprecated` is generated from the deprecation status, followed by the applied directives.
+// `@skip` and `@include` are dealt with elsewhere.
+// <https://facebook.github.io/graphql/draft/#sec-Type-System.Directives>
+fn generate_directives<'a, S, T>(
+    status: &DeprecationStatus,
+    applied: &'a [AppliedDirective<S>],
+) -> Vec<ExternalDirective<'a, T>>
+where
+    S: ScalarValue,
+    T: Text<'a>,
+{
+    deprecation_to_directive(status)
+        .into_iter()
+        .chain(applied.iter().map(translate_applied_directive))
+        .collect()
+}
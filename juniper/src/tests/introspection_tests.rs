@@ -2,10 +2,14 @@ use std::collections::HashSet;
 
 use crate::{
     executor::Variables,
+    graphql_object,
     introspection::IntrospectionFormat,
-    schema::model::RootNode,
+    parser::parse_document_source,
+    schema::{diff::diff, model::RootNode},
     tests::fixtures::starwars::schema::{Database, Query},
     types::scalars::{EmptyMutation, EmptySubscription},
+    validation::{visit_all_rules, ValidatorContext},
+    GraphQLEnum, GraphQLInputObject, SchemaType, Value,
 };
 
 use super::schema_introspection::*;
@@ -295,3 +299,178 @@ async fn test_builtin_introspection_query_without_descriptions() {
 
     assert_eq!(result, (expected, vec![]));
 }
+
+#[test]
+fn test_schema_from_introspection() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let (result, _) =
+        crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
+
+    let client_schema = SchemaType::from_introspection(&result).unwrap();
+
+    assert_eq!(diff(&schema.schema, &client_schema), vec![]);
+    assert_eq!(client_schema.query_type_name, "Query");
+    assert_eq!(client_schema.mutation_type_name, None);
+}
+
+#[test]
+fn test_schema_from_introspection_json() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let (result, _) =
+        crate::introspect(&schema, &database, IntrospectionFormat::WithoutDescriptions).unwrap();
+    let json = format!(r#"{{"data": {}}}"#, serde_json::to_string(&result).unwrap());
+
+    let result = serde_json::from_str::<Value>(&json).unwrap();
+    let client_schema = SchemaType::from_introspection(&result).unwrap();
+
+    assert_eq!(diff(&schema.schema, &client_schema), vec![]);
+}
+
+#[test]
+fn test_validation_against_schema_from_introspection() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let (result, _) =
+        crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
+    let client_schema = SchemaType::from_introspection(&result).unwrap();
+
+    let validate = |query| {
+        let document = parse_document_source(query, &client_schema).unwrap();
+        let mut ctx = ValidatorContext::new(&client_schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        ctx.into_errors()
+            .iter()
+            .map(|e| e.message().to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        validate(
+            r#"query($episode: Episode) {
+                hero(episode: $episode) {
+                    name
+                    friends { name }
+                    ... on Droid { primaryFunction }
+                }
+                __schema { queryType { name } }
+                __typename
+            }"#,
+        ),
+        Vec::<String>::new(),
+    );
+    assert_eq!(
+        validate("{ hero(episode: ALIENS) { name age } }"),
+        vec![
+            r#"Invalid value for argument "episode", expected type "Episode""#,
+            r#"Unknown field "age" on type "Character""#,
+        ],
+    );
+}
+
+#[test]
+fn test_enum_literals_against_schema_from_introspection() {
+    let database = Database::new();
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+    let (result, _) =
+        crate::introspect(&schema, &database, IntrospectionFormat::default()).unwrap();
+    let client_schema = SchemaType::from_introspection(&result).unwrap();
+
+    let validate = |query| {
+        let document = parse_document_source(query, &client_schema).unwrap();
+        let mut ctx = ValidatorContext::new(&client_schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        ctx.into_errors().len()
+    };
+
+    assert_eq!(validate("{ hero(episode: JEDI) { name } }"), 0);
+    assert_eq!(validate("{ hero(episode: jedi) { name } }"), 1);
+    assert_eq!(validate("{ hero(episode: ALIENS) { name } }"), 1);
+    assert_eq!(validate(r#"{ hero(episode: "JEDI") { name } }"#), 1);
+}
+
+#[test]
+fn test_schema_from_introspection_with_defaults() {
+    #[derive(GraphQLEnum)]
+    enum Order {
+        Asc,
+        Desc,
+    }
+
+    #[derive(GraphQLInputObject)]
+    struct Page {
+        #[graphql(default = "10")]
+        size: i32,
+        after: Option<String>,
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn items(
+            #[graphql(default = Order::Desc)] _order: Order,
+            #[graphql(default = Page { size: 5, after: None })] _page: Page,
+        ) -> Vec<i32> {
+            vec![]
+        }
+    }
+
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let (result, _) = crate::introspect(&schema, &(), IntrospectionFormat::default()).unwrap();
+
+    let client_schema = SchemaType::from_introspection(&result).unwrap();
+
+    assert_eq!(diff(&schema.schema, &client_schema), vec![]);
+}
+
+#[test]
+fn test_schema_from_invalid_introspection() {
+    let error = |value: Value| {
+        SchemaType::<crate::DefaultScalarValue>::from_introspection(&value)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+
+    assert_eq!(
+        error(graphql_value!({"data": None})),
+        "Missing `__schema` in introspection result",
+    );
+    assert_eq!(
+        error(graphql_value!({"__schema": {
+            "queryType": {"name": "Query"},
+            "types": [{
+                "kind": "OBJECT",
+                "name": "Query",
+                "fields": [{
+                    "name": "user",
+                    "args": [],
+                    "type": {"kind": "OBJECT", "name": "User", "ofType": None},
+                }],
+            }],
+        }})),
+        "Unknown type `User` referenced by `Query.user`",
+    );
+}
@@ -1023,6 +1023,72 @@ pub(crate) fn schema_introspection_result() -> Value {
                   "description": None,
                   "isDeprecated": false,
                   "deprecationReason": None
+                },
+                {
+                  "name": "SCHEMA",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "SCALAR",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "OBJECT",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "FIELD_DEFINITION",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "ARGUMENT_DEFINITION",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "INTERFACE",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "UNION",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "ENUM",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "ENUM_VALUE",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "INPUT_OBJECT",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "INPUT_FIELD_DEFINITION",
+                  "description": None,
+                  "isDeprecated": false,
+                  "deprecationReason": None
                 }
               ],
               "possibleTypes": None
@@ -1206,6 +1272,22 @@ pub(crate) fn schema_introspection_result() -> Value {
                   "isDeprecated": false,
                   "deprecationReason": None
                 },
+                {
+                  "name": "isRepeatable",
+                  "description": None,
+                  "args": [],
+                  "type": {
+                    "kind": "NON_NULL",
+                    "name": None,
+                    "ofType": {
+                      "kind": "SCALAR",
+                      "name": "Boolean",
+                      "ofType": None
+                    }
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
                 {
                   "name": "onOperation",
                   "description": None,
@@ -2231,6 +2313,61 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                   "name": "INLINE_FRAGMENT",
                   "isDeprecated": false,
                   "deprecationReason": None
+                },
+                {
+                  "name": "SCHEMA",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "SCALAR",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "OBJECT",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "FIELD_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "ARGUMENT_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "INTERFACE",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "UNION",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "ENUM",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "ENUM_VALUE",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "INPUT_OBJECT",
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
+                {
+                  "name": "INPUT_FIELD_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": None
                 }
               ],
               "possibleTypes": None
@@ -2404,6 +2541,21 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                   "isDeprecated": false,
                   "deprecationReason": None
                 },
+                {
+                  "name": "isRepeatable",
+                  "args": [],
+                  "type": {
+                    "kind": "NON_NULL",
+                    "name": None,
+                    "ofType": {
+                      "kind": "SCALAR",
+                      "name": "Boolean",
+                      "ofType": None
+                    }
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": None
+                },
                 {
                   "name": "onOperation",
                   "args": [],
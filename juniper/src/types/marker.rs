@@ -180,6 +180,63 @@ where
     }
 }
 
+/// Maker trait for [GraphQL input objects][1].
+///
+/// This trait extends the [`GraphQLType`] and is only used to mark an [input object][1]. During
+/// compile this addition information is required to prevent unwanted structure compiling. If an
+/// object requires this trait instead of the [`GraphQLType`], then it explicitly requires
+/// [GraphQL input objects][1]. Other types ([scalars][2], [enums][3], [objects][4],
+/// [interfaces][5] and [unions][6]) are not allowed.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Input-Objects
+/// [2]: https://spec.graphql.org/June2018/#sec-Scalars
+/// [3]: https://spec.graphql.org/June2018/#sec-Enums
+/// [4]: https://spec.graphql.org/June2018/#sec-Objects
+/// [5]: https://spec.graphql.org/June2018/#sec-Interfaces
+/// [6]: https://spec.graphql.org/June2018/#sec-Unions
+pub trait GraphQLInputObject<S: ScalarValue>: GraphQLType<S> {
+    /// An arbitrary function without meaning.
+    ///
+    /// May contain compile timed check logic which ensures that types are used correctly according
+    /// to the [GraphQL specification][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/
+    fn mark() {}
+}
+
+impl<'a, S, T> GraphQLInputObject<S> for &T
+where
+    T: GraphQLInputObject<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<S, T> GraphQLInputObject<S> for Box<T>
+where
+    T: GraphQLInputObject<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<S, T> GraphQLInputObject<S> for Arc<T>
+where
+    T: GraphQLInputObject<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 /// Marker trait for types which can be used as output types.
 ///
 /// The GraphQL specification differentiates between input and output
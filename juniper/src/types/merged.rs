@@ -0,0 +1,400 @@
+use std::fmt;
+
+use fnv::{FnvHashMap, FnvHashSet};
+use once_cell::sync::OnceCell;
+
+use crate::{
+    executor::{ExecutionResult, Executor, Registry, ValuesStream},
+    schema::meta::{Argument, Field, MetaType, ObjectMeta},
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{Arguments, GraphQLType, GraphQLValue},
+        marker::{GraphQLObject, IsOutputType},
+        subscriptions::GraphQLSubscriptionValue,
+    },
+    value::{ScalarValue, Value},
+    BoxFuture, FieldError,
+};
+
+/// Object combining the fields of two GraphQL objects into a single one.
+///
+/// This allows to build a single root (query, mutation or subscription) out of several separately
+/// defined ones. The merged object takes the name of the first one, and resolves every field with
+/// the object defining it:
+///
+/// ```
+/// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, MergedObject, RootNode};
+/// struct UserQuery;
+///
+/// #[graphql_object(name = "Query")]
+/// impl UserQuery {
+///     fn user_count() -> i32 {
+///         1
+///     }
+/// }
+///
+/// struct PostQuery;
+///
+/// #[graphql_object]
+/// impl PostQuery {
+///     fn post_count() -> i32 {
+///         2
+///     }
+/// }
+///
+/// let schema = RootNode::try_new(
+///     MergedObject::new(UserQuery, PostQuery),
+///     EmptyMutation::<()>::new(),
+///     EmptySubscription::<()>::new(),
+/// )
+/// .expect("roots have no conflicting fields or types");
+/// # assert!(schema.as_schema_language().contains("postCount"));
+/// ```
+///
+/// More than two objects are merged by nesting, i.e. `MergedObject<A, MergedObject<B, C>>`.
+///
+/// Fields defined by both objects, or different types sharing the same name, are recorded as
+/// [`SchemaConflict`]s, which are returned by [`RootNode::try_new`] when building the schema.
+///
+/// [`RootNode::try_new`]: crate::RootNode::try_new
+pub struct MergedObject<A, B> {
+    left: A,
+    right: B,
+    right_fields: OnceCell<FnvHashSet<String>>,
+}
+
+impl<A, B> MergedObject<A, B> {
+    /// Merges the fields of the two provided objects.
+    pub fn new(left: A, right: B) -> Self {
+        Self {
+            left,
+            right,
+            right_fields: OnceCell::new(),
+        }
+    }
+
+    /// Returns the first of the merged objects.
+    pub fn left(&self) -> &A {
+        &self.left
+    }
+
+    /// Returns the second of the merged objects.
+    pub fn right(&self) -> &B {
+        &self.right
+    }
+
+    /// Indicates whether the `field_name` is resolved by the second of the merged objects.
+    fn is_right_field<S>(&self, info: &B::TypeInfo, field_name: &str) -> bool
+    where
+        S: ScalarValue,
+        B: GraphQLType<S>,
+    {
+        self.right_fields
+            .get_or_init(|| {
+                let mut registry = Registry::new(FnvHashMap::default());
+                match B::meta(info, &mut registry) {
+                    MetaType::Object(ObjectMeta { fields, .. }) => {
+                        fields.iter().map(|f| f.name.to_string()).collect()
+                    }
+                    _ => FnvHashSet::default(),
+                }
+            })
+            .contains(field_name)
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Debug for MergedObject<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MergedObject")
+            .field(&self.left)
+            .field(&self.right)
+            .finish()
+    }
+}
+
+impl<S, A, B> GraphQLType<S> for MergedObject<A, B>
+where
+    S: ScalarValue,
+    A: GraphQLType<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo>,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        A::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let mut left = Registry::new(FnvHashMap::default());
+        let left_meta = A::meta(info, &mut left);
+        registry.merge(left);
+
+        let mut right = Registry::new(FnvHashMap::default());
+        let right_meta = B::meta(info, &mut right);
+        registry.merge(right);
+
+        let (mut left_meta, right_meta) = match (left_meta, right_meta) {
+            (MetaType::Object(l), MetaType::Object(r)) => (l, r),
+            (l, r) => {
+                for meta in [&l, &r].iter() {
+                    if !matches!(meta, MetaType::Object(_)) {
+                        registry.add_conflict(SchemaConflict::NotAnObject {
+                            type_name: meta.name().unwrap_or_default().to_owned(),
+                        });
+                    }
+                }
+                return l;
+            }
+        };
+
+        for field in right_meta.fields {
+            if field.is_builtin() {
+                continue;
+            }
+            if left_meta.fields.iter().any(|f| f.name == field.name) {
+                registry.add_conflict(SchemaConflict::DuplicateField {
+                    type_name: left_meta.name.to_string(),
+                    field_name: field.name.to_string(),
+                });
+            } else {
+                left_meta.fields.push(field);
+            }
+        }
+        for interface_name in right_meta.interface_names {
+            if !left_meta.interface_names.contains(&interface_name) {
+                left_meta.interface_names.push(interface_name);
+            }
+        }
+        left_meta.directives.extend(right_meta.directives);
+        left_meta.cache_control = left_meta.cache_control.restrict(right_meta.cache_control);
+        if left_meta.description.is_none() {
+            left_meta.description = right_meta.description;
+        }
+
+        left_meta.into_meta()
+    }
+}
+
+impl<S, A, B> GraphQLValue<S> for MergedObject<A, B>
+where
+    S: ScalarValue,
+    A: GraphQLType<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo>,
+{
+    type Context = A::Context;
+    type TypeInfo = A::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field_name: &str,
+        arguments: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        if self.is_right_field::<S>(info, field_name) {
+            self.right
+                .resolve_field(info, field_name, arguments, executor)
+        } else {
+            self.left
+                .resolve_field(info, field_name, arguments, executor)
+        }
+    }
+
+    fn concrete_type_name(&self, _: &Self::Context, info: &Self::TypeInfo) -> String {
+        self.type_name(info).unwrap_or_default().to_owned()
+    }
+}
+
+impl<S, A, B> GraphQLValueAsync<S> for MergedObject<A, B>
+where
+    S: ScalarValue + Send + Sync,
+    A: GraphQLType<S> + GraphQLValueAsync<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo> + GraphQLValueAsync<S>,
+    A::TypeInfo: Sync,
+    A::Context: Sync,
+{
+    fn resolve_field_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        if self.is_right_field::<S>(info, field_name) {
+            self.right
+                .resolve_field_async(info, field_name, arguments, executor)
+        } else {
+            self.left
+                .resolve_field_async(info, field_name, arguments, executor)
+        }
+    }
+}
+
+impl<S, A, B> GraphQLSubscriptionValue<S> for MergedObject<A, B>
+where
+    S: ScalarValue + Send + Sync,
+    A: GraphQLType<S> + GraphQLSubscriptionValue<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo> + GraphQLSubscriptionValue<S>,
+    A::TypeInfo: Sync,
+    A::Context: Sync,
+{
+    fn resolve_field_into_stream<'s, 'i, 'ft, 'args, 'e, 'ref_e, 'res, 'f>(
+        &'s self,
+        info: &'i Self::TypeInfo,
+        field_name: &'ft str,
+        arguments: Arguments<'args, S>,
+        executor: &'ref_e Executor<'ref_e, 'e, Self::Context, S>,
+    ) -> BoxFuture<'f, Result<Value<ValuesStream<'res, S>>, FieldError<S>>>
+    where
+        's: 'f,
+        'i: 'res,
+        'ft: 'f,
+        'args: 'f,
+        'ref_e: 'f,
+        'res: 'f,
+        'e: 'res,
+    {
+        if self.is_right_field::<S>(info, field_name) {
+            self.right
+                .resolve_field_into_stream(info, field_name, arguments, executor)
+        } else {
+            self.left
+                .resolve_field_into_stream(info, field_name, arguments, executor)
+        }
+    }
+}
+
+impl<S, A, B> IsOutputType<S> for MergedObject<A, B>
+where
+    S: ScalarValue,
+    A: GraphQLType<S> + IsOutputType<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo> + IsOutputType<S>,
+{
+    fn mark() {
+        A::mark();
+        B::mark();
+    }
+}
+
+impl<S, A, B> GraphQLObject<S> for MergedObject<A, B>
+where
+    S: ScalarValue,
+    A: GraphQLType<S> + GraphQLObject<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo> + GraphQLObject<S>,
+{
+    fn mark() {
+        <A as GraphQLObject<S>>::mark();
+        <B as GraphQLObject<S>>::mark();
+    }
+}
+
+/// Conflict between the parts of a schema, found while building it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaConflict {
+    /// The same field is defined by several of the merged objects.
+    DuplicateField {
+        /// Name of the merged object type.
+        type_name: String,
+        /// Name of the duplicated field.
+        field_name: String,
+    },
+
+    /// Different types are registered with the same name.
+    DuplicateType {
+        /// Name shared by the different types.
+        type_name: String,
+    },
+
+    /// A type being merged is not an object.
+    NotAnObject {
+        /// Name of the type which is not an object.
+        type_name: String,
+    },
+
+    /// A directive is registered with the name of an already registered one.
+    DuplicateDirective {
+        /// Name of the duplicated directive.
+        directive_name: String,
+    },
+}
+
+impl fmt::Display for SchemaConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DuplicateField {
+                type_name,
+                field_name,
+            } => write!(
+                f,
+                "Field \"{}\" is defined more than once on type \"{}\"",
+                field_name, type_name,
+            ),
+            Self::DuplicateType { type_name } => write!(
+                f,
+                "Different types are registered with the same name \"{}\"",
+                type_name,
+            ),
+            Self::NotAnObject { type_name } => {
+                write!(
+                    f,
+                    "Type \"{}\" cannot be merged as it is not an object",
+                    type_name
+                )
+            }
+            Self::DuplicateDirective { directive_name } => {
+                write!(f, "Directive @{} is already registered", directive_name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaConflict {}
+
+/// Indicates whether two types registered with the same name define the same shape.
+pub(crate) fn is_same_type<S>(left: &MetaType<S>, right: &MetaType<S>) -> bool {
+    fn same_args<S>(left: &[Argument<S>], right: &[Argument<S>]) -> bool {
+        left.len() == right.len()
+            && left
+                .iter()
+                .zip(right)
+                .all(|(l, r)| l.name == r.name && l.arg_type == r.arg_type)
+    }
+
+    fn same_fields<S>(left: &[Field<S>], right: &[Field<S>]) -> bool {
+        left.len() == right.len()
+            && left.iter().zip(right).all(|(l, r)| {
+                l.name == r.name
+                    && l.field_type == r.field_type
+                    && same_args(
+                        l.arguments.as_deref().unwrap_or_default(),
+                        r.arguments.as_deref().unwrap_or_default(),
+                    )
+            })
+    }
+
+    match (left, right) {
+        (MetaType::Placeholder(_), _) | (_, MetaType::Placeholder(_)) => true,
+        (MetaType::Scalar(_), MetaType::Scalar(_)) => true,
+        (MetaType::Object(l), MetaType::Object(r)) => {
+            same_fields(&l.fields, &r.fields) && l.interface_names == r.interface_names
+        }
+        (MetaType::Interface(l), MetaType::Interface(r)) => same_fields(&l.fields, &r.fields),
+        (MetaType::Union(l), MetaType::Union(r)) => l.of_type_names == r.of_type_names,
+        (MetaType::Enum(l), MetaType::Enum(r)) => {
+            l.values.len() == r.values.len()
+                && l.values
+                    .iter()
+                    .zip(&r.values)
+                    .all(|(l, r)| l.name == r.name)
+        }
+        (MetaType::InputObject(l), MetaType::InputObject(r)) => {
+            same_args(&l.input_fields, &r.input_fields)
+        }
+        _ => false,
+    }
+}
@@ -2,9 +2,13 @@ pub mod async_await;
 pub mod base;
 pub mod containers;
 pub mod marker;
+pub mod merged;
 pub mod name;
 pub mod nullable;
 pub mod pointers;
 pub mod scalars;
 pub mod subscriptions;
 pub mod utilities;
+
+#[cfg(feature = "wide-integers")]
+pub mod wide_integers;
@@ -1,7 +1,7 @@
 use crate::{
-    ast::InputValue,
+    ast::{InputValue, InputValueError},
     schema::{
-        meta::{EnumMeta, InputObjectMeta, MetaType},
+        meta::{EnumMeta, InputObjectMeta, InputValidator, MetaType, ScalarMeta},
         model::{SchemaType, TypeType},
     },
     value::ScalarValue,
@@ -16,32 +16,47 @@ pub fn is_valid_literal_value<S>(
 where
     S: ScalarValue,
 {
+    validate_literal_value(schema, arg_type, arg_value).is_ok()
+}
+
+/// Checks whether the given literal is a valid value of the given type,
+/// returning the reason reported by a failed input value conversion, if any.
+pub fn validate_literal_value<S>(
+    schema: &SchemaType<S>,
+    arg_type: &TypeType<S>,
+    arg_value: &InputValue<S>,
+) -> Result<(), InputValueError>
+where
+    S: ScalarValue,
+{
+    let invalid = || Err(InputValueError::default());
+
     match *arg_type {
         TypeType::NonNull(ref inner) => {
             if arg_value.is_null() {
-                false
+                invalid()
             } else {
-                is_valid_literal_value(schema, inner, arg_value)
+                validate_literal_value(schema, inner, arg_value)
             }
         }
         TypeType::List(ref inner, expected_size) => match *arg_value {
             InputValue::List(ref items) => {
                 if let Some(expected) = expected_size {
                     if items.len() != expected {
-                        return false;
+                        return invalid();
                     }
                 }
                 items
                     .iter()
-                    .all(|i| is_valid_literal_value(schema, inner, &i.item))
+                    .try_for_each(|i| validate_literal_value(schema, inner, &i.item))
             }
             ref v => {
                 if let Some(expected) = expected_size {
                     if expected != 1 {
-                        return false;
+                        return invalid();
                     }
                 }
-                is_valid_literal_value(schema, inner, v)
+                validate_literal_value(schema, inner, v)
             }
         },
         TypeType::Concrete(t) => {
@@ -50,19 +65,50 @@ where
             if let (&InputValue::Scalar(_), Some(&MetaType::Enum(EnumMeta { .. }))) =
                 (arg_value, arg_type.to_concrete())
             {
-                return false;
+                return invalid();
+            }
+            // Like enum variables, enum literals must name one of the values
+            // of the enum, whatever its `try_parse_fn` accepts (it can't know
+            // them for schemas built from introspection results), unless the
+            // enum accepts custom values.
+            if let (
+                InputValue::Enum(name),
+                MetaType::Enum(EnumMeta {
+                    values,
+                    accepts_custom_values: false,
+                    ..
+                }),
+            ) = (arg_value, t)
+            {
+                if !values.iter().any(|v| &v.name == name) {
+                    return invalid();
+                }
             }
 
             match *arg_value {
-                InputValue::Null | InputValue::Variable(_) => true,
+                InputValue::Null | InputValue::Variable(_) => Ok(()),
                 ref v @ InputValue::Scalar(_) | ref v @ InputValue::Enum(_) => {
                     if let Some(parse_fn) = t.input_value_parse_fn() {
                         parse_fn(v)
                     } else {
-                        false
+                        invalid()
                     }
                 }
-                InputValue::List(_) => false,
+                // Scalars accepting composite literals (e.g. `Json`) decide
+                // on their validity themselves.
+                ref v @ InputValue::List(_) | ref v @ InputValue::Object(_)
+                    if matches!(
+                        *t,
+                        MetaType::Scalar(ScalarMeta {
+                            accepts_composite: true,
+                            ..
+                        })
+                    ) =>
+                {
+                    t.input_value_parse_fn()
+                        .map_or_else(invalid, |parse_fn| parse_fn(v))
+                }
+                InputValue::List(_) => invalid(),
                 InputValue::Object(ref obj) => {
                     if let MetaType::InputObject(InputObjectMeta {
                         ref input_fields, ..
@@ -79,7 +125,7 @@ where
                             })
                             .collect::<HashSet<_>>();
 
-                        let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
+                        obj.iter().try_for_each(|(key, value)| {
                             remaining_required_fields.remove(&key.item);
                             if let Some(ref arg_type) = input_fields
                                 .iter()
@@ -87,18 +133,72 @@ where
                                 .map(|f| schema.make_type(&f.arg_type))
                                 .next()
                             {
-                                is_valid_literal_value(schema, arg_type, &value.item)
+                                validate_literal_value(schema, arg_type, &value.item)
                             } else {
-                                false
+                                invalid()
                             }
-                        });
+                        })?;
 
-                        all_types_ok && remaining_required_fields.is_empty()
+                        if remaining_required_fields.is_empty() {
+                            Ok(())
+                        } else {
+                            invalid()
+                        }
                     } else {
-                        false
+                        invalid()
                     }
                 }
             }
         }
     }
 }
+
+/// Checks the given literal against the `validators` of its position and the validators of the
+/// input object fields it contains, returning the first violation found, prefixed with its path.
+///
+/// Expects the literal to be a valid value of the given type already. Variables are skipped, as
+/// their values aren't known at this point.
+pub fn validate_literal_constraints<S>(
+    schema: &SchemaType<S>,
+    arg_type: &TypeType<S>,
+    validators: &[InputValidator<S>],
+    arg_value: &InputValue<S>,
+) -> Result<(), String>
+where
+    S: ScalarValue,
+{
+    if arg_value.is_null() || arg_value.is_variable() {
+        return Ok(());
+    }
+
+    validators.iter().try_for_each(|v| v.validate(arg_value))?;
+
+    match (arg_type, arg_value) {
+        (TypeType::NonNull(inner), v) => validate_literal_constraints(schema, inner, &[], v),
+        (TypeType::List(inner, _), InputValue::List(items)) => {
+            items.iter().enumerate().try_for_each(|(i, item)| {
+                validate_literal_constraints(schema, inner, &[], &item.item)
+                    .map_err(|e| format!("In element #{}: {}", i, e))
+            })
+        }
+        (TypeType::List(inner, _), v) => validate_literal_constraints(schema, inner, &[], v),
+        (
+            TypeType::Concrete(MetaType::InputObject(InputObjectMeta { input_fields, .. })),
+            InputValue::Object(obj),
+        ) => obj.iter().try_for_each(|(key, value)| {
+            input_fields
+                .iter()
+                .find(|f| f.name == key.item)
+                .map_or(Ok(()), |f| {
+                    validate_literal_constraints(
+                        schema,
+                        &schema.make_type(&f.arg_type),
+                        &f.validators,
+                        &value.item,
+                    )
+                })
+                .map_err(|e| format!(r#"In field "{}": {}"#, key.item, e))
+        }),
+        _ => Ok(()),
+    }
+}
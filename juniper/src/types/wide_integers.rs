@@ -0,0 +1,277 @@
+//! `Long`, `UnsignedLong` and `BigInt` scalars wrapping `i64`, `u64` and
+//! `i128`.
+//!
+//! The GraphQL spec doesn't define any built-in scalars wider than 32 bits, so
+//! these are serialized as numbers only when the [`ScalarValue`] is able to
+//! carry them losslessly (see [`ScalarValue::from_i64()`]), and as strings
+//! otherwise.
+//!
+//! They are newtypes rather than implementations for the primitives, so crates
+//! defining their own scalars for `i64` and the like keep compiling when this
+//! feature is enabled by another dependency.
+
+use std::{
+    convert::{TryFrom, TryInto},
+    str::FromStr,
+};
+
+use crate::{
+    ast::InputValue,
+    parser::{ParseError, ScalarToken, Token},
+    value::{ParseScalarResult, ParseScalarValue, ScalarValue, Value, MAX_SAFE_INTEGER},
+};
+
+/// A 64 bit signed integer, exposed as the `Long` scalar.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Long(pub i64);
+
+impl From<i64> for Long {
+    fn from(i: i64) -> Self {
+        Self(i)
+    }
+}
+
+impl From<Long> for i64 {
+    fn from(i: Long) -> Self {
+        i.0
+    }
+}
+
+#[crate::graphql_scalar]
+impl<S> GraphQLScalar for Long
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::Scalar(wide_integer_to_scalar(self.0))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Long, String> {
+        wide_integer_from_input_value(v, "Long").map(Long)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        wide_integer_from_str::<S, i64>(value)
+    }
+}
+
+/// A 64 bit unsigned integer, exposed as the `UnsignedLong` scalar.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct UnsignedLong(pub u64);
+
+impl From<u64> for UnsignedLong {
+    fn from(i: u64) -> Self {
+        Self(i)
+    }
+}
+
+impl From<UnsignedLong> for u64 {
+    fn from(i: UnsignedLong) -> Self {
+        i.0
+    }
+}
+
+#[crate::graphql_scalar]
+impl<S> GraphQLScalar for UnsignedLong
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::Scalar(wide_integer_to_scalar(self.0))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<UnsignedLong, String> {
+        wide_integer_from_input_value(v, "UnsignedLong").map(UnsignedLong)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        wide_integer_from_str::<S, u64>(value)
+    }
+}
+
+/// A 128 bit signed integer, exposed as the `BigInt` scalar.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BigInt(pub i128);
+
+impl From<i128> for BigInt {
+    fn from(i: i128) -> Self {
+        Self(i)
+    }
+}
+
+impl From<BigInt> for i128 {
+    fn from(i: BigInt) -> Self {
+        i.0
+    }
+}
+
+#[crate::graphql_scalar]
+impl<S> GraphQLScalar for BigInt
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::Scalar(wide_integer_to_scalar(self.0))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<BigInt, String> {
+        wide_integer_from_input_value(v, "BigInt").map(BigInt)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        wide_integer_from_str::<S, i128>(value)
+    }
+}
+
+/// Represents the given integer as a number if the scalar value is able to
+/// carry it losslessly, and as a string otherwise.
+fn wide_integer_to_scalar<S, T>(i: T) -> S
+where
+    S: ScalarValue,
+    T: TryInto<i64> + ToString + Copy,
+{
+    i.try_into()
+        .map(S::from_i64)
+        .unwrap_or_else(|_| S::from(i.to_string()))
+}
+
+/// Parses an integer from either a number or a string, rejecting floats
+/// which may have already lost precision.
+fn wide_integer_from_input_value<S, T>(v: &InputValue<S>, type_name: &str) -> Result<T, String>
+where
+    S: ScalarValue,
+    T: TryFrom<i64> + FromStr,
+{
+    let scalar = v
+        .as_scalar()
+        .ok_or_else(|| format!("Expected `{}`, found: {}", type_name, v))?;
+    if let Some(i) = scalar.as_i64() {
+        T::try_from(i).map_err(|_| format!("{} is out of range for `{}`", i, type_name))
+    } else if let Some(f) = scalar.as_float() {
+        if f.fract() != 0.0 || f.abs() > MAX_SAFE_INTEGER as f64 {
+            return Err(format!(
+                "{} cannot be represented exactly as `{}`, pass it as a string instead",
+                f, type_name,
+            ));
+        }
+        T::try_from(f as i64).map_err(|_| format!("{} is out of range for `{}`", f, type_name))
+    } else if let Some(s) = scalar.as_str() {
+        s.parse()
+            .map_err(|_| format!(r#""{}" is not a valid `{}`"#, s, type_name))
+    } else {
+        Err(format!("Expected `{}`, found: {}", type_name, v))
+    }
+}
+
+fn wide_integer_from_str<S, T>(value: ScalarToken<'_>) -> ParseScalarResult<'_, S>
+where
+    S: ScalarValue,
+    T: TryInto<i64> + ToString + FromStr + Copy,
+{
+    match value {
+        ScalarToken::Int(v) => v
+            .parse()
+            .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
+            .map(wide_integer_to_scalar::<S, T>),
+        ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(value),
+        ScalarToken::Float(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::{FromInputValue as _, InputValue, InputValueError, ToInputValue},
+        parser::ScalarToken,
+        value::{DefaultScalarValue, LongScalarValue, ParseScalarValue},
+    };
+
+    use super::{BigInt, Long, UnsignedLong};
+
+    #[test]
+    fn parse_wide_integers() {
+        let long = <Long as ParseScalarValue<DefaultScalarValue>>::from_str(ScalarToken::Int(
+            "9007199254740993",
+        ));
+        assert_eq!(long, Ok(DefaultScalarValue::from("9007199254740993")));
+
+        let long = <Long as ParseScalarValue<DefaultScalarValue>>::from_str(ScalarToken::Int(
+            "-9007199254740991",
+        ));
+        assert_eq!(
+            long,
+            Ok(DefaultScalarValue::Float(-9_007_199_254_740_991.0))
+        );
+
+        let long =
+            <Long as ParseScalarValue<LongScalarValue>>::from_str(ScalarToken::Int("-5000000000"));
+        assert_eq!(long, Ok(LongScalarValue::Long(-5_000_000_000)));
+
+        let small =
+            <UnsignedLong as ParseScalarValue<LongScalarValue>>::from_str(ScalarToken::Int("42"));
+        assert_eq!(small, Ok(LongScalarValue::Int(42)));
+
+        let negative = <UnsignedLong as ParseScalarValue<DefaultScalarValue>>::from_str(
+            ScalarToken::Int("-1"),
+        );
+        assert!(negative.is_err());
+    }
+
+    #[test]
+    fn wide_integers_from_input_value() {
+        let input =
+            InputValue::<DefaultScalarValue>::scalar("170141183460469231731687303715884105727");
+        assert_eq!(BigInt::try_from_input_value(&input), Ok(BigInt(i128::MAX)));
+
+        let input = InputValue::<DefaultScalarValue>::scalar(5_000_000_000.0);
+        assert_eq!(Long::try_from_input_value(&input), Ok(Long(5_000_000_000)));
+        assert_eq!(
+            UnsignedLong::try_from_input_value(&input),
+            Ok(UnsignedLong(5_000_000_000)),
+        );
+
+        let input = InputValue::<LongScalarValue>::scalar(LongScalarValue::Long(-1));
+        assert_eq!(Long::try_from_input_value(&input), Ok(Long(-1)));
+        assert_eq!(
+            UnsignedLong::try_from_input_value(&input),
+            Err(InputValueError::new(
+                "-1 is out of range for `UnsignedLong`"
+            )),
+        );
+
+        let input = InputValue::<DefaultScalarValue>::scalar(1e17);
+        assert_eq!(
+            Long::try_from_input_value(&input),
+            Err(InputValueError::new(
+                "100000000000000000 cannot be represented exactly as `Long`, \
+                 pass it as a string instead",
+            )),
+        );
+
+        let input = InputValue::<DefaultScalarValue>::scalar("12a");
+        assert_eq!(
+            Long::try_from_input_value(&input),
+            Err(InputValueError::new(r#""12a" is not a valid `Long`"#)),
+        );
+    }
+
+    #[test]
+    fn wide_integers_to_input_value() {
+        assert_eq!(
+            ToInputValue::<DefaultScalarValue>::to_input_value(&Long(42)),
+            InputValue::scalar(42),
+        );
+        assert_eq!(
+            ToInputValue::<DefaultScalarValue>::to_input_value(&Long(5_000_000_000)),
+            InputValue::scalar(5_000_000_000.0),
+        );
+        assert_eq!(
+            ToInputValue::<DefaultScalarValue>::to_input_value(&UnsignedLong(u64::MAX)),
+            InputValue::scalar("18446744073709551615"),
+        );
+        assert_eq!(
+            ToInputValue::<LongScalarValue>::to_input_value(&Long(i64::MIN)),
+            InputValue::scalar(LongScalarValue::Long(i64::MIN)),
+        );
+    }
+}
@@ -7,13 +7,121 @@ use crate::ast::{Definition, Document, Type};
 
 use crate::schema::{meta::MetaType, model::SchemaType};
 
-use crate::parser::SourcePosition;
+use crate::parser::{ParseError, SourcePosition, Spanning};
 
 /// Query validation error
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RuleError {
     locations: Vec<SourcePosition>,
     message: String,
+    code: RuleErrorCode,
+}
+
+/// Machine-readable code of a [`RuleError`], identifying the validation rule that failed
+///
+/// Most of the codes are named after the [validation rules][1] of the GraphQL specification.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Validation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuleErrorCode {
+    /// The document could not be parsed.
+    Syntax,
+    /// An argument value has a wrong type.
+    ArgumentsOfCorrectType,
+    /// A variable's default value has a wrong type.
+    DefaultValuesOfCorrectType,
+    /// A field is not defined on the selected type.
+    FieldsOnCorrectType,
+    /// A fragment is declared on a non-composite type.
+    FragmentsOnCompositeTypes,
+    /// An argument is not defined on the field or directive.
+    KnownArgumentNames,
+    /// A directive is unknown or used at a wrong location.
+    KnownDirectives,
+    /// A spread fragment is not defined.
+    KnownFragmentNames,
+    /// A referenced type is not defined.
+    KnownTypeNames,
+    /// An anonymous operation is not the only one in the document.
+    LoneAnonymousOperation,
+    /// Fragments spread each other in a cycle.
+    NoFragmentCycles,
+    /// A variable is used, but not defined by the operation.
+    NoUndefinedVariables,
+    /// A fragment is defined, but not used.
+    NoUnusedFragments,
+    /// A variable is defined, but not used.
+    NoUnusedVariables,
+    /// Selections of the same response name conflict with each other.
+    OverlappingFieldsCanBeMerged,
+    /// A fragment can never apply to the type it's spread in.
+    PossibleFragmentSpreads,
+    /// A required argument is not provided.
+    ProvidedNonNullArguments,
+    /// A leaf field has a selection set, or a composite field has none.
+    ScalarLeafs,
+    /// An argument is provided more than once.
+    UniqueArgumentNames,
+    /// A fragment name is defined more than once.
+    UniqueFragmentNames,
+    /// An input object field is provided more than once.
+    UniqueInputFieldNames,
+    /// An operation name is defined more than once.
+    UniqueOperationNames,
+    /// A variable is defined more than once.
+    UniqueVariableNames,
+    /// A variable has a non-input type.
+    VariablesAreInputTypes,
+    /// A variable is used at a position its type is not allowed in.
+    VariablesInAllowedPosition,
+    /// A provided variable value doesn't match the variable's type.
+    VariableValue,
+    /// The operation to validate the variables of is not found or not specified.
+    UnknownOperation,
+    /// The error was not reported by a built-in validation rule.
+    Custom,
+}
+
+impl RuleErrorCode {
+    /// Returns this code as a `SCREAMING_SNAKE_CASE` string, like `FIELDS_ON_CORRECT_TYPE`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Syntax => "SYNTAX",
+            Self::ArgumentsOfCorrectType => "ARGUMENTS_OF_CORRECT_TYPE",
+            Self::DefaultValuesOfCorrectType => "DEFAULT_VALUES_OF_CORRECT_TYPE",
+            Self::FieldsOnCorrectType => "FIELDS_ON_CORRECT_TYPE",
+            Self::FragmentsOnCompositeTypes => "FRAGMENTS_ON_COMPOSITE_TYPES",
+            Self::KnownArgumentNames => "KNOWN_ARGUMENT_NAMES",
+            Self::KnownDirectives => "KNOWN_DIRECTIVES",
+            Self::KnownFragmentNames => "KNOWN_FRAGMENT_NAMES",
+            Self::KnownTypeNames => "KNOWN_TYPE_NAMES",
+            Self::LoneAnonymousOperation => "LONE_ANONYMOUS_OPERATION",
+            Self::NoFragmentCycles => "NO_FRAGMENT_CYCLES",
+            Self::NoUndefinedVariables => "NO_UNDEFINED_VARIABLES",
+            Self::NoUnusedFragments => "NO_UNUSED_FRAGMENTS",
+            Self::NoUnusedVariables => "NO_UNUSED_VARIABLES",
+            Self::OverlappingFieldsCanBeMerged => "OVERLAPPING_FIELDS_CAN_BE_MERGED",
+            Self::PossibleFragmentSpreads => "POSSIBLE_FRAGMENT_SPREADS",
+            Self::ProvidedNonNullArguments => "PROVIDED_NON_NULL_ARGUMENTS",
+            Self::ScalarLeafs => "SCALAR_LEAFS",
+            Self::UniqueArgumentNames => "UNIQUE_ARGUMENT_NAMES",
+            Self::UniqueFragmentNames => "UNIQUE_FRAGMENT_NAMES",
+            Self::UniqueInputFieldNames => "UNIQUE_INPUT_FIELD_NAMES",
+            Self::UniqueOperationNames => "UNIQUE_OPERATION_NAMES",
+            Self::UniqueVariableNames => "UNIQUE_VARIABLE_NAMES",
+            Self::VariablesAreInputTypes => "VARIABLES_ARE_INPUT_TYPES",
+            Self::VariablesInAllowedPosition => "VARIABLES_IN_ALLOWED_POSITION",
+            Self::VariableValue => "VARIABLE_VALUE",
+            Self::UnknownOperation => "UNKNOWN_OPERATION",
+            Self::Custom => "CUSTOM",
+        }
+    }
+}
+
+impl fmt::Display for RuleErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[doc(hidden)]
@@ -29,14 +137,30 @@ pub struct ValidatorContext<'a, S: Debug + 'a> {
 }
 
 impl RuleError {
-    #[doc(hidden)]
+    /// Creates a new validation error with the [`RuleErrorCode::Custom`] code.
     pub fn new(message: &str, locations: &[SourcePosition]) -> RuleError {
         RuleError {
             message: message.to_owned(),
             locations: locations.to_vec(),
+            code: RuleErrorCode::Custom,
         }
     }
 
+    /// Sets the [`RuleErrorCode`] of this validation error.
+    pub fn with_code(mut self, code: RuleErrorCode) -> RuleError {
+        self.code = code;
+        self
+    }
+
+    /// Access the code of the validation rule that failed
+    pub fn code(&self) -> RuleErrorCode {
+        self.code
+    }
+
+    pub(crate) fn from_parse_error(error: Spanning<ParseError>) -> RuleError {
+        RuleError::new(&error.item.to_string(), &[error.start]).with_code(RuleErrorCode::Syntax)
+    }
+
     /// Access the message for a validation error
     pub fn message(&self) -> &str {
         &self.message
@@ -93,8 +217,14 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
     }
 
     #[doc(hidden)]
-    pub fn report_error(&mut self, message: &str, locations: &[SourcePosition]) {
-        self.errors.push(RuleError::new(message, locations))
+    pub fn report_error(
+        &mut self,
+        code: RuleErrorCode,
+        message: &str,
+        locations: &[SourcePosition],
+    ) {
+        self.errors
+            .push(RuleError::new(message, locations).with_code(code))
     }
 
     #[doc(hidden)]
@@ -1,14 +1,21 @@
-use std::{collections::HashSet, fmt};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, ptr,
+};
 
 use crate::{
-    ast::{InputValue, Operation, VariableDefinitions},
-    executor::Variables,
-    parser::{SourcePosition, Spanning},
+    ast::{
+        Definition, Directive, Document, Field, Fragment, InputValue, InputValueError, Operation,
+        Selection, VariableDefinitions,
+    },
+    executor::{get_operation, Variables},
+    parser::{parse_document_source, SourcePosition, Spanning},
     schema::{
-        meta::{EnumMeta, InputObjectMeta, MetaType, ScalarMeta},
+        meta::{Argument, EnumMeta, InputObjectMeta, MetaType, ScalarMeta},
         model::{SchemaType, TypeType},
     },
-    validation::RuleError,
+    types::utilities::validate_literal_constraints,
+    validation::{visit, MultiVisitorNil, RuleError, RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -19,7 +26,10 @@ enum Path<'a> {
     ObjectField(&'a str, &'a Path<'a>),
 }
 
-#[doc(hidden)]
+/// Validates the `values` of variables against their definitions in the `operation`, returning
+/// the errors found with the [`RuleErrorCode::VariableValue`] code.
+///
+/// See [`validate_variables()`] for validating the variables of an operation not parsed yet.
 pub fn validate_input_values<S>(
     values: &Variables<S>,
     operation: &Spanning<Operation<S>>,
@@ -38,6 +48,228 @@ where
     errs
 }
 
+/// Validates the `variables` of an operation in the `document_source` against the `schema`,
+/// returning the errors found.
+///
+/// The `operation_name` selects the operation to validate the variables of, and may be omitted
+/// if the document contains a single one. Otherwise, the [`RuleErrorCode::UnknownOperation`]
+/// error is returned. Syntax errors are reported with the [`RuleErrorCode::Syntax`] code.
+///
+/// Only the variables are validated, so the document itself should be checked with
+/// [`validate()`] too.
+///
+/// [`validate()`]: crate::validation::validate
+pub fn validate_variables<S>(
+    schema: &SchemaType<S>,
+    document_source: &str,
+    operation_name: Option<&str>,
+    variables: &Variables<S>,
+) -> Vec<RuleError>
+where
+    S: ScalarValue,
+{
+    let document = match parse_document_source(document_source, schema) {
+        Ok(document) => document,
+        Err(error) => return vec![RuleError::from_parse_error(error)],
+    };
+
+    match get_operation(&document, operation_name) {
+        Ok(operation) => {
+            let errors = validate_input_values(variables, operation, schema);
+            if !errors.is_empty() {
+                return errors;
+            }
+            validate_variable_usages(variables, &document, operation, schema)
+        }
+        Err(error) => vec![
+            RuleError::new(&error.to_string(), &[SourcePosition::new_origin()])
+                .with_code(RuleErrorCode::UnknownOperation),
+        ],
+    }
+}
+
+/// Validates the `values` of variables against the validators of the arguments and input object
+/// fields they are passed to within the `operation`, returning the errors found with the
+/// [`RuleErrorCode::VariableValue`] code.
+///
+/// The values are expected to be [unified][validate_input_values] with their definitions
+/// already. Fragments are looked up in the `document` the `operation` belongs to.
+pub fn validate_variable_usages<'a, S>(
+    values: &'a Variables<S>,
+    document: &'a Document<S>,
+    operation: &'a Spanning<Operation<S>>,
+    schema: &'a SchemaType<S>,
+) -> Vec<RuleError>
+where
+    S: ScalarValue,
+{
+    // Missing variables are nulls, which validators never reject.
+    if values.is_empty() || !schema.has_input_validators() {
+        return vec![];
+    }
+
+    let mut ctx = ValidatorContext::new(schema, document);
+    let mut visitor = MultiVisitorNil.with(VariableUsages {
+        values,
+        operation,
+        fragments: reachable_fragments(document, operation),
+        in_operation: false,
+        current_args: None,
+    });
+    visit(&mut visitor, &mut ctx, document);
+    ctx.into_errors()
+}
+
+/// Collects the names of the fragments spread within the `operation`, directly or through other
+/// fragments of the `document`.
+fn reachable_fragments<'a, S>(
+    document: &'a Document<S>,
+    operation: &'a Spanning<Operation<S>>,
+) -> HashSet<&'a str> {
+    fn collect<'a, S>(
+        fragments: &HashMap<&'a str, &'a [Selection<'a, S>]>,
+        selection_set: &'a [Selection<S>],
+        names: &mut HashSet<&'a str>,
+    ) {
+        for selection in selection_set {
+            match selection {
+                Selection::Field(field) => {
+                    if let Some(ref selection_set) = field.item.selection_set {
+                        collect(fragments, selection_set, names);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    collect(fragments, &fragment.item.selection_set, names);
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.item.name.item;
+                    if names.insert(name) {
+                        if let Some(selection_set) = fragments.get(name) {
+                            collect(fragments, selection_set, names);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let fragments = document
+        .iter()
+        .filter_map(|def| match def {
+            Definition::Fragment(f) => Some((f.item.name.item, f.item.selection_set.as_slice())),
+            _ => None,
+        })
+        .collect();
+    let mut names = HashSet::new();
+    collect(&fragments, &operation.item.selection_set, &mut names);
+    names
+}
+
+struct VariableUsages<'a, S: 'a> {
+    values: &'a Variables<S>,
+    operation: &'a Spanning<Operation<'a, S>>,
+    fragments: HashSet<&'a str>,
+    in_operation: bool,
+    current_args: Option<&'a Vec<Argument<'a, S>>>,
+}
+
+impl<'a, S> Visitor<'a, S> for VariableUsages<'a, S>
+where
+    S: ScalarValue,
+{
+    fn enter_operation_definition(
+        &mut self,
+        _: &mut ValidatorContext<'a, S>,
+        op: &'a Spanning<Operation<S>>,
+    ) {
+        self.in_operation = ptr::eq(op, self.operation);
+    }
+
+    fn exit_operation_definition(
+        &mut self,
+        _: &mut ValidatorContext<'a, S>,
+        _: &'a Spanning<Operation<S>>,
+    ) {
+        self.in_operation = false;
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _: &mut ValidatorContext<'a, S>,
+        fragment: &'a Spanning<Fragment<S>>,
+    ) {
+        // Fragments using variables not provided get nulls for them, which are never checked.
+        self.in_operation = self.fragments.contains(fragment.item.name.item);
+    }
+
+    fn exit_fragment_definition(
+        &mut self,
+        _: &mut ValidatorContext<'a, S>,
+        _: &'a Spanning<Fragment<S>>,
+    ) {
+        self.in_operation = false;
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        directive: &'a Spanning<Directive<S>>,
+    ) {
+        self.current_args = ctx
+            .schema
+            .directive_by_name(directive.item.name.item)
+            .map(|d| &d.arguments);
+    }
+
+    fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
+        self.current_args = None;
+    }
+
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
+        self.current_args = ctx
+            .parent_type()
+            .and_then(|t| t.field_by_name(field.item.name.item))
+            .and_then(|f| f.arguments.as_ref());
+    }
+
+    fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
+        self.current_args = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        &(ref arg_name, ref arg_value): &'a (Spanning<&'a str>, Spanning<InputValue<S>>),
+    ) {
+        if !self.in_operation || arg_value.item.referenced_variables().is_empty() {
+            return;
+        }
+        if let Some(argument_meta) = self
+            .current_args
+            .and_then(|args| args.iter().find(|a| a.name == arg_name.item))
+        {
+            // Constants are checked during the document validation already, so any violation
+            // found here comes from the variables.
+            let value = arg_value.item.clone().into_const(self.values);
+            if let Err(reason) = validate_literal_constraints(
+                ctx.schema,
+                &ctx.schema.make_type(&argument_meta.arg_type),
+                &argument_meta.validators,
+                &value,
+            ) {
+                ctx.report_error(
+                    RuleErrorCode::VariableValue,
+                    &format!(
+                        r#"Argument "{}" got invalid value from variables. {}."#,
+                        arg_name.item, reason,
+                    ),
+                    &[arg_value.start],
+                );
+            }
+        }
+    }
+}
+
 fn validate_var_defs<S>(
     values: &Variables<S>,
     var_defs: &VariableDefinitions<S>,
@@ -53,13 +285,16 @@ fn validate_var_defs<S>(
                 let ct = schema.make_type(&def.var_type.item);
 
                 if def.var_type.item.is_non_null() && is_absent_or_null(values.get(name.item)) {
-                    errors.push(RuleError::new(
-                        &format!(
-                            r#"Variable "${}" of required type "{}" was not provided."#,
-                            name.item, def.var_type.item,
-                        ),
-                        &[name.start],
-                    ));
+                    errors.push(
+                        RuleError::new(
+                            &format!(
+                                r#"Variable "${}" of required type "{}" was not provided."#,
+                                name.item, def.var_type.item,
+                            ),
+                            &[name.start],
+                        )
+                        .with_code(RuleErrorCode::VariableValue),
+                    );
                 } else if let Some(v) = values.get(name.item) {
                     errors.append(&mut unify_value(
                         name.item,
@@ -163,14 +398,17 @@ where
                     if e.is_empty() {
                         // All the fields didn't have errors, see if there is an
                         // overall error when parsing the input value.
-                        if !(iom.try_parse_fn)(value) {
+                        if let Err(e) = (iom.try_parse_fn)(value) {
                             errors.push(unification_error(
                                 var_name,
                                 var_pos,
                                 &path,
-                                &format!(
-                                    r#"Expected input of type "{}". Got: "{}""#,
-                                    iom.name, value
+                                &with_reason(
+                                    format!(
+                                        r#"Expected input of type "{}". Got: "{}""#,
+                                        iom.name, value
+                                    ),
+                                    &e,
                                 ),
                             ));
                         }
@@ -197,15 +435,18 @@ where
 {
     let mut errors: Vec<RuleError> = vec![];
 
-    if !(meta.try_parse_fn)(value) {
+    if let Err(e) = (meta.try_parse_fn)(value) {
         return vec![unification_error(
             var_name,
             var_pos,
             path,
-            &format!(r#"Expected "{}""#, meta.name),
+            &with_reason(format!(r#"Expected "{}""#, meta.name), &e),
         )];
     }
 
+    if meta.accepts_composite {
+        return errors;
+    }
     match *value {
         InputValue::List(_) => errors.push(unification_error(
             var_name,
@@ -236,6 +477,18 @@ where
 {
     let mut errors: Vec<RuleError> = vec![];
 
+    if meta.accepts_custom_values {
+        if let Err(e) = (meta.try_parse_fn)(value) {
+            errors.push(unification_error(
+                var_name,
+                var_pos,
+                path,
+                &with_reason(format!(r#"Invalid value for enum "{}""#, meta.name), &e),
+            ));
+        }
+        return errors;
+    }
+
     match *value {
         // TODO: avoid this bad duplicate as_str() call. (value system refactor)
         InputValue::Scalar(ref scalar) if scalar.as_str().is_some() => {
@@ -294,14 +547,32 @@ where
                 if !value.is_null() {
                     has_value = true;
 
-                    errors.append(&mut unify_value(
+                    let mut e = unify_value(
                         var_name,
                         var_pos,
                         value,
                         &schema.make_type(&input_field.arg_type),
                         schema,
                         Path::ObjectField(&input_field.name, path),
-                    ));
+                    );
+                    if e.is_empty() {
+                        // The value has the right type, so it can be checked against the
+                        // constraints of the field.
+                        if let Err(reason) = input_field
+                            .validators
+                            .iter()
+                            .try_for_each(|v| v.validate(value))
+                        {
+                            errors.push(unification_error(
+                                var_name,
+                                var_pos,
+                                &Path::ObjectField(&input_field.name, path),
+                                &reason,
+                            ));
+                        }
+                    } else {
+                        errors.append(&mut e);
+                    }
                 }
             }
 
@@ -354,6 +625,14 @@ fn unification_error<'a>(
         ),
         &[*var_pos],
     )
+    .with_code(RuleErrorCode::VariableValue)
+}
+
+fn with_reason(message: String, error: &InputValueError) -> String {
+    match error.message() {
+        Some(reason) => format!("{}: {}", message, reason),
+        None => message,
+    }
 }
 
 impl<'a> fmt::Display for Path<'a> {
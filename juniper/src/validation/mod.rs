@@ -11,10 +11,10 @@ mod visitor;
 pub(crate) mod test_harness;
 
 pub use self::{
-    context::{RuleError, ValidatorContext},
-    input_value::validate_input_values,
+    context::{RuleError, RuleErrorCode, ValidatorContext},
+    input_value::{validate_input_values, validate_variable_usages, validate_variables},
     multi_visitor::MultiVisitorNil,
-    rules::visit_all_rules,
+    rules::{validate, visit_all_rules},
     traits::Visitor,
     visitor::visit,
 };
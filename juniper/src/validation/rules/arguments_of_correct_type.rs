@@ -2,8 +2,8 @@ use crate::{
     ast::{Directive, Field, InputValue},
     parser::Spanning,
     schema::meta::Argument,
-    types::utilities::is_valid_literal_value,
-    validation::{ValidatorContext, Visitor},
+    types::utilities::{validate_literal_constraints, validate_literal_value},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -57,9 +57,30 @@ where
         {
             let meta_type = ctx.schema.make_type(&argument_meta.arg_type);
 
-            if !is_valid_literal_value(ctx.schema, &meta_type, &arg_value.item) {
+            if let Err(e) = validate_literal_value(ctx.schema, &meta_type, &arg_value.item) {
+                let mut message =
+                    error_message(arg_name.item, &format!("{}", argument_meta.arg_type));
+                if let Some(reason) = e.message() {
+                    message = format!("{}: {}", message, reason);
+                }
                 ctx.report_error(
-                    &error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
+                    RuleErrorCode::ArgumentsOfCorrectType,
+                    &message,
+                    &[arg_value.start],
+                );
+            } else if let Err(reason) = validate_literal_constraints(
+                ctx.schema,
+                &meta_type,
+                &argument_meta.validators,
+                &arg_value.item,
+            ) {
+                ctx.report_error(
+                    RuleErrorCode::ArgumentsOfCorrectType,
+                    &format!(
+                        "{}: {}",
+                        error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
+                        reason,
+                    ),
                     &[arg_value.start],
                 );
             }
@@ -2,7 +2,7 @@ use crate::{
     ast::VariableDefinition,
     parser::Spanning,
     types::utilities::is_valid_literal_value,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -29,6 +29,7 @@ where
         {
             if var_def.var_type.item.is_non_null() {
                 ctx.report_error(
+                    RuleErrorCode::DefaultValuesOfCorrectType,
                     &non_null_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                     &[*start],
                 )
@@ -37,6 +38,7 @@ where
 
                 if !is_valid_literal_value(ctx.schema, &meta_type, var_value) {
                     ctx.report_error(
+                        RuleErrorCode::DefaultValuesOfCorrectType,
                         &type_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                         &[*start],
                     );
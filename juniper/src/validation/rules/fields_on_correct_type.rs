@@ -2,7 +2,7 @@ use crate::{
     ast::Field,
     parser::Spanning,
     schema::meta::MetaType,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -37,6 +37,7 @@ where
                     }
 
                     context.report_error(
+                        RuleErrorCode::FieldsOnCorrectType,
                         &error_message(field_name.item, type_name),
                         &[field_name.start],
                     );
@@ -1,7 +1,7 @@
 use crate::{
     ast::{Fragment, InlineFragment},
     parser::Spanning,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -27,6 +27,7 @@ where
                     let type_cond = &f.item.type_condition;
 
                     context.report_error(
+                        RuleErrorCode::FragmentsOnCompositeTypes,
                         &error_message(Some(f.item.name.item), type_name),
                         &[type_cond.start],
                     );
@@ -50,7 +51,11 @@ where
                     .next();
 
                 if let Some(name) = invalid_type_name {
-                    context.report_error(&error_message(None, name), &[type_cond.start]);
+                    context.report_error(
+                        RuleErrorCode::FragmentsOnCompositeTypes,
+                        &error_message(None, name),
+                        &[type_cond.start],
+                    );
                 }
             }
         }
@@ -2,7 +2,7 @@ use crate::{
     ast::{Directive, Field, InputValue},
     parser::Spanning,
     schema::meta::Argument,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -84,7 +84,11 @@ where
                     }
                 };
 
-                ctx.report_error(&message, &[arg_name.start]);
+                ctx.report_error(
+                    RuleErrorCode::KnownArgumentNames,
+                    &message,
+                    &[arg_name.start],
+                );
             }
         }
     }
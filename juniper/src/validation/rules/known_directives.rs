@@ -2,7 +2,7 @@ use crate::{
     ast::{Directive, Field, Fragment, FragmentSpread, InlineFragment, Operation, OperationType},
     parser::Spanning,
     schema::model::DirectiveLocation,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -121,13 +121,18 @@ where
                     .any(|l| l == current_location)
                 {
                     ctx.report_error(
+                        RuleErrorCode::KnownDirectives,
                         &misplaced_error_message(directive_name, current_location),
                         &[directive.start],
                     );
                 }
             }
         } else {
-            ctx.report_error(&unknown_error_message(directive_name), &[directive.start]);
+            ctx.report_error(
+                RuleErrorCode::KnownDirectives,
+                &unknown_error_message(directive_name),
+                &[directive.start],
+            );
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::{
     ast::FragmentSpread,
     parser::Spanning,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -22,7 +22,11 @@ where
     ) {
         let spread_name = &spread.item.name;
         if !context.is_known_fragment(spread_name.item) {
-            context.report_error(&error_message(spread_name.item), &[spread_name.start]);
+            context.report_error(
+                RuleErrorCode::KnownFragmentNames,
+                &error_message(spread_name.item),
+                &[spread_name.start],
+            );
         }
     }
 }
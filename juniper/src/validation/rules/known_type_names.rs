@@ -1,7 +1,7 @@
 use crate::{
     ast::{Fragment, InlineFragment, VariableDefinition},
     parser::{SourcePosition, Spanning},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -51,7 +51,11 @@ fn validate_type<'a, S: Debug>(
     location: &SourcePosition,
 ) {
     if ctx.schema.type_by_name(type_name).is_none() {
-        ctx.report_error(&error_message(type_name), &[*location]);
+        ctx.report_error(
+            RuleErrorCode::KnownTypeNames,
+            &error_message(type_name),
+            &[*location],
+        );
     }
 }
 
@@ -1,7 +1,7 @@
 use crate::{
     ast::{Definition, Document, Operation},
     parser::Spanning,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -37,7 +37,11 @@ where
     ) {
         if let Some(operation_count) = self.operation_count {
             if operation_count > 1 && op.item.name.is_none() {
-                ctx.report_error(error_message(), &[op.start]);
+                ctx.report_error(
+                    RuleErrorCode::LoneAnonymousOperation,
+                    error_message(),
+                    &[op.start],
+                );
             }
         }
     }
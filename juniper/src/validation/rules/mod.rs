@@ -25,7 +25,9 @@ mod variables_in_allowed_position;
 
 use crate::{
     ast::Document,
-    validation::{visit, MultiVisitorNil, ValidatorContext},
+    parser::parse_document_source,
+    schema::model::SchemaType,
+    validation::{visit, MultiVisitorNil, RuleError, ValidatorContext},
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -63,3 +65,141 @@ where
 
     visit(&mut mv, ctx, doc)
 }
+
+/// Validates the operations of the `document_source` against the `schema`, returning the errors
+/// found, ordered by their locations.
+///
+/// The document is not executed, so it may be validated against a [`SchemaType`] without
+/// resolvers, like the one built by [`SchemaType::from_introspection()`]. Syntax errors are
+/// reported with the [`RuleErrorCode::Syntax`] code, and stop further validation.
+///
+/// ```
+/// # use juniper::{
+/// #     graphql_object, validation::validate, EmptyMutation, EmptySubscription, RootNode,
+/// #     RuleErrorCode,
+/// # };
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn hello() -> &'static str {
+///         "world"
+///     }
+/// }
+///
+/// let root = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+///
+/// assert!(validate(&root.schema, "{ hello }").is_empty());
+///
+/// let errors = validate(&root.schema, "{ hello goodbye }");
+/// assert_eq!(errors[0].code(), RuleErrorCode::FieldsOnCorrectType);
+/// assert_eq!(errors[0].message(), r#"Unknown field "goodbye" on type "Query""#);
+/// ```
+///
+/// [`RuleErrorCode::Syntax`]: crate::validation::RuleErrorCode::Syntax
+pub fn validate<S>(schema: &SchemaType<S>, document_source: &str) -> Vec<RuleError>
+where
+    S: ScalarValue,
+{
+    let document = match parse_document_source(document_source, schema) {
+        Ok(document) => document,
+        Err(error) => return vec![RuleError::from_parse_error(error)],
+    };
+
+    let mut ctx = ValidatorContext::new(schema, &document);
+    visit_all_rules(&mut ctx, &document);
+    ctx.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::InputValue,
+        executor::Variables,
+        schema::model::RootNode,
+        tests::fixtures::starwars::schema::{Database, Query},
+        types::scalars::{EmptyMutation, EmptySubscription},
+        validation::{validate_variables, RuleErrorCode},
+    };
+
+    use super::validate;
+
+    fn schema<'a>() -> RootNode<'a, Query, EmptyMutation<Database>, EmptySubscription<Database>> {
+        RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        )
+    }
+
+    #[test]
+    fn passes_valid_document() {
+        let schema = schema();
+
+        let errors = validate(
+            &schema.schema,
+            "query($id: String!) { human(id: $id) { name } }",
+        );
+
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn reports_errors_with_codes() {
+        let schema = schema();
+
+        let errors = validate(
+            &schema.schema,
+            "query($id: String!, $unused: Boolean) { human(id: $id) { name age } hero }",
+        );
+
+        let codes = errors.iter().map(|e| e.code()).collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                RuleErrorCode::NoUnusedVariables,
+                RuleErrorCode::FieldsOnCorrectType,
+                RuleErrorCode::ScalarLeafs,
+            ],
+        );
+        assert_eq!(codes[1].as_str(), "FIELDS_ON_CORRECT_TYPE");
+        assert_eq!(
+            errors[1].message(),
+            r#"Unknown field "age" on type "Human""#,
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        let schema = schema();
+
+        let errors = validate(&schema.schema, "{ human(id: ) }");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), RuleErrorCode::Syntax);
+        assert_eq!(errors[0].message(), r#"Unexpected ")""#);
+        assert_eq!(errors[0].locations()[0].column(), 12);
+    }
+
+    #[test]
+    fn validates_variables() {
+        let schema = schema();
+        let doc = "query Human($id: String!) { human(id: $id) { name } }";
+        let mut vars = Variables::new();
+        vars.insert("id".to_owned(), InputValue::scalar("1000"));
+
+        assert_eq!(validate_variables(&schema.schema, doc, None, &vars), vec![],);
+
+        let errors = validate_variables(&schema.schema, doc, None, &Variables::new());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), RuleErrorCode::VariableValue);
+        assert_eq!(
+            errors[0].message(),
+            r#"Variable "$id" of required type "String!" was not provided."#,
+        );
+
+        let errors = validate_variables(&schema.schema, doc, Some("Droid"), &vars);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code(), RuleErrorCode::UnknownOperation);
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use crate::{
     ast::{Document, Fragment, FragmentSpread},
     parser::Spanning,
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{RuleError, RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -112,8 +112,10 @@ impl<'a> CycleDetector<'a> {
                     node
                 };
 
-                self.errors
-                    .push(RuleError::new(&error_message(name), &[err_pos.start]));
+                self.errors.push(
+                    RuleError::new(&error_message(name), &[err_pos.start])
+                        .with_code(RuleErrorCode::NoFragmentCycles),
+                );
             } else if !self.visited.contains(name) {
                 path.push(node);
                 self.detect_from(name, path);
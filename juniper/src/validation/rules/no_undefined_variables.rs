@@ -1,7 +1,7 @@
 use crate::{
     ast::{Document, Fragment, FragmentSpread, InputValue, Operation, VariableDefinition},
     parser::{SourcePosition, Spanning},
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{RuleError, RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::collections::{HashMap, HashSet};
@@ -78,6 +78,7 @@ where
                     .into_iter()
                     .map(|var| {
                         RuleError::new(&error_message(var.item, *op_name), &[var.start, *pos])
+                            .with_code(RuleErrorCode::NoUndefinedVariables)
                     })
                     .collect(),
             );
@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use crate::{
     ast::{Definition, Document, Fragment, FragmentSpread, Operation},
     parser::Spanning,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -65,7 +65,11 @@ where
 
         for fragment in &self.defined_fragments {
             if !reachable.contains(&fragment.item) {
-                ctx.report_error(&error_message(fragment.item), &[fragment.start]);
+                ctx.report_error(
+                    RuleErrorCode::NoUnusedFragments,
+                    &error_message(fragment.item),
+                    &[fragment.start],
+                );
             }
         }
     }
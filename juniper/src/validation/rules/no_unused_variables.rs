@@ -1,7 +1,7 @@
 use crate::{
     ast::{Document, Fragment, FragmentSpread, InputValue, Operation, VariableDefinition},
     parser::Spanning,
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{RuleError, RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::collections::{HashMap, HashSet};
@@ -77,7 +77,10 @@ where
                 def_vars
                     .iter()
                     .filter(|var| !used.contains(var.item))
-                    .map(|var| RuleError::new(&error_message(var.item, *op_name), &[var.start]))
+                    .map(|var| {
+                        RuleError::new(&error_message(var.item, *op_name), &[var.start])
+                            .with_code(RuleErrorCode::NoUnusedVariables)
+                    })
                     .collect(),
             );
         }
@@ -4,7 +4,7 @@ use crate::{
     ast::{Arguments, Definition, Document, Field, Fragment, FragmentSpread, Selection, Type},
     parser::{SourcePosition, Spanning},
     schema::meta::{Field as FieldType, MetaType},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -706,7 +706,11 @@ where
             self.find_conflicts_within_selection_set(ctx.parent_type(), selection_set, ctx)
         {
             p1.append(&mut p2);
-            ctx.report_error(&error_message(&reason_name, &reason_msg), &p1);
+            ctx.report_error(
+                RuleErrorCode::OverlappingFieldsCanBeMerged,
+                &error_message(&reason_name, &reason_msg),
+                &p1,
+            );
         }
     }
 }
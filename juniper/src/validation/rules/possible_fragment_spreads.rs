@@ -4,7 +4,7 @@ use crate::{
     ast::{Definition, Document, FragmentSpread, InlineFragment},
     parser::Spanning,
     schema::meta::MetaType,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 use std::collections::HashMap;
@@ -47,6 +47,7 @@ where
         ) {
             if !ctx.schema.type_overlap(parent_type, frag_type) {
                 ctx.report_error(
+                    RuleErrorCode::PossibleFragmentSpreads,
                     &error_message(
                         None,
                         parent_type.name().unwrap_or("<unknown>"),
@@ -69,6 +70,7 @@ where
         ) {
             if !ctx.schema.type_overlap(parent_type, frag_type) {
                 ctx.report_error(
+                    RuleErrorCode::PossibleFragmentSpreads,
                     &error_message(
                         Some(spread.item.name.item),
                         parent_type.name().unwrap_or("<unknown>"),
@@ -2,7 +2,7 @@ use crate::{
     ast::{Directive, Field},
     parser::Spanning,
     schema::{meta::Field as FieldType, model::DirectiveType},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -34,6 +34,7 @@ where
                         .is_none()
                 {
                     ctx.report_error(
+                        RuleErrorCode::ProvidedNonNullArguments,
                         &field_error_message(
                             field_name,
                             &meta_arg.name,
@@ -68,6 +69,7 @@ where
                         .is_none()
                 {
                     ctx.report_error(
+                        RuleErrorCode::ProvidedNonNullArguments,
                         &directive_error_message(
                             directive_name,
                             &meta_arg.name,
@@ -1,7 +1,7 @@
 use crate::{
     ast::Field,
     parser::Spanning,
-    validation::{RuleError, ValidatorContext, Visitor},
+    validation::{RuleError, RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -37,7 +37,7 @@ where
         };
 
         if let Some(error) = error {
-            ctx.append_errors(vec![error]);
+            ctx.append_errors(vec![error.with_code(RuleErrorCode::ScalarLeafs)]);
         }
     }
 }
@@ -3,7 +3,7 @@ use std::collections::hash_map::{Entry, HashMap};
 use crate::{
     ast::{Directive, Field, InputValue},
     parser::{SourcePosition, Spanning},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -36,7 +36,11 @@ where
     ) {
         match self.known_names.entry(arg_name.item) {
             Entry::Occupied(e) => {
-                ctx.report_error(&error_message(arg_name.item), &[*e.get(), arg_name.start]);
+                ctx.report_error(
+                    RuleErrorCode::UniqueArgumentNames,
+                    &error_message(arg_name.item),
+                    &[*e.get(), arg_name.start],
+                );
             }
             Entry::Vacant(e) => {
                 e.insert(arg_name.start);
@@ -3,7 +3,7 @@ use std::collections::hash_map::{Entry, HashMap};
 use crate::{
     ast::Fragment,
     parser::{SourcePosition, Spanning},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -29,6 +29,7 @@ where
         match self.names.entry(f.item.name.item) {
             Entry::Occupied(e) => {
                 context.report_error(
+                    RuleErrorCode::UniqueFragmentNames,
                     &duplicate_message(f.item.name.item),
                     &[*e.get(), f.item.name.start],
                 );
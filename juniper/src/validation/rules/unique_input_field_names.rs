@@ -3,7 +3,7 @@ use std::collections::hash_map::{Entry, HashMap};
 use crate::{
     ast::InputValue,
     parser::{SourcePosition, Spanning},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -38,6 +38,7 @@ where
             match known_names.entry(&field_name.item) {
                 Entry::Occupied(e) => {
                     ctx.report_error(
+                        RuleErrorCode::UniqueInputFieldNames,
                         &error_message(&field_name.item),
                         &[*e.get(), field_name.start],
                     );
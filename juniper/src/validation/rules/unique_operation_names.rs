@@ -3,7 +3,7 @@ use std::collections::hash_map::{Entry, HashMap};
 use crate::{
     ast::Operation,
     parser::{SourcePosition, Spanning},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -29,7 +29,11 @@ where
         if let Some(ref op_name) = op.item.name {
             match self.names.entry(op_name.item) {
                 Entry::Occupied(e) => {
-                    ctx.report_error(&error_message(op_name.item), &[*e.get(), op.start]);
+                    ctx.report_error(
+                        RuleErrorCode::UniqueOperationNames,
+                        &error_message(op_name.item),
+                        &[*e.get(), op.start],
+                    );
                 }
                 Entry::Vacant(e) => {
                     e.insert(op.start);
@@ -3,7 +3,7 @@ use std::collections::hash_map::{Entry, HashMap};
 use crate::{
     ast::{Operation, VariableDefinition},
     parser::{SourcePosition, Spanning},
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -36,7 +36,11 @@ where
     ) {
         match self.names.entry(var_name.item) {
             Entry::Occupied(e) => {
-                ctx.report_error(&error_message(var_name.item), &[*e.get(), var_name.start]);
+                ctx.report_error(
+                    RuleErrorCode::UniqueVariableNames,
+                    &error_message(var_name.item),
+                    &[*e.get(), var_name.start],
+                );
             }
             Entry::Vacant(e) => {
                 e.insert(var_name.start);
@@ -1,7 +1,7 @@
 use crate::{
     ast::VariableDefinition,
     parser::Spanning,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -26,6 +26,7 @@ where
         {
             if !var_type.is_input() {
                 ctx.report_error(
+                    RuleErrorCode::VariablesAreInputTypes,
                     &error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                     &[var_def.var_type.start],
                 );
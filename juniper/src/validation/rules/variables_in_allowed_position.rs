@@ -7,7 +7,7 @@ use std::{
 use crate::{
     ast::{Document, Fragment, FragmentSpread, Operation, Type, VariableDefinition},
     parser::Spanning,
-    validation::{ValidatorContext, Visitor},
+    validation::{RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
 };
 
@@ -66,6 +66,7 @@ impl<'a, S: Debug> VariableInAllowedPosition<'a, S> {
 
                     if !ctx.schema.is_subtype(&expected_type, var_type) {
                         ctx.report_error(
+                            RuleErrorCode::VariablesInAllowedPosition,
                             &error_message(
                                 var_name.item,
                                 &format!("{}", expected_type),
@@ -12,7 +12,7 @@ use crate::{
         base::{GraphQLType, GraphQLValue},
         scalars::ID,
     },
-    validation::{visit, MultiVisitorNil, RuleError, ValidatorContext, Visitor},
+    validation::{visit, MultiVisitorNil, RuleError, RuleErrorCode, ValidatorContext, Visitor},
     value::ScalarValue,
     GraphQLInputObject,
 };
@@ -927,9 +927,15 @@ pub fn expect_fails_rule_with_schema<'a, Q, M, V, F, S>(
 {
     let errs = validate(r, m, crate::EmptySubscription::<S>::new(), q, factory);
 
+    let without_codes = |errs: &[RuleError]| {
+        errs.iter()
+            .map(|e| (e.locations().to_vec(), e.message().to_owned()))
+            .collect::<Vec<_>>()
+    };
+
     if errs.is_empty() {
         panic!("Expected rule to fail, but no errors were found");
-    } else if errs != expected_errors {
+    } else if without_codes(&errs) != without_codes(expected_errors) {
         println!("==> Expected errors:");
         print_errors(expected_errors);
 
@@ -937,6 +943,8 @@ pub fn expect_fails_rule_with_schema<'a, Q, M, V, F, S>(
         print_errors(&errs);
 
         panic!("Unexpected set of errors found");
+    } else if let Some(e) = errs.iter().find(|e| e.code() == RuleErrorCode::Custom) {
+        panic!("Error reported without a code: {}", e);
     }
 }
 
@@ -12,9 +12,13 @@ use crate::{
     parser::Spanning,
 };
 
+#[cfg(feature = "wide-integers")]
+pub(crate) use self::scalar::MAX_SAFE_INTEGER;
 pub use self::{
     object::Object,
-    scalar::{DefaultScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue},
+    scalar::{
+        DefaultScalarValue, LongScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue,
+    },
 };
 
 /// Serializable value returned from query and field execution.
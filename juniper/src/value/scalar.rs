@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{convert::TryFrom as _, fmt};
 
 use serde::{de, ser::Serialize};
 
@@ -7,6 +7,10 @@ use crate::{
     GraphQLScalarValue,
 };
 
+/// Largest integer represented exactly by an `f64`, and so by JSON numbers in
+/// most clients.
+pub(crate) const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
 /// The result of converting a string into a scalar value
 pub type ParseScalarResult<'a, S = DefaultScalarValue> = Result<S, ParseError<'a>>;
 
@@ -245,10 +249,38 @@ pub trait ScalarValue:
     /// scalar values.
     fn as_boolean(&self) -> Option<bool>;
 
+    /// Convert the given scalar value into a 64 bit integer value
+    ///
+    /// This function is used for implementing `GraphQLValue` for `i64`, `u64`
+    /// and `i128` for all scalar values. The default implementation only
+    /// converts the integers returned by [`ScalarValue::as_int`], so
+    /// implementations able to carry 64 bit integers should override it.
+    fn as_i64(&self) -> Option<i64> {
+        self.as_int().map(i64::from)
+    }
+
+    /// Creates a new [`ScalarValue`] from a 64 bit integer value.
+    ///
+    /// The default implementation represents integers not fitting into an
+    /// `i32` as floats while an `f64` holds them exactly (up to
+    /// 2<sup>53</sup> - 1 in magnitude), and as strings otherwise, so no
+    /// precision is lost by scalar values unable to carry 64 bit integers.
+    fn from_i64(i: i64) -> Self {
+        if let Ok(i) = i32::try_from(i) {
+            Self::from(i)
+        } else if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&i) {
+            Self::from(i as f64)
+        } else {
+            Self::from(i.to_string())
+        }
+    }
+
     /// Converts this [`ScalarValue`] into another one.
     fn into_another<S: ScalarValue>(self) -> S {
         if let Some(i) = self.as_int() {
             S::from(i)
+        } else if let Some(i) = self.as_i64() {
+            S::from_i64(i)
         } else if let Some(f) = self.as_float() {
             S::from(f)
         } else if let Some(b) = self.as_boolean() {
@@ -396,3 +428,148 @@ impl<'de> de::Visitor<'de> for DefaultScalarValueVisitor {
         Ok(DefaultScalarValue::String(value))
     }
 }
+
+/// A scalar value representation carrying 64 bit integers end to end.
+///
+/// Same as [`DefaultScalarValue`], but integers not fitting into an `i32` are
+/// represented as [`LongScalarValue::Long`] instead of strings or floats, both
+/// when parsed from a query or deserialized and when serialized back.
+#[derive(Debug, PartialEq, Clone, GraphQLScalarValue)]
+#[allow(missing_docs)]
+pub enum LongScalarValue {
+    Int(i32),
+    Long(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+}
+
+impl ScalarValue for LongScalarValue {
+    type Visitor = LongScalarValueVisitor;
+
+    fn as_int(&self) -> Option<i32> {
+        match *self {
+            Self::Int(ref i) => Some(*i),
+            Self::Long(ref l) => i32::try_from(*l).ok(),
+            _ => None,
+        }
+    }
+
+    fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Int(ref i) => Some(i64::from(*i)),
+            Self::Long(ref l) => Some(*l),
+            _ => None,
+        }
+    }
+
+    fn from_i64(i: i64) -> Self {
+        i32::try_from(i).map(Self::Int).unwrap_or(Self::Long(i))
+    }
+
+    fn as_float(&self) -> Option<f64> {
+        match *self {
+            Self::Int(ref i) => Some(*i as f64),
+            Self::Long(ref l) => Some(*l as f64),
+            Self::Float(ref f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match *self {
+            Self::String(ref s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    fn as_string(&self) -> Option<String> {
+        match *self {
+            Self::String(ref s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn into_string(self) -> Option<String> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_boolean(&self) -> Option<bool> {
+        match *self {
+            Self::Boolean(ref b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    fn into_another<S: ScalarValue>(self) -> S {
+        match self {
+            Self::Int(i) => S::from(i),
+            Self::Long(l) => S::from_i64(l),
+            Self::Float(f) => S::from(f),
+            Self::String(s) => S::from(s),
+            Self::Boolean(b) => S::from(b),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for LongScalarValue {
+    fn from(s: &'a str) -> Self {
+        Self::String(s.into())
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug)]
+pub struct LongScalarValueVisitor;
+
+impl<'de> de::Visitor<'de> for LongScalarValueVisitor {
+    type Value = LongScalarValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a valid input value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<LongScalarValue, E> {
+        Ok(LongScalarValue::Boolean(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<LongScalarValue, E>
+    where
+        E: de::Error,
+    {
+        Ok(LongScalarValue::from_i64(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<LongScalarValue, E>
+    where
+        E: de::Error,
+    {
+        if value <= i64::MAX as u64 {
+            self.visit_i64(value as i64)
+        } else {
+            // Browser's JSON.stringify serialize all numbers having no
+            // fractional part as integers (no decimal point), so we
+            // must parse large integers as floating point otherwise
+            // we would error on transferring large floating point
+            // numbers.
+            Ok(LongScalarValue::Float(value as f64))
+        }
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<LongScalarValue, E> {
+        Ok(LongScalarValue::Float(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<LongScalarValue, E>
+    where
+        E: de::Error,
+    {
+        self.visit_string(value.into())
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<LongScalarValue, E> {
+        Ok(LongScalarValue::String(value))
+    }
+}
@@ -41,41 +41,27 @@ Check the LICENSE file for details.
 #![doc(html_root_url = "https://docs.rs/juniper_actix/0.1.0")]
 
 use actix_web::{
-    error::JsonPayloadError, http::Method, web, Error, FromRequest, HttpMessage, HttpRequest,
-    HttpResponse,
+    http::{
+        header::{
+            ACCEPT, ACCEPT_ENCODING, ALLOW, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, VARY,
+        },
+        Method, StatusCode,
+    },
+    web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse,
 };
+use futures::StreamExt as _;
 use juniper::{
     http::{
-        graphiql::graphiql_source, playground::playground_source, GraphQLBatchRequest,
-        GraphQLRequest,
+        graphiql::graphiql_source,
+        multipart::{GraphQLMultipartRequest, MultipartError, MultipartLimits, MultipartParser},
+        negotiation::{self, ContentEncoding, CsrfPrevention, RequestError, ResponseFormat},
+        playground::playground_source,
+        streaming::JsonChunks,
+        GraphQLBatchRequest, GraphQLBatchResponse, Method as GraphQLMethod,
     },
     ScalarValue,
 };
-use serde::Deserialize;
-
-#[derive(Deserialize, Clone, PartialEq, Debug)]
-#[serde(deny_unknown_fields)]
-struct GetGraphQLRequest {
-    query: String,
-    #[serde(rename = "operationName")]
-    operation_name: Option<String>,
-    variables: Option<String>,
-}
-
-impl<S> From<GetGraphQLRequest> for GraphQLRequest<S>
-where
-    S: ScalarValue,
-{
-    fn from(get_req: GetGraphQLRequest) -> Self {
-        let GetGraphQLRequest {
-            query,
-            operation_name,
-            variables,
-        } = get_req;
-        let variables = variables.map(|s| serde_json::from_str(&s).unwrap());
-        Self::new(query, operation_name, variables)
-    }
-}
+use std::future::Future;
 
 /// Actix Web GraphQL Handler for GET and POST requests
 pub async fn graphql_handler<Query, Mutation, Subscription, CtxT, S>(
@@ -92,12 +78,12 @@ where
     Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
     Subscription::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
 {
     match *req.method() {
         Method::POST => post_graphql_handler(schema, context, req, payload).await,
         Method::GET => get_graphql_handler(schema, context, req).await,
-        _ => Err(actix_web::error::UrlGenerationError::ResourceNotFound.into()),
+        _ => Ok(error_response(RequestError::MethodNotAllowed)),
     }
 }
 /// Actix GraphQL Handler for GET requests
@@ -114,19 +100,18 @@ where
     Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
     Subscription::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
 {
-    let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
-    let req = GraphQLRequest::from(get_req.into_inner());
-    let gql_response = req.execute(schema, context).await;
-    let body_response = serde_json::to_string(&gql_response)?;
-    let mut response = match gql_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
+    let negotiated = match negotiate_response(&req) {
+        Ok(negotiated) => negotiated,
+        Err(e) => return Ok(error_response(e)),
     };
-    Ok(response
-        .content_type("application/json")
-        .body(body_response))
+    let req = match negotiation::parse_get_request(req.query_string()) {
+        Ok(req) => GraphQLBatchRequest::Single(req),
+        Err(e) => return Ok(error_response(e)),
+    };
+    let gql_batch_response = req.execute_via(GraphQLMethod::Get, schema, context).await;
+    Ok(json_response(gql_batch_response, negotiated))
 }
 
 /// Actix GraphQL Handler for POST requests
@@ -144,29 +129,194 @@ where
     Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
     Subscription::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let negotiated = match negotiate_response(&req) {
+        Ok(negotiated) => negotiated,
+        Err(e) => return Ok(error_response(e)),
+    };
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|ct| ct.to_str().ok());
+    if !content_type.is_some_and(negotiation::is_supported_content_type) {
+        return Ok(error_response(RequestError::UnsupportedMediaType));
+    }
+    let body = web::Bytes::from_request(&req, &mut payload.into_inner()).await?;
+    let req = match negotiation::parse_post_request::<S>(content_type, &body) {
+        Ok(req) => req,
+        Err(e) => return Ok(error_response(e)),
+    };
+    let gql_batch_response = req.execute(schema, context).await;
+    Ok(json_response(gql_batch_response, negotiated))
+}
+
+/// Wraps the given handler, like [`graphql_handler`] or
+/// [`graphql_handler_with_uploads`], so it replies with `400 Bad Request` to
+/// the requests blocked by the given [`CsrfPrevention`] guard instead of
+/// executing them.
+///
+/// Only `GET` and `POST` requests are checked, the other ones are passed to
+/// the `handler` as is.
+///
+/// Example:
+///
+/// ```
+/// # use actix_web::{web, Error, HttpRequest, HttpResponse};
+/// # use juniper::{
+/// #     http::negotiation::CsrfPrevention, EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper::tests::fixtures::starwars::schema::{Database, Query};
+/// # use juniper_actix::{graphql_handler, with_csrf_prevention};
+/// #
+/// # type Schema =
+/// #     RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+/// #
+/// async fn graphql(
+///     req: HttpRequest,
+///     payload: web::Payload,
+///     schema: web::Data<Schema>,
+///     csrf_prevention: web::Data<CsrfPrevention>,
+/// ) -> Result<HttpResponse, Error> {
+///     let context = Database::new();
+///     with_csrf_prevention(&csrf_prevention, req, |req| {
+///         graphql_handler(&schema, &context, req, payload)
+///     })
+///     .await
+/// }
+/// ```
+pub async fn with_csrf_prevention<F, Fut>(
+    prevention: &CsrfPrevention,
+    req: HttpRequest,
+    handler: F,
+) -> Result<HttpResponse, Error>
+where
+    F: FnOnce(HttpRequest) -> Fut,
+    Fut: Future<Output = Result<HttpResponse, Error>>,
 {
-    let req = match req.content_type() {
-        "application/json" => {
-            let body = String::from_request(&req, &mut payload.into_inner()).await?;
-            serde_json::from_str::<GraphQLBatchRequest<S>>(&body)
-                .map_err(JsonPayloadError::Deserialize)
+    if matches!(*req.method(), Method::GET | Method::POST) {
+        let header = |name: &str| req.headers().get(name).and_then(|v| v.to_str().ok());
+        if let Err(e) = prevention.check(header(CONTENT_TYPE.as_str()), header) {
+            return Ok(error_response(e));
         }
-        "application/graphql" => {
-            let body = String::from_request(&req, &mut payload.into_inner()).await?;
-            Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
-                body, None, None,
-            )))
+    }
+    handler(req).await
+}
+
+/// Negotiates the [`ResponseFormat`] and the [`ContentEncoding`] from the
+/// `Accept` and `Accept-Encoding` headers of the given [`HttpRequest`].
+fn negotiate_response(
+    req: &HttpRequest,
+) -> Result<(ResponseFormat, ContentEncoding), RequestError> {
+    let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+    let format = ResponseFormat::negotiate(header(ACCEPT))?;
+    Ok((format, ContentEncoding::negotiate(header(ACCEPT_ENCODING))))
+}
+
+/// Creates a response streaming the given [`GraphQLBatchResponse`] as JSON,
+/// compressed with the negotiated [`ContentEncoding`].
+fn json_response<S: ScalarValue + 'static>(
+    response: GraphQLBatchResponse<'_, S>,
+    (format, encoding): (ResponseFormat, ContentEncoding),
+) -> HttpResponse {
+    let mut builder = HttpResponse::build(
+        StatusCode::from_u16(negotiation::status_code(&response, format)).unwrap(),
+    );
+    builder.content_type(format.content_type());
+    if let Some(coding) = encoding.header_value() {
+        builder
+            .insert_header((CONTENT_ENCODING, coding))
+            .insert_header((VARY, "accept-encoding"));
+    }
+    if let Some(policy) = response.cache_policy() {
+        builder.insert_header((CACHE_CONTROL, policy.header_value()));
+    }
+    if let Some(methods) = negotiation::allowed_methods(&response) {
+        builder.insert_header((ALLOW, methods));
+    }
+    let chunks = JsonChunks::new(response).encoded(encoding);
+    builder.streaming(futures::stream::iter(chunks).map(|c| c.map(web::Bytes::from)))
+}
+
+fn error_response(err: RequestError) -> HttpResponse {
+    let mut builder = HttpResponse::build(StatusCode::from_u16(err.status_code()).unwrap());
+    if let Some(methods) = err.allowed_methods() {
+        builder.insert_header((ALLOW, methods));
+    }
+    builder
+        .content_type("text/plain; charset=utf-8")
+        .body(err.to_string())
+}
+
+/// Actix Web GraphQL Handler for GET and POST requests, additionally accepting
+/// file uploads via [GraphQL multipart requests][1] limited by the given
+/// `limits`.
+///
+/// Files are accepted as arguments of the [`Upload`] type. Apart from that,
+/// this handler behaves exactly like [`graphql_handler`].
+///
+/// [`Upload`]: juniper::http::multipart::Upload
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+pub async fn graphql_handler_with_uploads<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: HttpRequest,
+    payload: actix_web::web::Payload,
+    limits: MultipartLimits,
+) -> Result<HttpResponse, Error>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    if req.method() != Method::POST || req.content_type() != "multipart/form-data" {
+        return graphql_handler(schema, context, req, payload).await;
+    }
+
+    let negotiated = match negotiate_response(&req) {
+        Ok(negotiated) => negotiated,
+        Err(e) => return Ok(error_response(e)),
+    };
+    let req = match parse_multipart_req::<S>(&req, payload, limits).await {
+        Ok(req) => req,
+        Err(e) => {
+            let mut response = match e.downcast_ref::<MultipartError>() {
+                Some(e) if e.is_limit_exceeded() => HttpResponse::PayloadTooLarge(),
+                _ => HttpResponse::BadRequest(),
+            };
+            return Ok(response.body(e.to_string()));
         }
-        _ => Err(JsonPayloadError::ContentType),
-    }?;
-    let gql_batch_response = req.execute(schema, context).await;
-    let gql_response = serde_json::to_string(&gql_batch_response)?;
-    let mut response = match gql_batch_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
     };
-    Ok(response.content_type("application/json").body(gql_response))
+    let gql_batch_response = req.execute(schema, context).await;
+    Ok(json_response(gql_batch_response, negotiated))
+}
+
+async fn parse_multipart_req<S: ScalarValue>(
+    req: &HttpRequest,
+    payload: actix_web::web::Payload,
+    limits: MultipartLimits,
+) -> anyhow::Result<GraphQLMultipartRequest<S>> {
+    let mut multipart = actix_multipart::Multipart::new(req.headers(), payload);
+    let mut parser = MultipartParser::new(limits);
+    while let Some(field) = multipart.next().await {
+        let mut field = field.map_err(|e| anyhow::anyhow!("{}", e))?;
+        let disposition = field.content_disposition();
+        parser.start_part(
+            disposition.and_then(|d| d.get_name()).unwrap_or_default(),
+            disposition.and_then(|d| d.get_filename()).map(Into::into),
+            field.content_type().map(ToString::to_string),
+        )?;
+        while let Some(chunk) = field.next().await {
+            parser.push_chunk(&chunk.map_err(|e| anyhow::anyhow!("{}", e))?)?;
+        }
+        parser.end_part()?;
+    }
+    Ok(parser.finish()?)
 }
 
 /// Create a handler that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint
@@ -468,7 +618,6 @@ pub mod subscriptions {
 
 #[cfg(test)]
 mod tests {
-    use actix_http::body::AnyBody;
     use actix_web::{dev::ServiceResponse, http, http::header::CONTENT_TYPE, test, web::Data, App};
     use juniper::{
         http::tests::{run_http_test_suite, HttpIntegration, TestResponse},
@@ -482,11 +631,26 @@ mod tests {
     type Schema =
         juniper::RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
 
+    async fn take_response_body_bytes(resp: &mut ServiceResponse) -> web::Bytes {
+        let (req, res) = std::mem::replace(
+            resp,
+            ServiceResponse::new(
+                test::TestRequest::default().to_http_request(),
+                HttpResponse::Ok().finish(),
+            ),
+        )
+        .into_parts();
+        let (res, body) = res.into_parts();
+        let bytes = actix_web::body::to_bytes(body).await.ok().unwrap();
+        *resp = ServiceResponse::new(
+            req,
+            res.set_body(actix_web::body::BoxBody::new(bytes.clone())),
+        );
+        bytes
+    }
+
     async fn take_response_body_string(resp: &mut ServiceResponse) -> String {
-        match resp.response().body() {
-            AnyBody::Bytes(body) => String::from_utf8(body.to_vec()).unwrap(),
-            _ => String::from(""),
-        }
+        String::from_utf8(take_response_body_bytes(resp).await.to_vec()).unwrap()
     }
 
     async fn index(
@@ -498,6 +662,19 @@ mod tests {
         graphql_handler(&schema, &context, req, payload).await
     }
 
+    async fn guarded_index(
+        req: HttpRequest,
+        payload: actix_web::web::Payload,
+        schema: web::Data<Schema>,
+        csrf_prevention: web::Data<CsrfPrevention>,
+    ) -> Result<HttpResponse, Error> {
+        let context = Database::new();
+        with_csrf_prevention(&csrf_prevention, req, |req| {
+            graphql_handler(&schema, &context, req, payload)
+        })
+        .await
+    }
+
     #[actix_web::rt::test]
     async fn graphiql_response_does_not_panic() {
         let result = graphiql_handler("/abcd", None).await;
@@ -652,6 +829,93 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "compression")]
+    #[actix_web::rt::test]
+    async fn graphql_response_is_compressed() {
+        use std::io::Read as _;
+
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(index)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .append_header(("accept-encoding", "deflate, gzip"))
+            .uri("/?query=%7B%20hero%20%7B%20name%20%7D%20%7D")
+            .to_request();
+        let mut resp = test::call_service(&mut app, req).await;
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(resp.headers().get("content-encoding").unwrap(), "gzip");
+        let body = take_response_body_bytes(&mut resp).await;
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json, r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+    }
+
+    #[actix_web::rt::test]
+    async fn graphql_response_has_cache_control() {
+        struct CachedQuery;
+
+        #[juniper::graphql_object]
+        impl CachedQuery {
+            #[graphql(cache_control(max_age = 60))]
+            fn motd() -> &'static str {
+                "Hello"
+            }
+
+            fn version() -> &'static str {
+                "1.0"
+            }
+        }
+
+        type CachedSchema = RootNode<'static, CachedQuery, EmptyMutation, EmptySubscription>;
+
+        async fn cached_index(
+            req: HttpRequest,
+            payload: web::Payload,
+            schema: web::Data<CachedSchema>,
+        ) -> Result<HttpResponse, Error> {
+            graphql_handler(&schema, &(), req, payload).await
+        }
+
+        let schema: CachedSchema =
+            RootNode::new(CachedQuery, EmptyMutation::new(), EmptySubscription::new());
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(cached_index)),
+        )
+        .await;
+
+        for (query, cache_control) in [
+            ("%7Bmotd%7D", Some("max-age=60, public")),
+            ("%7Bmotd%20version%7D", None),
+        ] {
+            let req = test::TestRequest::get()
+                .uri(&format!("/?query={}", query))
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+
+            assert_eq!(resp.status(), http::StatusCode::OK);
+            assert_eq!(
+                resp.headers()
+                    .get("cache-control")
+                    .map(|v| v.to_str().unwrap()),
+                cache_control,
+            );
+        }
+    }
+
     #[actix_web::rt::test]
     async fn batch_request_works() {
         use juniper::{
@@ -696,7 +960,54 @@ mod tests {
         );
     }
 
-    #[test]
+    #[actix_web::rt::test]
+    async fn csrf_prevention_blocks_simple_requests() {
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .app_data(Data::new(CsrfPrevention::new()))
+                .route("/", web::get().to(guarded_index))
+                .route("/", web::post().to(guarded_index)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/?query=%7Bhero%7Bname%7D%7D")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .append_header(("content-type", "text/plain"))
+            .set_payload(r#"{"query": "{hero{name}}"}"#)
+            .uri("/")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .append_header(("x-apollo-operation-name", "Hero"))
+            .uri("/?query=%7Bhero%7Bname%7D%7D")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .append_header(("content-type", "application/json"))
+            .set_payload(r#"{"query": "{hero{name}}"}"#)
+            .uri("/")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    #[::core::prelude::v1::test]
     fn batch_request_deserialization_can_fail() {
         let json = r#"blah"#;
         let result: Result<GraphQLBatchRequest, _> = serde_json::from_str(json);
@@ -750,23 +1061,169 @@ mod tests {
                     .uri(url),
             )
         }
+
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> TestResponse {
+            let mut req = test::TestRequest::default()
+                .method(method.parse().unwrap())
+                .set_payload(body.to_string())
+                .uri(url);
+            for (name, value) in headers {
+                req = req.append_header((*name, *value));
+            }
+            self.make_request(req)
+        }
     }
 
     async fn make_test_response(mut resp: ServiceResponse) -> TestResponse {
         let body = take_response_body_string(&mut resp).await;
         let status_code = resp.status().as_u16();
         let content_type = resp.headers().get(CONTENT_TYPE).unwrap();
+        let allow = resp
+            .headers()
+            .get(ALLOW)
+            .map(|v| v.to_str().unwrap().to_string());
         TestResponse {
             status_code: status_code as i32,
             body: Some(body),
             content_type: content_type.to_str().unwrap().to_string(),
+            allow,
         }
     }
 
-    #[test]
+    #[::core::prelude::v1::test]
     fn test_actix_web_integration() {
         run_http_test_suite(&TestActixWebIntegration);
     }
+
+    mod uploads {
+        use actix_web::{http, test, web, App, HttpRequest};
+        use juniper::{
+            graphql_object,
+            http::{
+                multipart::{MultipartLimits, Upload},
+                tests as http_tests,
+            },
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        use super::take_response_body_string;
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            async fn upload(file: Upload) -> String {
+                let contents = file.to_bytes();
+                format!(
+                    "{}: {}",
+                    file.filename().unwrap_or_default(),
+                    String::from_utf8(contents).unwrap(),
+                )
+            }
+        }
+
+        async fn execute(
+            content_type: &str,
+            body: String,
+            limits: MultipartLimits,
+        ) -> (http::StatusCode, String) {
+            let handler = move |req: HttpRequest, payload: web::Payload| async move {
+                let schema = RootNode::new(
+                    Query,
+                    EmptyMutation::<()>::new(),
+                    EmptySubscription::<()>::new(),
+                );
+                super::super::graphql_handler_with_uploads(&schema, &(), req, payload, limits).await
+            };
+            let app = test::init_service(App::new().route("/", web::post().to(handler))).await;
+            let req = test::TestRequest::post()
+                .uri("/")
+                .append_header(("content-type", content_type))
+                .set_payload(body)
+                .to_request();
+
+            let mut resp = test::call_service(&app, req).await;
+            (resp.status(), take_response_body_string(&mut resp).await)
+        }
+
+        const OPERATIONS: &str =
+            r#"{"query": "query($f: Upload!) { upload(file: $f) }", "variables": {"f": null}}"#;
+
+        #[actix_web::rt::test]
+        async fn accepts_uploads() {
+            let body = http_tests::multipart_body(&[
+                ("operations", None, OPERATIONS),
+                ("map", None, r#"{"0": ["variables.f"]}"#),
+                ("0", Some("a.txt"), "hello"),
+            ]);
+
+            let (status, body) = execute(
+                http_tests::MULTIPART_CONTENT_TYPE,
+                body,
+                MultipartLimits::default(),
+            )
+            .await;
+
+            assert_eq!(status, http::StatusCode::OK);
+            assert_eq!(body, r#"{"data":{"upload":"a.txt: hello"}}"#);
+        }
+
+        #[actix_web::rt::test]
+        async fn enforces_limits() {
+            let body = http_tests::multipart_body(&[
+                ("operations", None, OPERATIONS),
+                ("map", None, r#"{"0": ["variables.f"]}"#),
+                ("0", Some("a.txt"), "hello"),
+            ]);
+
+            let (status, body) = execute(
+                http_tests::MULTIPART_CONTENT_TYPE,
+                body,
+                MultipartLimits::new().max_file_size(4),
+            )
+            .await;
+
+            assert_eq!(status, http::StatusCode::PAYLOAD_TOO_LARGE);
+            assert_eq!(body, "Request part too large, at most 4 bytes allowed");
+        }
+
+        #[actix_web::rt::test]
+        async fn rejects_malformed_requests() {
+            let body = http_tests::multipart_body(&[
+                ("operations", None, OPERATIONS),
+                ("map", None, r#"{"0": ["variables.f"]}"#),
+            ]);
+
+            let (status, body) = execute(
+                http_tests::MULTIPART_CONTENT_TYPE,
+                body,
+                MultipartLimits::default(),
+            )
+            .await;
+
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+            assert_eq!(body, "Missing file: 0");
+        }
+
+        #[actix_web::rt::test]
+        async fn handles_regular_requests() {
+            let (status, body) = execute(
+                "application/json",
+                r#"{"query": "{ __typename }"}"#.into(),
+                MultipartLimits::default(),
+            )
+            .await;
+
+            assert_eq!(status, http::StatusCode::OK);
+            assert_eq!(body, r#"{"data":{"__typename":"Query"}}"#);
+        }
+    }
 }
 
 #[cfg(feature = "subscriptions")]
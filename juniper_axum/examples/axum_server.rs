@@ -0,0 +1,42 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::WebSocketUpgrade, response::Response, routing::get, Extension, Router};
+use juniper::{
+    tests::fixtures::starwars::schema::{Database, Query, Subscription},
+    EmptyMutation, RootNode,
+};
+use juniper_axum::{playground, subscriptions::subscriptions_handler, GraphQL};
+use juniper_graphql_ws::ConnectionConfig;
+
+type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+async fn subscriptions(
+    Extension(schema): Extension<Arc<Schema>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    subscriptions_handler(ws, schema, ConnectionConfig::new(Database::new()))
+}
+
+#[tokio::main]
+async fn main() {
+    let schema = Arc::new(Schema::new(Query, EmptyMutation::new(), Subscription));
+
+    let app = Router::new()
+        .route_service(
+            "/graphql",
+            GraphQL::new(schema.clone(), |_: &_| Database::new()),
+        )
+        .route("/subscriptions", get(subscriptions))
+        .route("/", get(playground("/graphql", Some("/subscriptions"))))
+        .layer(Extension(schema));
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    println!("Listening on http://{}", addr);
+
+    if let Err(e) = axum::Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await
+    {
+        eprintln!("server error: {}", e)
+    }
+}
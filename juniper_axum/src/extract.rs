@@ -0,0 +1,171 @@
+//! Extraction of GraphQL requests from [`axum`] requests.
+
+use axum::{
+    async_trait,
+    body::{Bytes, HttpBody},
+    extract::FromRequest,
+    http::{header, Request},
+    response::{IntoResponse as _, Response},
+    BoxError,
+};
+use juniper::{
+    http::{
+        negotiation::{self, ContentEncoding, ResponseFormat},
+        GraphQLBatchRequest, Method,
+    },
+    DefaultScalarValue, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
+    ScalarValue,
+};
+
+use crate::{error_response, response::JuniperResponse};
+
+/// Extractor of a [`GraphQLBatchRequest`] from a GET or POST request.
+///
+/// Requests failing to be parsed are rejected with the status code chosen by
+/// [`negotiation`], so a handler taking this extractor behaves the same way as
+/// the other Juniper integrations:
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use axum::{
+///     response::{IntoResponse as _, Response},
+///     routing::post,
+///     Extension, Router,
+/// };
+/// use juniper::{
+///     tests::fixtures::starwars::schema::{Database, Query},
+///     EmptyMutation, EmptySubscription, RootNode,
+/// };
+/// use juniper_axum::extract::JuniperRequest;
+///
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// async fn graphql(Extension(schema): Extension<Arc<Schema>>, req: JuniperRequest) -> Response {
+///     let context = Database::new();
+///     req.execute(&*schema, &context).await.into_response()
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+///
+/// let app: Router = Router::new()
+///     .route("/graphql", post(graphql).get(graphql))
+///     .layer(Extension(Arc::new(schema)));
+/// ```
+#[derive(Debug)]
+pub struct JuniperRequest<S = DefaultScalarValue>
+where
+    S: ScalarValue,
+{
+    /// Parsed GraphQL request.
+    pub request: GraphQLBatchRequest<S>,
+
+    /// HTTP method the request has been received with.
+    pub method: Method,
+
+    /// Format to serialize the response in, negotiated from the `Accept`
+    /// header.
+    pub format: ResponseFormat,
+
+    /// Coding to compress the response with, negotiated from the
+    /// `Accept-Encoding` header.
+    pub encoding: ContentEncoding,
+}
+
+impl<S> JuniperRequest<S>
+where
+    S: ScalarValue,
+{
+    /// Executes this request synchronously using the specified schema and
+    /// context.
+    ///
+    /// Non-query operations received via GET requests are refused, as
+    /// [`GraphQLBatchRequest::execute_sync_via`] does.
+    pub fn execute_sync<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> JuniperResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        let response = self
+            .request
+            .execute_sync_via(self.method, root_node, context);
+        JuniperResponse(response, self.format, self.encoding)
+    }
+
+    /// Executes this request using the specified schema and context.
+    ///
+    /// Non-query operations received via GET requests are refused, as
+    /// [`GraphQLBatchRequest::execute_via`] does.
+    pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+    ) -> JuniperResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        let response = self
+            .request
+            .execute_via(self.method, root_node, context)
+            .await;
+        JuniperResponse(response, self.format, self.encoding)
+    }
+}
+
+#[async_trait]
+impl<S, St, B> FromRequest<St, B> for JuniperRequest<S>
+where
+    S: ScalarValue + Send,
+    St: Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, state: &St) -> Result<Self, Response> {
+        let (content_type, accept, encoding) = {
+            let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+            (
+                header(header::CONTENT_TYPE).map(ToOwned::to_owned),
+                header(header::ACCEPT).map(ToOwned::to_owned),
+                ContentEncoding::negotiate(header(header::ACCEPT_ENCODING)),
+            )
+        };
+        let format = ResponseFormat::negotiate(accept.as_deref()).map_err(error_response)?;
+        let method = negotiation::parse_method(req.method().as_str()).map_err(error_response)?;
+        let query = req.uri().query().map(ToOwned::to_owned);
+
+        let body = match method {
+            Method::Get => Bytes::new(),
+            Method::Post => Bytes::from_request(req, state)
+                .await
+                .map_err(|e| e.into_response())?,
+        };
+        let request = match method {
+            Method::Get => negotiation::parse_get_request(query.as_deref().unwrap_or_default())
+                .map(GraphQLBatchRequest::Single),
+            Method::Post => negotiation::parse_post_request(content_type.as_deref(), &body),
+        }
+        .map_err(error_response)?;
+
+        Ok(Self {
+            request,
+            method,
+            format,
+            encoding,
+        })
+    }
+}
@@ -0,0 +1,588 @@
+/*!
+
+# juniper_axum
+
+This repository contains the [Axum][Axum] web server integration for
+[Juniper][Juniper], a [GraphQL][GraphQL] implementation for Rust.
+
+It provides:
+- the [`JuniperRequest`] extractor and the [`JuniperResponse`] for writing
+  GraphQL handlers;
+- the [`GraphQL`] [`tower_service::Service`] executing GraphQL requests, which
+  can be mounted into an Axum router or served by any [Tower][Tower] compatible
+  server;
+- the [`subscriptions::subscriptions_handler`] serving GraphQL subscriptions
+  over WebSocket connections, when the `subscriptions` feature is enabled;
+- the [`graphiql`] and [`playground`] handlers.
+
+## Documentation
+
+For documentation, including guides and examples, check out [Juniper][Juniper].
+
+A basic usage example can also be found in the [API documentation][documentation].
+
+## Examples
+
+Check [examples/axum_server.rs][example] for example code of a working Axum
+server with GraphQL handlers.
+
+## Links
+
+* [Juniper][Juniper]
+* [API Reference][documentation]
+* [Axum][Axum]
+
+## License
+
+This project is under the BSD-2 license.
+
+Check the LICENSE file for details.
+
+[Axum]: https://github.com/tokio-rs/axum
+[Tower]: https://github.com/tower-rs/tower
+[Juniper]: https://github.com/graphql-rust/juniper
+[GraphQL]: http://graphql.org
+[documentation]: https://docs.rs/juniper_axum
+[example]: https://github.com/graphql-rust/juniper/blob/master/juniper_axum/examples/axum_server.rs
+*/
+
+#![deny(missing_docs)]
+#![deny(warnings)]
+#![doc(html_root_url = "https://docs.rs/juniper_axum/0.1.0")]
+
+pub mod extract;
+pub mod response;
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+
+use std::{
+    convert::Infallible,
+    future as std_future,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::HttpBody,
+    extract::FromRequest as _,
+    http::{header, request, HeaderValue, Request, StatusCode},
+    response::{Html, IntoResponse as _, Response},
+    BoxError,
+};
+use futures::future::BoxFuture;
+use juniper::{
+    http::negotiation::RequestError, DefaultScalarValue, GraphQLSubscriptionType, GraphQLType,
+    GraphQLTypeAsync, RootNode, ScalarValue,
+};
+use tower_service::Service;
+
+#[doc(inline)]
+pub use self::{extract::JuniperRequest, response::JuniperResponse};
+
+/// [`tower_service::Service`] executing GraphQL requests in the given schema,
+/// with the context made by the given function from the parts of each request.
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use juniper::{
+///     tests::fixtures::starwars::schema::{Database, Query},
+///     EmptyMutation, EmptySubscription, RootNode,
+/// };
+/// use juniper_axum::{graphiql, GraphQL};
+///
+/// let schema = RootNode::new(
+///     Query,
+///     EmptyMutation::<Database>::new(),
+///     EmptySubscription::<Database>::new(),
+/// );
+///
+/// let app: Router = Router::new()
+///     .route_service("/graphql", GraphQL::new(schema, |_: &_| Database::new()))
+///     .route("/graphiql", get(graphiql("/graphql", None)));
+/// ```
+pub struct GraphQL<Query, Mutation, Subscription, F, S = DefaultScalarValue>
+where
+    Query: GraphQLType<S>,
+    Mutation: GraphQLType<S>,
+    Subscription: GraphQLType<S>,
+    S: ScalarValue,
+{
+    schema: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    context_factory: F,
+}
+
+impl<Query, Mutation, Subscription, F, S> GraphQL<Query, Mutation, Subscription, F, S>
+where
+    Query: GraphQLType<S>,
+    Mutation: GraphQLType<S>,
+    Subscription: GraphQLType<S>,
+    S: ScalarValue,
+{
+    /// Creates a new [`GraphQL`] service executing requests in the given
+    /// `schema` with the contexts made by the `context_factory`.
+    pub fn new(
+        schema: impl Into<Arc<RootNode<'static, Query, Mutation, Subscription, S>>>,
+        context_factory: F,
+    ) -> Self {
+        Self {
+            schema: schema.into(),
+            context_factory,
+        }
+    }
+}
+
+impl<Query, Mutation, Subscription, F, S> Clone for GraphQL<Query, Mutation, Subscription, F, S>
+where
+    Query: GraphQLType<S>,
+    Mutation: GraphQLType<S>,
+    Subscription: GraphQLType<S>,
+    F: Clone,
+    S: ScalarValue,
+{
+    fn clone(&self) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            context_factory: self.context_factory.clone(),
+        }
+    }
+}
+
+impl<Query, Mutation, Subscription, CtxT, F, S, B> Service<Request<B>>
+    for GraphQL<Query, Mutation, Subscription, F, S>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    F: Fn(&request::Parts) -> CtxT,
+    S: ScalarValue + Send + Sync + 'static,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let (parts, body) = req.into_parts();
+        let context = (self.context_factory)(&parts);
+        let req = Request::from_parts(parts, body);
+        let schema = self.schema.clone();
+
+        Box::pin(async move {
+            let req = match JuniperRequest::<S>::from_request(req, &()).await {
+                Ok(req) => req,
+                Err(resp) => return Ok(resp),
+            };
+            Ok(req.execute(&*schema, &context).await.into_response())
+        })
+    }
+}
+
+/// Creates a handler replying with an HTML page containing GraphiQL.
+///
+/// This does not handle routing, so you can mount it on any endpoint:
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use juniper_axum::graphiql;
+///
+/// let app: Router = Router::new().route(
+///     "/graphiql",
+///     get(graphiql("/graphql", Some("/subscriptions"))),
+/// );
+/// ```
+pub fn graphiql(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> impl FnOnce() -> std_future::Ready<Html<String>> + Clone + Send + 'static {
+    let html = Html(juniper::http::graphiql::graphiql_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ));
+    move || std_future::ready(html)
+}
+
+/// Creates a handler replying with an HTML page containing GraphQL Playground.
+///
+/// This does not handle routing, so you can mount it on any endpoint:
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use juniper_axum::playground;
+///
+/// let app: Router = Router::new().route(
+///     "/playground",
+///     get(playground("/graphql", Some("/subscriptions"))),
+/// );
+/// ```
+pub fn playground(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> impl FnOnce() -> std_future::Ready<Html<String>> + Clone + Send + 'static {
+    let html = Html(juniper::http::playground::playground_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ));
+    move || std_future::ready(html)
+}
+
+/// Makes a `text/plain` response describing the given [`RequestError`].
+fn error_response(err: RequestError) -> Response {
+    let status = StatusCode::from_u16(err.status_code()).expect("valid status code");
+    let content_type = [(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    )];
+    let mut resp = (status, content_type, err.to_string()).into_response();
+    if let Some(methods) = err.allowed_methods() {
+        resp.headers_mut()
+            .insert(header::ALLOW, HeaderValue::from_static(methods));
+    }
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{SocketAddr, TcpListener};
+
+    use axum::{routing::get, Router};
+    use juniper::{
+        http::tests as http_tests,
+        tests::fixtures::starwars::schema::{Database, Query},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+    use reqwest::blocking::Response as ReqwestResponse;
+
+    use super::{graphiql, playground, GraphQL};
+
+    struct TestAxumIntegration {
+        addr: SocketAddr,
+    }
+
+    impl TestAxumIntegration {
+        fn url(&self, url: &str) -> String {
+            format!("http://{}/graphql{}", self.addr, url)
+        }
+    }
+
+    impl http_tests::HttpIntegration for TestAxumIntegration {
+        fn get(&self, url: &str) -> http_tests::TestResponse {
+            let url = self.url(url);
+            make_test_response(
+                reqwest::blocking::get(&url).unwrap_or_else(|_| panic!("failed GET {}", url)),
+            )
+        }
+
+        fn post_json(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.request("POST", url, &[("content-type", "application/json")], body)
+        }
+
+        fn post_graphql(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.request(
+                "POST",
+                url,
+                &[("content-type", "application/graphql")],
+                body,
+            )
+        }
+
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> http_tests::TestResponse {
+            let url = self.url(url);
+            let client = reqwest::blocking::Client::new();
+            let mut req = client.request(method.parse().unwrap(), &url);
+            for (name, value) in headers {
+                req = req.header(*name, *value);
+            }
+            let res = req
+                .body(body.to_string())
+                .send()
+                .unwrap_or_else(|_| panic!("failed {} {}", method, url));
+            make_test_response(res)
+        }
+    }
+
+    fn make_test_response(response: ReqwestResponse) -> http_tests::TestResponse {
+        let status_code = response.status().as_u16() as i32;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .map(|ct| ct.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        let allow = response
+            .headers()
+            .get(reqwest::header::ALLOW)
+            .map(|v| v.to_str().unwrap().to_owned());
+        let body = response.text().unwrap();
+
+        http_tests::TestResponse {
+            status_code,
+            body: Some(body),
+            content_type,
+            allow,
+        }
+    }
+
+    /// Serves the given [`Router`] on a free local port.
+    pub(crate) fn serve(app: Router) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        tokio::spawn(async move {
+            if let Err(e) = server.await {
+                eprintln!("server error: {}", e);
+            }
+        });
+        addr
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_axum_integration() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let service = GraphQL::new(schema, |_: &_| Database::new());
+        let addr = serve(
+            Router::new()
+                .route_service("/graphql", service.clone())
+                .route_service("/graphql/", service),
+        );
+
+        tokio::task::spawn_blocking(move || {
+            http_tests::run_http_test_suite(&TestAxumIntegration { addr })
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_graphiql_and_playground() {
+        let addr = serve(
+            Router::new()
+                .route("/graphiql", get(graphiql("/graphql", None)))
+                .route("/playground", get(playground("/graphql", None))),
+        );
+
+        tokio::task::spawn_blocking(move || {
+            for (path, needle) in [("graphiql", "GraphiQL"), ("playground", "Playground")] {
+                let res = reqwest::blocking::get(format!("http://{}/{}", addr, path)).unwrap();
+                assert_eq!(res.status(), 200);
+                assert_eq!(
+                    res.headers()[reqwest::header::CONTENT_TYPE],
+                    "text/html; charset=utf-8",
+                );
+                assert!(res.text().unwrap().contains(needle));
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cache_control() {
+        struct CachedQuery;
+
+        #[juniper::graphql_object]
+        impl CachedQuery {
+            #[graphql(cache_control(max_age = 60))]
+            fn motd() -> &'static str {
+                "Hello"
+            }
+
+            fn version() -> &'static str {
+                "1.0"
+            }
+        }
+
+        let schema = RootNode::new(
+            CachedQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let addr = serve(Router::new().route_service("/graphql", GraphQL::new(schema, |_: &_| ())));
+
+        tokio::task::spawn_blocking(move || {
+            for (query, cache_control) in [
+                ("{motd}", Some("max-age=60, public")),
+                ("{motd version}", None),
+            ] {
+                let res = reqwest::blocking::Client::new()
+                    .get(format!("http://{}/graphql", addr))
+                    .query(&[("query", query)])
+                    .send()
+                    .unwrap();
+                assert_eq!(res.status(), 200);
+                assert_eq!(
+                    res.headers()
+                        .get(reqwest::header::CACHE_CONTROL)
+                        .map(|v| v.to_str().unwrap()),
+                    cache_control,
+                );
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_compression() {
+        use std::io::Read as _;
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let addr = serve(
+            Router::new().route_service("/graphql", GraphQL::new(schema, |_: &_| Database::new())),
+        );
+
+        tokio::task::spawn_blocking(move || {
+            for (accept_encoding, content_encoding) in [
+                ("gzip", Some("gzip")),
+                ("deflate, gzip;q=0.5", Some("gzip")),
+                ("deflate", None),
+            ] {
+                let res = reqwest::blocking::Client::new()
+                    .get(format!("http://{}/graphql?query={{hero{{name}}}}", addr))
+                    .header(reqwest::header::ACCEPT_ENCODING, accept_encoding)
+                    .send()
+                    .unwrap();
+                assert_eq!(res.status(), 200);
+                assert_eq!(
+                    res.headers()
+                        .get(reqwest::header::CONTENT_ENCODING)
+                        .map(|v| v.to_str().unwrap()),
+                    content_encoding,
+                );
+
+                let body = res.bytes().unwrap();
+                let mut json = String::new();
+                match content_encoding {
+                    Some(_) => {
+                        flate2::read::GzDecoder::new(&body[..])
+                            .read_to_string(&mut json)
+                            .unwrap();
+                    }
+                    None => json = String::from_utf8(body.to_vec()).unwrap(),
+                }
+                assert_eq!(json, r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
+
+#[cfg(all(test, feature = "subscriptions"))]
+mod subscription_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{extract::WebSocketUpgrade, response::Response, routing::get, Extension, Router};
+    use juniper::{
+        futures::{SinkExt as _, StreamExt as _},
+        http::tests::{run_ws_test_suite, WsIntegration, WsIntegrationMessage},
+        tests::fixtures::starwars::schema::{Database, Query, Subscription},
+        EmptyMutation, LocalBoxFuture, RootNode,
+    };
+    use juniper_graphql_ws::ConnectionConfig;
+    use tokio::time::timeout;
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{client::IntoClientRequest as _, Message},
+    };
+
+    use super::{subscriptions::subscriptions_handler, tests::serve};
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+    async fn subscriptions(
+        Extension(schema): Extension<Arc<Schema>>,
+        ws: WebSocketUpgrade,
+    ) -> Response {
+        subscriptions_handler(ws, schema, ConnectionConfig::new(Database::new()))
+    }
+
+    struct TestAxumWsIntegration;
+
+    impl TestAxumWsIntegration {
+        async fn run_async(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            let schema = Schema::new(Query, EmptyMutation::new(), Subscription);
+            let addr = serve(
+                Router::new()
+                    .route("/subscriptions", get(subscriptions))
+                    .layer(Extension(Arc::new(schema))),
+            );
+            let mut request = format!("ws://{}/subscriptions", addr).into_client_request()?;
+            request
+                .headers_mut()
+                .insert("Sec-WebSocket-Protocol", "graphql-ws".parse()?);
+            let (mut socket, _) = connect_async(request).await?;
+
+            for message in messages {
+                match message {
+                    WsIntegrationMessage::Send(body) => socket.send(Message::Text(body)).await?,
+                    WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message =
+                            timeout(Duration::from_millis(message_timeout), socket.next())
+                                .await
+                                .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                                .ok_or_else(|| anyhow::anyhow!("Empty message received"))??;
+
+                        let text = match message {
+                            Message::Text(text) => text,
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        };
+                        let expected_value = serde_json::from_str::<serde_json::Value>(&body)?;
+                        let value = serde_json::from_str::<serde_json::Value>(&text)?;
+                        if value != expected_value {
+                            return Err(anyhow::anyhow!(
+                                "Expected message: {}. Received message: {}",
+                                expected_value,
+                                value,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl WsIntegration for TestAxumWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_axum_ws_integration() {
+        run_ws_test_suite(&TestAxumWsIntegration).await;
+    }
+}
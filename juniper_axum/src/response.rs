@@ -0,0 +1,71 @@
+//! Conversion of GraphQL responses into [`axum`] responses.
+
+use axum::{
+    body::StreamBody,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::stream;
+use juniper::{
+    http::{
+        negotiation::{self, ContentEncoding, ResponseFormat},
+        streaming::JsonChunks,
+        GraphQLBatchResponse,
+    },
+    DefaultScalarValue, ScalarValue,
+};
+
+/// [`GraphQLBatchResponse`] serialized as JSON in the given [`ResponseFormat`]
+/// and compressed with the given [`ContentEncoding`].
+///
+/// The status code of the response is chosen by [`negotiation::status_code`],
+/// and its `Cache-Control` header is set from the [`CachePolicy`] of the
+/// [`GraphQLBatchResponse`], if it may be cached. The body is serialized while
+/// being streamed to the client.
+///
+/// [`CachePolicy`]: juniper::CachePolicy
+pub struct JuniperResponse<'a, S = DefaultScalarValue>(
+    pub GraphQLBatchResponse<'a, S>,
+    pub ResponseFormat,
+    pub ContentEncoding,
+)
+where
+    S: ScalarValue;
+
+impl<'a, S> IntoResponse for JuniperResponse<'a, S>
+where
+    S: ScalarValue + Send + 'static,
+{
+    fn into_response(self) -> Response {
+        let Self(response, format, encoding) = self;
+        let status = StatusCode::from_u16(negotiation::status_code(&response, format))
+            .expect("valid status code");
+        let cache_policy = response.cache_policy();
+        let allowed_methods = negotiation::allowed_methods(&response);
+        let mut resp = (
+            status,
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            )],
+            StreamBody::new(stream::iter(JsonChunks::new(response).encoded(encoding))),
+        )
+            .into_response();
+        if let Some(coding) = encoding.header_value() {
+            let headers = resp.headers_mut();
+            headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(coding));
+            headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+        }
+        if let Some(policy) = cache_policy {
+            resp.headers_mut().insert(
+                header::CACHE_CONTROL,
+                HeaderValue::from_str(&policy.header_value()).expect("valid header value"),
+            );
+        }
+        if let Some(methods) = allowed_methods {
+            resp.headers_mut()
+                .insert(header::ALLOW, HeaderValue::from_static(methods));
+        }
+        resp
+    }
+}
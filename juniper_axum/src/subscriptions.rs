@@ -0,0 +1,108 @@
+//! Serving GraphQL subscriptions over WebSocket connections.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::ws::{self, WebSocket, WebSocketUpgrade},
+    response::Response,
+};
+use futures::{future, SinkExt as _, StreamExt as _, TryStreamExt as _};
+use juniper::{GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue};
+use juniper_graphql_ws::{ArcSchema, Init, ServeError};
+
+/// Errors that can happen while serving a connection, either in Axum or while serializing
+/// outgoing messages.
+pub type Error = ServeError<axum::Error>;
+
+/// Upgrades the connection to a WebSocket one serving the graphql-ws protocol.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use axum::{extract::WebSocketUpgrade, response::Response, routing::get, Extension, Router};
+/// use juniper::{
+///     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+///     EmptyMutation, RootNode,
+/// };
+/// use juniper_axum::subscriptions::subscriptions_handler;
+/// use juniper_graphql_ws::ConnectionConfig;
+///
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+///
+/// async fn subscriptions(
+///     Extension(schema): Extension<Arc<Schema>>,
+///     ws: WebSocketUpgrade,
+/// ) -> Response {
+///     subscriptions_handler(ws, schema, ConnectionConfig::new(Database::new()))
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation::new(), Subscription);
+///
+/// let app: Router = Router::new()
+///     .route("/subscriptions", get(subscriptions))
+///     .layer(Extension(Arc::new(schema)));
+/// ```
+pub fn subscriptions_handler<Query, Mutation, Subscription, CtxT, S, I>(
+    ws: WebSocketUpgrade,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Response
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send + 'static,
+{
+    ws.protocols(["graphql-ws"])
+        .on_upgrade(move |socket| async move {
+            // Axum offers no way to report the errors of an upgraded connection, which mostly
+            // mean the client has gone away.
+            let _ = serve_graphql_ws(socket, root_node, init).await;
+        })
+}
+
+/// Serves the graphql-ws protocol over a WebSocket connection.
+///
+/// See [`subscriptions_handler`] for the meaning of the `init` argument.
+pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    websocket: WebSocket,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Result<(), Error>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let (ws_tx, ws_rx) = websocket.split();
+
+    // Axum answers pings and ends the stream on close by itself, yielding those messages for
+    // information only.
+    let incoming = ws_rx.try_filter_map(|msg| {
+        future::ok(match msg {
+            ws::Message::Text(text) => Some(text.into_bytes()),
+            ws::Message::Binary(bytes) => Some(bytes),
+            ws::Message::Ping(_) | ws::Message::Pong(_) | ws::Message::Close(_) => None,
+        })
+    });
+    let outgoing = ws_tx.with(|text| future::ok(ws::Message::Text(text)));
+
+    juniper_graphql_ws::serve(ArcSchema(root_node), init, incoming, outgoing).await
+}
@@ -0,0 +1,95 @@
+//! Common functions, definitions and extensions for parsing and code generation
+//! of cache control hints applied to [GraphQL fields][1] and types.
+//!
+//! [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    token,
+};
+
+use crate::common::parse::{
+    attr::{err, OptionExt as _},
+    ParseBufferExt as _,
+};
+
+/// Cache control hint applied to a [GraphQL field][1] or type via
+/// `#[graphql(cache_control(max_age = 60, scope = "PRIVATE"))]` attribute
+/// argument.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CacheControl {
+    /// Number of seconds a value may be cached for.
+    pub(crate) max_age: Option<syn::LitInt>,
+
+    /// Scope a value may be cached in.
+    pub(crate) scope: Option<syn::Ident>,
+}
+
+impl Parse for CacheControl {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let content;
+        let paren = syn::parenthesized!(content in input);
+
+        let mut out = Self::default();
+        while !content.is_empty() {
+            let ident = content.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "max_age" => {
+                    content.parse::<token::Eq>()?;
+                    let age = content.parse::<syn::LitInt>()?;
+                    age.base10_parse::<u32>()?;
+                    out.max_age
+                        .replace(age)
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "scope" => {
+                    content.parse::<token::Eq>()?;
+                    let scope = content.parse::<syn::LitStr>()?;
+                    let variant = match scope.value().as_str() {
+                        "PUBLIC" => "Public",
+                        "PRIVATE" => "Private",
+                        _ => {
+                            return Err(syn::Error::new(
+                                scope.span(),
+                                "expects `PUBLIC` or `PRIVATE` scope",
+                            ))
+                        }
+                    };
+                    out.scope
+                        .replace(syn::Ident::new(variant, scope.span()))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            content.try_parse::<token::Comma>()?;
+        }
+
+        if out.max_age.is_none() && out.scope.is_none() {
+            return Err(syn::Error::new(
+                paren.span,
+                "expects at least one of `max_age` or `scope` arguments",
+            ));
+        }
+        Ok(out)
+    }
+}
+
+impl ToTokens for CacheControl {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        let max_age = self.max_age.as_ref().map(|age| quote! { .max_age(#age) });
+        let scope = self
+            .scope
+            .as_ref()
+            .map(|scope| quote! { .scope(::juniper::CacheScope::#scope) });
+        quote! {
+            .cache_control(::juniper::CacheHint::new()#max_age #scope)
+        }
+        .to_tokens(into);
+    }
+}
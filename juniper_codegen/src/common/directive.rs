@@ -0,0 +1,81 @@
+//! Common functions, definitions and extensions for parsing and code generation
+//! of [GraphQL directives][1] applied to type system definitions.
+//!
+//! [1]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token,
+};
+
+use crate::common::parse::ParseBufferExt as _;
+
+/// Single [GraphQL directive][1] applied to a type system definition via
+/// `#[graphql(directive = name(arg = value, ...))]` attribute argument.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+#[derive(Clone, Debug)]
+pub struct Directive {
+    /// Name of the applied [GraphQL directive][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) name: syn::Ident,
+
+    /// Arguments of the applied [GraphQL directive][1] along with their Rust
+    /// expressions, converted into [GraphQL input values][2] at runtime.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    /// [2]: https://spec.graphql.org/June2018/#sec-Input-Values
+    pub(crate) arguments: Vec<(syn::Ident, syn::Expr)>,
+}
+
+impl Parse for Directive {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse_any_ident()?;
+        let mut arguments = vec![];
+        if input.is_next::<token::Paren>() {
+            let content;
+            syn::parenthesized!(content in input);
+            let args = Punctuated::<DirectiveArgument, token::Comma>::parse_terminated(&content)?;
+            arguments = args.into_iter().map(|arg| (arg.name, arg.value)).collect();
+        }
+        Ok(Self { name, arguments })
+    }
+}
+
+/// Single `name = value` argument of the applied [`Directive`].
+struct DirectiveArgument {
+    name: syn::Ident,
+    value: syn::Expr,
+}
+
+impl Parse for DirectiveArgument {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse_any_ident()?;
+        input.parse::<token::Eq>()?;
+        let value = input.parse::<syn::Expr>()?;
+        Ok(Self { name, value })
+    }
+}
+
+impl ToTokens for Directive {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        let name = self.name.to_string();
+        let arguments = self.arguments.iter().map(|(arg, value)| {
+            let arg = arg.to_string();
+            quote! {
+                .argument(
+                    #arg,
+                    ::juniper::ToInputValue::to_input_value(&(#value)),
+                )
+            }
+        });
+        quote! {
+            .directive(::juniper::meta::AppliedDirective::new(#name)#( #arguments )*)
+        }
+        .to_tokens(into);
+    }
+}
@@ -16,11 +16,13 @@ use syn::{
 
 use crate::{
     common::{
+        directive::Directive,
         parse::{
             attr::{err, OptionExt as _},
             ParseBufferExt as _, TypeExt as _,
         },
         scalar,
+        validate::{self, Validator},
     },
     result::GraphQLScope,
     util::{filter_attrs, path_eq_single, span_container::SpanContainer, RenameRule},
@@ -58,6 +60,19 @@ pub(crate) struct Attr {
     /// [2]: https://spec.graphql.org/June2018/#sec-Required-Arguments
     pub(crate) default: Option<SpanContainer<Option<syn::Expr>>>,
 
+    /// Explicitly specified [directives][2] applied to this
+    /// [GraphQL argument][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    /// [2]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) directives: Vec<SpanContainer<Directive>>,
+
+    /// Explicitly specified validators constraining the values of this
+    /// [GraphQL argument][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    pub(crate) validators: Vec<SpanContainer<Validator>>,
+
     /// Explicitly specified marker indicating that this method argument doesn't
     /// represent a [GraphQL argument][1], but is a [`Context`] being injected
     /// into a [GraphQL field][2] resolving function.
@@ -121,6 +136,23 @@ impl Parse for Attr {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "directive" => {
+                    input.parse::<token::Eq>()?;
+                    let directive = input.parse::<Directive>()?;
+                    out.directives.push(SpanContainer::new(
+                        ident.span(),
+                        Some(directive.name.span()),
+                        directive,
+                    ))
+                }
+                "validate" => {
+                    let validators = validate::parse_list(input)?;
+                    out.validators.extend(
+                        validators
+                            .into_iter()
+                            .map(|v| SpanContainer::new(ident.span(), None, v)),
+                    )
+                }
                 "ctx" | "context" | "Context" => {
                     let span = ident.span();
                     out.context
@@ -146,11 +178,15 @@ impl Parse for Attr {
 impl Attr {
     /// Tries to merge two [`Attr`]s into a single one, reporting about
     /// duplicates, if any.
-    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+    fn try_merge(mut self, mut another: Self) -> syn::Result<Self> {
+        self.directives.append(&mut another.directives);
+        self.validators.append(&mut another.validators);
         Ok(Self {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
             default: try_merge_opt!(default: self, another),
+            directives: self.directives,
+            validators: self.validators,
             context: try_merge_opt!(context: self, another),
             executor: try_merge_opt!(executor: self, another),
         })
@@ -167,6 +203,8 @@ impl Attr {
             if attr.name.is_some()
                 || attr.description.is_some()
                 || attr.default.is_some()
+                || !attr.directives.is_empty()
+                || !attr.validators.is_empty()
                 || attr.executor.is_some()
             {
                 return Err(syn::Error::new(
@@ -180,6 +218,8 @@ impl Attr {
             if attr.name.is_some()
                 || attr.description.is_some()
                 || attr.default.is_some()
+                || !attr.directives.is_empty()
+                || !attr.validators.is_empty()
                 || attr.context.is_some()
             {
                 return Err(syn::Error::new(
@@ -204,6 +244,15 @@ impl Attr {
         if let Some(span) = &self.default {
             return Err(Self::err_disallowed(&span, "default"));
         }
+        if let Some(span) = self.directives.first() {
+            return Err(Self::err_disallowed(&span, "directive"));
+        }
+        if let Some(span) = self.validators.first() {
+            return Err(syn::Error::new(
+                span.span_ident(),
+                "attribute argument `#[graphql(validate(...))]` is not allowed here",
+            ));
+        }
         Ok(())
     }
 
@@ -224,7 +273,7 @@ impl Attr {
 /// Representation of a [GraphQL field argument][1] for code generation.
 ///
 /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct OnField {
     /// Rust type that this [GraphQL field argument][1] is represented by.
     ///
@@ -253,10 +302,22 @@ pub(crate) struct OnField {
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
     /// [2]: https://spec.graphql.org/June2018/#sec-Required-Arguments
     pub(crate) default: Option<Option<syn::Expr>>,
+
+    /// [Directives][2] applied to this [GraphQL field argument][1] to put into
+    /// GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    /// [2]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) directives: Vec<Directive>,
+
+    /// Validators constraining the values of this [GraphQL field argument][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    pub(crate) validators: Vec<Validator>,
 }
 
 /// Possible kinds of Rust method arguments for code generation.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) enum OnMethod {
     /// Regular [GraphQL field argument][1].
     ///
@@ -338,7 +399,12 @@ impl OnMethod {
             quote! { .arg::<#ty>(#name, info) }
         };
 
-        Some(quote! { .argument(registry#method#description) })
+        let directives = &arg.directives;
+        let validators = &arg.validators;
+
+        Some(quote! {
+            .argument(registry#method#description #( #directives )* #( #validators )*)
+        })
     }
 
     /// Returns generated code for the [`GraphQLValue::resolve_field`] method,
@@ -446,6 +512,16 @@ impl OnMethod {
             ty: argument.ty.as_ref().clone(),
             description: attr.description.as_ref().map(|d| d.as_ref().value()),
             default: attr.default.as_ref().map(|v| v.as_ref().clone()),
+            directives: attr
+                .directives
+                .into_iter()
+                .map(SpanContainer::into_inner)
+                .collect(),
+            validators: attr
+                .validators
+                .into_iter()
+                .map(SpanContainer::into_inner)
+                .collect(),
         }))
     }
 }
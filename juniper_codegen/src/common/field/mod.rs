@@ -16,6 +16,8 @@ use syn::{
 
 use crate::{
     common::{
+        cache_control::CacheControl,
+        directive::Directive,
         gen,
         parse::{
             attr::{err, OptionExt as _},
@@ -59,6 +61,18 @@ pub(crate) struct Attr {
     /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
     pub(crate) deprecated: Option<SpanContainer<Option<syn::LitStr>>>,
 
+    /// Explicitly specified [directives][2] applied to this
+    /// [GraphQL field][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    /// [2]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) directives: Vec<SpanContainer<Directive>>,
+
+    /// Explicitly specified cache control hint of this [GraphQL field][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Explicitly specified marker indicating that this method (or struct
     /// field) should be omitted by code generation and not considered as the
     /// [GraphQL field][1] definition.
@@ -115,6 +129,21 @@ impl Parse for Attr {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "directive" => {
+                    input.parse::<token::Eq>()?;
+                    let directive = input.parse::<Directive>()?;
+                    out.directives.push(SpanContainer::new(
+                        ident.span(),
+                        Some(directive.name.span()),
+                        directive,
+                    ))
+                }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    out.cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "ignore" | "skip" => out
                     .ignore
                     .replace(SpanContainer::new(ident.span(), None, ident.clone()))
@@ -136,11 +165,14 @@ impl Parse for Attr {
 impl Attr {
     /// Tries to merge two [`Attrs`]s into a single one, reporting about
     /// duplicates, if any.
-    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+    fn try_merge(mut self, mut another: Self) -> syn::Result<Self> {
+        self.directives.append(&mut another.directives);
         Ok(Self {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
             deprecated: try_merge_opt!(deprecated: self, another),
+            directives: self.directives,
+            cache_control: try_merge_opt!(cache_control: self, another),
             ignore: try_merge_opt!(ignore: self, another),
             downcast: try_merge_opt!(downcast: self, another),
         })
@@ -159,6 +191,8 @@ impl Attr {
             if attr.name.is_some()
                 || attr.description.is_some()
                 || attr.deprecated.is_some()
+                || !attr.directives.is_empty()
+                || attr.cache_control.is_some()
                 || attr.downcast.is_some()
             {
                 return Err(syn::Error::new(
@@ -172,6 +206,8 @@ impl Attr {
             if attr.name.is_some()
                 || attr.description.is_some()
                 || attr.deprecated.is_some()
+                || !attr.directives.is_empty()
+                || attr.cache_control.is_some()
                 || attr.ignore.is_some()
             {
                 return Err(syn::Error::new(
@@ -202,7 +238,7 @@ impl Attr {
 /// Representation of a [GraphQL field][1] for code generation.
 ///
 /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct Definition {
     /// Rust type that this [GraphQL field][1] is represented by (method return
     /// type or struct field type).
@@ -230,6 +266,19 @@ pub(crate) struct Definition {
     /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
     pub(crate) deprecated: Option<Option<String>>,
 
+    /// [Directives][2] applied to this [GraphQL field][1] to put into GraphQL
+    /// schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    /// [2]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) directives: Vec<Directive>,
+
+    /// Cache control hint of this [GraphQL field][1] to put into GraphQL
+    /// schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) cache_control: Option<CacheControl>,
+
     /// Ident of the Rust method (or struct field) representing this
     /// [GraphQL field][1].
     ///
@@ -371,6 +420,9 @@ impl Definition {
             quote! { .deprecated(#reason) }
         });
 
+        let directives = &self.directives;
+        let cache_control = &self.cache_control;
+
         let args = self
             .arguments
             .iter()
@@ -381,6 +433,8 @@ impl Definition {
                 #( #args )*
                 #description
                 #deprecated
+                #( #directives )*
+                #cache_control
         }
     }
 
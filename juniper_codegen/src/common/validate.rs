@@ -0,0 +1,204 @@
+//! Common functions, definitions and extensions for parsing and code generation
+//! of validators constraining the values of [GraphQL arguments][1] and
+//! [input object fields][2].
+//!
+//! [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+//! [2]: https://spec.graphql.org/June2018/#sec-Input-Objects
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token,
+};
+
+use crate::common::parse::{attr::err, ParseBufferExt as _};
+
+/// Single validator specified via `#[graphql(validate(...))]` attribute
+/// argument.
+#[derive(Clone, Debug)]
+pub enum Validator {
+    /// `length(min = .., max = ..)` constraining the number of characters in a
+    /// string.
+    Length(Bounds<usize>),
+
+    /// `range(min = .., max = ..)` constraining a number.
+    Range(Bounds<f64>),
+
+    /// `pattern = ".."` requiring a string to match the regular expression.
+    Pattern(syn::LitStr),
+
+    /// `items(min = .., max = ..)` constraining the number of items in a list.
+    Items(Bounds<usize>),
+
+    /// `custom = path::to::function` calling the function with the value.
+    Custom(syn::ExprPath),
+}
+
+/// Inclusive bounds of a [`Validator`], at least one of which is specified.
+#[derive(Clone, Debug)]
+pub struct Bounds<T> {
+    /// Lower bound, if any.
+    min: Option<T>,
+
+    /// Upper bound, if any.
+    max: Option<T>,
+}
+
+/// Parses a comma-separated list of [`Validator`]s, placed inside
+/// `validate(...)`.
+pub(crate) fn parse_list(input: ParseStream<'_>) -> syn::Result<Vec<Validator>> {
+    let content;
+    syn::parenthesized!(content in input);
+    Ok(
+        Punctuated::<Validator, token::Comma>::parse_terminated(&content)?
+            .into_iter()
+            .collect(),
+    )
+}
+
+impl Parse for Validator {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse_any_ident()?;
+        match ident.to_string().as_str() {
+            "length" => Ok(Self::Length(Bounds::parse_with(input, &ident, |b| {
+                b.parse::<syn::LitInt>()?.base10_parse()
+            })?)),
+            "range" => Ok(Self::Range(Bounds::parse_with(
+                input,
+                &ident,
+                parse_number,
+            )?)),
+            "items" => Ok(Self::Items(Bounds::parse_with(input, &ident, |b| {
+                b.parse::<syn::LitInt>()?.base10_parse()
+            })?)),
+            "pattern" => {
+                input.parse::<token::Eq>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                if let Err(e) = regex_syntax::Parser::new().parse(&lit.value()) {
+                    let reason = match &e {
+                        regex_syntax::Error::Parse(e) => e.kind().to_string(),
+                        regex_syntax::Error::Translate(e) => e.kind().to_string(),
+                        e => e.to_string(),
+                    };
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("invalid regular expression: {}", reason),
+                    ));
+                }
+                Ok(Self::Pattern(lit))
+            }
+            "custom" => {
+                input.parse::<token::Eq>()?;
+                Ok(Self::Custom(input.parse()?))
+            }
+            name => Err(err::unknown_arg(&ident, name)),
+        }
+    }
+}
+
+impl<T: Copy + PartialOrd> Bounds<T> {
+    /// Parses parenthesized `min = .., max = ..` [`Bounds`] of the `validator`
+    /// named by the given [`syn::Ident`], using the given function to parse
+    /// their values.
+    fn parse_with(
+        input: ParseStream<'_>,
+        validator: &syn::Ident,
+        parse_value: impl Fn(ParseStream<'_>) -> syn::Result<T>,
+    ) -> syn::Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+
+        let mut out = Self {
+            min: None,
+            max: None,
+        };
+        while !content.is_empty() {
+            let ident = content.parse_any_ident()?;
+            let bound = match ident.to_string().as_str() {
+                "min" => &mut out.min,
+                "max" => &mut out.max,
+                name => return Err(err::unknown_arg(&ident, name)),
+            };
+            content.parse::<token::Eq>()?;
+            if bound.replace(parse_value(&content)?).is_some() {
+                return Err(err::dup_arg(&ident));
+            }
+            content.try_parse::<token::Comma>()?;
+        }
+
+        match (out.min, out.max) {
+            (None, None) => Err(syn::Error::new(
+                validator.span(),
+                format!("`{}` validator expects `min` and/or `max`", validator),
+            )),
+            (Some(min), Some(max)) if min > max => Err(syn::Error::new(
+                validator.span(),
+                format!(
+                    "`{}` validator expects `min` not to exceed `max`",
+                    validator
+                ),
+            )),
+            _ => Ok(out),
+        }
+    }
+}
+
+/// Parses an integer or a float literal, optionally negated, as [`f64`].
+fn parse_number(input: ParseStream<'_>) -> syn::Result<f64> {
+    let negative = input.try_parse::<token::Sub>()?.is_some();
+    let value = if input.peek(syn::LitFloat) {
+        input.parse::<syn::LitFloat>()?.base10_parse::<f64>()?
+    } else {
+        input.parse::<syn::LitInt>()?.base10_parse::<f64>()?
+    };
+    Ok(if negative { -value } else { value })
+}
+
+impl ToTokens for Validator {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        let validator = match self {
+            Self::Length(b) => {
+                let (min, max) = b.to_token_pair(ToTokens::to_token_stream);
+                quote! { ::juniper::meta::InputValidator::Length { min: #min, max: #max } }
+            }
+            Self::Range(b) => {
+                let (min, max) = b.to_token_pair(|v| {
+                    let abs = v.abs();
+                    if v.is_sign_negative() {
+                        quote! { -#abs }
+                    } else {
+                        quote! { #abs }
+                    }
+                });
+                quote! { ::juniper::meta::InputValidator::Range { min: #min, max: #max } }
+            }
+            Self::Items(b) => {
+                let (min, max) = b.to_token_pair(ToTokens::to_token_stream);
+                quote! { ::juniper::meta::InputValidator::Items { min: #min, max: #max } }
+            }
+            Self::Pattern(re) => quote! {
+                ::juniper::meta::InputValidator::pattern(#re)
+                    .expect("pattern is checked at compile time")
+            },
+            Self::Custom(f) => quote! { ::juniper::meta::InputValidator::custom(#f) },
+        };
+        quote! { .validator(#validator) }.to_tokens(into);
+    }
+}
+
+impl<T> Bounds<T> {
+    /// Returns generated code of the [`Option`]al `min` and `max` bounds,
+    /// using the given function to generate code of their values.
+    fn to_token_pair(&self, value: impl Fn(&T) -> TokenStream) -> (TokenStream, TokenStream) {
+        let opt = |b: &Option<T>| match b {
+            Some(b) => {
+                let b = value(b);
+                quote! { ::std::option::Option::Some(#b) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+        (opt(&self.min), opt(&self.max))
+    }
+}
@@ -28,6 +28,8 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         .map(SpanContainer::into_inner)
         .unwrap_or_else(|| ident.unraw().to_string());
 
+    let mut ignored = Vec::new();
+
     let fields = variants
         .into_iter()
         .filter_map(|field| {
@@ -44,6 +46,25 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
             };
 
             let field_name = field.ident;
+
+            if let Some(ignore) = field_attrs.ignore {
+                match field_attrs.fallback {
+                    Some(fallback) => ignored.push((field_name, fallback)),
+                    None => error.emit_custom(
+                        ignore.span(),
+                        "requires `fallback` for variants marked with `ignore`",
+                    ),
+                }
+                return None;
+            }
+
+            if let Some(fallback) = field_attrs.fallback {
+                error.emit_custom(
+                    fallback.span_ident(),
+                    "allows `fallback` only for variants marked with `ignore`",
+                );
+            }
+
             let name = field_attrs
                 .name
                 .clone()
@@ -52,7 +73,7 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                     attrs
                         .rename
                         .unwrap_or(RenameRule::ScreamingSnakeCase)
-                        .apply(&field_name.unraw().to_string())
+                        .apply_to_variant(&field_name.unraw().to_string())
                 });
 
             let resolver_code = quote!( #ident::#field_name );
@@ -62,7 +83,8 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 _ => {
                     error.emit_custom(
                         field.fields.span(),
-                        "all fields of the enum must be unnamed, e.g., None",
+                        "all fields of the enum must be unnamed, e.g., None, \
+                         unless marked with `ignore`",
                     );
                     return None;
                 }
@@ -88,6 +110,20 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 );
             }
 
+            if let Some(flatten) = field_attrs.flatten {
+                error.unsupported_attribute_within(
+                    flatten.span_ident(),
+                    UnsupportedAttribute::Flatten,
+                );
+            }
+
+            if let Some(validator) = field_attrs.validators.first() {
+                error.unsupported_attribute_within(
+                    validator.span_ident(),
+                    UnsupportedAttribute::Validate,
+                );
+            }
+
             Some(util::GraphQLTypeDefinitionField {
                 name,
                 _type,
@@ -98,13 +134,45 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 is_type_inferred: true,
                 is_async: false,
                 default: None,
+                directives: field_attrs.directives,
+                validators: Vec::new(),
+                flatten: false,
                 span,
             })
+            .map(|field| (field_name, field))
         })
         .collect::<Vec<_>>();
 
     proc_macro_error::abort_if_dirty();
 
+    let ignored_variants = ignored
+        .into_iter()
+        .filter_map(|(variant, fallback)| {
+            let fallback = match fields.iter().find(|(name, _)| name == fallback.inner()) {
+                Some((_, field)) => field.name.clone(),
+                None => {
+                    error.emit_custom(
+                        fallback.inner().span(),
+                        format!(
+                            "expects `fallback` to be a variant exposed in the schema, \
+                             found `{}`",
+                            fallback.inner(),
+                        ),
+                    );
+                    return None;
+                }
+            };
+            Some(util::IgnoredVariant {
+                pattern: quote!( #ident::#variant { .. } ),
+                fallback,
+            })
+        })
+        .collect();
+    let fields = fields
+        .into_iter()
+        .map(|(_, field)| field)
+        .collect::<Vec<_>>();
+
     if fields.is_empty() {
         error.not_empty(ast_span);
     }
@@ -144,9 +212,13 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         // NOTICE: only unit variants allow -> no generics possible
         generics: syn::Generics::default(),
         interfaces: vec![],
+        directives: attrs.directives,
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        ignored_variants,
+        to_input_value_with: attrs.to_input_value_with.map(SpanContainer::into_inner),
+        from_input_value_with: attrs.from_input_value_with.map(SpanContainer::into_inner),
     };
 
     Ok(definition.into_enum_tokens())
@@ -60,6 +60,15 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 error.unsupported_attribute_within(span.span(), UnsupportedAttribute::Skip)
             }
 
+            if let Some(span) = field_attrs.ignore {
+                error.unsupported_attribute_within(span.span(), UnsupportedAttribute::Ignore)
+            }
+
+            if let Some(span) = field_attrs.fallback {
+                error
+                    .unsupported_attribute_within(span.span_ident(), UnsupportedAttribute::Fallback)
+            }
+
             if let Some(span) = field_attrs.deprecation {
                 error.unsupported_attribute_within(
                     span.span_ident(),
@@ -67,7 +76,23 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 )
             }
 
-            if name.starts_with("__") {
+            if let Some(flatten) = &field_attrs.flatten {
+                if field_attrs.name.is_some()
+                    || field_attrs.description.is_some()
+                    || field_attrs.default.is_some()
+                    || !field_attrs.directives.is_empty()
+                    || !field_attrs.validators.is_empty()
+                {
+                    error.emit_custom(
+                        flatten.span_ident(),
+                        "does not allow `flatten` to be combined with other field attributes, \
+                         as the field is not present in the schema",
+                    );
+                    return None;
+                }
+            }
+
+            if field_attrs.flatten.is_none() && name.starts_with("__") {
                 error.no_double_underscore(if let Some(name) = field_attrs.name {
                     name.span_ident()
                 } else {
@@ -94,6 +119,13 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 is_type_inferred: true,
                 is_async: false,
                 default,
+                directives: field_attrs.directives,
+                validators: field_attrs
+                    .validators
+                    .into_iter()
+                    .map(SpanContainer::into_inner)
+                    .collect(),
+                flatten: field_attrs.flatten.is_some(),
                 span,
             })
         })
@@ -117,6 +149,14 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         });
     }
 
+    if let Some(with) = attrs.to_input_value_with {
+        error.unsupported_attribute(with.span_ident(), UnsupportedAttribute::ToInputValueWith);
+    }
+
+    if let Some(with) = attrs.from_input_value_with {
+        error.unsupported_attribute(with.span_ident(), UnsupportedAttribute::FromInputValueWith);
+    }
+
     if let Some(duplicates) =
         crate::util::duplicate::Duplicate::find_by_key(&fields, |field| field.name.as_str())
     {
@@ -142,9 +182,13 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         fields,
         generics: ast.generics,
         interfaces: vec![],
+        directives: attrs.directives,
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        ignored_variants: vec![],
+        to_input_value_with: None,
+        from_input_value_with: None,
     };
 
     Ok(definition.into_input_object_tokens())
@@ -0,0 +1,195 @@
+//! Code generation for `#[derive(GraphQLError)]` macro.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::ResultExt as _;
+use quote::ToTokens;
+use syn::{ext::IdentExt as _, spanned::Spanned as _, Data, Fields};
+
+use crate::{
+    common::scalar,
+    result::GraphQLScope,
+    util::{get_doc_comment, span_container::SpanContainer, RenameRule},
+};
+
+use super::{
+    Attr, Binding, Definition, ExtensionDefinition, FieldAttr, FieldsKind, Message, VariantAttr,
+    VariantDefinition,
+};
+
+/// [`GraphQLScope`] of errors for `#[derive(GraphQLError)]` macro.
+const ERR: GraphQLScope = GraphQLScope::ErrorDerive;
+
+/// Expands `#[derive(GraphQLError)]` macro into generated code.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let ast = syn::parse2::<syn::DeriveInput>(input).unwrap_or_abort();
+
+    let attr = Attr::from_attrs("graphql", &ast.attrs)?;
+
+    let data = match ast.data {
+        Data::Enum(data) => data,
+        _ => return Err(ERR.custom_error(ast.span(), "can only be derived for enums")),
+    };
+
+    let variants: Vec<_> = data
+        .variants
+        .into_iter()
+        .filter_map(parse_variant)
+        .collect();
+
+    proc_macro_error::abort_if_dirty();
+
+    if variants.is_empty() {
+        ERR.emit_custom(ast.ident.span(), "expects at least one variant");
+    }
+
+    if let Some(codes) = &attr.codes {
+        if codes.to_string().starts_with("__") {
+            ERR.no_double_underscore(codes.span_ident());
+        }
+        let mut seen = Vec::with_capacity(variants.len());
+        for v in &variants {
+            if seen.contains(&&v.code) {
+                ERR.emit_custom(v.ident.span(), format!("has duplicate code `{}`", v.code));
+            }
+            seen.push(&v.code);
+        }
+    }
+
+    proc_macro_error::abort_if_dirty();
+
+    Ok(Definition {
+        ident: ast.ident,
+        vis: ast.vis,
+        scalar: scalar::Type::parse(attr.scalar.as_deref(), &ast.generics),
+        generics: ast.generics,
+        codes: attr.codes.map(SpanContainer::into_inner),
+        variants,
+    }
+    .into_token_stream())
+}
+
+/// Parses the given Rust enum `var`iant as a [GraphQL error][1].
+///
+/// On failure returns [`None`] and internally fills up [`proc_macro_error`]
+/// with the corresponding errors.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+fn parse_variant(var: syn::Variant) -> Option<VariantDefinition> {
+    let attr = VariantAttr::from_attrs("graphql", &var.attrs)
+        .map_err(|e| proc_macro_error::emit_error!(e))
+        .ok()?;
+
+    let fields = match &var.fields {
+        Fields::Unit => FieldsKind::Unit,
+        Fields::Named(_) => FieldsKind::Named,
+        Fields::Unnamed(f) => FieldsKind::Unnamed(f.unnamed.len()),
+    };
+
+    let mut bindings = vec![];
+    let mut extensions = vec![];
+    for (i, field) in var.fields.iter().enumerate() {
+        let field_attr = match FieldAttr::from_attrs("graphql", &field.attrs) {
+            Ok(attr) => attr,
+            Err(e) => {
+                proc_macro_error::emit_error!(e);
+                continue;
+            }
+        };
+        if field_attr.extension.is_none() {
+            if let Some(name) = &field_attr.name {
+                ERR.emit_custom(
+                    name.span_ident(),
+                    "allows `name` only for fields marked with `extension`",
+                );
+            }
+            continue;
+        }
+
+        let name = match (&field_attr.name, &field.ident) {
+            (Some(name), _) => name.as_str().to_owned(),
+            (None, Some(ident)) => RenameRule::CamelCase.apply(&ident.unraw().to_string()),
+            (None, None) => {
+                ERR.emit_custom(
+                    field.span(),
+                    "expects `name` for unnamed fields marked with `extension`, \
+                     e.g. `#[graphql(extension, name = \"id\")]`",
+                );
+                continue;
+            }
+        };
+        if name == "code"
+            || extensions
+                .iter()
+                .any(|e: &ExtensionDefinition| e.name == name)
+        {
+            ERR.emit_custom(
+                field_attr
+                    .name
+                    .as_ref()
+                    .map_or_else(|| field.span(), SpanContainer::span_ident),
+                format!("has duplicate extension `{}`", name),
+            );
+            continue;
+        }
+
+        let binding = Binding::new(member(field, i));
+        extensions.push(ExtensionDefinition {
+            name,
+            ty: field.ty.clone(),
+            binding: binding.ident.clone(),
+        });
+        bindings.push(binding);
+    }
+
+    let message = match (&attr.message, get_doc_comment(&var.attrs)) {
+        (Some(template), _) => Message::parse(template, |placeholder| {
+            let (i, field) = var.fields.iter().enumerate().find(|(i, f)| {
+                f.ident.as_ref().map_or_else(
+                    || placeholder == i.to_string(),
+                    |ident| ident.unraw() == placeholder,
+                )
+            })?;
+            let member = member(field, i);
+            if let Some(b) = bindings.iter().find(|b| b.member == member) {
+                return Some(b.ident.clone());
+            }
+            let binding = Binding::new(member);
+            let ident = binding.ident.clone();
+            bindings.push(binding);
+            Some(ident)
+        })
+        .map_err(|e| proc_macro_error::emit_error!(e))
+        .ok()?,
+        (None, Some(doc)) => Message::Plain(doc.into_inner()),
+        (None, None) => {
+            ERR.emit_custom(
+                var.ident.span(),
+                "expects a message, specified either with a doc comment or with \
+                 `#[graphql(message = \"...\")]` attribute",
+            );
+            return None;
+        }
+    };
+
+    let code = attr.code.map(SpanContainer::into_inner).unwrap_or_else(|| {
+        RenameRule::ScreamingSnakeCase.apply_to_variant(&var.ident.unraw().to_string())
+    });
+
+    Some(VariantDefinition {
+        description: get_doc_comment(&var.attrs).map(SpanContainer::into_inner),
+        ident: var.ident,
+        fields,
+        code,
+        message,
+        extensions,
+        bindings,
+    })
+}
+
+/// Returns [`syn::Member`] of the given `field` placed at the given `index`.
+fn member(field: &syn::Field, index: usize) -> syn::Member {
+    field
+        .ident
+        .clone()
+        .map_or_else(|| syn::Member::from(index), syn::Member::Named)
+}
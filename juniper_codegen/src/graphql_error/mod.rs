@@ -0,0 +1,671 @@
+//! Code generation for converting Rust error enums into [GraphQL errors][1].
+//!
+//! [1]: https://spec.graphql.org/June2018/#sec-Errors
+
+pub mod derive;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned as _,
+    token,
+};
+
+use crate::{
+    common::{
+        parse::{
+            attr::{err, OptionExt as _},
+            ParseBufferExt as _,
+        },
+        scalar,
+    },
+    util::{filter_attrs, span_container::SpanContainer},
+};
+
+/// Available arguments behind `#[graphql]` attribute placed on a Rust enum
+/// when generating code for [GraphQL errors][1].
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+#[derive(Debug, Default)]
+struct Attr {
+    /// Explicitly specified name of the [GraphQL enum][2] to generate with the
+    /// codes of [GraphQL errors][1].
+    ///
+    /// If [`None`], then no [GraphQL enum][2] is generated.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    /// [2]: https://spec.graphql.org/June2018/#sec-Enums
+    codes: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified type of [`ScalarValue`] to convert the Rust enum
+    /// into [GraphQL errors][1] with.
+    ///
+    /// If [`None`], then generated code will be generic over any
+    /// [`ScalarValue`] type, which, in turn, requires all the extension fields
+    /// to be convertible into a [`Value`] of any [`ScalarValue`] type too.
+    ///
+    /// [`ScalarValue`]: juniper::ScalarValue
+    /// [`Value`]: juniper::Value
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    scalar: Option<SpanContainer<scalar::AttrValue>>,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "codes" => {
+                    input.parse::<token::Eq>()?;
+                    let codes = input.parse::<syn::Ident>()?;
+                    out.codes
+                        .replace(SpanContainer::new(ident.span(), Some(codes.span()), codes))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "scalar" | "Scalar" | "ScalarValue" => {
+                    input.parse::<token::Eq>()?;
+                    let scl = input.parse::<scalar::AttrValue>()?;
+                    out.scalar
+                        .replace(SpanContainer::new(ident.span(), Some(scl.span()), scl))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl Attr {
+    /// Tries to merge two [`Attr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+        Ok(Self {
+            codes: try_merge_opt!(codes: self, another),
+            scalar: try_merge_opt!(scalar: self, another),
+        })
+    }
+
+    /// Parses [`Attr`] from the given multiple `name`d [`syn::Attribute`]s
+    /// placed on an enum definition.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))
+    }
+}
+
+/// Available arguments behind `#[graphql]` attribute when generating code for
+/// a Rust enum variant being converted into a [GraphQL error][1].
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+#[derive(Debug, Default)]
+struct VariantAttr {
+    /// Explicitly specified message template of the [GraphQL error][1].
+    ///
+    /// If [`None`], then Rust doc comment is used as the message, if any.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    message: Option<SpanContainer<syn::LitStr>>,
+
+    /// Explicitly specified `extensions.code` of the [GraphQL error][1].
+    ///
+    /// If [`None`], then Rust variant name in `SCREAMING_SNAKE_CASE` is used.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    code: Option<SpanContainer<String>>,
+}
+
+impl Parse for VariantAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "message" | "msg" => {
+                    input.parse::<token::Eq>()?;
+                    let msg = input.parse::<syn::LitStr>()?;
+                    out.message
+                        .replace(SpanContainer::new(ident.span(), Some(msg.span()), msg))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "code" => {
+                    input.parse::<token::Eq>()?;
+                    let code = input.parse::<syn::LitStr>()?;
+                    out.code
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            Some(code.span()),
+                            code.value(),
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl VariantAttr {
+    /// Tries to merge two [`VariantAttr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+        Ok(Self {
+            message: try_merge_opt!(message: self, another),
+            code: try_merge_opt!(code: self, another),
+        })
+    }
+
+    /// Parses [`VariantAttr`] from the given multiple `name`d
+    /// [`syn::Attribute`]s placed on a variant definition.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))
+    }
+}
+
+/// Available arguments behind `#[graphql]` attribute when generating code for
+/// a field of a Rust enum variant being converted into a [GraphQL error][1].
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+#[derive(Debug, Default)]
+struct FieldAttr {
+    /// Explicitly specified marker for the field being put into the
+    /// `extensions` of the [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    extension: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified name of the field in the `extensions` of the
+    /// [GraphQL error][1].
+    ///
+    /// If [`None`], then Rust field name in `camelCase` is used.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    name: Option<SpanContainer<String>>,
+}
+
+impl Parse for FieldAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "extension" => out
+                    .extension
+                    .replace(SpanContainer::new(ident.span(), None, ident.clone()))
+                    .none_or_else(|_| err::dup_arg(&ident))?,
+                "name" => {
+                    input.parse::<token::Eq>()?;
+                    let name = input.parse::<syn::LitStr>()?;
+                    out.name
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            Some(name.span()),
+                            name.value(),
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl FieldAttr {
+    /// Tries to merge two [`FieldAttr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+        Ok(Self {
+            extension: try_merge_opt!(extension: self, another),
+            name: try_merge_opt!(name: self, another),
+        })
+    }
+
+    /// Parses [`FieldAttr`] from the given multiple `name`d
+    /// [`syn::Attribute`]s placed on a field definition.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))
+    }
+}
+
+/// Definition of a Rust enum converted into [GraphQL errors][1] for code
+/// generation.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+struct Definition {
+    /// Rust enum that is converted into [GraphQL errors][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    ident: syn::Ident,
+
+    /// Visibility of the Rust enum, inherited by the generated code.
+    vis: syn::Visibility,
+
+    /// Generics of the Rust enum.
+    generics: syn::Generics,
+
+    /// Rust type of [`ScalarValue`] to generate [`IntoFieldError`]
+    /// implementation with.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    /// [`ScalarValue`]: juniper::ScalarValue
+    scalar: scalar::Type,
+
+    /// Name of the [GraphQL enum][1] to generate with the codes of all the
+    /// [`Definition::variants`], if any.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Enums
+    codes: Option<syn::Ident>,
+
+    /// Variants of the Rust enum, each converted into a distinct
+    /// [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    variants: Vec<VariantDefinition>,
+}
+
+impl ToTokens for Definition {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        self.impl_into_field_error_tokens().to_tokens(into);
+        self.codes_enum_tokens().to_tokens(into);
+    }
+}
+
+impl Definition {
+    /// Returns generated code implementing [`IntoFieldError`] trait for this
+    /// Rust enum.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    #[must_use]
+    fn impl_into_field_error_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let scalar = &self.scalar;
+
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let mut generics = self.generics.clone();
+        if scalar.is_implicit_generic() {
+            generics.params.push(syn::parse_quote! { #scalar });
+        }
+        if scalar.is_generic() {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! { #scalar: ::juniper::ScalarValue });
+        }
+        if let Some(bound) = scalar.bounds() {
+            generics.make_where_clause().predicates.push(bound);
+        }
+        for ext in self.variants.iter().flat_map(|v| &v.extensions) {
+            let ty = &ext.ty;
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! {
+                    ::juniper::Value<#scalar>: ::std::convert::From<#ty>
+                });
+        }
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let match_arms = self.variants.iter().map(|v| {
+            let pattern = v.pattern(true);
+            let code = &v.code;
+            let message = &v.message;
+            let extensions = v.extensions.iter().map(|ext| {
+                let name = &ext.name;
+                let ty = &ext.ty;
+                let binding = &ext.binding;
+                quote! {
+                    extensions.add_field(
+                        #name,
+                        <::juniper::Value<#scalar> as ::std::convert::From<#ty>>::from(#binding),
+                    );
+                }
+            });
+            let capacity = v.extensions.len() + 1;
+
+            quote! {
+                #pattern => {
+                    let message = #message;
+                    let mut extensions = ::juniper::Object::with_capacity(#capacity);
+                    extensions.add_field("code", ::juniper::Value::scalar(#code.to_owned()));
+                    #( #extensions )*
+                    ::juniper::FieldError::new(message, ::juniper::Value::Object(extensions))
+                }
+            }
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl#impl_generics ::juniper::IntoFieldError<#scalar> for #ident#ty_generics
+                #where_clause
+            {
+                fn into_field_error(self) -> ::juniper::FieldError<#scalar> {
+                    match self {
+                        #( #match_arms )*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns generated code of the [GraphQL enum][1] with the codes of all
+    /// the [`Definition::variants`], along with the `code()` method returning
+    /// it, if [`Definition::codes`] is specified.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Enums
+    #[must_use]
+    fn codes_enum_tokens(&self) -> Option<TokenStream> {
+        let codes = self.codes.as_ref()?;
+        let ident = &self.ident;
+        let vis = &self.vis;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let scalar = match &self.scalar {
+            scalar::Type::Concrete(ty) => Some(quote! { #[graphql(scalar = #ty)] }),
+            _ => None,
+        };
+        let enum_doc = format!("Codes of `{}` errors.", ident);
+
+        let variants = self.variants.iter().map(|v| {
+            let var_ident = &v.ident;
+            let code = &v.code;
+            let description = v.description.as_ref().map(|desc| {
+                quote! {
+                    #[doc = #desc]
+                    #[graphql(description = #desc)]
+                }
+            });
+            quote! {
+                #description
+                #[graphql(name = #code)]
+                #var_ident,
+            }
+        });
+
+        let match_arms = self.variants.iter().map(|v| {
+            let pattern = v.pattern(false);
+            let var_ident = &v.ident;
+            quote! { #pattern => #codes::#var_ident, }
+        });
+
+        Some(quote! {
+            #[doc = #enum_doc]
+            #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ::juniper::GraphQLEnum)]
+            #scalar
+            #vis enum #codes {
+                #( #variants )*
+            }
+
+            #[automatically_derived]
+            impl#impl_generics #ident#ty_generics #where_clause {
+                /// Returns the code of this error, as put into its `extensions.code`.
+                #vis fn code(&self) -> #codes {
+                    match self {
+                        #( #match_arms )*
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Definition of a Rust enum variant converted into a [GraphQL error][1] for
+/// code generation.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+struct VariantDefinition {
+    /// Rust enum variant.
+    ident: syn::Ident,
+
+    /// Kind of fields of the Rust enum variant.
+    fields: FieldsKind,
+
+    /// `extensions.code` of the [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    code: String,
+
+    /// Description of the code of the [GraphQL error][1] to put into the
+    /// generated [GraphQL enum][2], if any.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    /// [2]: https://spec.graphql.org/June2018/#sec-Enums
+    description: Option<String>,
+
+    /// Message of the [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    message: Message,
+
+    /// Fields of the Rust enum variant put into the `extensions` of the
+    /// [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    extensions: Vec<ExtensionDefinition>,
+
+    /// Fields of the Rust enum variant bound while converting it into the
+    /// [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    bindings: Vec<Binding>,
+}
+
+impl VariantDefinition {
+    /// Returns a pattern matching this Rust enum variant, binding the
+    /// [`VariantDefinition::bindings`] if `bind` is `true`.
+    #[must_use]
+    fn pattern(&self, bind: bool) -> TokenStream {
+        let ident = &self.ident;
+        match &self.fields {
+            FieldsKind::Unit => quote! { Self::#ident },
+            FieldsKind::Named if bind => {
+                let fields = self.bindings.iter().map(|b| {
+                    let member = &b.member;
+                    let binding = &b.ident;
+                    quote! { #member: #binding }
+                });
+                quote! { Self::#ident { #( #fields, )* .. } }
+            }
+            FieldsKind::Named => quote! { Self::#ident { .. } },
+            FieldsKind::Unnamed(len) if bind => {
+                let fields = (0..*len).map(|i| {
+                    self.bindings
+                        .iter()
+                        .find(|b| b.member == syn::Member::from(i))
+                        .map_or_else(|| quote! { _ }, |b| b.ident.to_token_stream())
+                });
+                quote! { Self::#ident(#( #fields ),*) }
+            }
+            FieldsKind::Unnamed(_) => quote! { Self::#ident(..) },
+        }
+    }
+}
+
+/// Kind of fields of a Rust enum variant.
+enum FieldsKind {
+    /// Variant without fields.
+    Unit,
+
+    /// Variant with named fields.
+    Named,
+
+    /// Variant with the given number of unnamed fields.
+    Unnamed(usize),
+}
+
+/// Field of a Rust enum variant bound to a local variable while converting it
+/// into a [GraphQL error][1].
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+struct Binding {
+    /// Rust enum variant field.
+    member: syn::Member,
+
+    /// Local variable the field is bound to.
+    ident: syn::Ident,
+}
+
+impl Binding {
+    /// Creates a new [`Binding`] of the given `member`.
+    fn new(member: syn::Member) -> Self {
+        let ident = match &member {
+            syn::Member::Named(ident) => {
+                format_ident!("__{}", syn::ext::IdentExt::unraw(ident))
+            }
+            syn::Member::Unnamed(index) => format_ident!("__{}", index.index),
+        };
+        Self { member, ident }
+    }
+}
+
+/// Definition of a Rust enum variant field put into the `extensions` of a
+/// [GraphQL error][1].
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+struct ExtensionDefinition {
+    /// Name of the field in the `extensions` of the [GraphQL error][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    name: String,
+
+    /// Rust type of the field.
+    ty: syn::Type,
+
+    /// Local variable the field is bound to.
+    binding: syn::Ident,
+}
+
+/// Message of a [GraphQL error][1], either a plain string or a [`format!`]
+/// template referring to the fields of a Rust enum variant.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+enum Message {
+    /// Plain message, not referring to any fields.
+    Plain(String),
+
+    /// [`format!`] template along with the local variables it refers to.
+    Template {
+        /// [`format!`] string with the fields' placeholders replaced by the
+        /// [`Message::Template::args`].
+        format: String,
+
+        /// Local variables the fields are bound to.
+        args: Vec<syn::Ident>,
+    },
+}
+
+impl ToTokens for Message {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        match self {
+            Self::Plain(msg) => quote! { #msg },
+            Self::Template { format, args } => {
+                let args = args.iter().map(|arg| quote! { #arg = #arg });
+                quote! { ::std::format!(#format, #( #args ),*) }
+            }
+        }
+        .to_tokens(into)
+    }
+}
+
+impl Message {
+    /// Parses the given `template` of a [GraphQL error][1] message, resolving
+    /// its `{field}` placeholders into [`Binding`]s with the given `resolve`
+    /// function.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Errors
+    fn parse(
+        template: &syn::LitStr,
+        mut resolve: impl FnMut(&str) -> Option<syn::Ident>,
+    ) -> syn::Result<Self> {
+        let value = template.value();
+        let mut plain = String::with_capacity(value.len());
+        let mut format = String::with_capacity(value.len());
+        let mut args: Vec<syn::Ident> = vec![];
+
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    plain.push('{');
+                    format.push_str("{{");
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    plain.push('}');
+                    format.push_str("}}");
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(syn::Error::new(
+                                    template.span(),
+                                    "unclosed `{` in message, use `{{` to escape it",
+                                ))
+                            }
+                        }
+                    }
+                    let (field, spec) = match placeholder.find(':') {
+                        Some(i) => placeholder.split_at(i),
+                        None => (placeholder.as_str(), ""),
+                    };
+                    let field = field.trim();
+                    if field.is_empty() {
+                        return Err(syn::Error::new(
+                            template.span(),
+                            "message placeholders should refer to fields by their names \
+                             or indices, e.g. `{id}` or `{0}`",
+                        ));
+                    }
+                    let binding = resolve(field).ok_or_else(|| {
+                        syn::Error::new(
+                            template.span(),
+                            format!("message refers to unknown field `{}`", field),
+                        )
+                    })?;
+                    format.push_str(&format!("{{{}{}}}", binding, spec));
+                    if !args.contains(&binding) {
+                        args.push(binding);
+                    }
+                }
+                '}' => {
+                    return Err(syn::Error::new(
+                        template.span(),
+                        "unmatched `}` in message, use `}}` to escape it",
+                    ))
+                }
+                c => {
+                    plain.push(c);
+                    format.push(c);
+                }
+            }
+        }
+
+        Ok(if args.is_empty() {
+            Self::Plain(plain)
+        } else {
+            Self::Template { format, args }
+        })
+    }
+}
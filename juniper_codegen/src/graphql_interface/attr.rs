@@ -198,6 +198,7 @@ fn expand_on_trait(
 
         fields,
         implementers,
+        cache_control: attr.cache_control.map(SpanContainer::into_inner),
     };
 
     // Attach the `juniper::AsDynGraphQLValue` on top of the trait if dynamic dispatch is used.
@@ -460,12 +461,20 @@ impl TraitMethod {
             .deprecated
             .as_deref()
             .map(|d| d.as_ref().map(syn::LitStr::value));
+        let directives = attr
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect();
+        let cache_control = attr.cache_control.map(SpanContainer::into_inner);
 
         Some(field::Definition {
             name,
             ty,
             description,
             deprecated,
+            directives,
+            cache_control,
             ident: method_ident.clone(),
             arguments: Some(arguments),
             has_receiver: method.sig.receiver().is_some(),
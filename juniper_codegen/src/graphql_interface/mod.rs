@@ -21,6 +21,7 @@ use syn::{
 
 use crate::{
     common::{
+        cache_control::CacheControl,
         field, gen,
         parse::{
             attr::{err, OptionExt as _},
@@ -133,6 +134,12 @@ struct TraitAttr {
     /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
     rename_fields: Option<SpanContainer<RenameRule>>,
 
+    /// Explicitly specified cache control hint of all the fields returning
+    /// this [GraphQL interface][1] type.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Indicator whether the generated code is intended to be used only inside
     /// the [`juniper`] library.
     is_internal: bool,
@@ -233,6 +240,12 @@ impl Parse for TraitAttr {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?;
                 }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    out.cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "internal" => {
                     out.is_internal = true;
                 }
@@ -263,6 +276,7 @@ impl TraitAttr {
                 external_downcasts: self, another => span_joined
             ),
             rename_fields: try_merge_opt!(rename_fields: self, another),
+            cache_control: try_merge_opt!(cache_control: self, another),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -431,6 +445,12 @@ struct Definition {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
     implementers: Vec<Implementer>,
+
+    /// Cache control hint of all the fields returning this
+    /// [GraphQL interface][1] to put into GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    cache_control: Option<CacheControl>,
 }
 
 impl ToTokens for Definition {
@@ -522,6 +542,7 @@ impl Definition {
             .description
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
+        let cache_control = &self.cache_control;
 
         // Sorting is required to preserve/guarantee the order of implementers registered in schema.
         let mut impler_tys: Vec<_> = self.implementers.iter().map(|impler| &impler.ty).collect();
@@ -554,6 +575,7 @@ impl Definition {
                     ];
                     registry.build_interface_type::<#ty>(info, &fields)
                         #description
+                        #cache_control
                         .into_meta()
                 }
             }
@@ -65,6 +65,8 @@ where
         );
     }
 
+    attr.validate_concrete(&ast.generics, &ERR);
+
     let scalar = scalar::Type::parse(attr.scalar.as_deref(), &ast.generics);
 
     proc_macro_error::abort_if_dirty();
@@ -127,6 +129,17 @@ where
             .iter()
             .map(|ty| ty.as_ref().clone())
             .collect(),
+        directives: attr
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
+        cache_control: attr.cache_control.map(SpanContainer::into_inner),
+        concrete: attr
+            .concrete
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         _operation: PhantomData,
     };
 
@@ -221,12 +234,20 @@ fn parse_field(
         .deprecated
         .as_deref()
         .map(|d| d.as_ref().map(syn::LitStr::value));
+    let directives = attr
+        .directives
+        .into_iter()
+        .map(SpanContainer::into_inner)
+        .collect();
+    let cache_control = attr.cache_control.map(SpanContainer::into_inner);
 
     Some(field::Definition {
         name,
         ty,
         description,
         deprecated,
+        directives,
+        cache_control,
         ident: method_ident.clone(),
         arguments: Some(arguments),
         has_receiver: method.sig.receiver().is_some(),
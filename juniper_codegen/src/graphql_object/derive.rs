@@ -54,6 +54,8 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<Definition<Query>> {
         );
     }
 
+    attr.validate_concrete(&ast.generics, &ERR);
+
     let scalar = scalar::Type::parse(attr.scalar.as_deref(), &ast.generics);
 
     proc_macro_error::abort_if_dirty();
@@ -104,6 +106,17 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<Definition<Query>> {
             .iter()
             .map(|ty| ty.as_ref().clone())
             .collect(),
+        directives: attr
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
+        cache_control: attr.cache_control.map(SpanContainer::into_inner),
+        concrete: attr
+            .concrete
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         _operation: PhantomData,
     })
 }
@@ -146,12 +159,20 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
         .deprecated
         .as_deref()
         .map(|d| d.as_ref().map(syn::LitStr::value));
+    let directives = attr
+        .directives
+        .into_iter()
+        .map(SpanContainer::into_inner)
+        .collect();
+    let cache_control = attr.cache_control.map(SpanContainer::into_inner);
 
     Some(field::Definition {
         name,
         ty,
         description,
         deprecated,
+        directives,
+        cache_control,
         ident: field_ident.clone(),
         arguments: None,
         has_receiver: false,
@@ -5,19 +5,28 @@
 pub mod attr;
 pub mod derive;
 
-use std::{any::TypeId, collections::HashSet, convert::TryInto as _, marker::PhantomData};
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+    convert::TryInto as _,
+    marker::PhantomData,
+};
 
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
     parse_quote,
+    punctuated::Punctuated,
     spanned::Spanned as _,
     token,
+    visit_mut::{self, VisitMut},
 };
 
 use crate::{
     common::{
+        cache_control::CacheControl,
+        directive::Directive,
         field,
         parse::{
             attr::{err, OptionExt as _},
@@ -25,6 +34,7 @@ use crate::{
         },
         scalar,
     },
+    result::GraphQLScope,
     util::{filter_attrs, get_doc_comment, span_container::SpanContainer, RenameRule},
 };
 use syn::ext::IdentExt;
@@ -80,6 +90,19 @@ pub(crate) struct Attr {
     /// [2]: https://spec.graphql.org/June2018/#sec-Interfaces
     pub(crate) interfaces: HashSet<SpanContainer<syn::Type>>,
 
+    /// Explicitly specified [directives][2] applied to this [GraphQL object][1]
+    /// type.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    /// [2]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) directives: Vec<SpanContainer<Directive>>,
+
+    /// Explicitly specified cache control hint of all the fields returning
+    /// this [GraphQL object][1] type.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Explicitly specified [`RenameRule`] for all fields of this
     /// [GraphQL object][1] type.
     ///
@@ -88,6 +111,12 @@ pub(crate) struct Attr {
     /// [1]: https://spec.graphql.org/June2018/#sec-Objects
     pub(crate) rename_fields: Option<SpanContainer<RenameRule>>,
 
+    /// Explicitly specified [`Concrete`] instantiations of this generic
+    /// [GraphQL object][1] type, each one having its own name.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) concrete: Vec<SpanContainer<Concrete>>,
+
     /// Indicator whether the generated code is intended to be used only inside
     /// the [`juniper`] library.
     pub(crate) is_internal: bool,
@@ -147,6 +176,21 @@ impl Parse for Attr {
                             .none_or_else(|_| err::dup_arg(iface_span))?;
                     }
                 }
+                "directive" => {
+                    input.parse::<token::Eq>()?;
+                    let directive = input.parse::<Directive>()?;
+                    out.directives.push(SpanContainer::new(
+                        ident.span(),
+                        Some(directive.name.span()),
+                        directive,
+                    ))
+                }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    out.cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "rename_all" => {
                     input.parse::<token::Eq>()?;
                     let val = input.parse::<syn::LitStr>()?;
@@ -158,6 +202,14 @@ impl Parse for Attr {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?;
                 }
+                "concrete" => {
+                    let concrete = input.parse::<Concrete>()?;
+                    out.concrete.push(SpanContainer::new(
+                        ident.span(),
+                        Some(concrete.name.span()),
+                        concrete,
+                    ))
+                }
                 "internal" => {
                     out.is_internal = true;
                 }
@@ -174,14 +226,19 @@ impl Parse for Attr {
 impl Attr {
     /// Tries to merge two [`Attr`]s into a single one, reporting about
     /// duplicates, if any.
-    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+    fn try_merge(mut self, mut another: Self) -> syn::Result<Self> {
+        self.directives.append(&mut another.directives);
+        self.concrete.append(&mut another.concrete);
         Ok(Self {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
             context: try_merge_opt!(context: self, another),
             scalar: try_merge_opt!(scalar: self, another),
             interfaces: try_merge_hashset!(interfaces: self, another => span_joined),
+            directives: self.directives,
+            cache_control: try_merge_opt!(cache_control: self, another),
             rename_fields: try_merge_opt!(rename_fields: self, another),
+            concrete: self.concrete,
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -199,6 +256,134 @@ impl Attr {
 
         Ok(attr)
     }
+
+    /// Checks the [`Concrete`] instantiations of this [`Attr`] to be applicable
+    /// to the given `generics`, emitting errors otherwise.
+    pub(crate) fn validate_concrete(&self, generics: &syn::Generics, scope: &GraphQLScope) {
+        if self.concrete.is_empty() {
+            return;
+        }
+        if let Some(name) = &self.name {
+            scope.emit_custom(
+                name.span_ident(),
+                "cannot specify both `name` and `concrete`",
+            );
+        }
+
+        let params_count = generics.type_params().count();
+        if params_count == 0 {
+            scope.emit_custom(
+                self.concrete[0].span_ident(),
+                "expects `concrete` to be used on generic types only",
+            );
+            return;
+        }
+
+        let mut names = HashSet::new();
+        for concrete in &self.concrete {
+            let name = concrete.name.value();
+            if concrete.params.len() != params_count {
+                scope.emit_custom(
+                    concrete.span_joined(),
+                    format!(
+                        "expects `concrete` to specify all {} type parameters in `params`",
+                        params_count,
+                    ),
+                );
+            }
+            if name.starts_with("__") {
+                scope.no_double_underscore(concrete.name.span());
+            }
+            if !names.insert(name) {
+                scope.emit_custom(
+                    concrete.name.span(),
+                    "must have a different name for each `concrete` type",
+                );
+            }
+        }
+    }
+}
+
+/// Concrete instantiation of a generic [GraphQL object][1] type, represented in
+/// GraphQL schema by its own name.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[derive(Debug)]
+pub(crate) struct Concrete {
+    /// Name of this instantiation in GraphQL schema.
+    pub(crate) name: syn::LitStr,
+
+    /// Types to instantiate the type parameters with, in their declaration
+    /// order.
+    pub(crate) params: Vec<syn::Type>,
+}
+
+impl Parse for Concrete {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let content;
+        let paren = syn::parenthesized!(content in input);
+
+        let (mut name, mut params) = (None, None);
+        while !content.is_empty() {
+            let ident = content.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "name" => {
+                    content.parse::<token::Eq>()?;
+                    name.replace(content.parse::<syn::LitStr>()?)
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "params" => {
+                    let inner;
+                    syn::parenthesized!(inner in content);
+                    let tys = Punctuated::<syn::Type, token::Comma>::parse_terminated(&inner)?;
+                    params
+                        .replace(tys.into_iter().collect())
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            content.try_parse::<token::Comma>()?;
+        }
+
+        Ok(Self {
+            name: name.ok_or_else(|| {
+                syn::Error::new(paren.span, "expects `name` to be specified in `concrete`")
+            })?,
+            params: params.unwrap_or_default(),
+        })
+    }
+}
+
+/// [`VisitMut`] substituting type parameters with the concrete types.
+struct SubstituteTypeParams<'a>(HashMap<&'a syn::Ident, &'a syn::Type>);
+
+impl VisitMut for SubstituteTypeParams<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(path) = ty {
+            if path.qself.is_none() && path.path.leading_colon.is_none() {
+                let segments = &path.path.segments;
+                let first = &segments[0];
+                if let Some(concrete) = first
+                    .arguments
+                    .is_empty()
+                    .then(|| self.0.get(&first.ident))
+                    .flatten()
+                {
+                    *ty = if segments.len() == 1 {
+                        (*concrete).clone()
+                    } else {
+                        // Associated type, like `T::Item`.
+                        let rest = segments.iter().skip(1);
+                        parse_quote! { <#concrete>#( ::#rest )* }
+                    };
+                    return;
+                }
+            }
+        }
+        visit_mut::visit_type_mut(self, ty)
+    }
 }
 
 /// Definition of [GraphQL object][1] for code generation.
@@ -257,6 +442,25 @@ pub(crate) struct Definition<Operation: ?Sized> {
     /// [2]: https://spec.graphql.org/June2018/#sec-Interfaces
     pub(crate) interfaces: HashSet<syn::Type>,
 
+    /// [Directives][2] applied to this [GraphQL object][1] to put into GraphQL
+    /// schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    /// [2]: https://spec.graphql.org/June2018/#sec-Type-System.Directives
+    pub(crate) directives: Vec<Directive>,
+
+    /// Cache control hint of all the fields returning this [GraphQL object][1]
+    /// to put into GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) cache_control: Option<CacheControl>,
+
+    /// [`Concrete`] instantiations of this generic [GraphQL object][1], if
+    /// it's named per instantiation.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) concrete: Vec<Concrete>,
+
     /// [GraphQL operation][1] this [`Definition`] should generate code for.
     ///
     /// Either [GraphQL query][2] or [GraphQL subscription][3].
@@ -268,6 +472,82 @@ pub(crate) struct Definition<Operation: ?Sized> {
 }
 
 impl<Operation: ?Sized + 'static> Definition<Operation> {
+    /// Instantiates this generic [GraphQL object][1] with the type parameters
+    /// of the given [`Concrete`] one, so the code generated for it is no
+    /// longer generic over them.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    #[must_use]
+    pub(crate) fn instantiate(&self, concrete: &Concrete) -> Self {
+        let mut subst = SubstituteTypeParams(
+            self.generics
+                .type_params()
+                .map(|param| &param.ident)
+                .zip(&concrete.params)
+                .collect(),
+        );
+
+        let mut generics = self.generics.clone();
+        generics.params = generics
+            .params
+            .into_iter()
+            .filter(|param| match param {
+                syn::GenericParam::Type(param) => !subst.0.contains_key(&param.ident),
+                _ => true,
+            })
+            .collect();
+        if let Some(where_clause) = &mut generics.where_clause {
+            subst.visit_where_clause_mut(where_clause);
+        }
+
+        let mut ty = self.ty.clone();
+        subst.visit_type_mut(&mut ty);
+        let mut context = self.context.clone();
+        subst.visit_type_mut(&mut context);
+
+        let fields = self
+            .fields
+            .iter()
+            .cloned()
+            .map(|mut field| {
+                subst.visit_type_mut(&mut field.ty);
+                for arg in field.arguments.iter_mut().flatten() {
+                    match arg {
+                        field::MethodArgument::Regular(arg) => subst.visit_type_mut(&mut arg.ty),
+                        field::MethodArgument::Context(ty) => subst.visit_type_mut(ty),
+                        field::MethodArgument::Executor => {}
+                    }
+                }
+                field
+            })
+            .collect();
+
+        let interfaces = self
+            .interfaces
+            .iter()
+            .cloned()
+            .map(|mut iface| {
+                subst.visit_type_mut(&mut iface);
+                iface
+            })
+            .collect();
+
+        Self {
+            name: concrete.name.value(),
+            ty,
+            generics,
+            description: self.description.clone(),
+            context,
+            scalar: self.scalar.clone(),
+            fields,
+            interfaces,
+            directives: self.directives.clone(),
+            cache_control: self.cache_control.clone(),
+            concrete: vec![],
+            _operation: PhantomData,
+        }
+    }
+
     /// Returns prepared [`syn::Generics::split_for_impl`] for [`GraphQLType`]
     /// trait (and similar) implementation of this [GraphQL object][1].
     ///
@@ -378,6 +658,8 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
             .description
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
+        let directives = &self.directives;
+        let cache_control = &self.cache_control;
 
         let extract_stream_type = TypeId::of::<Operation>() != TypeId::of::<Query>();
         let fields_meta = self
@@ -419,6 +701,8 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
                     registry.build_object_type::<#ty>(info, &fields)
                         #description
                         #interfaces
+                        #( #directives )*
+                        #cache_control
                         .into_meta()
                 }
             }
@@ -433,6 +717,13 @@ struct Query;
 
 impl ToTokens for Definition<Query> {
     fn to_tokens(&self, into: &mut TokenStream) {
+        if !self.concrete.is_empty() {
+            return self
+                .concrete
+                .iter()
+                .for_each(|concrete| self.instantiate(concrete).to_tokens(into));
+        }
+
         self.impl_graphql_object_tokens().to_tokens(into);
         self.impl_output_type_tokens().to_tokens(into);
         self.impl_graphql_type_tokens().to_tokens(into);
@@ -18,6 +18,13 @@ struct Subscription;
 
 impl ToTokens for Definition<Subscription> {
     fn to_tokens(&self, into: &mut TokenStream) {
+        if !self.concrete.is_empty() {
+            return self
+                .concrete
+                .iter()
+                .for_each(|concrete| self.instantiate(concrete).to_tokens(into));
+        }
+
         self.impl_output_type_tokens().to_tokens(into);
         self.impl_graphql_type_tokens().to_tokens(into);
         self.impl_graphql_value_tokens().to_tokens(into);
@@ -99,6 +99,7 @@ fn expand_on_trait(
         scalar: scalar::Type::parse(attr.scalar.as_deref(), &ast.generics),
         generics: ast.generics.clone(),
         variants,
+        cache_control: attr.cache_control.map(SpanContainer::into_inner),
     };
 
     Ok(quote! {
@@ -90,6 +90,7 @@ fn expand_enum(ast: syn::DeriveInput) -> syn::Result<Definition> {
         scalar: scalar::Type::parse(attr.scalar.as_deref(), &ast.generics),
         generics: ast.generics,
         variants,
+        cache_control: attr.cache_control.map(SpanContainer::into_inner),
     })
 }
 
@@ -220,5 +221,6 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<Definition> {
         scalar: scalar::Type::parse(attr.scalar.as_deref(), &ast.generics),
         generics: ast.generics,
         variants,
+        cache_control: attr.cache_control.map(SpanContainer::into_inner),
     })
 }
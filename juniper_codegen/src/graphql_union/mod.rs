@@ -19,6 +19,7 @@ use syn::{
 
 use crate::{
     common::{
+        cache_control::CacheControl,
         gen,
         parse::{
             attr::{err, OptionExt as _},
@@ -87,6 +88,12 @@ struct Attr {
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     external_resolvers: AttrResolvers,
 
+    /// Explicitly specified cache control hint of all the fields returning
+    /// this [GraphQL union][1] type.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    cache_control: Option<SpanContainer<CacheControl>>,
+
     /// Indicator whether the generated code is intended to be used only inside
     /// the [`juniper`] library.
     is_internal: bool,
@@ -144,6 +151,12 @@ impl Parse for Attr {
                         .insert(ty, rslvr_spanned)
                         .none_or_else(|_| err::dup_arg(rslvr_span))?
                 }
+                "cache_control" => {
+                    let hint = input.parse::<CacheControl>()?;
+                    out.cache_control
+                        .replace(SpanContainer::new(ident.span(), None, hint))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "internal" => {
                     out.is_internal = true;
                 }
@@ -169,6 +182,7 @@ impl Attr {
             external_resolvers: try_merge_hashmap!(
                 external_resolvers: self, another => span_joined
             ),
+            cache_control: try_merge_opt!(cache_control: self, another),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -311,6 +325,12 @@ struct Definition {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     variants: Vec<VariantDefinition>,
+
+    /// Cache control hint of all the fields returning this [GraphQL union][1]
+    /// to put into GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    cache_control: Option<CacheControl>,
 }
 
 impl ToTokens for Definition {
@@ -473,6 +493,7 @@ impl Definition {
             .description
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
+        let cache_control = &self.cache_control;
 
         let variant_tys = self.variants.iter().map(|var| &var.ty);
 
@@ -495,6 +516,7 @@ impl Definition {
                     ];
                     registry.build_union_type::<#ty_full>(info, &types)
                         #description
+                        #cache_control
                         .into_meta()
                 }
             }
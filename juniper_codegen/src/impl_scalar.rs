@@ -6,7 +6,10 @@ use crate::{
 };
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::spanned::Spanned;
+use syn::{
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+};
 
 #[derive(Debug)]
 struct ScalarCodegenInput {
@@ -41,6 +44,18 @@ fn get_method_return_type(output: syn::ReturnType) -> Option<syn::Type> {
     }
 }
 
+// Checks whether the return type of `from_input_value` is a `Result`, so the
+// method describes its failures
+fn is_result_type(return_type: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = return_type {
+        if let Some(path_segment) = type_path.path.segments.last() {
+            return path_segment.ident == "Result";
+        }
+    }
+
+    false
+}
+
 // Find the enum type by inspecting the type parameter on the return value
 fn get_enum_type(return_type: &Option<syn::Type>) -> Option<syn::PathSegment> {
     if let Some(syn::Type::Path(type_path)) = return_type {
@@ -147,6 +162,18 @@ impl syn::parse::Parse for ScalarCodegenInput {
     }
 }
 
+/// [`VisitMut`] replacing `Self` with the type the scalar is implemented for.
+struct ReplaceSelf<'a>(&'a syn::PathSegment);
+
+impl VisitMut for ReplaceSelf<'_> {
+    fn visit_path_mut(&mut self, path: &mut syn::Path) {
+        if path.leading_colon.is_none() && path.segments[0].ident == "Self" {
+            path.segments[0] = self.0.clone();
+        }
+        visit_mut::visit_path_mut(self, path);
+    }
+}
+
 /// Generate code for the juniper::graphql_scalar proc macro.
 pub fn build_scalar(
     attributes: TokenStream,
@@ -227,6 +254,42 @@ pub fn build_scalar(
         _ => quote!(),
     };
 
+    let from_input_value = if is_result_type(&from_input_value_result) {
+        // Inner functions can't refer to `Self` of the outer `impl` block.
+        let mut from_input_value_result = from_input_value_result;
+        let mut from_input_value_body = from_input_value_body;
+        let mut replace_self = ReplaceSelf(&impl_for_type);
+        replace_self.visit_type_mut(&mut from_input_value_result);
+        replace_self.visit_block_mut(&mut from_input_value_body);
+
+        quote! {
+            fn from_input_value(
+                v: &::juniper::InputValue<#generic_type>,
+            ) -> ::std::option::Option<Self> {
+                <Self as ::juniper::FromInputValue<#generic_type>>::try_from_input_value(v).ok()
+            }
+
+            fn try_from_input_value(
+                #from_input_value_arg: &::juniper::InputValue<#generic_type>,
+            ) -> ::std::result::Result<Self, ::juniper::InputValueError> {
+                fn from_input_value#generic_type_decl(
+                    #from_input_value_arg: &::juniper::InputValue<#generic_type>,
+                ) -> #from_input_value_result
+                #generic_type_bound
+                #from_input_value_body
+
+                from_input_value(#from_input_value_arg)
+                    .map_err(|e| ::juniper::InputValueError::new(e.to_string()))
+            }
+        }
+    } else {
+        quote! {
+            fn from_input_value(#from_input_value_arg: &::juniper::InputValue<#generic_type>) -> #from_input_value_result {
+                #from_input_value_body
+            }
+        }
+    };
+
     let _async = quote!(
         impl#async_generic_type_decl ::juniper::GraphQLValueAsync<#async_generic_type> for #impl_for_type
         where
@@ -309,9 +372,7 @@ pub fn build_scalar(
         impl#generic_type_decl ::juniper::FromInputValue<#generic_type> for #impl_for_type
         #generic_type_bound
         {
-            fn from_input_value(#from_input_value_arg: &::juniper::InputValue<#generic_type>) -> #from_input_value_result {
-                #from_input_value_body
-            }
+            #from_input_value
         }
 
         impl#generic_type_decl ::juniper::ParseScalarValue<#generic_type> for #impl_for_type
@@ -112,6 +112,7 @@ mod derive_scalar_value;
 mod impl_scalar;
 
 mod common;
+mod graphql_error;
 mod graphql_interface;
 mod graphql_object;
 mod graphql_subscription;
@@ -245,6 +246,38 @@ pub fn derive_scalar_value(input: TokenStream) -> TokenStream {
 /// In addition to implementing `GraphQLType` for the type in question,
 /// `FromInputValue` and `ToInputValue` is also implemented. This makes the type
 /// usable as arguments and default values.
+///
+/// `from_input_value` may also return a `Result` with any error implementing
+/// `Display` (e.g. a `String`). The error message is then reported to clients
+/// when the scalar is given an invalid value.
+///
+/// ```rust
+/// # use std::convert::TryFrom as _;
+/// struct Port(u16);
+///
+/// #[juniper::graphql_scalar]
+/// impl<S> GraphQLScalar for Port
+/// where
+///     S: juniper::ScalarValue
+///  {
+///     fn resolve(&self) -> juniper::Value {
+///         juniper::Value::scalar(i32::from(self.0))
+///     }
+///
+///     fn from_input_value(value: &juniper::InputValue) -> Result<Port, String> {
+///         let port = value.as_int_value().ok_or("Expected `Int`")?;
+///         u16::try_from(port)
+///             .map(Port)
+///             .map_err(|_| format!("{} is out of range", port))
+///     }
+///
+///     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> juniper::ParseScalarResult<'a, S> {
+///         <i32 as juniper::ParseScalarValue<S>>::from_str(value)
+///     }
+/// }
+///
+/// # fn main() { }
+/// ```
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_scalar(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -750,6 +783,27 @@ pub fn graphql_interface(attr: TokenStream, body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Generic types
+///
+/// A generic struct can't be named in GraphQL schema the same way for all its
+/// instantiations. Instead, list the needed ones with `concrete` attribute's
+/// arguments, each specifying the GraphQL name and the types to instantiate
+/// the type parameters with. A separate [GraphQL object][1] is generated for
+/// each of them.
+///
+/// ```
+/// # use juniper::GraphQLObject;
+/// #
+/// #[derive(GraphQLObject)]
+/// #[graphql(
+///     concrete(name = "IntPage", params(i32)),
+///     concrete(name = "StringPage", params(String)),
+/// )]
+/// struct Page<T> {
+///     items: Vec<T>,
+/// }
+/// ```
+///
 /// # Ignoring struct fields
 ///
 /// To omit exposing a struct field in the GraphQL schema, use an `ignore`
@@ -948,6 +1002,33 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Argument validation
+///
+/// Values of a field argument may be constrained with a `validate` attribute
+/// argument, accepting `length(min = .., max = ..)`, `range(min = .., max = ..)`,
+/// `pattern = ".."`, `items(min = .., max = ..)` and `custom = function`
+/// validators. Violating values are reported as validation errors, so the
+/// field is never resolved with them. The `pattern` validator requires the
+/// `regex` feature of `juniper`.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn greet(
+///         #[graphql(validate(length(min = 1, max = 20), pattern = "^[A-Z]"))]
+///         name: String,
+///         #[graphql(validate(range(min = 1, max = 3)))]
+///         times: i32,
+///     ) -> String {
+///         format!("Hello, {}!", name).repeat(times as usize)
+///     }
+/// }
+/// ```
+///
 /// # Renaming policy
 ///
 /// By default, all [GraphQL object][1] fields and their arguments are renamed
@@ -981,6 +1062,30 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Generic types
+///
+/// A generic type can't be named in GraphQL schema the same way for all its
+/// instantiations. Instead, list the needed ones with `concrete` attribute's
+/// arguments, each specifying the GraphQL name and the types to instantiate
+/// the type parameters with. A separate [GraphQL object][1] is generated for
+/// each of them.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Wrapper<T>(T);
+///
+/// #[graphql_object(
+///     concrete(name = "IntWrapper", params(i32)),
+///     concrete(name = "BoolWrapper", params(bool)),
+/// )]
+/// impl<T: ToString> Wrapper<T> {
+///     fn value(&self) -> String {
+///         self.0.to_string()
+///     }
+/// }
+/// ```
+///
 /// # Ignoring methods
 ///
 /// To omit some method to be assumed as a [GraphQL object][1] field and ignore
@@ -1779,3 +1884,102 @@ pub fn graphql_union(attr: TokenStream, body: TokenStream) -> TokenStream {
         .unwrap_or_abort()
         .into()
 }
+
+/// `#[derive(GraphQLError)]` macro for deriving an [`IntoFieldError`] implementation for enums,
+/// so they can be returned from resolvers as [GraphQL errors][1] with a machine-readable
+/// `extensions.code`.
+///
+/// The `#[graphql]` helper attribute is used for configuring the derived implementation. Specifying
+/// multiple `#[graphql]` attributes on the same definition is totally okay. They all will be
+/// treated as a single attribute.
+///
+/// Every enum variant becomes a [GraphQL error][1] with the message given by a `message` attribute's
+/// argument, or by the variant's doc comment otherwise, and the variant's name in
+/// `SCREAMING_SNAKE_CASE` as its `extensions.code`, unless a `code` attribute's argument is
+/// specified. A message may refer to the variant's fields in the same way as [`format!`] does.
+///
+/// Fields marked with an `extension` attribute's argument are put into the `extensions` of the
+/// [GraphQL error][1] along with the code, under their names in `camelCase`, unless a `name`
+/// attribute's argument is specified. Their types should be convertible into a [`Value`].
+///
+/// ```
+/// use juniper::{graphql_object, FieldResult, GraphQLError};
+///
+/// #[derive(GraphQLError)]
+/// enum UserError {
+///     /// User is not authenticated.
+///     Unauthenticated,
+///
+///     #[graphql(message = "User `{id}` is not found", code = "NO_USER")]
+///     NotFound {
+///         #[graphql(extension)]
+///         id: String,
+///     },
+///
+///     #[graphql(message = "Too many requests, retry in {0} seconds")]
+///     RateLimited(#[graphql(extension, name = "retryAfter")] i32),
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     // Errors as `{"message": "User `42` is not found", "extensions": {"code": "NO_USER", "id": "42"}}`.
+///     fn user_name(id: String) -> Result<String, UserError> {
+///         Err(UserError::NotFound { id })
+///     }
+/// }
+/// ```
+///
+/// # Codes in schema
+///
+/// To let clients know the possible codes through introspection, specify a `codes` attribute's
+/// argument: it generates a [GraphQL enum][2] of all the codes with the given name, along with a
+/// `code()` method returning the code of an error. The [GraphQL enum][2] is then registered in a
+/// schema with [`RootNode::with_type`].
+///
+/// ```
+/// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, GraphQLError, RootNode};
+/// #
+/// #[derive(GraphQLError)]
+/// #[graphql(codes = UserErrorCode)]
+/// enum UserError {
+///     /// User is not authenticated.
+///     Unauthenticated,
+///
+///     /// User is not allowed to do this.
+///     Forbidden,
+/// }
+///
+/// assert_eq!(UserError::Forbidden.code(), UserErrorCode::Forbidden);
+///
+/// # struct Query;
+/// #
+/// # #[graphql_object]
+/// # impl Query {
+/// #     fn ok() -> bool { true }
+/// # }
+/// #
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_type::<UserErrorCode>(&());
+/// ```
+///
+/// # Custom `ScalarValue`
+///
+/// By default, the derived implementation is generic over all [`ScalarValue`]s, which requires the
+/// types of extension fields to be convertible into a [`Value`] of any [`ScalarValue`]. To use a
+/// concrete [`ScalarValue`] instead, specify a `scalar` attribute's argument.
+///
+/// [`IntoFieldError`]: juniper::IntoFieldError
+/// [`RootNode::with_type`]: juniper::RootNode::with_type
+/// [`ScalarValue`]: juniper::ScalarValue
+/// [`Value`]: juniper::Value
+/// [1]: https://spec.graphql.org/June2018/#sec-Errors
+/// [2]: https://spec.graphql.org/June2018/#sec-Enums
+#[proc_macro_error]
+#[proc_macro_derive(GraphQLError, attributes(graphql))]
+pub fn derive_error(body: TokenStream) -> TokenStream {
+    self::graphql_error::derive::expand(body.into())
+        .unwrap_or_abort()
+        .into()
+}
@@ -15,6 +15,7 @@ pub enum GraphQLScope {
     ObjectDerive,
     UnionAttr,
     UnionDerive,
+    ErrorDerive,
     DeriveInputObject,
     DeriveEnum,
     DeriveScalar,
@@ -27,6 +28,7 @@ impl GraphQLScope {
             Self::InterfaceAttr => "#sec-Interfaces",
             Self::ObjectAttr | Self::ObjectDerive => "#sec-Objects",
             Self::UnionAttr | Self::UnionDerive => "#sec-Unions",
+            Self::ErrorDerive => "#sec-Errors",
             Self::DeriveInputObject => "#sec-Input-Objects",
             Self::DeriveEnum => "#sec-Enums",
             Self::DeriveScalar | Self::ImplScalar => "#sec-Scalars",
@@ -40,6 +42,7 @@ impl fmt::Display for GraphQLScope {
             Self::InterfaceAttr => "interface",
             Self::ObjectAttr | Self::ObjectDerive => "object",
             Self::UnionAttr | Self::UnionDerive => "union",
+            Self::ErrorDerive => "error",
             Self::DeriveInputObject => "input object",
             Self::DeriveEnum => "enum",
             Self::DeriveScalar | Self::ImplScalar => "scalar",
@@ -57,6 +60,12 @@ pub enum UnsupportedAttribute {
     Scalar,
     Deprecation,
     Default,
+    Ignore,
+    Fallback,
+    ToInputValueWith,
+    FromInputValueWith,
+    Validate,
+    Flatten,
 }
 
 impl GraphQLScope {
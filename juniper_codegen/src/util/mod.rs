@@ -18,7 +18,11 @@ use syn::{
     token, Attribute, Ident, Lit, Meta, MetaList, MetaNameValue, NestedMeta,
 };
 
-use crate::common::parse::ParseBufferExt as _;
+use crate::common::{
+    directive::Directive,
+    parse::ParseBufferExt as _,
+    validate::{self, Validator},
+};
 
 /// Compares a path to a one-segment string value,
 /// return true if equal.
@@ -263,6 +267,21 @@ impl RenameRule {
             Self::ScreamingSnakeCase => to_upper_snake_case(field),
         }
     }
+
+    /// Applies this rule to an enum variant name, which is `PascalCase`
+    /// instead of `snake_case`.
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        match self {
+            Self::CamelCase => {
+                let mut chars = variant.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            Self::None | Self::ScreamingSnakeCase => self.apply(variant),
+        }
+    }
 }
 
 impl FromStr for RenameRule {
@@ -299,9 +318,14 @@ pub struct ObjectAttributes {
     pub context: Option<SpanContainer<syn::Type>>,
     pub scalar: Option<SpanContainer<syn::Type>>,
     pub interfaces: Vec<SpanContainer<syn::Type>>,
+    pub directives: Vec<Directive>,
     pub no_async: Option<SpanContainer<()>>,
     pub is_internal: bool,
     pub rename: Option<RenameRule>,
+    /// Only relevant for enums.
+    pub to_input_value_with: Option<SpanContainer<syn::ExprPath>>,
+    /// Only relevant for enums.
+    pub from_input_value_with: Option<SpanContainer<syn::ExprPath>>,
 }
 
 impl Parse for ObjectAttributes {
@@ -356,6 +380,10 @@ impl Parse for ObjectAttributes {
                         })
                         .collect();
                 }
+                "directive" => {
+                    input.parse::<token::Eq>()?;
+                    output.directives.push(input.parse::<Directive>()?);
+                }
                 // FIXME: make this unneccessary.
                 "noasync" => {
                     output.no_async = Some(SpanContainer::new(ident.span(), None, ()));
@@ -363,10 +391,22 @@ impl Parse for ObjectAttributes {
                 "internal" => {
                     output.is_internal = true;
                 }
-                "rename" => {
+                "rename" | "rename_all" => {
                     input.parse::<token::Eq>()?;
                     output.rename = Some(input.parse::<RenameRule>()?);
                 }
+                "to_input_value_with" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::ExprPath>()?;
+                    output.to_input_value_with =
+                        Some(SpanContainer::new(ident.span(), Some(val.span()), val));
+                }
+                "from_input_value_with" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::ExprPath>()?;
+                    output.from_input_value_with =
+                        Some(SpanContainer::new(ident.span(), Some(val.span()), val));
+                }
                 _ => {
                     return Err(syn::Error::new(ident.span(), "unknown attribute"));
                 }
@@ -456,8 +496,13 @@ enum FieldAttribute {
     Description(SpanContainer<syn::LitStr>),
     Deprecation(SpanContainer<DeprecationAttr>),
     Skip(SpanContainer<syn::Ident>),
+    Ignore(SpanContainer<syn::Ident>),
+    Fallback(SpanContainer<syn::Ident>),
     Arguments(HashMap<String, FieldAttributeArgument>),
     Default(Box<SpanContainer<Option<syn::Expr>>>),
+    Directive(Directive),
+    Validate(Vec<SpanContainer<Validator>>),
+    Flatten(SpanContainer<syn::Ident>),
 }
 
 impl Parse for FieldAttribute {
@@ -508,6 +553,20 @@ impl Parse for FieldAttribute {
                 None,
                 ident,
             ))),
+            "ignore" => Ok(FieldAttribute::Ignore(SpanContainer::new(
+                ident.span(),
+                None,
+                ident,
+            ))),
+            "fallback" => {
+                input.parse::<token::Eq>()?;
+                let variant = input.parse::<syn::Ident>()?;
+                Ok(FieldAttribute::Fallback(SpanContainer::new(
+                    ident.span(),
+                    Some(variant.span()),
+                    variant,
+                )))
+            }
             "arguments" => {
                 let arg_content;
                 syn::parenthesized!(arg_content in input);
@@ -532,6 +591,30 @@ impl Parse for FieldAttribute {
 
                 Ok(FieldAttribute::Default(Box::new(default_expr)))
             }
+            "default_with" => {
+                input.parse::<token::Eq>()?;
+                let path = input.parse::<syn::ExprPath>()?;
+                Ok(FieldAttribute::Default(Box::new(SpanContainer::new(
+                    ident.span(),
+                    Some(path.span()),
+                    Some(parse_quote! { #path() }),
+                ))))
+            }
+            "flatten" => Ok(FieldAttribute::Flatten(SpanContainer::new(
+                ident.span(),
+                None,
+                ident,
+            ))),
+            "directive" => {
+                input.parse::<token::Eq>()?;
+                Ok(FieldAttribute::Directive(input.parse()?))
+            }
+            "validate" => Ok(FieldAttribute::Validate(
+                validate::parse_list(input)?
+                    .into_iter()
+                    .map(|v| SpanContainer::new(ident.span(), None, v))
+                    .collect(),
+            )),
             _ => Err(syn::Error::new(ident.span(), "unknown attribute")),
         }
     }
@@ -544,10 +627,20 @@ pub struct FieldAttributes {
     pub deprecation: Option<SpanContainer<DeprecationAttr>>,
     // Only relevant for GraphQLObject derive.
     pub skip: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for enums.
+    pub ignore: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for enums.
+    pub fallback: Option<SpanContainer<syn::Ident>>,
     /// Only relevant for object macro.
     pub arguments: HashMap<String, FieldAttributeArgument>,
     /// Only relevant for object input objects.
     pub default: Option<SpanContainer<Option<syn::Expr>>>,
+    /// Only relevant for enums and input objects.
+    pub directives: Vec<Directive>,
+    /// Only relevant for input objects.
+    pub validators: Vec<SpanContainer<Validator>>,
+    /// Only relevant for input objects.
+    pub flatten: Option<SpanContainer<syn::Ident>>,
 }
 
 impl Parse for FieldAttributes {
@@ -570,12 +663,27 @@ impl Parse for FieldAttributes {
                 FieldAttribute::Skip(ident) => {
                     output.skip = Some(ident);
                 }
+                FieldAttribute::Ignore(ident) => {
+                    output.ignore = Some(ident);
+                }
+                FieldAttribute::Fallback(variant) => {
+                    output.fallback = Some(variant);
+                }
                 FieldAttribute::Arguments(args) => {
                     output.arguments = args;
                 }
                 FieldAttribute::Default(expr) => {
                     output.default = Some(*expr);
                 }
+                FieldAttribute::Directive(directive) => {
+                    output.directives.push(directive);
+                }
+                FieldAttribute::Validate(mut validators) => {
+                    output.validators.append(&mut validators);
+                }
+                FieldAttribute::Flatten(ident) => {
+                    output.flatten = Some(ident);
+                }
             }
         }
 
@@ -633,6 +741,9 @@ pub struct GraphQLTypeDefinitionField {
     pub is_type_inferred: bool,
     pub is_async: bool,
     pub default: Option<TokenStream>,
+    pub directives: Vec<Directive>,
+    pub validators: Vec<Validator>,
+    pub flatten: bool,
     pub span: Span,
 }
 
@@ -661,6 +772,7 @@ pub struct GraphQLTypeDefiniton {
     pub fields: Vec<GraphQLTypeDefinitionField>,
     pub generics: syn::Generics,
     pub interfaces: Vec<syn::Type>,
+    pub directives: Vec<Directive>,
     // Due to syn parsing differences,
     // when parsing an impl the type generics are included in the type
     // directly, but in syn::DeriveInput, the type generics are
@@ -675,6 +787,22 @@ pub struct GraphQLTypeDefiniton {
     pub generic_scalar: bool,
     // FIXME: make this redundant.
     pub no_async: bool,
+    /// Only relevant for enums.
+    pub ignored_variants: Vec<IgnoredVariant>,
+    /// Only relevant for enums.
+    pub to_input_value_with: Option<syn::ExprPath>,
+    /// Only relevant for enums.
+    pub from_input_value_with: Option<syn::ExprPath>,
+}
+
+/// Enum variant omitted from the GraphQL schema, which is represented by the
+/// value of its fallback variant instead.
+#[derive(Debug)]
+pub struct IgnoredVariant {
+    /// Pattern matching the ignored variant.
+    pub pattern: TokenStream,
+    /// GraphQL name of the fallback variant.
+    pub fallback: String,
 }
 
 impl GraphQLTypeDefiniton {
@@ -710,6 +838,7 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let directives = &self.directives;
 
         let values = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
@@ -729,42 +858,100 @@ impl GraphQLTypeDefiniton {
                 })
                 .unwrap_or_else(|| quote!(::juniper::meta::DeprecationStatus::Current));
 
+            let directives = &variant.directives;
+
             quote!(
                 ::juniper::meta::EnumValue {
                     name: #variant_name.to_string(),
                     description: #descr,
                     deprecation_status: #depr,
-                },
+                    directives: vec![],
+                }
+                #( #directives )*,
             )
         });
 
-        let resolves = self.fields.iter().map(|variant| {
-            let variant_name = &variant.name;
-            let resolver_code = &variant.resolver_code;
+        let resolves = self
+            .fields
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.name;
+                let resolver_code = &variant.resolver_code;
 
-            quote!(
-                &#resolver_code => ::juniper::Value::scalar(String::from(#variant_name)),
-            )
-        });
+                quote!(
+                    &#resolver_code => ::juniper::Value::scalar(String::from(#variant_name)),
+                )
+            })
+            .chain(self.ignored_variants.iter().map(|variant| {
+                let pattern = &variant.pattern;
+                let fallback = &variant.fallback;
 
-        let from_inputs = self.fields.iter().map(|variant| {
-            let variant_name = &variant.name;
-            let resolver_code = &variant.resolver_code;
+                quote!(
+                    &#pattern => ::juniper::Value::scalar(String::from(#fallback)),
+                )
+            }));
 
-            quote!(
-                Some(#variant_name) => Some(#resolver_code),
-            )
-        });
+        let from_input = match &self.from_input_value_with {
+            Some(with) => quote!( #with(v) ),
+            None => {
+                let from_inputs = self.fields.iter().map(|variant| {
+                    let variant_name = &variant.name;
+                    let resolver_code = &variant.resolver_code;
 
-        let to_inputs = self.fields.iter().map(|variant| {
-            let variant_name = &variant.name;
-            let resolver_code = &variant.resolver_code;
+                    quote!(
+                        Some(#variant_name) => Some(#resolver_code),
+                    )
+                });
 
-            quote!(
-                &#resolver_code =>
-                    ::juniper::InputValue::scalar(#variant_name.to_string()),
-            )
-        });
+                quote!(
+                    match v.as_enum_value().or_else(|| {
+                        v.as_string_value()
+                    }) {
+                        #( #from_inputs )*
+                        _ => None,
+                    }
+                )
+            }
+        };
+
+        // Values converted by a custom function aren't necessarily named by
+        // the enum values, so validation has to defer to that function.
+        let accepts_custom_values = self
+            .from_input_value_with
+            .as_ref()
+            .map(|_| quote!( .accepts_custom_values() ));
+
+        let to_input = match &self.to_input_value_with {
+            Some(with) => quote!( #with(self) ),
+            None => {
+                let to_inputs = self
+                    .fields
+                    .iter()
+                    .map(|variant| {
+                        let variant_name = &variant.name;
+                        let resolver_code = &variant.resolver_code;
+
+                        quote!(
+                            &#resolver_code =>
+                                ::juniper::InputValue::scalar(#variant_name.to_string()),
+                        )
+                    })
+                    .chain(self.ignored_variants.iter().map(|variant| {
+                        let pattern = &variant.pattern;
+                        let fallback = &variant.fallback;
+
+                        quote!(
+                            &#pattern => ::juniper::InputValue::scalar(#fallback.to_string()),
+                        )
+                    }));
+
+                quote!(
+                    match self {
+                        #( #to_inputs )*
+                    }
+                )
+            }
+        };
 
         let mut generics = self.generics.clone();
 
@@ -830,6 +1017,8 @@ impl GraphQLTypeDefiniton {
                         #( #values )*
                     ])
                     #description
+                    #( #directives )*
+                    #accepts_custom_values
                     .into_meta()
                 }
             }
@@ -862,12 +1051,7 @@ impl GraphQLTypeDefiniton {
             {
                 fn from_input_value(v: &::juniper::InputValue<#scalar>) -> Option<#ty>
                 {
-                    match v.as_enum_value().or_else(|| {
-                        v.as_string_value()
-                    }) {
-                        #( #from_inputs )*
-                        _ => None,
-                    }
+                    #from_input
                 }
             }
 
@@ -875,9 +1059,7 @@ impl GraphQLTypeDefiniton {
                 #where_clause
             {
                 fn to_input_value(&self) -> ::juniper::InputValue<#scalar> {
-                    match self {
-                        #( #to_inputs )*
-                    }
+                    #to_input
                 }
             }
         );
@@ -920,6 +1102,12 @@ impl GraphQLTypeDefiniton {
                 let field_ty = &field._type;
                 let field_name = &field.name;
 
+                if field.flatten {
+                    return quote! {
+                        fields.extend(registry.flattened_input_fields::<#field_ty>(&()));
+                    };
+                }
+
                 let description = match field.description.as_ref() {
                     Some(description) => quote!( .description(#description) ),
                     None => quote!(),
@@ -949,13 +1137,18 @@ impl GraphQLTypeDefiniton {
                     }
                 };
 
-                quote!(
-                    {
+                let directives = &field.directives;
+                let validators = &field.validators;
+
+                quote! {
+                    fields.push(
                         #create_meta_field
                         #description
                         #deprecation
-                    },
-                )
+                        #( #directives )*
+                        #( #validators )*
+                    );
+                }
             })
             .collect::<Vec<_>>();
 
@@ -966,6 +1159,12 @@ impl GraphQLTypeDefiniton {
                 let field_ident = &field.resolver_code;
                 let field_name = &field.name;
 
+                if field.flatten {
+                    return quote! {
+                        #field_ident: ::juniper::FromInputValue::<#scalar>::from_input_value(value)?,
+                    };
+                }
+
                 // Build from_input clause.
                 let from_input_default = match field.default {
                     Some(ref def) => {
@@ -995,9 +1194,22 @@ impl GraphQLTypeDefiniton {
                 let field_name = &field.name;
                 let field_ident = &field.resolver_code;
                 // Build to_input clause.
-                quote!(
-                    (#field_name, self.#field_ident.to_input_value()),
-                )
+                if field.flatten {
+                    quote! {
+                        if let Some(obj) = ::juniper::ToInputValue::<#scalar>::to_input_value(
+                            &self.#field_ident,
+                        ).to_object_value() {
+                            fields.extend(obj.into_iter().map(|(k, v)| (k.to_owned(), v.clone())));
+                        }
+                    }
+                } else {
+                    quote! {
+                        fields.push((
+                            #field_name.to_owned(),
+                            ::juniper::ToInputValue::<#scalar>::to_input_value(&self.#field_ident),
+                        ));
+                    }
+                }
             })
             .collect::<Vec<_>>();
 
@@ -1005,6 +1217,7 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let directives = &self.directives;
 
         // Preserve the original type_generics before modification,
         // since alteration makes them invalid if self.generic_scalar
@@ -1060,6 +1273,9 @@ impl GraphQLTypeDefiniton {
                     }
                 }
 
+            impl#impl_generics ::juniper::marker::GraphQLInputObject<#scalar> for #ty #type_generics_tokens
+                #where_clause {}
+
             impl#impl_generics ::juniper::GraphQLType<#scalar> for #ty #type_generics_tokens
                 #where_clause
             {
@@ -1073,11 +1289,11 @@ impl GraphQLTypeDefiniton {
                 ) -> ::juniper::meta::MetaType<'r, #scalar>
                 where #scalar: 'r
                 {
-                    let fields = &[
-                        #( #meta_fields )*
-                    ];
-                    registry.build_input_object_type::<#ty>(&(), fields)
+                    let mut fields = ::std::vec::Vec::new();
+                    #( #meta_fields )*
+                    registry.build_input_object_type::<#ty>(&(), &fields)
                     #description
+                    #( #directives )*
                     .into_meta()
                 }
             }
@@ -1109,9 +1325,12 @@ impl GraphQLTypeDefiniton {
                 #where_clause
             {
                 fn to_input_value(&self) -> ::juniper::InputValue<#scalar> {
-                    ::juniper::InputValue::object(vec![
-                        #( #to_inputs )*
-                    ].into_iter().collect())
+                    let mut fields = ::std::vec::Vec::<(
+                        ::std::string::String,
+                        ::juniper::InputValue<#scalar>,
+                    )>::new();
+                    #( #to_inputs )*
+                    ::juniper::InputValue::object(fields.into_iter().collect())
                 }
             }
         );
@@ -1261,6 +1480,20 @@ mod test {
         assert_eq!(to_upper_snake_case("some_INpuT"), "SOME_INPU_T");
     }
 
+    #[test]
+    fn test_rename_rule_apply_to_variant() {
+        assert_eq!(
+            RenameRule::CamelCase.apply_to_variant("SomeInput"),
+            "someInput"
+        );
+        assert_eq!(RenameRule::CamelCase.apply_to_variant("A"), "a");
+        assert_eq!(RenameRule::None.apply_to_variant("SomeInput"), "SomeInput");
+        assert_eq!(
+            RenameRule::ScreamingSnakeCase.apply_to_variant("SomeInput"),
+            "SOME_INPUT",
+        );
+    }
+
     #[test]
     fn test_is_valid_name() {
         assert_eq!(is_valid_name("yesItIs"), true);
@@ -18,6 +18,9 @@ pub use server_message::*;
 mod schema;
 pub use schema::*;
 
+mod serving;
+pub use serving::*;
+
 mod utils;
 
 use std::{
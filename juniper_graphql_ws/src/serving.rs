@@ -0,0 +1,91 @@
+use std::{
+    convert::{Infallible, TryFrom},
+    error::Error,
+    fmt,
+};
+
+use juniper::{
+    futures::{
+        future::{self, Either},
+        Sink, SinkExt as _, Stream, StreamExt as _, TryStreamExt as _,
+    },
+    ScalarValue,
+};
+
+use crate::{ClientMessage, Connection, Init, Schema};
+
+/// Payload of a data message received over a WebSocket connection.
+struct Payload<D>(D);
+
+impl<S: ScalarValue, D: AsRef<[u8]>> TryFrom<Payload<D>> for ClientMessage<S> {
+    type Error = ServeError<Infallible>;
+
+    fn try_from(payload: Payload<D>) -> Result<Self, Self::Error> {
+        serde_json::from_slice(payload.0.as_ref()).map_err(ServeError::Serde)
+    }
+}
+
+/// Error of serving the graphql-ws protocol with [`serve`].
+#[derive(Debug)]
+pub enum ServeError<E> {
+    /// Error of the underlying WebSocket connection.
+    Transport(E),
+
+    /// Error of serializing or deserializing a message. Errors of deserializing incoming messages
+    /// are reported to the client by the protocol itself.
+    Serde(serde_json::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for ServeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Transport(e) => write!(f, "websocket error: {}", e),
+            Self::Serde(e) => write!(f, "serde error: {}", e),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ServeError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Transport(e) => Some(e),
+            Self::Serde(e) => Some(e),
+        }
+    }
+}
+
+/// Serves the graphql-ws protocol over a WebSocket connection, until either side of it ends.
+///
+/// The `incoming` stream should yield the payloads of the text and binary messages received from
+/// the client, leaving pings, pongs and closes to the WebSocket implementation. The JSON encoded
+/// messages of the server are sent into the `outgoing` sink.
+///
+/// See [`Connection::new`] for the meaning of the `schema` and `init` arguments.
+pub async fn serve<S, I, D, E, St, Si>(
+    schema: S,
+    init: I,
+    incoming: St,
+    outgoing: Si,
+) -> Result<(), ServeError<E>>
+where
+    S: Schema,
+    I: Init<S::ScalarValue, S::Context> + Send,
+    D: AsRef<[u8]>,
+    St: Stream<Item = Result<D, E>> + Unpin,
+    Si: Sink<String, Error = E> + Unpin,
+{
+    let (s_tx, s_rx) = Connection::new(schema, init).split();
+
+    let incoming = incoming.map_ok(Payload).map_err(ServeError::Transport);
+    let s_rx = s_rx.map(|msg| serde_json::to_string(&msg).map_err(ServeError::Serde));
+
+    match future::select(
+        incoming.forward(s_tx.sink_map_err(|e: Infallible| match e {})),
+        s_rx.forward(outgoing.sink_map_err(ServeError::Transport)),
+    )
+    .await
+    {
+        Either::Left((r, _)) => r,
+        Either::Right((r, _)) => r,
+    }
+}
@@ -1,18 +1,24 @@
 #![doc(html_root_url = "https://docs.rs/juniper_hyper/0.2.0")]
 
-use std::{error::Error, fmt, string::FromUtf8Error, sync::Arc};
+use std::{error::Error, fmt, future::Future, sync::Arc};
 
 use hyper::{
+    body::Bytes,
     header::{self, HeaderValue},
     Body, Method, Request, Response, StatusCode,
 };
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest},
-    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, RootNode, ScalarValue,
+    http::{
+        multipart::{GraphQLMultipartRequest, MultipartError, MultipartLimits, MultipartParser},
+        negotiation::{self, ContentEncoding, CsrfPrevention, RequestError, ResponseFormat},
+        streaming::JsonChunks,
+        GraphQLBatchRequest, GraphQLBatchResponse, Method as GraphQLMethod,
+    },
+    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode, ScalarValue,
 };
-use serde_json::error::Error as SerdeError;
-use url::form_urlencoded;
 
+/// Handles a GraphQL request like [`graphql`] does, executing it
+/// synchronously.
 pub async fn graphql_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
@@ -26,14 +32,17 @@ where
     SubscriptionT: GraphQLType<S, Context = CtxT>,
     SubscriptionT::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
 {
     match parse_req(req).await {
-        Ok(req) => execute_request_sync(root_node, context, req).await,
+        Ok((req, method, negotiated)) => {
+            execute_request_sync(root_node, context, req, method, negotiated).await
+        }
         Err(resp) => resp,
     }
 }
 
+/// Handles a GraphQL request sent via `GET` or `POST`.
 pub async fn graphql<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
@@ -47,75 +56,179 @@ where
     SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
     SubscriptionT::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
 {
     match parse_req(req).await {
-        Ok(req) => execute_request(root_node, context, req).await,
+        Ok((req, method, negotiated)) => {
+            execute_request(root_node, context, req, method, negotiated).await
+        }
         Err(resp) => resp,
     }
 }
 
-async fn parse_req<S: ScalarValue>(
+/// Handles GraphQL requests like [`graphql`] does, additionally accepting
+/// file uploads via [GraphQL multipart requests][1] limited by the given
+/// `limits`.
+///
+/// Files are accepted as arguments of the [`Upload`] type.
+///
+/// [`Upload`]: juniper::http::multipart::Upload
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+pub async fn graphql_with_uploads<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
     req: Request<Body>,
-) -> Result<GraphQLBatchRequest<S>, Response<Body>> {
-    match *req.method() {
-        Method::GET => parse_get_req(req),
-        Method::POST => {
-            let content_type = req
-                .headers()
-                .get(header::CONTENT_TYPE)
-                .map(HeaderValue::to_str);
-            match content_type {
-                Some(Ok("application/json")) => parse_post_json_req(req.into_body()).await,
-                Some(Ok("application/graphql")) => parse_post_graphql_req(req.into_body()).await,
-                _ => return Err(new_response(StatusCode::BAD_REQUEST)),
+    limits: MultipartLimits,
+) -> Response<Body>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let boundary = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|ct| ct.to_str().ok())
+        .and_then(|ct| multer::parse_boundary(ct).ok());
+    match (req.method(), boundary) {
+        (&Method::POST, Some(boundary)) => {
+            let negotiated = match negotiate_response(&req) {
+                Ok(negotiated) => negotiated,
+                Err(resp) => return resp,
+            };
+            match parse_multipart_req(req.into_body(), boundary, limits).await {
+                Ok(req) => {
+                    let res = req.execute(&*root_node, &context).await;
+                    new_json_response(res, negotiated)
+                }
+                Err(err) => render_error(err),
             }
         }
-        _ => return Err(new_response(StatusCode::METHOD_NOT_ALLOWED)),
+        _ => graphql(root_node, context, req).await,
     }
-    .map_err(render_error)
 }
 
-fn parse_get_req<S: ScalarValue>(
+/// Wraps the given handler, like [`graphql`], [`graphql_sync`] or
+/// [`graphql_with_uploads`], so it replies with `400 Bad Request` to the
+/// requests blocked by the given [`CsrfPrevention`] guard instead of executing
+/// them.
+///
+/// Only `GET` and `POST` requests are checked, the other ones are passed to
+/// the `handler` as is.
+///
+/// Example:
+///
+/// ```
+/// # use std::{convert::Infallible, sync::Arc};
+/// #
+/// # use hyper::{service::service_fn, Body, Request};
+/// # use juniper::{
+/// #     http::negotiation::CsrfPrevention, EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper::tests::fixtures::starwars::schema::{Database, Query};
+/// #
+/// # let root_node = Arc::new(RootNode::new(
+/// #     Query,
+/// #     EmptyMutation::<Database>::new(),
+/// #     EmptySubscription::<Database>::new(),
+/// # ));
+/// # let ctx = Arc::new(Database::new());
+/// #
+/// let csrf_prevention = Arc::new(CsrfPrevention::new());
+/// let service = service_fn(move |req: Request<Body>| {
+///     let (root_node, ctx) = (root_node.clone(), ctx.clone());
+///     let csrf_prevention = csrf_prevention.clone();
+///     async move {
+///         Ok::<_, Infallible>(
+///             juniper_hyper::with_csrf_prevention(&csrf_prevention, req, |req| {
+///                 juniper_hyper::graphql(root_node, ctx, req)
+///             })
+///             .await,
+///         )
+///     }
+/// });
+/// ```
+pub async fn with_csrf_prevention<F, Fut>(
+    prevention: &CsrfPrevention,
     req: Request<Body>,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    req.uri()
-        .query()
-        .map(|q| gql_request_from_get(q).map(GraphQLBatchRequest::Single))
-        .unwrap_or_else(|| {
-            Err(GraphQLRequestError::Invalid(
-                "'query' parameter is missing".to_string(),
-            ))
-        })
+    handler: F,
+) -> Response<Body>
+where
+    F: FnOnce(Request<Body>) -> Fut,
+    Fut: Future<Output = Response<Body>>,
+{
+    if matches!(*req.method(), Method::GET | Method::POST) {
+        let header = |name: &str| req.headers().get(name).and_then(|v| v.to_str().ok());
+        if let Err(e) = prevention.check(header(header::CONTENT_TYPE.as_str()), header) {
+            return render_error(GraphQLRequestError::Request(e));
+        }
+    }
+    handler(req).await
 }
 
-async fn parse_post_json_req<S: ScalarValue>(
-    body: Body,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
-
-    let input = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
+async fn parse_req<S: ScalarValue>(
+    req: Request<Body>,
+) -> Result<(GraphQLBatchRequest<S>, GraphQLMethod, NegotiatedResponse), Response<Body>> {
+    let negotiated = negotiate_response(&req)?;
+    let method = negotiation::parse_method(req.method().as_str())
+        .map_err(|e| render_error(GraphQLRequestError::Request(e)))?;
+    let (parts, body) = req.into_parts();
+    let body = match parts.method {
+        Method::POST => hyper::body::to_bytes(body)
+            .await
+            .map_err(|e| render_error(GraphQLRequestError::BodyHyper(e)))?,
+        _ => Bytes::new(),
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|ct| ct.to_str().ok());
 
-    serde_json::from_str::<GraphQLBatchRequest<S>>(&input)
-        .map_err(GraphQLRequestError::BodyJSONError)
+    negotiation::parse_request(
+        parts.method.as_str(),
+        parts.uri.query(),
+        content_type,
+        &body,
+    )
+    .map(|req| (req, method, negotiated))
+    .map_err(|e| render_error(GraphQLRequestError::Request(e)))
 }
 
-async fn parse_post_graphql_req<S: ScalarValue>(
-    body: Body,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
+/// Format and content coding of a response negotiated with the client.
+type NegotiatedResponse = (ResponseFormat, ContentEncoding);
 
-    let query = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
+fn negotiate_response(req: &Request<Body>) -> Result<NegotiatedResponse, Response<Body>> {
+    let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+    let format = ResponseFormat::negotiate(header(header::ACCEPT))
+        .map_err(|e| render_error(GraphQLRequestError::Request(e)))?;
+    let encoding = ContentEncoding::negotiate(header(header::ACCEPT_ENCODING));
+    Ok((format, encoding))
+}
 
-    Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
-        query, None, None,
-    )))
+async fn parse_multipart_req<S: ScalarValue>(
+    body: Body,
+    boundary: String,
+    limits: MultipartLimits,
+) -> Result<GraphQLMultipartRequest<S>, GraphQLRequestError> {
+    let mut multipart = multer::Multipart::new(body, boundary);
+    let mut parser = MultipartParser::new(limits);
+    while let Some(mut field) = multipart.next_field().await? {
+        parser.start_part(
+            field.name().unwrap_or_default(),
+            field.file_name().map(Into::into),
+            field.content_type().map(ToString::to_string),
+        )?;
+        while let Some(chunk) = field.chunk().await? {
+            parser.push_chunk(&chunk)?;
+        }
+        parser.end_part()?;
+    }
+    Ok(parser.finish()?)
 }
 
 pub async fn graphiql(
@@ -145,7 +258,22 @@ pub async fn playground(
 
 fn render_error(err: GraphQLRequestError) -> Response<Body> {
     let message = format!("{}", err);
-    let mut resp = new_response(StatusCode::BAD_REQUEST);
+    let allowed_methods = match err {
+        GraphQLRequestError::Request(ref e) => e.allowed_methods(),
+        _ => None,
+    };
+    let code = match err {
+        GraphQLRequestError::Multipart(ref e) if e.is_limit_exceeded() => {
+            StatusCode::PAYLOAD_TOO_LARGE
+        }
+        GraphQLRequestError::Request(ref e) => StatusCode::from_u16(e.status_code()).unwrap(),
+        _ => StatusCode::BAD_REQUEST,
+    };
+    let mut resp = new_response(code);
+    if let Some(methods) = allowed_methods {
+        resp.headers_mut()
+            .insert(header::ALLOW, HeaderValue::from_static(methods));
+    }
     *resp.body_mut() = Body::from(message);
     resp
 }
@@ -154,6 +282,8 @@ async fn execute_request_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    method: GraphQLMethod,
+    negotiated: NegotiatedResponse,
 ) -> Response<Body>
 where
     QueryT: GraphQLType<S, Context = CtxT>,
@@ -163,28 +293,18 @@ where
     SubscriptionT: GraphQLType<S, Context = CtxT>,
     SubscriptionT::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
 {
-    let res = request.execute_sync(&*root_node, &context);
-    let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
-    let mut resp = new_response(code);
-    resp.headers_mut().insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
-    );
-    *resp.body_mut() = body;
-    resp
+    let res = request.execute_sync_via(method, &*root_node, &context);
+    new_json_response(res, negotiated)
 }
 
 async fn execute_request<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    method: GraphQLMethod,
+    negotiated: NegotiatedResponse,
 ) -> Response<Body>
 where
     QueryT: GraphQLTypeAsync<S, Context = CtxT>,
@@ -194,71 +314,10 @@ where
     SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
     SubscriptionT::TypeInfo: Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
 {
-    let res = request.execute(&*root_node, &context).await;
-    let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
-    let mut resp = new_response(code);
-    resp.headers_mut().insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
-    );
-    *resp.body_mut() = body;
-    resp
-}
-
-fn gql_request_from_get<S>(input: &str) -> Result<JuniperGraphQLRequest<S>, GraphQLRequestError>
-where
-    S: ScalarValue,
-{
-    let mut query = None;
-    let operation_name = None;
-    let mut variables = None;
-    for (key, value) in form_urlencoded::parse(input.as_bytes()).into_owned() {
-        match key.as_ref() {
-            "query" => {
-                if query.is_some() {
-                    return Err(invalid_err("query"));
-                }
-                query = Some(value)
-            }
-            "operationName" => {
-                if operation_name.is_some() {
-                    return Err(invalid_err("operationName"));
-                }
-            }
-            "variables" => {
-                if variables.is_some() {
-                    return Err(invalid_err("variables"));
-                }
-                match serde_json::from_str::<InputValue<S>>(&value)
-                    .map_err(GraphQLRequestError::Variables)
-                {
-                    Ok(parsed_variables) => variables = Some(parsed_variables),
-                    Err(e) => return Err(e),
-                }
-            }
-            _ => continue,
-        }
-    }
-    match query {
-        Some(query) => Ok(JuniperGraphQLRequest::new(query, operation_name, variables)),
-        None => Err(GraphQLRequestError::Invalid(
-            "'query' parameter is missing".to_string(),
-        )),
-    }
-}
-
-fn invalid_err(parameter_name: &str) -> GraphQLRequestError {
-    GraphQLRequestError::Invalid(format!(
-        "'{}' parameter is specified multiple times",
-        parameter_name
-    ))
+    let res = request.execute_via(method, &*root_node, &context).await;
+    new_json_response(res, negotiated)
 }
 
 fn new_response(code: StatusCode) -> Response<Body> {
@@ -267,6 +326,38 @@ fn new_response(code: StatusCode) -> Response<Body> {
     r
 }
 
+/// Creates a response streaming the given [`GraphQLBatchResponse`] as JSON,
+/// compressed with the negotiated [`ContentEncoding`].
+fn new_json_response<S: ScalarValue + Send + 'static>(
+    res: GraphQLBatchResponse<S>,
+    (format, encoding): NegotiatedResponse,
+) -> Response<Body> {
+    let code = StatusCode::from_u16(negotiation::status_code(&res, format)).unwrap();
+    let mut resp = new_response(code);
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    if let Some(coding) = encoding.header_value() {
+        let headers = resp.headers_mut();
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(coding));
+        headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+    }
+    if let Some(policy) = res.cache_policy() {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&policy.header_value()).unwrap(),
+        );
+    }
+    if let Some(methods) = negotiation::allowed_methods(&res) {
+        resp.headers_mut()
+            .insert(header::ALLOW, HeaderValue::from_static(methods));
+    }
+    let chunks = JsonChunks::new(res).encoded(encoding);
+    *resp.body_mut() = Body::wrap_stream(futures::stream::iter(chunks));
+    resp
+}
+
 fn new_html_response(code: StatusCode) -> Response<Body> {
     let mut resp = new_response(code);
     resp.headers_mut().insert(
@@ -279,20 +370,30 @@ fn new_html_response(code: StatusCode) -> Response<Body> {
 #[derive(Debug)]
 enum GraphQLRequestError {
     BodyHyper(hyper::Error),
-    BodyUtf8(FromUtf8Error),
-    BodyJSONError(SerdeError),
-    Variables(SerdeError),
-    Invalid(String),
+    Request(RequestError),
+    BodyMultipart(multer::Error),
+    Multipart(MultipartError),
+}
+
+impl From<multer::Error> for GraphQLRequestError {
+    fn from(err: multer::Error) -> Self {
+        GraphQLRequestError::BodyMultipart(err)
+    }
+}
+
+impl From<MultipartError> for GraphQLRequestError {
+    fn from(err: MultipartError) -> Self {
+        GraphQLRequestError::Multipart(err)
+    }
 }
 
 impl fmt::Display for GraphQLRequestError {
     fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             GraphQLRequestError::BodyHyper(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::BodyUtf8(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::BodyJSONError(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::Variables(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::Invalid(ref err) => fmt::Display::fmt(err, &mut f),
+            GraphQLRequestError::Request(ref err) => fmt::Display::fmt(err, &mut f),
+            GraphQLRequestError::BodyMultipart(ref err) => fmt::Display::fmt(err, &mut f),
+            GraphQLRequestError::Multipart(ref err) => fmt::Display::fmt(err, &mut f),
         }
     }
 }
@@ -301,23 +402,180 @@ impl Error for GraphQLRequestError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match *self {
             GraphQLRequestError::BodyHyper(ref err) => Some(err),
-            GraphQLRequestError::BodyUtf8(ref err) => Some(err),
-            GraphQLRequestError::BodyJSONError(ref err) => Some(err),
-            GraphQLRequestError::Variables(ref err) => Some(err),
-            GraphQLRequestError::Invalid(_) => None,
+            GraphQLRequestError::Request(ref err) => Some(err),
+            GraphQLRequestError::BodyMultipart(ref err) => Some(err),
+            GraphQLRequestError::Multipart(ref err) => Some(err),
+        }
+    }
+}
+
+/// Serving GraphQL subscriptions over WebSocket connections upgraded from
+/// Hyper requests.
+///
+/// Keep-alive messages of the graphql-ws protocol are sent by the
+/// [`juniper_graphql_ws::Connection`] itself, at the interval configured with
+/// [`juniper_graphql_ws::ConnectionConfig::with_keep_alive_interval`], while
+/// WebSocket pings sent by clients are answered with pongs.
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions {
+    use std::sync::Arc;
+
+    use hyper::{
+        header::{self, HeaderValue},
+        upgrade::Upgraded,
+        Body, Method, Request, Response, StatusCode,
+    };
+    use juniper::{
+        futures::{future, SinkExt as _, StreamExt as _, TryStreamExt as _},
+        GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
+    };
+    use juniper_graphql_ws::{ArcSchema, Init, ServeError};
+    use tokio_tungstenite::{
+        tungstenite::{self, handshake::derive_accept_key, protocol::Role},
+        WebSocketStream,
+    };
+
+    /// Subprotocol served over the upgraded connections.
+    const PROTOCOL: &str = "graphql-ws";
+
+    /// Errors that can happen while serving a connection, either in Tungstenite or while
+    /// serializing outgoing messages.
+    pub type Error = ServeError<tungstenite::Error>;
+
+    /// Upgrades the request to a WebSocket connection serving the graphql-ws protocol.
+    ///
+    /// Returns the `101 Switching Protocols` response to be sent back to the client, and spawns
+    /// a task serving the connection once Hyper has upgraded it, so it must be called within a
+    /// Tokio runtime. Requests that aren't valid WebSocket handshakes are responded with
+    /// `400 Bad Request`.
+    ///
+    /// The `init` argument is used to provide the context and additional configuration for
+    /// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+    /// configuration are already known, or it can be a closure that gets executed asynchronously
+    /// when the client sends the ConnectionInit message. Using a closure allows you to perform
+    /// authentication based on the parameters provided by the client.
+    pub fn subscriptions_handler<Query, Mutation, Subscription, CtxT, S, I>(
+        mut req: Request<Body>,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        init: I,
+    ) -> Response<Body>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send + 'static,
+    {
+        let accept_key = match handshake_accept_key(&req) {
+            Some(key) => key,
+            None => {
+                let mut resp = Response::new(Body::from("Invalid WebSocket handshake"));
+                *resp.status_mut() = StatusCode::BAD_REQUEST;
+                return resp;
+            }
+        };
+        let offers_protocol = req
+            .headers()
+            .get_all(header::SEC_WEBSOCKET_PROTOCOL)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|p| p.trim() == PROTOCOL);
+
+        let upgrade = hyper::upgrade::on(&mut req);
+        tokio::spawn(async move {
+            // The `101 Switching Protocols` response is sent already, so a failed upgrade or a
+            // broken connection can't be reported to the client anymore.
+            if let Ok(upgraded) = upgrade.await {
+                let websocket =
+                    WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+                let _ = serve_graphql_ws(websocket, root_node, init).await;
+            }
+        });
+
+        let mut resp = Response::new(Body::empty());
+        *resp.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+        let headers = resp.headers_mut();
+        headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+        headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+        headers.insert(
+            header::SEC_WEBSOCKET_ACCEPT,
+            HeaderValue::from_str(&accept_key).expect("accept key is a valid header value"),
+        );
+        if offers_protocol {
+            headers.insert(
+                header::SEC_WEBSOCKET_PROTOCOL,
+                HeaderValue::from_static(PROTOCOL),
+            );
         }
+        resp
+    }
+
+    /// Checks the given request to be a WebSocket handshake, returning the
+    /// `Sec-WebSocket-Accept` value to respond it with.
+    fn handshake_accept_key(req: &Request<Body>) -> Option<String> {
+        let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+        let has_token = |name, token: &str| {
+            header(name).is_some_and(|v| v.split(',').any(|t| t.trim().eq_ignore_ascii_case(token)))
+        };
+
+        if req.method() != Method::GET
+            || !has_token(header::CONNECTION, "upgrade")
+            || !has_token(header::UPGRADE, "websocket")
+            || header(header::SEC_WEBSOCKET_VERSION) != Some("13")
+        {
+            return None;
+        }
+        header(header::SEC_WEBSOCKET_KEY).map(|key| derive_accept_key(key.as_bytes()))
+    }
+
+    /// Serves the graphql-ws protocol over a WebSocket connection.
+    ///
+    /// See [`subscriptions_handler`] for the meaning of the `init` argument.
+    pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+        websocket: WebSocketStream<Upgraded>,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        init: I,
+    ) -> Result<(), Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        let (ws_tx, ws_rx) = websocket.split();
+
+        // Tungstenite answers pings while reading, but leaves it to us to stop reading once the
+        // client closes the connection.
+        let incoming = ws_rx
+            .try_take_while(|msg| future::ready(Ok(!msg.is_close())))
+            .try_filter(|msg| future::ready(msg.is_text() || msg.is_binary()))
+            .map_ok(tungstenite::Message::into_data);
+        let outgoing = ws_tx.with(|text| future::ok(tungstenite::Message::Text(text)));
+
+        juniper_graphql_ws::serve(ArcSchema(root_node), init, incoming, outgoing).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use hyper::{
+        header,
         server::Server,
         service::{make_service_fn, service_fn},
-        Body, Method, Response, StatusCode,
+        Body, Method, Request, Response, StatusCode,
     };
     use juniper::{
-        http::tests as http_tests,
+        http::{negotiation::CsrfPrevention, tests as http_tests},
         tests::fixtures::starwars::schema::{Database, Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
@@ -357,6 +615,26 @@ mod tests {
                 .expect(&format!("failed POST {}", url));
             make_test_response(res)
         }
+
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> http_tests::TestResponse {
+            let url = format!("http://127.0.0.1:{}/graphql{}", self.port, url);
+            let client = reqwest::blocking::Client::new();
+            let mut req = client.request(method.parse().unwrap(), &url);
+            for (name, value) in headers {
+                req = req.header(*name, *value);
+            }
+            let res = req
+                .body(body.to_string())
+                .send()
+                .expect(&format!("failed {} {}", method, url));
+            make_test_response(res)
+        }
     }
 
     fn make_test_response(response: ReqwestResponse) -> http_tests::TestResponse {
@@ -367,12 +645,17 @@ mod tests {
         } else {
             String::default()
         };
+        let allow = response
+            .headers()
+            .get(reqwest::header::ALLOW)
+            .map(|v| v.to_str().unwrap().to_owned());
         let body = response.text().unwrap();
 
         http_tests::TestResponse {
             status_code,
             body: Some(body),
             content_type,
+            allow,
         }
     }
 
@@ -443,6 +726,47 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_csrf_prevention() {
+        let db = Arc::new(Database::new());
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let csrf = CsrfPrevention::new();
+        let request = |headers: &[(&str, &str)]| {
+            let mut req = Request::builder()
+                .method(Method::GET)
+                .uri("/graphql?query=%7Bhero%7Bname%7D%7D");
+            for (name, value) in headers {
+                req = req.header(*name, *value);
+            }
+            req.body(Body::empty()).unwrap()
+        };
+
+        let resp = super::with_csrf_prevention(&csrf, request(&[]), |req| {
+            super::graphql(root_node.clone(), db.clone(), req)
+        })
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp = super::with_csrf_prevention(&csrf, request(&[]), |req| {
+            super::graphql_sync(root_node.clone(), db.clone(), req)
+        })
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = super::graphql(root_node.clone(), db.clone(), request(&[])).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = super::with_csrf_prevention(
+            &csrf,
+            request(&[("X-Apollo-Operation-Name", "A")]),
+            |req| super::graphql(root_node.clone(), db.clone(), req),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_hyper_integration() {
         run_hyper_integration(false).await
@@ -452,4 +776,340 @@ mod tests {
     async fn test_sync_hyper_integration() {
         run_hyper_integration(true).await
     }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_compression() {
+        use std::io::Read as _;
+
+        let db = Arc::new(Database::new());
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+
+        for (accept_encoding, content_encoding) in [
+            ("gzip", Some("gzip")),
+            ("deflate, gzip;q=0.5", Some("gzip")),
+            ("deflate", None),
+        ] {
+            let req = Request::builder()
+                .uri("/graphql?query=%7Bhero%7Bname%7D%7D")
+                .header(header::ACCEPT_ENCODING, accept_encoding)
+                .body(Body::empty())
+                .unwrap();
+            let resp = super::graphql(root_node.clone(), db.clone(), req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                resp.headers()
+                    .get(header::CONTENT_ENCODING)
+                    .map(|v| v.to_str().unwrap()),
+                content_encoding,
+            );
+
+            let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+            let mut json = String::new();
+            match content_encoding {
+                Some(_) => {
+                    flate2::read::GzDecoder::new(&body[..])
+                        .read_to_string(&mut json)
+                        .unwrap();
+                }
+                None => json = String::from_utf8(body.to_vec()).unwrap(),
+            }
+            assert_eq!(json, r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_control() {
+        struct Query;
+
+        #[juniper::graphql_object]
+        impl Query {
+            #[graphql(cache_control(max_age = 60, scope = "PRIVATE"))]
+            fn me() -> &'static str {
+                "John"
+            }
+
+            fn version() -> &'static str {
+                "1.0"
+            }
+        }
+
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        ));
+
+        for (query, cache_control) in [
+            ("%7Bme%7D", Some("max-age=60, private")),
+            ("%7Bme%20version%7D", None),
+        ] {
+            let req = Request::builder()
+                .uri(format!("/graphql?query={}", query))
+                .body(Body::empty())
+                .unwrap();
+            let resp = super::graphql(root_node.clone(), Arc::new(()), req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                resp.headers()
+                    .get(header::CACHE_CONTROL)
+                    .map(|v| v.to_str().unwrap()),
+                cache_control,
+            );
+        }
+    }
+
+    mod uploads {
+        use std::sync::Arc;
+
+        use hyper::{header, Body, Method, Request, StatusCode};
+        use juniper::{
+            graphql_object,
+            http::{
+                multipart::{MultipartLimits, Upload},
+                tests as http_tests,
+            },
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            async fn upload(file: Upload) -> String {
+                let contents = file.to_bytes();
+                format!(
+                    "{}: {}",
+                    file.filename().unwrap_or_default(),
+                    String::from_utf8(contents).unwrap(),
+                )
+            }
+        }
+
+        fn multipart_request(parts: &[(&str, Option<&str>, &str)]) -> Request<Body> {
+            let body = http_tests::multipart_body(parts);
+            Request::builder()
+                .method(Method::POST)
+                .uri("/graphql")
+                .header(header::CONTENT_TYPE, http_tests::MULTIPART_CONTENT_TYPE)
+                .body(Body::from(body))
+                .unwrap()
+        }
+
+        async fn execute(req: Request<Body>, limits: MultipartLimits) -> (StatusCode, String) {
+            let root_node = Arc::new(RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            ));
+
+            let resp =
+                super::super::graphql_with_uploads(root_node, Arc::new(()), req, limits).await;
+            let status = resp.status();
+            let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        }
+
+        const OPERATIONS: &str =
+            r#"{"query": "query($f: Upload!) { upload(file: $f) }", "variables": {"f": null}}"#;
+
+        #[tokio::test]
+        async fn accepts_uploads() {
+            let req = multipart_request(&[
+                ("operations", None, OPERATIONS),
+                ("map", None, r#"{"0": ["variables.f"]}"#),
+                ("0", Some("a.txt"), "hello"),
+            ]);
+
+            let (status, body) = execute(req, MultipartLimits::default()).await;
+
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+                serde_json::json!({"data": {"upload": "a.txt: hello"}}),
+            );
+        }
+
+        #[tokio::test]
+        async fn enforces_limits() {
+            let req = multipart_request(&[
+                ("operations", None, OPERATIONS),
+                ("map", None, r#"{"0": ["variables.f"]}"#),
+                ("0", Some("a.txt"), "hello"),
+            ]);
+
+            let (status, _) = execute(req, MultipartLimits::new().max_file_size(4)).await;
+
+            assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        }
+
+        #[tokio::test]
+        async fn rejects_malformed_requests() {
+            let req = multipart_request(&[("map", None, "{}")]);
+
+            let (status, body) = execute(req, MultipartLimits::default()).await;
+
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body, "`operations` must be the first part");
+        }
+
+        #[tokio::test]
+        async fn handles_regular_requests() {
+            let req = Request::builder()
+                .method(Method::POST)
+                .uri("/graphql")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"query": "{ __typename }"}"#))
+                .unwrap();
+
+            let (status, body) = execute(req, MultipartLimits::default()).await;
+
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+                serde_json::json!({"data": {"__typename": "Query"}}),
+            );
+        }
+    }
+}
+
+#[cfg(all(test, feature = "subscriptions"))]
+mod subscription_tests {
+    use std::{convert::Infallible, net::TcpListener, sync::Arc, time::Duration};
+
+    use hyper::{
+        server::Server,
+        service::{make_service_fn, service_fn},
+        Body, Request, StatusCode,
+    };
+    use juniper::{
+        futures::{SinkExt as _, StreamExt as _},
+        http::tests::{run_ws_test_suite, WsIntegration, WsIntegrationMessage},
+        tests::fixtures::starwars::schema::{Database, Query, Subscription},
+        EmptyMutation, LocalBoxFuture, RootNode,
+    };
+    use juniper_graphql_ws::ConnectionConfig;
+    use tokio::time::timeout;
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{client::IntoClientRequest as _, Message},
+    };
+
+    use super::subscriptions::subscriptions_handler;
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+    fn schema() -> Arc<Schema> {
+        Arc::new(Schema::new(Query, EmptyMutation::new(), Subscription))
+    }
+
+    struct TestHyperWsIntegration;
+
+    impl TestHyperWsIntegration {
+        async fn run_async(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            let listener = TcpListener::bind("127.0.0.1:0")?;
+            let addr = listener.local_addr()?;
+            let root_node = schema();
+            let new_service = make_service_fn(move |_| {
+                let root_node = root_node.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |req| {
+                        let config = ConnectionConfig::new(Database::new());
+                        let resp = subscriptions_handler(req, root_node.clone(), config);
+                        async move { Ok::<_, Infallible>(resp) }
+                    }))
+                }
+            });
+            tokio::spawn(Server::from_tcp(listener)?.serve(new_service));
+
+            let mut request = format!("ws://{}/subscriptions", addr).into_client_request()?;
+            request
+                .headers_mut()
+                .insert("Sec-WebSocket-Protocol", "graphql-ws".parse()?);
+            let (mut socket, resp) = connect_async(request).await?;
+            assert_eq!(resp.headers()["Sec-WebSocket-Protocol"], "graphql-ws");
+
+            for message in messages {
+                match message {
+                    WsIntegrationMessage::Send(body) => socket.send(Message::Text(body)).await?,
+                    WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message =
+                            timeout(Duration::from_millis(message_timeout), socket.next())
+                                .await
+                                .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                                .ok_or_else(|| anyhow::anyhow!("Empty message received"))??;
+
+                        let text = match message {
+                            Message::Text(text) => text,
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        };
+                        let expected_value = serde_json::from_str::<serde_json::Value>(&body)?;
+                        let value = serde_json::from_str::<serde_json::Value>(&text)?;
+                        if value != expected_value {
+                            return Err(anyhow::anyhow!(
+                                "Expected message: {}. Received message: {}",
+                                expected_value,
+                                value,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl WsIntegration for TestHyperWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hyper_ws_integration() {
+        run_ws_test_suite(&TestHyperWsIntegration).await;
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_handshakes() {
+        let request = |headers: &[(&str, &str)]| {
+            let mut req = Request::get("/subscriptions");
+            for (name, value) in headers {
+                req = req.header(*name, *value);
+            }
+            req.body(Body::empty()).unwrap()
+        };
+        let handshake = [
+            ("Connection", "Upgrade"),
+            ("Upgrade", "websocket"),
+            ("Sec-WebSocket-Version", "13"),
+            ("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="),
+        ];
+        let config = || ConnectionConfig::new(Database::new());
+
+        let resp = subscriptions_handler(request(&[]), schema(), config());
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = subscriptions_handler(request(&handshake[1..]), schema(), config());
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = subscriptions_handler(request(&handshake), schema(), config());
+        assert_eq!(resp.status(), StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(
+            resp.headers()["Sec-WebSocket-Accept"],
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=",
+        );
+        assert!(resp.headers().get("Sec-WebSocket-Protocol").is_none());
+    }
 }
@@ -106,22 +106,23 @@ supported.
 
 #![doc(html_root_url = "https://docs.rs/juniper_iron/0.3.0")]
 
-use std::{error::Error, fmt, io::Read, ops::Deref as _};
+use std::{error::Error, fmt, io::Read};
 
 use iron::{
-    headers::ContentType,
+    headers::{Accept, ContentType},
     itry, method,
     middleware::Handler,
-    mime::{Mime, TopLevel},
+    mime::Mime,
     prelude::*,
     status,
 };
 use juniper::{
-    http, http::GraphQLBatchRequest, DefaultScalarValue, GraphQLType, InputValue, RootNode,
-    ScalarValue,
+    http::{
+        negotiation::{self, RequestError, ResponseFormat},
+        GraphQLBatchRequest, Method,
+    },
+    DefaultScalarValue, GraphQLType, RootNode, ScalarValue,
 };
-use serde_json::error::Error as SerdeError;
-use urlencoded::{UrlDecodingError, UrlEncodedQuery};
 
 /// Handler that executes `GraphQL` queries in the given schema
 ///
@@ -165,37 +166,6 @@ pub struct PlaygroundHandler {
     subscription_url: Option<String>,
 }
 
-fn get_single_value<T>(mut values: Vec<T>) -> IronResult<T> {
-    if values.len() == 1 {
-        Ok(values.remove(0))
-    } else {
-        Err(GraphQLIronError::InvalidData("Duplicate URL query parameter").into())
-    }
-}
-
-fn parse_url_param(params: Option<Vec<String>>) -> IronResult<Option<String>> {
-    if let Some(values) = params {
-        get_single_value(values).map(Some)
-    } else {
-        Ok(None)
-    }
-}
-
-fn parse_variable_param<S>(params: Option<Vec<String>>) -> IronResult<Option<InputValue<S>>>
-where
-    S: ScalarValue,
-{
-    if let Some(values) = params {
-        Ok(
-            serde_json::from_str::<InputValue<S>>(get_single_value(values)?.as_ref())
-                .map(Some)
-                .map_err(GraphQLIronError::Serde)?,
-        )
-    } else {
-        Ok(None)
-    }
-}
-
 impl<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
     GraphQLHandler<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
 where
@@ -224,56 +194,42 @@ where
         }
     }
 
-    fn handle_get(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let url_query = req
-            .get_mut::<UrlEncodedQuery>()
-            .map_err(GraphQLIronError::Url)?;
-
-        let query = parse_url_param(url_query.remove("query"))?
-            .ok_or(GraphQLIronError::InvalidData("No query provided"))?;
-        let operation_name = parse_url_param(url_query.remove("operationName"))?;
-        let variables = parse_variable_param(url_query.remove("variables"))?;
-
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            query,
-            operation_name,
-            variables,
-        )))
-    }
-
-    fn handle_post_json(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
+    fn parse_request(&self, req: &mut Request) -> IronResult<(GraphQLBatchRequest<S>, Method)> {
+        let method =
+            negotiation::parse_method(req.method.as_ref()).map_err(GraphQLIronError::Request)?;
+        let content_type = req.headers.get::<ContentType>().map(ToString::to_string);
+        let mut body = Vec::new();
+        if req.method == method::Post {
+            itry!(req.body.read_to_end(&mut body));
+        }
 
-        Ok(
-            serde_json::from_str::<GraphQLBatchRequest<S>>(payload.as_str())
-                .map_err(GraphQLIronError::Serde)?,
+        let request = negotiation::parse_request(
+            req.method.as_ref(),
+            req.url.query(),
+            content_type.as_deref(),
+            &body,
         )
-    }
-
-    fn handle_post_graphql(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
-
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            payload, None, None,
-        )))
+        .map_err(GraphQLIronError::Request)?;
+        Ok((request, method))
     }
 
     fn execute_sync(
         &self,
         context: &CtxT,
         request: GraphQLBatchRequest<S>,
+        method: Method,
+        format: ResponseFormat,
     ) -> IronResult<Response> {
-        let response = request.execute_sync(&self.root_node, context);
-        let content_type = "application/json".parse::<Mime>().unwrap();
+        let response = request.execute_sync_via(method, &self.root_node, context);
+        let content_type = format.content_type().parse::<Mime>().unwrap();
         let json = serde_json::to_string_pretty(&response).unwrap();
-        let status = if response.is_ok() {
-            status::Ok
-        } else {
-            status::BadRequest
-        };
-        Ok(Response::with((content_type, status, json)))
+        let status = status::Status::from_u16(negotiation::status_code(&response, format));
+        let mut resp = Response::with((content_type, status, json));
+        if let Some(methods) = negotiation::allowed_methods(&response) {
+            resp.headers
+                .set_raw("allow", vec![methods.as_bytes().to_vec()]);
+        }
+        Ok(resp)
     }
 }
 
@@ -314,23 +270,15 @@ where
     Subscription: GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
     'a: 'static,
 {
-    fn handle(&self, mut req: &mut Request) -> IronResult<Response> {
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
         let context = (self.context_factory)(req)?;
 
-        let graphql_request = match req.method {
-            method::Get => self.handle_get(&mut req)?,
-            method::Post => match req.headers.get::<ContentType>().map(ContentType::deref) {
-                Some(Mime(TopLevel::Application, sub_lvl, _)) => match sub_lvl.as_str() {
-                    "json" => self.handle_post_json(&mut req)?,
-                    "graphql" => self.handle_post_graphql(&mut req)?,
-                    _ => return Ok(Response::with(status::BadRequest)),
-                },
-                _ => return Ok(Response::with(status::BadRequest)),
-            },
-            _ => return Ok(Response::with(status::MethodNotAllowed)),
-        };
+        let accept = req.headers.get::<Accept>().map(ToString::to_string);
+        let format =
+            ResponseFormat::negotiate(accept.as_deref()).map_err(GraphQLIronError::Request)?;
+        let (graphql_request, method) = self.parse_request(req)?;
 
-        self.execute_sync(&context, graphql_request)
+        self.execute_sync(&context, graphql_request, method, format)
     }
 }
 
@@ -366,17 +314,13 @@ impl Handler for PlaygroundHandler {
 
 #[derive(Debug)]
 enum GraphQLIronError {
-    Serde(SerdeError),
-    Url(UrlDecodingError),
-    InvalidData(&'static str),
+    Request(RequestError),
 }
 
 impl fmt::Display for GraphQLIronError {
     fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            GraphQLIronError::Serde(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::Url(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::InvalidData(err) => fmt::Display::fmt(err, &mut f),
+            GraphQLIronError::Request(ref err) => fmt::Display::fmt(err, &mut f),
         }
     }
 }
@@ -384,9 +328,7 @@ impl fmt::Display for GraphQLIronError {
 impl Error for GraphQLIronError {
     fn cause(&self) -> Option<&dyn Error> {
         match *self {
-            GraphQLIronError::Serde(ref err) => Some(err),
-            GraphQLIronError::Url(ref err) => Some(err),
-            GraphQLIronError::InvalidData(_) => None,
+            GraphQLIronError::Request(ref err) => Some(err),
         }
     }
 }
@@ -394,7 +336,20 @@ impl Error for GraphQLIronError {
 impl From<GraphQLIronError> for IronError {
     fn from(err: GraphQLIronError) -> IronError {
         let message = format!("{}", err);
-        IronError::new(err, (status::BadRequest, message))
+        let (status, allowed_methods) = match err {
+            GraphQLIronError::Request(ref err) => (
+                status::Status::from_u16(err.status_code()),
+                err.allowed_methods(),
+            ),
+        };
+        let mut error = IronError::new(err, (status, message));
+        if let Some(methods) = allowed_methods {
+            error
+                .response
+                .headers
+                .set_raw("allow", vec![methods.as_bytes().to_vec()]);
+        }
+        error
     }
 }
 
@@ -465,6 +420,28 @@ mod tests {
                 .map(make_test_response)
                 .unwrap_or_else(make_test_error_response)
         }
+
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> http_tests::TestResponse {
+            let mut raw_headers = Headers::new();
+            for (name, value) in headers {
+                raw_headers.set_raw(name.to_string(), vec![value.as_bytes().to_vec()]);
+            }
+            request::request(
+                method.parse().unwrap(),
+                &fixup_url(url),
+                body,
+                raw_headers,
+                &make_handler(),
+            )
+            .map(make_test_response)
+            .unwrap_or_else(make_test_error_response)
+        }
     }
 
     #[test]
@@ -478,13 +455,16 @@ mod tests {
         Ok(Database::new())
     }
 
-    fn make_test_error_response(_: IronError) -> http_tests::TestResponse {
-        // For now all errors return the same status code.
-        // `juniper_iron` users can choose to do something different if desired.
+    fn make_test_error_response(err: IronError) -> http_tests::TestResponse {
         http_tests::TestResponse {
-            status_code: 400,
+            status_code: err
+                .response
+                .status
+                .expect("No status code returned from handler")
+                .to_u16() as i32,
             body: None,
             content_type: "application/json".to_string(),
+            allow: None,
         }
     }
 
@@ -501,12 +481,17 @@ mod tests {
                 .clone(),
         )
         .expect("Content-type header invalid UTF-8");
+        let allow = response
+            .headers
+            .get_raw("allow")
+            .map(|v| String::from_utf8(v[0].clone()).expect("Allow header invalid UTF-8"));
         let body = response::extract_body_to_string(response);
 
         http_tests::TestResponse {
             status_code,
             body: Some(body),
             content_type,
+            allow,
         }
     }
 
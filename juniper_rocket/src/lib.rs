@@ -44,15 +44,19 @@ use rocket::{
     data::{self, FromData, ToByteUnit},
     form::{error::ErrorKind, DataField, Error, Errors, FromForm, Options, ValueField},
     http::{ContentType, Status},
-    outcome::Outcome::{Failure, Forward, Success},
+    outcome::Outcome::{Failure, Success},
     response::{self, content, Responder, Response},
     Data, Request,
 };
 
 use juniper::{
-    http::{self, GraphQLBatchRequest},
+    http::{
+        self,
+        negotiation::{self, GetRequestParser, RequestError, ResponseFormat},
+        GraphQLBatchRequest, GraphQLBatchResponse, Method,
+    },
     DefaultScalarValue, FieldError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync,
-    InputValue, RootNode, ScalarValue,
+    RootNode, ScalarValue,
 };
 
 /// Simple wrapper around an incoming GraphQL request
@@ -61,7 +65,7 @@ use juniper::{
 /// automatically from both GET and POST routes by implementing the `FromForm`
 /// and `FromData` traits.
 #[derive(Debug, PartialEq)]
-pub struct GraphQLRequest<S = DefaultScalarValue>(GraphQLBatchRequest<S>)
+pub struct GraphQLRequest<S = DefaultScalarValue>(GraphQLBatchRequest<S>, Method)
 where
     S: ScalarValue;
 
@@ -117,12 +121,8 @@ where
         MutationT: GraphQLType<S, Context = CtxT>,
         SubscriptionT: GraphQLType<S, Context = CtxT>,
     {
-        let response = self.0.execute_sync(root_node, context);
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
+        let response = self.0.execute_sync_via(self.1, root_node, context);
+        let status = response_status(&response);
         let json = serde_json::to_string(&response).unwrap();
 
         GraphQLResponse(status, json)
@@ -144,12 +144,8 @@ where
         CtxT: Sync,
         S: Send + Sync,
     {
-        let response = self.0.execute(root_node, context).await;
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
+        let response = self.0.execute_via(self.1, root_node, context).await;
+        let status = response_status(&response);
         let json = serde_json::to_string(&response).unwrap();
 
         GraphQLResponse(status, json)
@@ -213,51 +209,20 @@ impl GraphQLResponse {
 
 pub struct GraphQLContext<'f, S: ScalarValue> {
     opts: Options,
-    query: Option<String>,
-    operation_name: Option<String>,
-    variables: Option<InputValue<S>>,
+    parser: GetRequestParser<S>,
     errors: Errors<'f>,
 }
 
-impl<'f, S: ScalarValue> GraphQLContext<'f, S> {
-    fn query(&mut self, value: String) {
-        if self.query.is_some() {
-            let error = Error::from(ErrorKind::Duplicate).with_name("query");
-
-            self.errors.push(error)
-        } else {
-            self.query = Some(value);
-        }
-    }
-
-    fn operation_name(&mut self, value: String) {
-        if self.operation_name.is_some() {
-            let error = Error::from(ErrorKind::Duplicate).with_name("operation_name");
-
-            self.errors.push(error)
-        } else {
-            self.operation_name = Some(value);
-        }
-    }
-
-    fn variables(&mut self, value: String) {
-        if self.variables.is_some() {
-            let error = Error::from(ErrorKind::Duplicate).with_name("variables");
-
-            self.errors.push(error)
-        } else {
-            let parse_result = serde_json::from_str::<InputValue<S>>(&value);
-
-            match parse_result {
-                Ok(variables) => self.variables = Some(variables),
-                Err(e) => {
-                    let error = Error::from(ErrorKind::Validation(Cow::Owned(e.to_string())))
-                        .with_name("variables");
-
-                    self.errors.push(error);
-                }
-            }
+/// Converts the [`RequestError`] of parsing a GET request into a form
+/// [`Error`].
+fn form_error<'f>(err: RequestError) -> Error<'f> {
+    match err {
+        RequestError::MissingQuery => Error::from(ErrorKind::Missing).with_name("query"),
+        RequestError::DuplicateParameter(name) => Error::from(ErrorKind::Duplicate).with_name(name),
+        RequestError::InvalidVariables(e) => {
+            Error::from(ErrorKind::Validation(Cow::Owned(e.to_string()))).with_name("variables")
         }
+        e => Error::from(ErrorKind::Validation(Cow::Owned(e.to_string()))),
     }
 }
 
@@ -271,25 +236,24 @@ where
     fn init(opts: Options) -> Self::Context {
         GraphQLContext {
             opts,
-            query: None,
-            operation_name: None,
-            variables: None,
+            parser: GetRequestParser::new(),
             errors: Errors::new(),
         }
     }
 
     fn push_value(ctx: &mut Self::Context, field: ValueField<'f>) {
         match field.name.key().map(|key| key.as_str()) {
-            Some("query") => ctx.query(field.value.to_owned()),
-            Some("operation_name") => ctx.operation_name(field.value.to_owned()),
-            Some("variables") => ctx.variables(field.value.to_owned()),
-            Some(key) => {
-                if ctx.opts.strict {
-                    let error = Error::from(ErrorKind::Unknown).with_name(key);
-
-                    ctx.errors.push(error)
+            Some(key) => match ctx.parser.push(key, field.value.to_owned()) {
+                Ok(true) => {}
+                Ok(false) => {
+                    if ctx.opts.strict {
+                        let error = Error::from(ErrorKind::Unknown).with_name(key);
+
+                        ctx.errors.push(error)
+                    }
                 }
-            }
+                Err(e) => ctx.errors.push(form_error(e)),
+            },
             None => {
                 if ctx.opts.strict {
                     let error = Error::from(ErrorKind::Unexpected);
@@ -309,17 +273,16 @@ where
     }
 
     fn finalize(mut ctx: Self::Context) -> rocket::form::Result<'f, Self> {
-        if ctx.query.is_none() {
-            let error = Error::from(ErrorKind::Missing).with_name("query");
-
-            ctx.errors.push(error)
-        }
-
-        match ctx.errors.is_empty() {
-            true => Ok(GraphQLRequest(GraphQLBatchRequest::Single(
-                http::GraphQLRequest::new(ctx.query.unwrap(), ctx.operation_name, ctx.variables),
-            ))),
-            false => Err(ctx.errors),
+        match ctx.parser.finish() {
+            Ok(req) if ctx.errors.is_empty() => Ok(GraphQLRequest(
+                GraphQLBatchRequest::Single(req),
+                Method::Get,
+            )),
+            Ok(_) => Err(ctx.errors),
+            Err(e) => {
+                ctx.errors.push(form_error(e));
+                Err(ctx.errors)
+            }
         }
     }
 }
@@ -337,46 +300,252 @@ where
         req: &'r Request<'_>,
         data: Data<'r>,
     ) -> data::Outcome<'r, Self, Self::Error> {
-        use rocket::tokio::io::AsyncReadExt as _;
-
-        let content_type = req
-            .content_type()
-            .map(|ct| (ct.top().as_str(), ct.sub().as_str()));
-        let is_json = match content_type {
-            Some(("application", "json")) => true,
-            Some(("application", "graphql")) => false,
-            _ => return Box::pin(async move { Forward(data) }).await,
-        };
+        let content_type = req.content_type().map(ToString::to_string);
+        if !content_type
+            .as_deref()
+            .is_some_and(negotiation::is_supported_content_type)
+        {
+            let err = RequestError::UnsupportedMediaType;
+            return Failure((Status::UnsupportedMediaType, err.to_string()));
+        }
+        if let Err(e) = negotiate_format(req) {
+            return Failure((Status::NotAcceptable, e.to_string()));
+        }
 
         Box::pin(async move {
-            let mut body = String::new();
-            let mut reader = data.open(BODY_LIMIT.bytes());
-            if let Err(e) = reader.read_to_string(&mut body).await {
-                return Failure((Status::InternalServerError, format!("{:?}", e)));
-            }
+            let body = match data.open(BODY_LIMIT.bytes()).into_bytes().await {
+                Ok(body) => body.into_inner(),
+                Err(e) => return Failure((Status::InternalServerError, format!("{:?}", e))),
+            };
 
-            Success(GraphQLRequest(if is_json {
-                match serde_json::from_str(&body) {
-                    Ok(req) => req,
-                    Err(e) => return Failure((Status::BadRequest, format!("{}", e))),
-                }
-            } else {
-                GraphQLBatchRequest::Single(http::GraphQLRequest::new(body, None, None))
-            }))
+            match negotiation::parse_post_request(content_type.as_deref(), &body) {
+                Ok(req) => Success(GraphQLRequest(req, Method::Post)),
+                Err(e) => Failure((Status::BadRequest, e.to_string())),
+            }
         })
         .await
     }
 }
 
+/// Returns the [`Status`] to respond with the given [`GraphQLBatchResponse`].
+///
+/// The [`ResponseFormat`] is only negotiated once responding, so the status is
+/// chosen for the default [`ResponseFormat::Json`] here, and adjusted for the
+/// [`ResponseFormat::ExplicitJson`] by the [`GraphQLResponse`] itself.
+fn response_status<S: ScalarValue>(response: &GraphQLBatchResponse<'_, S>) -> Status {
+    Status::from_code(negotiation::status_code(response, ResponseFormat::Json)).unwrap()
+}
+
+/// Indicates whether the given JSON `body` of a response misses the `data`
+/// entry, so the request failed before any operation has been executed.
+fn lacks_data(body: &str) -> bool {
+    let has_data = |resp: &serde_json::Value| resp.get("data").is_some();
+    match serde_json::from_str(body) {
+        Ok(serde_json::Value::Object(resp)) => !resp.contains_key("data"),
+        Ok(serde_json::Value::Array(resps)) => !resps.iter().all(has_data),
+        _ => false,
+    }
+}
+
+/// Negotiates the [`ResponseFormat`] from the `Accept` header of the given
+/// [`Request`].
+fn negotiate_format(req: &Request<'_>) -> Result<ResponseFormat, RequestError> {
+    ResponseFormat::negotiate(req.headers().get_one("Accept"))
+}
+
 impl<'r, 'o: 'r> Responder<'r, 'o> for GraphQLResponse {
-    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
-        let GraphQLResponse(status, body) = self;
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let GraphQLResponse(mut status, body) = self;
+        let content_type = match negotiate_format(req).map_err(|_| Status::NotAcceptable)? {
+            ResponseFormat::GraphQLResponseJson => {
+                ContentType::new("application", "graphql-response+json")
+            }
+            ResponseFormat::Json => ContentType::JSON,
+            ResponseFormat::ExplicitJson => {
+                // Clients explicitly accepting this format opt in to `200 OK`
+                // for requests failed to be parsed or validated.
+                if status == Status::BadRequest && lacks_data(&body) {
+                    status = Status::Ok;
+                }
+                ContentType::JSON
+            }
+        };
 
-        Response::build()
-            .header(ContentType::new("application", "json"))
-            .status(status)
-            .sized_body(body.len(), Cursor::new(body))
-            .ok()
+        let mut response = Response::build();
+        response.header(content_type).status(status);
+        // Only operations refused via GET are responded with this status, and
+        // those may be sent via POST instead.
+        if status == Status::MethodNotAllowed {
+            response.raw_header("Allow", "POST");
+        }
+        response.sized_body(body.len(), Cursor::new(body)).ok()
+    }
+}
+
+/// Serving GraphQL subscriptions as [Server-Sent Events][1].
+///
+/// Rocket doesn't support upgrading requests to WebSocket connections, so
+/// each subscription is served by its own request with an [`EventStream`]
+/// response, in the "distinct connections mode" of the [GraphQL over SSE][2]
+/// protocol: every result is sent as a `next` event, and a `complete` event
+/// is sent once the operation ends.
+///
+/// The operation is executed by a [`juniper_graphql_ws::Connection`], so the
+/// connection configuration (such as its keep-alive interval) applies to it
+/// the same way as to subscriptions served over WebSocket connections by the
+/// other integrations.
+///
+/// [1]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+/// [2]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions {
+    use std::sync::Arc;
+
+    use juniper::{
+        futures::{SinkExt as _, Stream, StreamExt as _},
+        http::{GraphQLBatchRequest, Method},
+        serde::Serialize,
+        FieldError, GraphQLSubscriptionType, GraphQLTypeAsync, OperationType, RootNode,
+        ScalarValue, Value, Variables,
+    };
+    use juniper_graphql_ws::{
+        ArcSchema, ClientMessage, Connection, Init, ServerMessage, StartPayload,
+    };
+    use rocket::{
+        http::Status,
+        response::stream::{Event, EventStream},
+    };
+
+    use super::{GraphQLRequest, GraphQLResponse};
+
+    /// ID of the only operation executed by each connection.
+    const OPERATION_ID: &str = "1";
+
+    /// Executes the given request, streaming its results as Server-Sent
+    /// Events.
+    ///
+    /// Both GET and POST requests are accepted, as browsers' `EventSource`
+    /// only sends GET requests, but mutations received via GET requests are
+    /// refused with `405 Method Not Allowed`. Batch requests are refused with
+    /// `400 Bad Request`.
+    ///
+    /// The `init` argument is used to provide the context and additional configuration for
+    /// the connection. This is typically a `juniper_graphql_ws::ConnectionConfig` holding a
+    /// context made from the request guards of the route:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use juniper::{
+    ///     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+    ///     EmptyMutation, RootNode,
+    /// };
+    /// use juniper_graphql_ws::ConnectionConfig;
+    /// use juniper_rocket::{
+    ///     subscriptions::subscriptions_handler, GraphQLRequest, GraphQLResponse,
+    /// };
+    /// use rocket::{
+    ///     futures::Stream,
+    ///     response::stream::{Event, EventStream},
+    ///     State,
+    /// };
+    ///
+    /// type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+    ///
+    /// #[rocket::get("/subscriptions?<request..>")]
+    /// fn subscriptions(
+    ///     request: GraphQLRequest,
+    ///     schema: &State<Arc<Schema>>,
+    /// ) -> Result<EventStream<impl Stream<Item = Event> + Send>, GraphQLResponse> {
+    ///     let context = Database::new();
+    ///     subscriptions_handler(request, schema.inner().clone(), ConnectionConfig::new(context))
+    /// }
+    /// ```
+    pub fn subscriptions_handler<Query, Mutation, Subscription, CtxT, S, I>(
+        request: GraphQLRequest<S>,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        init: I,
+    ) -> Result<EventStream<impl Stream<Item = Event> + Send>, GraphQLResponse>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        let GraphQLRequest(request, method) = request;
+        let request = match request {
+            GraphQLBatchRequest::Single(request) => request,
+            GraphQLBatchRequest::Batch(_) => {
+                return Err(error_response(
+                    Status::BadRequest,
+                    "Batch requests are not supported by subscriptions",
+                ))
+            }
+        };
+        if method == Method::Get
+            && request.operation_type(&*root_node) == Some(OperationType::Mutation)
+        {
+            return Err(error_response(
+                Status::MethodNotAllowed,
+                "Mutations are not allowed over HTTP GET",
+            ));
+        }
+
+        let start = ClientMessage::Start {
+            id: OPERATION_ID.into(),
+            payload: StartPayload {
+                variables: request.variables(),
+                query: request.query,
+                operation_name: request.operation_name,
+            },
+        };
+        let mut connection = Connection::new(ArcSchema(root_node), init);
+
+        let events = EventStream! {
+            // The sink of a `Connection` is infallible.
+            let init = ClientMessage::ConnectionInit { payload: Variables::new() };
+            connection.send(init).await.unwrap_or_else(|e| match e {});
+            connection.send(start).await.unwrap_or_else(|e| match e {});
+
+            while let Some(msg) = connection.next().await {
+                match msg {
+                    ServerMessage::ConnectionAck => {}
+                    ServerMessage::ConnectionKeepAlive => yield Event::comment("ka"),
+                    ServerMessage::Data { payload, .. } => yield next_event(&payload),
+                    ServerMessage::Error { payload, .. } => {
+                        yield next_event(&serde_json::json!({ "errors": payload }));
+                        break;
+                    }
+                    ServerMessage::ConnectionError { payload } => {
+                        let error = serde_json::json!({ "message": payload.message });
+                        yield next_event(&serde_json::json!({ "errors": [error] }));
+                        break;
+                    }
+                    ServerMessage::Complete { .. } => break,
+                }
+            }
+            yield Event::data("").event("complete");
+        };
+        // Keep-alive comments are sent by the connection itself, while the heartbeat of the
+        // `EventStream` may be interleaved with the lines of an event.
+        Ok(events.heartbeat(None))
+    }
+
+    /// Makes a `next` event carrying the given execution result.
+    fn next_event<T: Serialize>(result: &T) -> Event {
+        Event::data(serde_json::to_string(result).unwrap()).event("next")
+    }
+
+    /// Makes a response refusing to execute a request for the given reason.
+    fn error_response(status: Status, message: &str) -> GraphQLResponse {
+        let GraphQLResponse(_, body) =
+            GraphQLResponse::error(FieldError::new(message, Value::null()));
+        GraphQLResponse(status, body)
     }
 }
 
@@ -424,7 +593,7 @@ mod fromform_tests {
     #[test]
     fn test_no_query() {
         check_error(
-            "operation_name=foo&variables={}",
+            "operationName=foo&variables={}",
             vec![Error::from(ErrorKind::Missing).with_name("query")],
             false,
         );
@@ -451,8 +620,8 @@ mod fromform_tests {
     #[test]
     fn test_duplicate_operation_name() {
         check_error(
-            "query=test&operation_name=op1&operation_name=op2",
-            vec![Error::from(ErrorKind::Duplicate).with_name("operation_name")],
+            "query=test&operationName=op1&operationName=op2",
+            vec![Error::from(ErrorKind::Duplicate).with_name("operationName")],
             false,
         );
     }
@@ -484,11 +653,14 @@ mod fromform_tests {
             Form::parse_encoded(RawStr::new(r#"query=test&variables={"foo":"bar"}"#));
         assert!(result.is_ok());
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"bar"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
+        let expected = GraphQLRequest(
+            GraphQLBatchRequest::Single(http::GraphQLRequest::new(
+                "test".to_string(),
+                None,
+                Some(variables),
+            )),
+            Method::Get,
+        );
         assert_eq!(result.unwrap(), expected);
     }
 
@@ -498,25 +670,31 @@ mod fromform_tests {
             r#"query=test&variables={"foo":"x%20y%26%3F+z"}"#,
         ));
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"x y&? z"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
+        let expected = GraphQLRequest(
+            GraphQLBatchRequest::Single(http::GraphQLRequest::new(
+                "test".to_string(),
+                None,
+                Some(variables),
+            )),
+            Method::Get,
+        );
         assert_eq!(result.unwrap(), expected);
     }
 
     #[test]
     fn test_url_decode() {
         let result: Result<GraphQLRequest, Errors> = Form::parse_encoded(RawStr::new(
-            "query=%25foo%20bar+baz%26%3F&operation_name=test",
+            "query=%25foo%20bar+baz%26%3F&operationName=test",
         ));
         assert!(result.is_ok());
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "%foo bar baz&?".to_string(),
-            Some("test".to_string()),
-            None,
-        )));
+        let expected = GraphQLRequest(
+            GraphQLBatchRequest::Single(http::GraphQLRequest::new(
+                "%foo bar baz&?".to_string(),
+                Some("test".to_string()),
+                None,
+            )),
+            Method::Get,
+        );
         assert_eq!(result.unwrap(), expected);
     }
 }
@@ -533,7 +711,7 @@ mod tests {
     };
     use rocket::{
         self, get,
-        http::ContentType,
+        http::{ContentType, Header},
         local::asynchronous::{Client, LocalResponse},
         post, routes, Build, Rocket, State,
     };
@@ -584,6 +762,21 @@ mod tests {
             let req = futures::executor::block_on(req.dispatch());
             futures::executor::block_on(make_test_response(req))
         }
+
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> http_tests::TestResponse {
+            let mut req = self.client.req(method.parse().unwrap(), url).body(body);
+            for (name, value) in headers {
+                req = req.header(Header::new(name.to_string(), value.to_string()));
+            }
+            let req = futures::executor::block_on(req.dispatch());
+            futures::executor::block_on(make_test_response(req))
+        }
     }
 
     #[rocket::async_test]
@@ -640,6 +833,7 @@ mod tests {
             .content_type()
             .expect("No content type header from handler")
             .to_string();
+        let allow = response.headers().get_one("Allow").map(ToOwned::to_owned);
         let body = response
             .into_string()
             .await
@@ -649,6 +843,122 @@ mod tests {
             status_code,
             body: Some(body),
             content_type,
+            allow,
         }
     }
 }
+
+#[cfg(all(test, feature = "subscriptions"))]
+mod subscription_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use juniper::{
+        futures::Stream,
+        tests::fixtures::starwars::schema::{Database, Query, Subscription},
+        EmptyMutation, RootNode,
+    };
+    use juniper_graphql_ws::ConnectionConfig;
+    use rocket::{
+        get,
+        http::{ContentType, Status},
+        local::asynchronous::Client,
+        post,
+        response::stream::{Event, EventStream},
+        routes, State,
+    };
+
+    use super::{subscriptions::subscriptions_handler, GraphQLRequest, GraphQLResponse};
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+    #[get("/?<request..>")]
+    fn get_subscriptions(
+        request: GraphQLRequest,
+        schema: &State<Arc<Schema>>,
+    ) -> Result<EventStream<impl Stream<Item = Event> + Send>, GraphQLResponse> {
+        subscriptions_handler(request, schema.inner().clone(), config())
+    }
+
+    #[post("/", data = "<request>")]
+    fn post_subscriptions(
+        request: GraphQLRequest,
+        schema: &State<Arc<Schema>>,
+    ) -> Result<EventStream<impl Stream<Item = Event> + Send>, GraphQLResponse> {
+        subscriptions_handler(request, schema.inner().clone(), config())
+    }
+
+    fn config() -> ConnectionConfig<Database> {
+        ConnectionConfig::new(Database::new()).with_keep_alive_interval(Duration::from_secs(0))
+    }
+
+    async fn client() -> Client {
+        let schema = Schema::new(Query, EmptyMutation::new(), Subscription);
+        let rocket = rocket::build()
+            .manage(Arc::new(schema))
+            .mount("/", routes![get_subscriptions, post_subscriptions]);
+        Client::untracked(rocket).await.expect("valid rocket")
+    }
+
+    #[rocket::async_test]
+    async fn streams_results_as_events() {
+        let client = client().await;
+
+        let resp = client
+            .get("/?query=subscription%7BasyncHuman%7Bname%7D%7D")
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::Ok);
+        assert_eq!(resp.content_type(), Some(ContentType::EventStream));
+        assert_eq!(
+            resp.into_string().await.unwrap(),
+            "event:next\ndata:{\"data\":{\"asyncHuman\":{\"name\":\"Luke Skywalker\"}}}\n\n\
+             event:complete\ndata:\n\n",
+        );
+
+        let resp = client
+            .post("/")
+            .header(ContentType::JSON)
+            .body(r#"{"query": "query { hero { name } }"}"#)
+            .dispatch()
+            .await;
+        assert_eq!(
+            resp.into_string().await.unwrap(),
+            "event:next\ndata:{\"data\":{\"hero\":{\"name\":\"R2-D2\"}}}\n\n\
+             event:complete\ndata:\n\n",
+        );
+    }
+
+    #[rocket::async_test]
+    async fn streams_errors_as_events() {
+        let client = client().await;
+
+        let resp = client
+            .get("/?query=subscription%7Bunknown%7D")
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::Ok);
+        let body = resp.into_string().await.unwrap();
+        assert!(body.starts_with("event:next\ndata:{\"errors\":[{\"message\":\"Unknown field"));
+        assert!(body.ends_with("event:complete\ndata:\n\n"));
+    }
+
+    #[rocket::async_test]
+    async fn refuses_mutations_via_get_and_batches() {
+        let client = client().await;
+
+        let resp = client
+            .get("/?query=mutation%7BaddHuman%7D")
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::MethodNotAllowed);
+        assert_eq!(resp.headers().get_one("Allow"), Some("POST"));
+
+        let resp = client
+            .post("/")
+            .header(ContentType::JSON)
+            .body(r#"[{"query": "subscription { asyncHuman { name } }"}]"#)
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::BadRequest);
+    }
+}
@@ -40,15 +40,27 @@ Check the LICENSE file for details.
 #![deny(warnings)]
 #![doc(html_root_url = "https://docs.rs/juniper_warp/0.2.0")]
 
-use anyhow::anyhow;
-use futures::{FutureExt as _, TryFutureExt};
+use futures::{StreamExt as _, TryFutureExt};
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest},
+    http::{
+        multipart::{GraphQLMultipartRequest, MultipartError, MultipartLimits, MultipartParser},
+        negotiation::{self, ContentEncoding, CsrfPrevention, RequestError, ResponseFormat},
+        streaming::JsonChunks,
+        GraphQLBatchRequest, GraphQLBatchResponse, Method,
+    },
     ScalarValue,
 };
-use std::{collections::HashMap, str, sync::Arc};
+use std::sync::Arc;
 use tokio::task;
-use warp::{body, filters::BoxedFilter, http, hyper::body::Bytes, query, Filter};
+use warp::{
+    body,
+    filters::{multipart::FormData, BoxedFilter},
+    header, http,
+    hyper::body::{Body, Buf as _, Bytes},
+    query,
+    reply::Response,
+    Filter,
+};
 
 /// Make a filter for graphql queries/mutations.
 ///
@@ -108,7 +120,33 @@ use warp::{body, filters::BoxedFilter, http, hyper::body::Bytes, query, Filter};
 pub fn make_graphql_filter<Query, Mutation, Subscription, CtxT, S>(
     schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context_extractor: BoxedFilter<(CtxT,)>,
-) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+) -> BoxedFilter<(Response,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    graphql_filter(Arc::new(schema), context_extractor)
+}
+
+/// Make a filter for graphql queries/mutations, additionally accepting file
+/// uploads via [GraphQL multipart requests][1] limited by the given `limits`.
+///
+/// Files are accepted as arguments of the [`Upload`] type. Apart from that,
+/// this filter behaves exactly like the one made by [`make_graphql_filter`].
+///
+/// [`Upload`]: juniper::http::multipart::Upload
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+pub fn make_graphql_filter_with_uploads<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    limits: MultipartLimits,
+) -> BoxedFilter<(Response,)>
 where
     Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
     Query::TypeInfo: Send + Sync,
@@ -120,72 +158,161 @@ where
     S: ScalarValue + Send + Sync + 'static,
 {
     let schema = Arc::new(schema);
-    let post_json_schema = schema.clone();
-    let post_graphql_schema = schema.clone();
+    let multipart_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
+    let handle_multipart_request = move |negotiated: Result<NegotiatedResponse, RequestError>,
+                                         context: CtxT,
+                                         form: FormData| {
+        let schema = multipart_schema.clone();
         async move {
+            let negotiated = match negotiated {
+                Ok(negotiated) => negotiated,
+                Err(e) => return Ok(build_error_response(e)),
+            };
+            let req = match parse_multipart_req::<S>(form, limits).await {
+                Ok(req) => req,
+                Err(e) => {
+                    let status = match e.downcast_ref::<MultipartError>() {
+                        Some(e) if e.is_limit_exceeded() => http::StatusCode::PAYLOAD_TOO_LARGE,
+                        _ => http::StatusCode::BAD_REQUEST,
+                    };
+                    return Ok::<_, warp::Rejection>(
+                        http::Response::builder()
+                            .status(status)
+                            .body(e.to_string().into())
+                            .expect("response is valid"),
+                    );
+                }
+            };
+
             let resp = req.execute(&schema, &context).await;
 
-            Ok::<_, warp::Rejection>(build_response(
-                serde_json::to_vec(&resp)
-                    .map(|json| (json, resp.is_ok()))
-                    .map_err(Into::into),
-            ))
+            Ok::<_, warp::Rejection>(build_response(resp, negotiated))
         }
     };
-    let post_json_filter = warp::post()
+    // The contents of the parts are limited by the parser, so only leave some
+    // room for the boundaries and headers of the parts around them.
+    let max_length = (limits.max_request_size as u64).saturating_add(64 * 1024);
+    let multipart_filter = warp::post()
+        .and(negotiate_response())
         .and(context_extractor.clone())
-        .and(body::json())
-        .and_then(handle_post_json_request);
+        .and(warp::multipart::form().max_length(max_length))
+        .and_then(handle_multipart_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let query = str::from_utf8(body.as_ref())
-                .map_err(|e| anyhow!("Request body query is not a valid UTF-8 string: {}", e))?;
-            let req = GraphQLRequest::new(query.into(), None, None);
+    multipart_filter
+        .or(graphql_filter(schema, context_extractor))
+        .unify()
+        .boxed()
+}
 
-            let resp = req.execute(&schema, &context).await;
+/// Wraps a filter made by [`make_graphql_filter`], [`make_graphql_filter_sync`]
+/// or [`make_graphql_filter_with_uploads`], so it replies with
+/// `400 Bad Request` to the requests blocked by the given [`CsrfPrevention`]
+/// guard instead of executing them.
+///
+/// Only `GET` and `POST` requests are checked, as the wrapped filter handles
+/// no other ones. Like the wrapped filter, the returned one doesn't handle
+/// routing, so it should be mounted on the GraphQL endpoint only, leaving the
+/// requests to the other ones unchecked.
+///
+/// Example:
+///
+/// ```
+/// # use juniper::{
+/// #     http::negotiation::CsrfPrevention, EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper::tests::fixtures::starwars::schema::{Database, Query};
+/// # use juniper_warp::{make_graphql_filter, with_csrf_prevention};
+/// # use warp::Filter;
+/// #
+/// # let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+/// # let context_extractor = warp::any().map(Database::new).boxed();
+/// #
+/// let graphql_filter = warp::path("graphql").and(with_csrf_prevention(
+///     make_graphql_filter(schema, context_extractor),
+///     CsrfPrevention::new(),
+/// ));
+/// ```
+pub fn with_csrf_prevention(
+    filter: BoxedFilter<(Response,)>,
+    prevention: CsrfPrevention,
+) -> BoxedFilter<(Response,)> {
+    let prevention = Arc::new(prevention);
+    let blocked = warp::get()
+        .or(warp::post())
+        .unify()
+        .and(header::headers_cloned())
+        .and_then(move |headers: http::HeaderMap| {
+            let prevention = prevention.clone();
+            async move {
+                let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+                match prevention.check(header("content-type"), header) {
+                    Ok(()) => Err(warp::reject()),
+                    Err(e) => Ok(build_error_response(e)),
+                }
+            }
+        });
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+    blocked.or(filter).unify().boxed()
+}
+
+async fn parse_multipart_req<S: ScalarValue>(
+    mut form: FormData,
+    limits: MultipartLimits,
+) -> anyhow::Result<GraphQLMultipartRequest<S>> {
+    let mut parser = MultipartParser::new(limits);
+    while let Some(part) = form.next().await {
+        let part = part?;
+        parser.start_part(
+            part.name(),
+            part.filename().map(Into::into),
+            part.content_type().map(Into::into),
+        )?;
+        let mut stream = Box::pin(part.stream());
+        while let Some(mut buf) = stream.next().await.transpose()? {
+            while buf.has_remaining() {
+                let chunk = buf.chunk();
+                let len = chunk.len();
+                parser.push_chunk(chunk)?;
+                buf.advance(len);
+            }
         }
-        .then(|res| async { Ok::<_, warp::Rejection>(build_response(res)) })
-    };
-    let post_graphql_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::bytes())
-        .and_then(handle_post_graphql_request);
+        parser.end_part()?;
+    }
+    Ok(parser.finish()?)
+}
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
+fn graphql_filter<Query, Mutation, Subscription, CtxT, S>(
+    schema: Arc<juniper::RootNode<'static, Query, Mutation, Subscription, S>>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+) -> BoxedFilter<(Response,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let handle_request = move |req: NegotiatedRequest<S>, context: CtxT| {
         let schema = schema.clone();
         async move {
-            let req = GraphQLRequest::new(
-                qry.remove("query")
-                    .ok_or_else(|| anyhow!("Missing GraphQL query string in query parameters"))?,
-                qry.remove("operation_name"),
-                qry.remove("variables")
-                    .map(|vs| serde_json::from_str(&vs))
-                    .transpose()?,
-            );
+            let (req, method, negotiated) = match req {
+                Ok(req) => req,
+                Err(e) => return Ok::<_, warp::Rejection>(build_error_response(e)),
+            };
 
-            let resp = req.execute(&schema, &context).await;
+            let resp = req.execute_via(method, &schema, &context).await;
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            Ok(build_response(resp, negotiated))
         }
-        .then(|res| async move { Ok::<_, warp::Rejection>(build_response(res)) })
     };
-    let get_filter = warp::get()
-        .and(context_extractor)
-        .and(query::query())
-        .and_then(handle_get_request);
 
-    get_filter
-        .or(post_json_filter)
-        .unify()
-        .or(post_graphql_filter)
-        .unify()
+    graphql_request()
+        .and(context_extractor)
+        .and_then(handle_request)
         .boxed()
 }
 
@@ -193,7 +320,7 @@ where
 pub fn make_graphql_filter_sync<Query, Mutation, Subscription, CtxT, S>(
     schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context_extractor: BoxedFilter<(CtxT,)>,
-) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+) -> BoxedFilter<(Response,)>
 where
     Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
     Mutation: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
@@ -202,82 +329,81 @@ where
     S: ScalarValue + Send + Sync + 'static,
 {
     let schema = Arc::new(schema);
-    let post_json_schema = schema.clone();
-    let post_graphql_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
+    let handle_request = move |req: NegotiatedRequest<S>, context: CtxT| {
+        let schema = schema.clone();
         async move {
-            let res = task::spawn_blocking(move || {
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            let (req, method, negotiated) = match req {
+                Ok(req) => req,
+                Err(e) => return Ok(build_error_response(e)),
+            };
+
+            task::spawn_blocking(move || {
+                let resp = req.execute_sync_via(method, &schema, &context);
+                build_response(resp, negotiated)
             })
-            .await?;
-
-            Ok(build_response(res))
+            .await
         }
         .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
     };
-    let post_json_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::json())
-        .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let query = str::from_utf8(body.as_ref())
-                    .map_err(|e| anyhow!("Request body is not a valid UTF-8 string: {}", e))?;
-                let req = GraphQLRequest::new(query.into(), None, None);
+    graphql_request()
+        .and(context_extractor)
+        .and_then(handle_request)
+        .boxed()
+}
 
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+/// Format and content coding of a response negotiated with the client.
+type NegotiatedResponse = (ResponseFormat, ContentEncoding);
 
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
-    let post_graphql_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::bytes())
-        .and_then(handle_post_graphql_request);
-
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
-        let schema = schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let req = GraphQLRequest::new(
-                    qry.remove("query").ok_or_else(|| {
-                        anyhow!("Missing GraphQL query string in query parameters")
-                    })?,
-                    qry.remove("operation_name"),
-                    qry.remove("variables")
-                        .map(|vs| serde_json::from_str(&vs))
-                        .transpose()?,
-                );
-
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+/// Result of parsing a GraphQL request along with the [`Method`] it has been
+/// received with and negotiating its [`NegotiatedResponse`].
+type NegotiatedRequest<S> =
+    Result<(GraphQLBatchRequest<S>, Method, NegotiatedResponse), RequestError>;
 
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
+/// Makes a filter parsing GET and POST GraphQL requests, and negotiating the
+/// [`ResponseFormat`] and [`ContentEncoding`] of their responses.
+fn graphql_request<S>() -> BoxedFilter<(NegotiatedRequest<S>,)>
+where
+    S: ScalarValue + Send + 'static,
+{
     let get_filter = warp::get()
-        .and(context_extractor)
-        .and(query::query())
-        .and_then(handle_get_request);
+        .and(query::raw().or(warp::any().map(String::new)).unify())
+        .map(|query: String| {
+            negotiation::parse_get_request(&query)
+                .map(|req| (GraphQLBatchRequest::Single(req), Method::Get))
+        });
+    let post_filter = warp::post()
+        .and(header::optional::<String>("content-type"))
+        .and(body::bytes())
+        .map(|content_type: Option<String>, body: Bytes| {
+            negotiation::parse_post_request(content_type.as_deref(), &body)
+                .map(|req| (req, Method::Post))
+        });
 
     get_filter
-        .or(post_json_filter)
-        .unify()
-        .or(post_graphql_filter)
+        .or(post_filter)
         .unify()
+        .and(negotiate_response())
+        .map(|req: Result<_, _>, negotiated: Result<_, _>| {
+            let (req, method) = req?;
+            Ok((req, method, negotiated?))
+        })
+        .boxed()
+}
+
+/// Makes a filter negotiating the [`ResponseFormat`] and [`ContentEncoding`]
+/// from the `Accept` and `Accept-Encoding` headers.
+fn negotiate_response() -> BoxedFilter<(Result<NegotiatedResponse, RequestError>,)> {
+    header::optional::<String>("accept")
+        .and(header::optional::<String>("accept-encoding"))
+        .map(|accept: Option<String>, accept_encoding: Option<String>| {
+            let format = ResponseFormat::negotiate(accept.as_deref())?;
+            Ok((
+                format,
+                ContentEncoding::negotiate(accept_encoding.as_deref()),
+            ))
+        })
         .boxed()
 }
 
@@ -289,18 +415,42 @@ pub struct JoinError(task::JoinError);
 
 impl warp::reject::Reject for JoinError {}
 
-fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Response<Vec<u8>> {
-    match response {
-        Ok((body, is_ok)) => http::Response::builder()
-            .status(if is_ok { 200 } else { 400 })
-            .header("content-type", "application/json")
-            .body(body)
-            .expect("response is valid"),
-        Err(_) => http::Response::builder()
-            .status(http::StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Vec::new())
-            .expect("status code is valid"),
+/// Creates a response streaming the given [`GraphQLBatchResponse`] as JSON,
+/// compressed with the negotiated [`ContentEncoding`].
+fn build_response<S: ScalarValue + Send + 'static>(
+    response: GraphQLBatchResponse<'_, S>,
+    (format, encoding): NegotiatedResponse,
+) -> Response {
+    let mut builder = http::Response::builder()
+        .status(negotiation::status_code(&response, format))
+        .header("content-type", format.content_type());
+    if let Some(coding) = encoding.header_value() {
+        builder = builder
+            .header("content-encoding", coding)
+            .header("vary", "accept-encoding");
+    }
+    if let Some(policy) = response.cache_policy() {
+        builder = builder.header("cache-control", policy.header_value());
+    }
+    if let Some(methods) = negotiation::allowed_methods(&response) {
+        builder = builder.header("allow", methods);
     }
+    let chunks = JsonChunks::new(response).encoded(encoding);
+    builder
+        .body(Body::wrap_stream(futures::stream::iter(chunks)))
+        .expect("response is valid")
+}
+
+fn build_error_response(err: RequestError) -> Response {
+    let mut builder = http::Response::builder()
+        .status(err.status_code())
+        .header("content-type", "text/plain; charset=utf-8");
+    if let Some(methods) = err.allowed_methods() {
+        builder = builder.header("allow", methods);
+    }
+    builder
+        .body(err.to_string().into())
+        .expect("response is valid")
 }
 
 /// Create a filter that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint.
@@ -382,26 +532,12 @@ fn playground_response(
 #[cfg(feature = "subscriptions")]
 pub mod subscriptions {
     use juniper::{
-        futures::{
-            future::{self, Either},
-            sink::SinkExt,
-            stream::StreamExt,
-        },
+        futures::{future, SinkExt as _, StreamExt as _, TryStreamExt as _},
         GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
     };
-    use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+    use juniper_graphql_ws::{ArcSchema, Init, ServeError};
     use std::{convert::Infallible, fmt, sync::Arc};
 
-    struct Message(warp::ws::Message);
-
-    impl<S: ScalarValue> std::convert::TryFrom<Message> for ClientMessage<S> {
-        type Error = serde_json::Error;
-
-        fn try_from(msg: Message) -> serde_json::Result<Self> {
-            serde_json::from_slice(msg.0.as_bytes())
-        }
-    }
-
     /// Errors that can happen while serving a connection.
     #[derive(Debug)]
     pub enum Error {
@@ -436,6 +572,15 @@ pub mod subscriptions {
         }
     }
 
+    impl From<ServeError<warp::Error>> for Error {
+        fn from(err: ServeError<warp::Error>) -> Self {
+            match err {
+                ServeError::Transport(e) => Self::Warp(e),
+                ServeError::Serde(e) => Self::Serde(e),
+            }
+        }
+    }
+
     /// Serves the graphql-ws protocol over a WebSocket connection.
     ///
     /// The `init` argument is used to provide the context and additional configuration for
@@ -460,24 +605,16 @@ pub mod subscriptions {
         I: Init<S, CtxT> + Send,
     {
         let (ws_tx, ws_rx) = websocket.split();
-        let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
 
-        let ws_rx = ws_rx.map(|r| r.map(Message));
-        let s_rx = s_rx.map(|msg| {
-            serde_json::to_string(&msg)
-                .map(warp::ws::Message::text)
-                .map_err(Error::Serde)
-        });
+        // Warp answers pings and closes by itself, passing them along for information only.
+        let incoming = ws_rx
+            .try_filter(|msg| future::ready(msg.is_text() || msg.is_binary()))
+            .map_ok(warp::ws::Message::into_bytes);
+        let outgoing = ws_tx.with(|text| future::ok(warp::ws::Message::text(text)));
 
-        match future::select(
-            ws_rx.forward(s_tx.sink_err_into()),
-            s_rx.forward(ws_tx.sink_err_into()),
-        )
-        .await
-        {
-            Either::Left((r, _)) => r.map_err(|e| e.into()),
-            Either::Right((r, _)) => r,
-        }
+        juniper_graphql_ws::serve(ArcSchema(root_node), init, incoming, outgoing)
+            .await
+            .map_err(Error::from)
     }
 }
 
@@ -626,6 +763,84 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn graphql_handler_compresses_responses() {
+        use std::io::Read as _;
+
+        use juniper::{
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let state = warp::any().map(Database::new);
+        let filter = warp::path("graphql2").and(make_graphql_filter(schema, state.boxed()));
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7Bhero%7Bname%7D%7D")
+            .header("accept-encoding", "deflate, gzip")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+        assert_eq!(response.headers().get("vary").unwrap(), "accept-encoding");
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&response.body()[..])
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json, r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+    }
+
+    #[tokio::test]
+    async fn graphql_handler_sets_cache_control() {
+        use juniper::{graphql_object, EmptyMutation, EmptySubscription, RootNode};
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            #[graphql(cache_control(max_age = 60))]
+            fn motd() -> &'static str {
+                "Hello"
+            }
+
+            fn version() -> &'static str {
+                "1.0"
+            }
+        }
+
+        let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let filter =
+            warp::path("graphql2").and(make_graphql_filter(schema, warp::any().map(|| ()).boxed()));
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7Bmotd%7D")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get("cache-control").unwrap(),
+            "max-age=60, public",
+        );
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7Bmotd%20version%7D")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert!(response.headers().get("cache-control").is_none());
+    }
+
     #[tokio::test]
     async fn batch_requests_work() {
         use juniper::{
@@ -670,6 +885,64 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn csrf_prevention_blocks_simple_requests() {
+        use juniper::{
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let state = warp::any().map(Database::new);
+        let filter = with_csrf_prevention(
+            make_graphql_filter(schema, state.boxed()),
+            CsrfPrevention::new(),
+        );
+
+        let response = request()
+            .method("GET")
+            .path("/?query=%7Bhero%7Bname%7D%7D")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+
+        let response = request()
+            .method("POST")
+            .header("content-type", "text/plain")
+            .body(r#"{"query": "{hero{name}}"}"#)
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+
+        let response = request()
+            .method("GET")
+            .path("/?query=%7Bhero%7Bname%7D%7D")
+            .header("graphql-preflight", "1")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let response = request()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(r#"{"query": "{hero{name}}"}"#)
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let response = request()
+            .method("PUT")
+            .header("content-type", "text/plain")
+            .body(r#"{"query": "{hero{name}}"}"#)
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+    }
+
     #[test]
     fn batch_request_deserialization_can_fail() {
         let json = r#"blah"#;
@@ -694,7 +967,7 @@ mod tests_http_harness {
     use super::*;
 
     struct TestWarpIntegration {
-        filter: BoxedFilter<(http::Response<Vec<u8>>,)>,
+        filter: BoxedFilter<(Response,)>,
     }
 
     impl TestWarpIntegration {
@@ -719,7 +992,7 @@ mod tests_http_harness {
         fn make_request(&self, req: warp::test::RequestBuilder) -> TestResponse {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio::Runtime");
             make_test_response(rt.block_on(async move {
-                req.filter(&self.filter).await.unwrap_or_else(|rejection| {
+                let resp = req.filter(&self.filter).await.unwrap_or_else(|rejection| {
                     let code = if rejection.is_not_found() {
                         http::StatusCode::NOT_FOUND
                     } else if let Some(body::BodyDeserializeError { .. }) = rejection.find() {
@@ -730,9 +1003,10 @@ mod tests_http_harness {
                     http::Response::builder()
                         .status(code)
                         .header("content-type", "application/json")
-                        .body(Vec::new())
+                        .body(Body::empty())
                         .unwrap()
-                })
+                });
+                collect_body(resp).await
             }))
         }
     }
@@ -779,12 +1053,32 @@ mod tests_http_harness {
                     .body(body),
             )
         }
+
+        fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(&str, &str)],
+            body: &str,
+        ) -> TestResponse {
+            let mut req = warp::test::request().method(method).path(url).body(body);
+            for (name, value) in headers {
+                req = req.header(*name, *value);
+            }
+            self.make_request(req)
+        }
+    }
+
+    async fn collect_body(resp: Response) -> http::Response<Bytes> {
+        let (parts, body) = resp.into_parts();
+        let body = warp::hyper::body::to_bytes(body).await.unwrap();
+        http::Response::from_parts(parts, body)
     }
 
-    fn make_test_response(resp: http::Response<Vec<u8>>) -> TestResponse {
+    fn make_test_response(resp: http::Response<Bytes>) -> TestResponse {
         TestResponse {
             status_code: resp.status().as_u16() as i32,
-            body: Some(String::from_utf8(resp.body().to_owned()).unwrap()),
+            body: Some(String::from_utf8(resp.body().to_vec()).unwrap()),
             content_type: resp
                 .headers()
                 .get("content-type")
@@ -792,6 +1086,10 @@ mod tests_http_harness {
                 .to_str()
                 .expect("invalid content-type string")
                 .to_owned(),
+            allow: resp
+                .headers()
+                .get("allow")
+                .map(|v| v.to_str().unwrap().to_owned()),
         }
     }
 
@@ -805,3 +1103,136 @@ mod tests_http_harness {
         run_http_test_suite(&TestWarpIntegration::new(true));
     }
 }
+
+#[cfg(test)]
+mod tests_uploads {
+    use juniper::{
+        graphql_object,
+        http::{multipart::Upload, tests as http_tests},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+    use warp::{http, test::request, Filter as _};
+
+    use super::*;
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        async fn upload(file: Upload) -> String {
+            let contents = file.to_bytes();
+            format!(
+                "{}: {}",
+                file.filename().unwrap_or_default(),
+                String::from_utf8(contents).unwrap(),
+            )
+        }
+    }
+
+    async fn execute(
+        content_type: &str,
+        body: String,
+        limits: MultipartLimits,
+    ) -> http::Response<Bytes> {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let filter =
+            make_graphql_filter_with_uploads(schema, warp::any().map(|| ()).boxed(), limits);
+
+        request()
+            .method("POST")
+            .header("content-type", content_type)
+            .body(body)
+            .reply(&filter)
+            .await
+    }
+
+    const OPERATIONS: &str =
+        r#"{"query": "query($f: Upload!) { upload(file: $f) }", "variables": {"f": null}}"#;
+
+    #[tokio::test]
+    async fn accepts_uploads() {
+        let body = http_tests::multipart_body(&[
+            ("operations", None, OPERATIONS),
+            ("map", None, r#"{"0": ["variables.f"]}"#),
+            ("0", Some("a.txt"), "hello"),
+        ]);
+
+        let resp = execute(
+            http_tests::MULTIPART_CONTENT_TYPE,
+            body,
+            MultipartLimits::default(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            String::from_utf8(resp.into_body().to_vec()).unwrap(),
+            r#"{"data":{"upload":"a.txt: hello"}}"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn enforces_limits() {
+        let body = http_tests::multipart_body(&[
+            ("operations", None, OPERATIONS),
+            (
+                "map",
+                None,
+                r#"{"0": ["variables.f"], "1": ["variables.f"]}"#,
+            ),
+            ("0", Some("a.txt"), "hello"),
+            ("1", Some("b.txt"), "world"),
+        ]);
+
+        let resp = execute(
+            http_tests::MULTIPART_CONTENT_TYPE,
+            body,
+            MultipartLimits::new().max_files(1),
+        )
+        .await;
+
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            String::from_utf8(resp.into_body().to_vec()).unwrap(),
+            "Too many files, at most 1 allowed",
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_requests() {
+        let body = http_tests::multipart_body(&[("operations", None, OPERATIONS)]);
+
+        let resp = execute(
+            http_tests::MULTIPART_CONTENT_TYPE,
+            body,
+            MultipartLimits::default(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            String::from_utf8(resp.into_body().to_vec()).unwrap(),
+            "`map` must follow the `operations` part",
+        );
+    }
+
+    #[tokio::test]
+    async fn handles_regular_requests() {
+        let resp = execute(
+            "application/json",
+            r#"{"query": "{ __typename }"}"#.into(),
+            MultipartLimits::default(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            String::from_utf8(resp.into_body().to_vec()).unwrap(),
+            r#"{"data":{"__typename":"Query"}}"#,
+        );
+    }
+}